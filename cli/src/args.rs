@@ -101,6 +101,13 @@ pub struct Args {
     #[arg(long, global = true, help = "Verbose mode")]
     pub verbose: bool,
 
+    #[arg(
+        long,
+        global = true,
+        help = "Fetch the snapshot on every read instead of reusing it for a few slots"
+    )]
+    pub no_cache: bool,
+
     #[arg(long, global = true, hide = true)]
     pub markdown_help: bool,
 }
@@ -123,6 +130,18 @@ pub enum ProgramCommand {
             help = "Timeout in milliseconds when an error occurs before retrying"
         )]
         error_timeout_ms: u64,
+        #[arg(
+            long,
+            env,
+            help = "Number of epochs to snapshot and vote in before exiting, runs forever if not set"
+        )]
+        max_epochs: Option<u64>,
+        #[arg(
+            long,
+            env,
+            help = "JSON file with the aggregated_signature, aggregated_g2 and signers_bitmap (hex) to vote with each epoch"
+        )]
+        vote_file: Option<String>,
     },
 
     /// Crank Functions
@@ -132,6 +151,11 @@ pub enum ProgramCommand {
         #[arg(long, help = "Show detailed progress information")]
         verbose: bool,
     },
+    /// Crank the snapshot until every operator is registered and snapshotted
+    CrankUntilFinalized {
+        #[arg(long, default_value_t = 10, help = "Maximum number of crank attempts")]
+        max_iterations: u64,
+    },
 
     /// Admin
     AdminCreateConfig {
@@ -154,8 +178,50 @@ pub enum ProgramCommand {
         tie_breaker_admin: Option<String>,
         #[arg(long, help = "Minimum stake required for operators (in lamports)")]
         minimum_stake: u128,
+        #[arg(long, help = "Curve id of the operators' BLS keys (defaults to BN254)")]
+        curve_id: Option<u8>,
+        #[arg(
+            long,
+            help = "Operators with the minimum stake required before votes are accepted (defaults to 0, which disables the guard)"
+        )]
+        minimum_operators: Option<u64>,
+        #[arg(
+            long,
+            help = "Minimum share, in bps, of the eligible stake weight the signers of a vote must hold (defaults to 0, which disables the check)"
+        )]
+        quorum_bps: Option<u16>,
+        #[arg(
+            long,
+            help = "Maximum slots between the last snapshot update and a cast vote (defaults to 0, which disables the check)"
+        )]
+        max_snapshot_age_slots: Option<u64>,
+    },
+    AdminRepairConfig {
+        #[arg(long, help = "Ncn Fee Wallet Address")]
+        ncn_fee_wallet: String,
+        #[arg(long, help = "Ncn Fee bps")]
+        ncn_fee_bps: u64,
+
+        #[arg(long, default_value_t = 10 as u64, help = "Epochs before tie breaker can set consensus")]
+        epochs_before_stall: u64,
+        #[arg(long, default_value_t = (DEFAULT_SLOTS_PER_EPOCH as f64 * 0.1) as u64, help = "Valid slots after consensus")]
+        valid_slots_after_consensus: u64,
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "Epochs after consensus before accounts can be closed"
+        )]
+        epochs_after_consensus_before_close: u64,
+        #[arg(long, help = "Minimum stake required for operators (in lamports)")]
+        minimum_stake: u128,
+    },
+    AdminRegisterStMint {
+        #[arg(
+            long,
+            help = "Weight of the stake in the mint, 1_000_000_000 counts it 1:1 (defaults to 1:1)"
+        )]
+        weight: Option<u128>,
     },
-    AdminRegisterStMint {},
 
     AdminSetTieBreaker {
         #[arg(long, help = "tie breaker for voting")]
@@ -170,6 +236,35 @@ pub enum ProgramCommand {
         valid_slots_after_consensus: Option<u64>,
         #[arg(long, help = "Starting valid epoch")]
         starting_valid_epoch: Option<u64>,
+        #[arg(long, help = "Require ordered signer accounts when casting votes")]
+        enforce_signer_order: Option<bool>,
+        #[arg(
+            long,
+            help = "Registered operators with the minimum stake required before votes are accepted (0 disables)"
+        )]
+        minimum_operators: Option<u64>,
+        #[arg(
+            long,
+            help = "Reject cast votes repeating the last accepted aggregate signature"
+        )]
+        reject_duplicate_aggregates: Option<bool>,
+        #[arg(long, help = "Accept more than one cast vote in the same epoch")]
+        allow_multiple_votes_per_epoch: Option<bool>,
+        #[arg(
+            long,
+            help = "Minimum share, in bps, of the eligible stake weight the signers of a vote must hold (0 disables)"
+        )]
+        quorum_bps: Option<u16>,
+        #[arg(
+            long,
+            help = "Maximum slots between the last snapshot update and a cast vote (0 disables)"
+        )]
+        max_snapshot_age_slots: Option<u64>,
+        #[arg(
+            long,
+            help = "Accept ed25519 fallback votes signed with operator voter keys"
+        )]
+        allow_ed25519_votes: Option<bool>,
     },
     AdminSetNewAdmin {
         #[arg(long, help = "New admin address")]
@@ -180,6 +275,13 @@ pub enum ProgramCommand {
     AdminFundAccountPayer {
         #[arg(long, help = "Amount of SOL to fund")]
         amount_in_sol: f64,
+        #[arg(
+            long,
+            help = "Expected account payer address, refused if it does not match the derived PDA"
+        )]
+        account_payer: Option<String>,
+        #[arg(long, help = "Skip the confirmation prompt")]
+        yes: bool,
     },
 
     /// Instructions
@@ -189,6 +291,12 @@ pub enum ProgramCommand {
 
     RegisterVault {},
 
+    /// Generate a BLS keypair and the proof of possession signature for `register-operator`
+    GenerateOperatorKeys {
+        #[arg(long, help = "Seed for deterministic key derivation")]
+        seed: Option<u64>,
+    },
+
     RegisterOperator {
         #[arg(long, help = "Operator address")]
         operator: String,
@@ -213,6 +321,12 @@ pub enum ProgramCommand {
             default_value = "bls-keys.json"
         )]
         keys_file: String,
+        #[arg(
+            long,
+            conflicts_with_all = ["g1_pubkey", "g2_pubkey", "signature"],
+            help = "Path to a JSON keystore with hex `g1`, `g2` and `privkey` fields - the signature is regenerated from it"
+        )]
+        operator_keystore: Option<String>,
     },
 
     UpdateOperatorIpPort {
@@ -244,18 +358,49 @@ pub enum ProgramCommand {
         signers_bitmap: String,
         #[arg(
             long,
-            help = "Message to sign (32 bytes hex, defaults to current vote counter)"
+            help = "Message to sign (32 bytes hex, defaults to the current vote signing payload)"
         )]
         message: Option<String>,
+        #[arg(
+            long,
+            help = "Minimum share, in bps, of the eligible stake weight the signers must hold"
+        )]
+        quorum_bps: Option<u16>,
+        #[arg(
+            long,
+            help = "Priority fee in micro lamports for this vote, overrides --priority-fee-micro-lamports"
+        )]
+        priority_fee: Option<u64>,
     },
 
-    /// Generate BLS signature for vote aggregation
+    /// Dry-run a vote against the current snapshot without sending a transaction
+    SimulateVote {
+        #[arg(long, help = "Comma-separated list of signing operator addresses")]
+        operators: String,
+        #[arg(
+            long,
+            help = "Comma-separated list of the operators' signatures (64 bytes hex each), in the same order"
+        )]
+        signatures: String,
+        #[arg(
+            long,
+            help = "Signed message (32 bytes hex, defaults to the current vote signing payload)"
+        )]
+        message: Option<String>,
+        #[arg(
+            long,
+            help = "Minimum share, in bps, of the eligible stake weight the signers must hold"
+        )]
+        quorum_bps: Option<u16>,
+    },
+
+    /// Generate BLS signature for vote aggregation, under the NCN's domain separation tag
     GenerateVoteSignature {
         #[arg(long, help = "Operator private key (32 bytes hex)")]
         private_key: String,
         #[arg(
             long,
-            help = "Message to sign (32 bytes hex, defaults to current vote counter)"
+            help = "Message to sign (32 bytes hex, defaults to the current vote signing payload)"
         )]
         message: Option<String>,
     },
@@ -278,6 +423,26 @@ pub enum ProgramCommand {
         signers_bitmap: String,
     },
 
+    /// Aggregate the operators' partial signature files against the snapshot of the epoch given
+    /// with `--epoch` and cast the vote
+    AggregateAndVote {
+        #[arg(
+            long,
+            help = "Directory of JSON signature files, each with an `operator` address and its `signature` (64 bytes hex)"
+        )]
+        signatures_dir: String,
+        #[arg(
+            long,
+            help = "Minimum share, in bps, of the eligible stake weight the signers must hold"
+        )]
+        quorum_bps: Option<u16>,
+        #[arg(
+            long,
+            help = "Priority fee in micro lamports for this vote, overrides --priority-fee-micro-lamports"
+        )]
+        priority_fee: Option<u64>,
+    },
+
     /// Getters
     GetNcn,
     GetNcnOperatorState {
@@ -298,14 +463,107 @@ pub enum ProgramCommand {
     GetVaultRegistry,
 
     GetVoteCounter {},
+    /// Print the vote counter with its per-epoch vote tracking and consensus records
+    ShowVoteCounter,
+    /// Print the hex message operators sign to vote in the NCN epoch given with `--epoch`
+    VoteMessage {
+        #[arg(
+            long,
+            help = "Vote counter value to sign - defaults to the on-chain vote counter"
+        )]
+        vote_counter: Option<u64>,
+    },
 
     GetSnapshot,
     GetOperatorSnapshot {
         #[arg(long, env = "OPERATOR", help = "Operator Account Address")]
         operator: String,
     },
+    /// Write an operator's snapshot entry to a file in the compact format used for gossip
+    ExportOperatorSnapshot {
+        #[arg(long, env = "OPERATOR", help = "Operator Account Address")]
+        operator: String,
+        #[arg(long, help = "File to write the operator snapshot to")]
+        output: String,
+    },
+    /// List the active operators with the minimum stake in the current epoch
+    ListEligibleVoters,
+    /// List the active operators ranked by stake weight, highest first
+    RankOperators,
     GetAccountPayer,
     GetTotalEpochRentCost,
+    GetSnapshotTransactionEstimate,
+    VerifySnapshotKeys,
+    /// Print the snapshot content hash, to confirm keepers produced the same snapshot
+    SnapshotHash,
+    /// Print the snapshot of the epoch given with `--epoch` as JSON, pubkeys in hex
+    SnapshotJson {
+        #[arg(
+            long,
+            default_value = "snapshots",
+            help = "Directory of the backups written by `backup-snapshot`, the current epoch falls back to the on-chain snapshot"
+        )]
+        backup_dir: String,
+    },
+    /// Report how a new operator with the given stake weight would change the stake distribution
+    SimulateOperatorJoin {
+        #[arg(long, help = "Stake weight of the joining operator")]
+        stake_weight: u128,
+    },
+    /// Check every precondition for an operator to take part in votes this epoch
+    CheckOperatorReady {
+        #[arg(long, env = "OPERATOR", help = "Operator Account Address")]
+        operator: String,
+    },
+    /// Check that an operator landed in the snapshot with the given G1 pubkey
+    VerifyOperatorRegistration {
+        #[arg(long, env = "OPERATOR", help = "Operator Account Address")]
+        operator: String,
+        #[arg(long, help = "Local G1 public key (32 bytes as hex string)")]
+        g1_pubkey: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "g1_pubkey",
+            help = "Path to a JSON keystore with hex `g1`, `g2` and `privkey` fields to read the local G1 public key from"
+        )]
+        operator_keystore: Option<String>,
+    },
+    /// Print the total stake weight of the active operators that meet the minimum stake
+    TotalStake,
+    /// Save the current snapshot as the backup of the current epoch, for `diff-snapshots`
+    BackupSnapshot {
+        #[arg(
+            long,
+            default_value = "snapshots",
+            help = "Directory to write the snapshot backup to"
+        )]
+        backup_dir: String,
+    },
+    /// Print per-operator changes in stake weight, minimum stake eligibility and active status
+    /// between the snapshots of two epochs
+    DiffSnapshots {
+        #[arg(help = "Epoch of the earlier snapshot")]
+        epoch_a: u64,
+        #[arg(help = "Epoch of the later snapshot")]
+        epoch_b: u64,
+        #[arg(
+            long,
+            default_value = "snapshots",
+            help = "Directory of the backups written by `backup-snapshot`, the current epoch falls back to the on-chain snapshot"
+        )]
+        backup_dir: String,
+    },
+    /// List the active operators that did not sign a vote of the epoch given with `--epoch`
+    ListNonSigners {
+        #[arg(long, help = "Signers bitmap of the vote as hex string")]
+        signers_bitmap: String,
+        #[arg(
+            long,
+            default_value = "snapshots",
+            help = "Directory of the backups written by `backup-snapshot`, the current epoch falls back to the on-chain snapshot"
+        )]
+        backup_dir: String,
+    },
 
     GetOperatorStakes,
     GetVaultStakes,
@@ -341,6 +599,7 @@ impl fmt::Display for Args {
         // Optional Settings
         writeln!(f, "\n⚙️  Additional Settings:")?;
         writeln!(f, "  • Verbose Mode:  {}", if self.verbose { "Enabled" } else { "Disabled" })?;
+        writeln!(f, "  • Snapshot Cache: {}", if self.no_cache { "Disabled" } else { "Enabled" })?;
         writeln!(f, "  • Markdown Help: {}", if self.markdown_help { "Enabled" } else { "Disabled" })?;
 
         writeln!(f, "\n")?;
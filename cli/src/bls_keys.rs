@@ -1,15 +1,18 @@
 use anyhow::{anyhow, Result};
 use chrono::Utc;
 use ncn_program_core::{
+    constants::MAX_QUORUM_BPS,
     g1_point::{G1CompressedPoint, G1Point},
     g2_point::{G2CompressedPoint, G2Point},
     privkey::PrivKey,
-    schemes::Sha256Normalized,
+    schemes::{Sha256Normalized, Sha256NormalizedScheme},
+    snapshot::Snapshot,
+    utils::build_signer_bitmap,
 };
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use solana_sdk::pubkey::Pubkey;
-use std::{fs, path::Path};
+use std::{fs, path::Path, str::FromStr};
 
 /// BLS key set for an operator
 #[serde_as]
@@ -92,6 +95,22 @@ pub fn generate_signature_from_private_key(
     Ok(signature.0)
 }
 
+/// Generate an operator's BLS signature over the vote `message` of `ncn`, hashed under the
+/// NCN's domain separation tag as `CastVote` verifies it
+pub fn generate_vote_signature(
+    private_key: &[u8; 32],
+    ncn: &Pubkey,
+    message: &[u8; 32],
+) -> Result<[u8; 64]> {
+    let privkey = PrivKey(*private_key);
+
+    let signature = privkey
+        .sign_with_scheme(&Sha256NormalizedScheme::for_ncn(ncn), message)
+        .map_err(|e| anyhow!("Failed to generate vote signature: {:?}", e))?;
+
+    Ok(signature.0)
+}
+
 /// Load BLS keys from file
 pub fn load_keys_from_file<P: AsRef<Path>>(file_path: P) -> Result<BlsKeyStorage> {
     let file_path = file_path.as_ref();
@@ -214,6 +233,113 @@ pub fn log_generated_keys(key_set: &BlsKeySet, operator: &Pubkey) {
     println!("==========================================\n");
 }
 
+/// BLS keypair and proof of possession signature for `register-operator`
+#[derive(Debug, Clone, Copy)]
+pub struct OperatorKeys {
+    pub private_key: [u8; 32],
+    pub g1_pubkey: [u8; 32],
+    pub g2_pubkey: [u8; 64],
+    pub signature: [u8; 64],
+}
+
+/// Generate a BLS keypair and sign its G1 public key as the proof of possession.
+/// Passing a `seed` derives the same keys every time.
+pub fn generate_operator_keys(seed: Option<u64>) -> Result<OperatorKeys> {
+    let privkey = match seed {
+        Some(seed) => PrivKey::from_seed(seed),
+        None => PrivKey::from_random(),
+    };
+
+    let g1_point = G1Point::try_from(privkey)
+        .map_err(|e| anyhow!("Failed to generate G1 public key: {:?}", e))?;
+    let g2_point = G2Point::try_from(&privkey)
+        .map_err(|e| anyhow!("Failed to generate G2 public key: {:?}", e))?;
+
+    let g1_compressed = G1CompressedPoint::try_from(g1_point)
+        .map_err(|e| anyhow!("Failed to compress G1 public key: {:?}", e))?;
+    let g2_compressed = G2CompressedPoint::try_from(&g2_point)
+        .map_err(|e| anyhow!("Failed to compress G2 public key: {:?}", e))?;
+
+    let signature = generate_signature_from_private_key(&privkey.0, &g1_compressed.0)?;
+
+    Ok(OperatorKeys {
+        private_key: privkey.0,
+        g1_pubkey: g1_compressed.0,
+        g2_pubkey: g2_compressed.0,
+        signature,
+    })
+}
+
+/// Log operator keys as hex, ready to pass to `register-operator`
+pub fn log_operator_keys(keys: &OperatorKeys) {
+    println!("\n=== Generated Operator BLS Keys ===");
+    println!("BN128 Private Key: {}", hex::encode(keys.private_key));
+    println!("G1 Public Key: {}", hex::encode(keys.g1_pubkey));
+    println!("G2 Public Key: {}", hex::encode(keys.g2_pubkey));
+    println!("Signature: {}", hex::encode(keys.signature));
+    println!("===================================\n");
+}
+
+/// Operator BLS keys as stored in a JSON keystore file, every field hex encoded
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatorKeystore {
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub g1: [u8; 32],
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub g2: [u8; 64],
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub privkey: [u8; 32],
+}
+
+impl OperatorKeystore {
+    /// Checks both pubkeys derive from the private key and signs the G1 pubkey as the proof of
+    /// possession
+    pub fn operator_keys(&self) -> Result<OperatorKeys> {
+        let privkey = PrivKey(self.privkey);
+
+        let g1_compressed = G1CompressedPoint::try_from(privkey)
+            .map_err(|e| anyhow!("Failed to derive G1 public key: {:?}", e))?;
+        if g1_compressed.0 != self.g1 {
+            return Err(anyhow!(
+                "Keystore G1 public key {} does not derive from its private key",
+                hex::encode(self.g1)
+            ));
+        }
+
+        let g2_compressed = G2CompressedPoint::try_from(&privkey)
+            .map_err(|e| anyhow!("Failed to derive G2 public key: {:?}", e))?;
+        if g2_compressed.0 != self.g2 {
+            return Err(anyhow!(
+                "Keystore G2 public key {} does not derive from its private key",
+                hex::encode(self.g2)
+            ));
+        }
+
+        let signature = generate_signature_from_private_key(&self.privkey, &self.g1)?;
+
+        Ok(OperatorKeys {
+            private_key: self.privkey,
+            g1_pubkey: self.g1,
+            g2_pubkey: self.g2,
+            signature,
+        })
+    }
+}
+
+/// Load an operator keystore file and regenerate its proof of possession signature
+pub fn load_operator_keystore<P: AsRef<Path>>(file_path: P) -> Result<OperatorKeys> {
+    let file_path = file_path.as_ref();
+
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| anyhow!("Failed to read keystore {}: {}", file_path.display(), e))?;
+
+    let keystore: OperatorKeystore = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Failed to parse keystore {}: {}", file_path.display(), e))?;
+
+    keystore.operator_keys()
+}
+
 /// Parse hex string to byte array of specified length
 pub fn hex_to_bytes<const N: usize>(hex_str: &str) -> Result<[u8; N]> {
     let bytes = hex::decode(hex_str)
@@ -229,13 +355,46 @@ pub fn hex_to_bytes<const N: usize>(hex_str: &str) -> Result<[u8; N]> {
 }
 
 /// Result of signature aggregation
-#[derive(Debug)]
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AggregationResult {
+    #[serde_as(as = "serde_with::hex::Hex")]
     pub aggregated_signature: [u8; 32],
+    #[serde_as(as = "serde_with::hex::Hex")]
     pub aggregated_g2: [u8; 64],
+    #[serde_as(as = "serde_with::hex::Hex")]
     pub signers_bitmap: Vec<u8>,
 }
 
+/// Load an aggregated signature from a JSON file, `None` when the file does not exist yet
+pub fn load_aggregation_from_file<P: AsRef<Path>>(
+    file_path: P,
+) -> Result<Option<AggregationResult>> {
+    let file_path = file_path.as_ref();
+
+    if !file_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(file_path).map_err(|e| {
+        anyhow!(
+            "Failed to read aggregation file {}: {}",
+            file_path.display(),
+            e
+        )
+    })?;
+
+    let aggregation: AggregationResult = serde_json::from_str(&content).map_err(|e| {
+        anyhow!(
+            "Failed to parse aggregation file {}: {}",
+            file_path.display(),
+            e
+        )
+    })?;
+
+    Ok(Some(aggregation))
+}
+
 /// Aggregate multiple BLS signatures and public keys
 pub fn aggregate_signatures_and_keys(
     signatures: &str,
@@ -335,9 +494,207 @@ pub fn aggregate_signatures_and_keys(
     })
 }
 
+/// Partial vote signature of one operator, as dropped in the directory read by
+/// `aggregate-and-vote`
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignatureFile {
+    pub operator: String,
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub signature: [u8; 64],
+}
+
+/// Loads the partial signature of every `.json` file in `dir`, in file name order
+pub fn load_partial_signatures<P: AsRef<Path>>(dir: P) -> Result<Vec<(Pubkey, [u8; 64])>> {
+    let dir = dir.as_ref();
+
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| anyhow!("Failed to read signatures dir {}: {}", dir.display(), e))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()
+        .map_err(|e| anyhow!("Failed to read signatures dir {}: {}", dir.display(), e))?;
+    paths.retain(|path| {
+        path.extension()
+            .is_some_and(|extension| extension == "json")
+    });
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            let content = fs::read_to_string(path)
+                .map_err(|e| anyhow!("Failed to read signature file {}: {}", path.display(), e))?;
+            let file: PartialSignatureFile = serde_json::from_str(&content)
+                .map_err(|e| anyhow!("Failed to parse signature file {}: {}", path.display(), e))?;
+            let operator = Pubkey::from_str(&file.operator).map_err(|e| {
+                anyhow!(
+                    "Invalid operator in signature file {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+
+            Ok((operator, file.signature))
+        })
+        .collect()
+}
+
+/// The signers of a vote with their partial signatures and G2 pubkeys summed up
+struct AggregatedSigners {
+    signers_bitmap: Vec<u8>,
+    signature: G1Point,
+    g2: G2Point,
+}
+
+/// Sums up the partial signatures of `signers` and their G2 pubkeys from `snapshot`, and sets
+/// their bits in the signer bitmap. Fails on an operator missing from the snapshot, inactive or
+/// listed twice
+fn aggregate_signers(
+    snapshot: &Snapshot,
+    signers: &[(Pubkey, [u8; 64])],
+) -> Result<AggregatedSigners> {
+    let mut signer_indices = Vec::new();
+    let mut signatures = Vec::new();
+    let mut g2_points = Vec::new();
+    for (operator, signature) in signers {
+        let (index, operator_snapshot) = snapshot
+            .registered_operator_snapshots()
+            .find(|(_, operator_snapshot)| operator_snapshot.operator().eq(operator))
+            .ok_or_else(|| anyhow!("Operator {} is not in the snapshot", operator))?;
+        if !operator_snapshot.is_active() {
+            return Err(anyhow!("Operator {} is inactive and can't vote", operator));
+        }
+        if signer_indices.contains(&(index as u64)) {
+            return Err(anyhow!("Operator {} is listed more than once", operator));
+        }
+
+        signer_indices.push(index as u64);
+        signatures.push(G1Point::from(*signature));
+        g2_points.push(
+            G2Point::try_from(G2CompressedPoint::from(operator_snapshot.g2_pubkey()))
+                .map_err(|e| anyhow!("Failed to decompress G2 point of {}: {:?}", operator, e))?,
+        );
+    }
+
+    let signature = signatures
+        .into_iter()
+        .reduce(|acc, sig| acc + sig)
+        .ok_or_else(|| anyhow!("No signatures to aggregate"))?;
+    let g2 = g2_points
+        .into_iter()
+        .reduce(|acc, g2| acc + g2)
+        .ok_or_else(|| anyhow!("No G2 public keys to aggregate"))?;
+
+    Ok(AggregatedSigners {
+        signers_bitmap: build_signer_bitmap(snapshot.operator_slot_count(), &signer_indices),
+        signature,
+        g2,
+    })
+}
+
+/// Assembles the `CastVote` arguments from the partial signatures of `signers`. Operators of
+/// `snapshot` without a partial signature are left out of the signer bitmap
+pub fn aggregate_vote(
+    snapshot: &Snapshot,
+    signers: &[(Pubkey, [u8; 64])],
+) -> Result<AggregationResult> {
+    let aggregated_signers = aggregate_signers(snapshot, signers)?;
+
+    let aggregated_signature = G1CompressedPoint::try_from(aggregated_signers.signature)
+        .map_err(|e| anyhow!("Failed to compress aggregated signature: {:?}", e))?;
+    let aggregated_g2 = G2CompressedPoint::try_from(&aggregated_signers.g2)
+        .map_err(|e| anyhow!("Failed to compress aggregated G2 point: {:?}", e))?;
+
+    Ok(AggregationResult {
+        aggregated_signature: aggregated_signature.0,
+        aggregated_g2: aggregated_g2.0,
+        signers_bitmap: aggregated_signers.signers_bitmap,
+    })
+}
+
+/// Result of a vote simulation
+#[derive(Debug)]
+pub struct VoteSimulation {
+    pub signers_bitmap: Vec<u8>,
+    pub signature_valid: bool,
+    pub quorum_met: bool,
+    pub signer_stake_weight: u128,
+    pub eligible_stake_weight: u128,
+}
+
+impl VoteSimulation {
+    /// Whether `CastVote` would accept the vote
+    pub fn passed(&self) -> bool {
+        self.signature_valid && self.quorum_met
+    }
+}
+
+/// Dry-runs a vote against `snapshot` without sending anything: aggregates the signers' partial
+/// signatures and G2 pubkeys, runs the pairing check `CastVote` performs and checks the quorum.
+///
+/// `signers` pairs each signing operator with its 64 byte partial signature over `message`. The
+/// on-chain NCN operator link checks are not simulated.
+pub fn simulate_vote(
+    snapshot: &Snapshot,
+    signers: &[(Pubkey, [u8; 64])],
+    message: &[u8; 32],
+    quorum_bps: Option<u16>,
+) -> Result<VoteSimulation> {
+    let AggregatedSigners {
+        signers_bitmap,
+        signature: aggregated_signature,
+        g2: aggregated_g2,
+    } = aggregate_signers(snapshot, signers)?;
+
+    let aggregated_g1 = snapshot
+        .aggregate_signers_by_addition(&signers_bitmap)
+        .map_err(|e| anyhow!("Failed to aggregate signers G1 pubkeys: {:?}", e))?;
+
+    let signature_valid = Sha256NormalizedScheme::for_ncn(snapshot.ncn())
+        .verify_aggregate(
+            &aggregated_g1,
+            &aggregated_g2,
+            message,
+            &aggregated_signature,
+        )
+        .map_err(|e| anyhow!("Failed to verify aggregated signature: {:?}", e))?;
+
+    let signer_stake_weight = snapshot
+        .signer_stake_weight_total(&signers_bitmap)
+        .map_err(|e| anyhow!("Failed to sum signer stake weight: {:?}", e))?;
+    let eligible_stake_weight = snapshot
+        .total_eligible_stake_weight()
+        .map_err(|e| anyhow!("Failed to sum eligible stake weight: {:?}", e))?;
+
+    // Paused operators can't sign and don't count as non-signers
+    let active_non_signers_count = snapshot.non_signers(&signers_bitmap).len() as u64;
+    let active_operators_count = snapshot.active_operators_count();
+    let stake_quorum_met = match quorum_bps {
+        Some(quorum_bps) => {
+            let signer_stake_weight_scaled = signer_stake_weight
+                .checked_mul(MAX_QUORUM_BPS as u128)
+                .ok_or_else(|| anyhow!("Signer stake weight overflow"))?;
+            let required_stake_weight_scaled = eligible_stake_weight
+                .checked_mul(quorum_bps as u128)
+                .ok_or_else(|| anyhow!("Eligible stake weight overflow"))?;
+            eligible_stake_weight > 0 && signer_stake_weight_scaled >= required_stake_weight_scaled
+        }
+        None => true,
+    };
+
+    Ok(VoteSimulation {
+        signers_bitmap,
+        signature_valid,
+        quorum_met: active_non_signers_count <= active_operators_count / 3 && stake_quorum_met,
+        signer_stake_weight,
+        eligible_stake_weight,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ncn_program_core::{snapshot::OperatorSnapshot, stake_weight::StakeWeights};
     use tempfile::NamedTempFile;
 
     #[test]
@@ -403,4 +760,242 @@ mod tests {
         // Test wrong length
         assert!(hex_to_bytes::<16>(hex).is_err());
     }
+
+    #[test]
+    fn test_generate_operator_keys_round_trip() {
+        let keys = generate_operator_keys(Some(42)).unwrap();
+
+        // The same seed always derives the same keys
+        let same_keys = generate_operator_keys(Some(42)).unwrap();
+        assert_eq!(keys.private_key, same_keys.private_key);
+        assert_eq!(keys.signature, same_keys.signature);
+
+        // Parse the printed hex back the way `register-operator` does and verify it
+        let g1_pubkey: [u8; 32] = hex_to_bytes(&hex::encode(keys.g1_pubkey)).unwrap();
+        let g2_pubkey: [u8; 64] = hex_to_bytes(&hex::encode(keys.g2_pubkey)).unwrap();
+        let signature: [u8; 64] = hex_to_bytes(&hex::encode(keys.signature)).unwrap();
+
+        let g1_point = G1Point::try_from(&G1CompressedPoint::from(g1_pubkey)).unwrap();
+        let g2_point = G2Point::try_from(G2CompressedPoint::from(g2_pubkey)).unwrap();
+
+        assert!(g1_point.verify_g2(&g2_point).unwrap());
+        g2_point
+            .verify_proof_of_possession(G1Point::from(signature), g1_pubkey)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_load_operator_keystore() {
+        let fixture = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/operator_keystore.json"
+        );
+        let keys = load_operator_keystore(fixture).unwrap();
+
+        let g1_point = G1Point::try_from(&G1CompressedPoint::from(keys.g1_pubkey)).unwrap();
+        let g2_point = G2Point::try_from(G2CompressedPoint::from(keys.g2_pubkey)).unwrap();
+
+        assert!(g1_point.verify_g2(&g2_point).unwrap());
+        g2_point
+            .verify_proof_of_possession(G1Point::from(keys.signature), keys.g1_pubkey)
+            .unwrap();
+
+        // A G1 pubkey that does not belong to the private key is rejected before signing
+        let mut keystore: OperatorKeystore =
+            serde_json::from_str(&fs::read_to_string(fixture).unwrap()).unwrap();
+        keystore.g1 = generate_operator_keys(Some(1)).unwrap().g1_pubkey;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::write(temp_file.path(), serde_json::to_string(&keystore).unwrap()).unwrap();
+        assert!(load_operator_keystore(temp_file.path()).is_err());
+    }
+
+    /// Snapshot of three operators with real BN128 keys and stake weights 100, 200 and 300
+    fn signing_snapshot() -> (Box<Snapshot>, Vec<BlsKeySet>) {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            0,
+            StakeWeights::new(1),
+        ));
+
+        let key_sets: Vec<BlsKeySet> = (0..3)
+            .map(|_| generate_bls_keypair(&Pubkey::new_unique()).unwrap())
+            .collect();
+
+        for (index, key_set) in key_sets.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::from_str(&key_set.operator).unwrap(),
+                0,
+                true,
+                index as u64,
+                index as u64,
+                key_set.g1_pubkey,
+                key_set.g2_pubkey,
+            )
+            .unwrap();
+            let stake_weight = StakeWeights::new(100 * (index as u128 + 1));
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    1,
+                    &stake_weight,
+                    &stake_weight,
+                    snapshot.minimum_stake(),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 0)
+                .unwrap();
+        }
+
+        (snapshot, key_sets)
+    }
+
+    fn sign(snapshot: &Snapshot, key_set: &BlsKeySet, message: &[u8; 32]) -> (Pubkey, [u8; 64]) {
+        (
+            Pubkey::from_str(&key_set.operator).unwrap(),
+            generate_vote_signature(&key_set.private_key, snapshot.ncn(), message).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_simulate_vote() {
+        let (mut snapshot, key_sets) = signing_snapshot();
+        let message = [7u8; 32];
+
+        let signers = vec![
+            sign(&snapshot, &key_sets[0], &message),
+            sign(&snapshot, &key_sets[1], &message),
+        ];
+        let simulation = simulate_vote(&snapshot, &signers, &message, Some(5_000)).unwrap();
+        assert!(simulation.passed());
+        assert_eq!(simulation.signers_bitmap, vec![0b011]);
+        assert_eq!(simulation.signer_stake_weight, 300);
+        assert_eq!(simulation.eligible_stake_weight, 600);
+
+        // Signers listed out of index order aggregate to the same vote
+        let reversed: Vec<_> = signers.iter().rev().cloned().collect();
+        assert!(simulate_vote(&snapshot, &reversed, &message, None)
+            .unwrap()
+            .passed());
+
+        // Not enough stake for the requested quorum
+        let simulation = simulate_vote(&snapshot, &signers, &message, Some(6_000)).unwrap();
+        assert!(simulation.signature_valid);
+        assert!(!simulation.passed());
+
+        // Pausing the third operator leaves the signers with all of the eligible stake, and the
+        // paused operator can't sign anymore
+        let paused = Pubkey::from_str(&key_sets[2].operator).unwrap();
+        snapshot.set_operator_active(&paused, false).unwrap();
+        let simulation = simulate_vote(&snapshot, &signers, &message, Some(10_000)).unwrap();
+        assert!(simulation.passed());
+        assert_eq!(simulation.eligible_stake_weight, 300);
+        let paused_signer = sign(&snapshot, &key_sets[2], &message);
+        assert!(simulate_vote(&snapshot, &[paused_signer], &message, None).is_err());
+    }
+
+    #[test]
+    fn test_simulate_vote_fails() {
+        let (snapshot, key_sets) = signing_snapshot();
+        let message = [7u8; 32];
+
+        // One partial signature is over another message
+        let signers = vec![
+            sign(&snapshot, &key_sets[0], &message),
+            sign(&snapshot, &key_sets[2], &[8u8; 32]),
+        ];
+        let simulation = simulate_vote(&snapshot, &signers, &message, None).unwrap();
+        assert!(!simulation.signature_valid);
+        assert!(!simulation.passed());
+
+        // Two of three operators not signing misses the quorum
+        let simulation = simulate_vote(
+            &snapshot,
+            &[sign(&snapshot, &key_sets[2], &message)],
+            &message,
+            None,
+        )
+        .unwrap();
+        assert!(simulation.signature_valid);
+        assert!(!simulation.quorum_met);
+
+        // Unknown and duplicate signers are rejected
+        let unknown = (
+            Pubkey::new_unique(),
+            sign(&snapshot, &key_sets[0], &message).1,
+        );
+        assert!(simulate_vote(&snapshot, &[unknown], &message, None).is_err());
+        let duplicate = vec![
+            sign(&snapshot, &key_sets[0], &message),
+            sign(&snapshot, &key_sets[0], &message),
+        ];
+        assert!(simulate_vote(&snapshot, &duplicate, &message, None).is_err());
+    }
+
+    /// Checks the aggregated vote against the snapshot as `CastVote` does
+    fn verify_aggregated_vote(
+        snapshot: &Snapshot,
+        aggregation: &AggregationResult,
+        message: &[u8; 32],
+    ) -> bool {
+        let aggregated_g1 = snapshot
+            .aggregate_signers_by_addition(&aggregation.signers_bitmap)
+            .unwrap();
+        let aggregated_g2 =
+            G2Point::try_from(G2CompressedPoint::from(aggregation.aggregated_g2)).unwrap();
+        let aggregated_signature =
+            G1Point::try_from(&G1CompressedPoint::from(aggregation.aggregated_signature)).unwrap();
+
+        Sha256NormalizedScheme::for_ncn(snapshot.ncn())
+            .verify_aggregate(
+                &aggregated_g1,
+                &aggregated_g2,
+                message,
+                &aggregated_signature,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_vote_from_signature_files() {
+        let (snapshot, key_sets) = signing_snapshot();
+        let message = [7u8; 32];
+
+        let signatures_dir = tempfile::tempdir().unwrap();
+        for (index, key_set) in key_sets.iter().enumerate() {
+            let (operator, signature) = sign(&snapshot, key_set, &message);
+            let file = PartialSignatureFile {
+                operator: operator.to_string(),
+                signature,
+            };
+            fs::write(
+                signatures_dir
+                    .path()
+                    .join(format!("operator-{}.json", index)),
+                serde_json::to_string(&file).unwrap(),
+            )
+            .unwrap();
+        }
+        // Files other than JSON are ignored
+        fs::write(signatures_dir.path().join("README"), "partial signatures").unwrap();
+
+        let signers = load_partial_signatures(signatures_dir.path()).unwrap();
+        assert_eq!(signers.len(), 3);
+
+        let aggregation = aggregate_vote(&snapshot, &signers).unwrap();
+        assert_eq!(aggregation.signers_bitmap, vec![0b111]);
+        assert!(verify_aggregated_vote(&snapshot, &aggregation, &message));
+
+        // An operator without a signature file is left out of the bitmap
+        fs::remove_file(signatures_dir.path().join("operator-1.json")).unwrap();
+        let signers = load_partial_signatures(signatures_dir.path()).unwrap();
+        let aggregation = aggregate_vote(&snapshot, &signers).unwrap();
+        assert_eq!(aggregation.signers_bitmap, vec![0b101]);
+        assert!(verify_aggregated_vote(&snapshot, &aggregation, &message));
+
+        // A signature file that does not parse fails the whole aggregation
+        fs::write(signatures_dir.path().join("operator-1.json"), "{}").unwrap();
+        assert!(load_partial_signatures(signatures_dir.path()).is_err());
+    }
 }
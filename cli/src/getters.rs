@@ -1,5 +1,11 @@
 use std::mem::size_of;
-use std::{fmt, time::Duration};
+use std::{
+    fmt, fs,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
 
 use crate::handler::CliHandler;
 use crate::instructions::create_vault_registry;
@@ -20,8 +26,10 @@ use log::{info, warn};
 use ncn_program_core::{
     account_payer::AccountPayer,
     config::Config as NCNProgramConfig,
+    constants::MAX_FEE_BPS,
     ncn_operator_account::NCNOperatorAccount,
     snapshot::{OperatorSnapshot, Snapshot},
+    utils::get_epoch,
     vault_registry::VaultRegistry,
     vote_counter::VoteCounter,
 };
@@ -160,7 +168,67 @@ pub async fn get_or_create_vault_registry(handler: &CliHandler) -> Result<VaultR
     }
 }
 
+/// Slots a cached snapshot is reused for, counted from the slot it was fetched at
+pub const SNAPSHOT_CACHE_SLOTS: u64 = 10;
+
+/// In-process cache of the snapshot read by `get_snapshot`, keyed by NCN and reused for
+/// `SNAPSHOT_CACHE_SLOTS` slots. Cleared whenever the CLI sends a transaction.
+#[derive(Default)]
+pub struct SnapshotCache {
+    entry: Mutex<Option<(Pubkey, u64, Box<Snapshot>)>>,
+}
+
+impl SnapshotCache {
+    /// The cached snapshot of `ncn` when it was fetched less than `SNAPSHOT_CACHE_SLOTS` slots
+    /// before `current_slot`, otherwise the snapshot returned by `fetch`, which is then cached
+    pub async fn get_or_fetch<F, Fut>(
+        &self,
+        ncn: &Pubkey,
+        current_slot: u64,
+        fetch: F,
+    ) -> Result<Snapshot>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Snapshot>>,
+    {
+        if let Some((cached_ncn, slot, snapshot)) = self.lock().as_ref() {
+            let window = *slot..slot.saturating_add(SNAPSHOT_CACHE_SLOTS);
+            if cached_ncn == ncn && window.contains(&current_slot) {
+                return Ok(**snapshot);
+            }
+        }
+
+        let snapshot = fetch().await?;
+        *self.lock() = Some((*ncn, current_slot, Box::new(snapshot)));
+        Ok(snapshot)
+    }
+
+    /// Drops the cached snapshot, the next read fetches it again
+    pub fn invalidate(&self) {
+        *self.lock() = None;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<(Pubkey, u64, Box<Snapshot>)>> {
+        self.entry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The snapshot of the NCN, reused from the handler's `SnapshotCache` when enabled
 pub async fn get_snapshot(handler: &CliHandler, _epoch: u64) -> Result<Snapshot> {
+    let Some(snapshot_cache) = &handler.snapshot_cache else {
+        return fetch_snapshot(handler).await;
+    };
+
+    let ncn = *handler.ncn()?;
+    let current_slot = handler.rpc_client().get_slot().await?;
+    snapshot_cache
+        .get_or_fetch(&ncn, current_slot, || fetch_snapshot(handler))
+        .await
+}
+
+async fn fetch_snapshot(handler: &CliHandler) -> Result<Snapshot> {
     let (address, _, _) = Snapshot::find_program_address(&handler.ncn_program_id, handler.ncn()?);
 
     let account = get_account(handler, &address).await?;
@@ -171,6 +239,7 @@ pub async fn get_snapshot(handler: &CliHandler, _epoch: u64) -> Result<Snapshot>
     let account = account.unwrap();
 
     let account = Snapshot::try_from_slice_unchecked(account.data.as_slice())?;
+    account.check_version()?;
     Ok(*account)
 }
 
@@ -229,6 +298,27 @@ pub async fn get_operator_snapshot(
     Ok(*operator_snapshot)
 }
 
+/// Active operators with the minimum stake in the current epoch, i.e. the operators whose
+/// signatures count towards a vote right now
+pub async fn get_eligible_voters(handler: &CliHandler) -> Result<Vec<OperatorSnapshot>> {
+    let client = handler.rpc_client();
+
+    let snapshot = get_snapshot(handler, handler.epoch).await?;
+    let config = get_ncn_program_config(handler).await?;
+
+    let slot = client.get_epoch_info().await?.absolute_slot;
+    let current_epoch = config.current_epoch(slot)?;
+    let snapshot_epoch = config.current_epoch(snapshot.last_snapshot_slot())?;
+
+    let eligible_voters = snapshot
+        .get_eligible_voters(current_epoch, snapshot_epoch)?
+        .into_iter()
+        .copied()
+        .collect();
+
+    Ok(eligible_voters)
+}
+
 pub async fn get_account_payer(handler: &CliHandler) -> Result<Account> {
     let (address, _, _) =
         AccountPayer::find_program_address(&handler.ncn_program_id, handler.ncn()?);
@@ -657,6 +747,54 @@ pub async fn get_vote_counter(handler: &CliHandler) -> Result<VoteCounter> {
     Ok(*vote_counter)
 }
 
+/// Builds the message operators sign to vote at `slot`, exactly as `CastVote` does from the NCN
+/// epoch of the slot and the vote counter value
+pub fn vote_signing_payload_at(
+    ncn: &Pubkey,
+    slot: u64,
+    epoch_length: u64,
+    count: u64,
+) -> Result<[u8; 32]> {
+    let epoch = get_epoch(slot, epoch_length)?;
+    Ok(NCNProgramConfig::consensus_message(ncn, epoch, count))
+}
+
+/// Builds the message operators sign to vote on the current vote counter value in the current
+/// NCN epoch
+pub async fn get_vote_signing_payload(handler: &CliHandler) -> Result<[u8; 32]> {
+    let client = handler.rpc_client();
+
+    let vote_counter = get_vote_counter(handler).await?;
+    let restaking_config = get_restaking_config(handler).await?;
+    let slot = client.get_epoch_info().await?.absolute_slot;
+
+    vote_signing_payload_at(
+        handler.ncn()?,
+        slot,
+        restaking_config.epoch_length(),
+        vote_counter.count(),
+    )
+}
+
+/// Builds the message operators sign to vote on `vote_counter` in NCN epoch `epoch`, defaulting
+/// to the on-chain vote counter value
+pub async fn get_vote_message(
+    handler: &CliHandler,
+    epoch: u64,
+    vote_counter: Option<u64>,
+) -> Result<[u8; 32]> {
+    let vote_counter = match vote_counter {
+        Some(vote_counter) => vote_counter,
+        None => get_vote_counter(handler).await?.count(),
+    };
+
+    Ok(NCNProgramConfig::consensus_message(
+        handler.ncn()?,
+        epoch,
+        vote_counter,
+    ))
+}
+
 pub async fn get_or_create_vote_counter(handler: &CliHandler) -> Result<VoteCounter> {
     let (address, _, _) =
         VoteCounter::find_program_address(&handler.ncn_program_id, handler.ncn()?);
@@ -1073,3 +1211,1153 @@ pub async fn get_all_ncn_operator_accounts(
 
     Ok(accounts)
 }
+
+/// Number of register + delegation-snapshot transactions needed to fully snapshot the NCN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotTransactionEstimate {
+    pub register_operator_transactions: u64,
+    pub snapshot_delegation_transactions: u64,
+}
+
+impl SnapshotTransactionEstimate {
+    /// Every operator not yet registered needs one `RegisterOperator` transaction, and every
+    /// operator-vault pair needs one `SnapshotVaultOperatorDelegation` transaction
+    pub const fn new(
+        operator_count: u64,
+        registered_operator_count: u64,
+        vault_count: u64,
+    ) -> Self {
+        Self {
+            register_operator_transactions: operator_count
+                .saturating_sub(registered_operator_count),
+            snapshot_delegation_transactions: operator_count.saturating_mul(vault_count),
+        }
+    }
+
+    /// The estimate for `snapshot`, whose registered operators need no `RegisterOperator`
+    /// transaction
+    pub fn from_snapshot(snapshot: &Snapshot, operator_count: u64, vault_count: u64) -> Self {
+        Self::new(operator_count, snapshot.operators_registered(), vault_count)
+    }
+
+    pub const fn total(&self) -> u64 {
+        self.register_operator_transactions
+            .saturating_add(self.snapshot_delegation_transactions)
+    }
+}
+
+pub async fn estimate_snapshot_transactions(
+    handler: &CliHandler,
+) -> Result<SnapshotTransactionEstimate> {
+    let operator_count = get_all_operators_in_ncn(handler).await?.len() as u64;
+    let snapshot = get_snapshot(handler, handler.epoch).await?;
+    let vault_count = get_vault_registry(handler)
+        .await?
+        .get_valid_vault_entries()
+        .len() as u64;
+
+    Ok(SnapshotTransactionEstimate::from_snapshot(
+        &snapshot,
+        operator_count,
+        vault_count,
+    ))
+}
+
+/// Whether every operator of the NCN is registered in the snapshot and every active one had its
+/// delegation snapshotted in `epoch`
+pub fn is_snapshot_finalized(
+    snapshot: &Snapshot,
+    operator_count: u64,
+    epoch_length: u64,
+    epoch: u64,
+) -> Result<bool> {
+    if !snapshot.has_every_operator(operator_count) {
+        return Ok(false);
+    }
+
+    for (_, operator_snapshot) in snapshot.registered_operator_snapshots() {
+        if operator_snapshot.is_active()
+            && get_epoch(operator_snapshot.last_snapshot_slot(), epoch_length)? < epoch
+        {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// The `(index, operator)` of every active registered operator whose delegation was not
+/// snapshotted in `epoch` yet, in snapshot index order, so a crank can resume where a previous
+/// batch stopped
+pub fn pending_snapshot_operators(
+    snapshot: &Snapshot,
+    epoch_length: u64,
+    epoch: u64,
+) -> Result<Vec<(u64, Pubkey)>> {
+    let mut pending = Vec::new();
+
+    for (_, operator_snapshot) in snapshot.registered_operator_snapshots() {
+        if operator_snapshot.is_active()
+            && get_epoch(operator_snapshot.last_snapshot_slot(), epoch_length)? < epoch
+        {
+            pending.push((
+                operator_snapshot.ncn_operator_index(),
+                *operator_snapshot.operator(),
+            ));
+        }
+    }
+
+    Ok(pending)
+}
+
+/// An operator whose snapshot G1 pubkey no longer matches its ncn operator account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleSnapshotKey {
+    pub operator: Pubkey,
+    pub snapshot_g1_pubkey: [u8; 32],
+    pub current_g1_pubkey: [u8; 32],
+}
+
+/// Compares the G1 pubkey of every registered operator in the snapshot against its ncn operator
+/// account, which is the authoritative copy after a key rotation
+pub fn find_stale_snapshot_keys(
+    snapshot: &Snapshot,
+    ncn_operator_accounts: &[NCNOperatorAccount],
+) -> Vec<StaleSnapshotKey> {
+    ncn_operator_accounts
+        .iter()
+        .filter_map(|ncn_operator_account| {
+            let operator = *ncn_operator_account.operator_pubkey();
+            let operator_snapshot = snapshot.find_operator_snapshot(&operator)?;
+
+            let snapshot_g1_pubkey = operator_snapshot.g1_pubkey();
+            let current_g1_pubkey = *ncn_operator_account.g1_pubkey();
+
+            (snapshot_g1_pubkey != current_g1_pubkey).then_some(StaleSnapshotKey {
+                operator,
+                snapshot_g1_pubkey,
+                current_g1_pubkey,
+            })
+        })
+        .collect()
+}
+
+/// Flags every operator whose snapshot key diverged from its ncn operator account and needs to be
+/// re-snapshotted
+pub async fn verify_snapshot_keys_current(handler: &CliHandler) -> Result<Vec<StaleSnapshotKey>> {
+    let snapshot = get_snapshot(handler, handler.epoch).await?;
+    let ncn_operator_accounts: Vec<NCNOperatorAccount> = get_all_ncn_operator_accounts(handler)
+        .await?
+        .into_iter()
+        .map(|(_, ncn_operator_account)| ncn_operator_account)
+        .collect();
+
+    let stale_keys = find_stale_snapshot_keys(&snapshot, &ncn_operator_accounts);
+
+    for stale_key in stale_keys.iter() {
+        warn!(
+            "Snapshot key for operator {} is outdated and needs re-snapshotting. Snapshot: {}, current: {}",
+            stale_key.operator,
+            hex::encode(stale_key.snapshot_g1_pubkey),
+            hex::encode(stale_key.current_g1_pubkey)
+        );
+    }
+
+    Ok(stale_keys)
+}
+
+/// An operator's share of the eligible stake weight before and after a simulated join, in bps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorStakeFraction {
+    pub operator: Pubkey,
+    pub before_bps: u64,
+    pub after_bps: u64,
+}
+
+/// How a new operator joining with a given stake weight would change the stake distribution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorJoinSimulation {
+    /// Whether the new operator meets the minimum stake, otherwise it changes nothing
+    pub new_operator_eligible: bool,
+    pub eligible_stake_weight_before: u128,
+    pub eligible_stake_weight_after: u128,
+    pub nakamoto_coefficient_before: u64,
+    pub nakamoto_coefficient_after: u64,
+    pub new_operator_fraction_bps: u64,
+    /// Every registered operator with the minimum stake, in snapshot order
+    pub operator_fractions: Vec<OperatorStakeFraction>,
+}
+
+fn stake_fraction_bps(stake_weight: u128, total_stake_weight: u128) -> Result<u64> {
+    if total_stake_weight == 0 {
+        return Ok(0);
+    }
+
+    let fraction_bps = stake_weight
+        .checked_mul(MAX_FEE_BPS as u128)
+        .ok_or_else(|| anyhow::anyhow!("Stake weight overflow"))?
+        / total_stake_weight;
+
+    Ok(fraction_bps as u64)
+}
+
+/// Smallest number of operators, largest first, holding more than a third of the combined stake
+/// weight, i.e. enough to stall consensus. 0 when there is no stake.
+pub fn nakamoto_coefficient(stake_weights: &[u128]) -> u64 {
+    let total_stake_weight = stake_weights.iter().fold(0u128, |total, stake_weight| {
+        total.saturating_add(*stake_weight)
+    });
+    if total_stake_weight == 0 {
+        return 0;
+    }
+
+    let mut sorted_stake_weights = stake_weights.to_vec();
+    sorted_stake_weights.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut cumulative_stake_weight: u128 = 0;
+    for (index, stake_weight) in sorted_stake_weights.iter().enumerate() {
+        cumulative_stake_weight = cumulative_stake_weight.saturating_add(*stake_weight);
+        if cumulative_stake_weight.saturating_mul(3) > total_stake_weight {
+            return index as u64 + 1;
+        }
+    }
+
+    sorted_stake_weights.len() as u64
+}
+
+/// Recomputes the eligible stake weight, Nakamoto coefficient and every eligible operator's
+/// fraction as if an operator with `stake_weight` joined `snapshot`
+pub fn operator_join_effect(
+    snapshot: &Snapshot,
+    stake_weight: u128,
+) -> Result<OperatorJoinSimulation> {
+    let eligible_operators: Vec<(Pubkey, u128)> = snapshot
+        .registered_operator_snapshots()
+        .filter(|(_, operator_snapshot)| {
+            operator_snapshot.is_active() && operator_snapshot.has_minimum_stake()
+        })
+        .map(|(_, operator_snapshot)| {
+            (
+                *operator_snapshot.operator(),
+                operator_snapshot.stake_weight().stake_weight(),
+            )
+        })
+        .collect();
+
+    let new_operator_eligible = stake_weight >= snapshot.minimum_stake().stake_weight();
+    let new_stake_weight = if new_operator_eligible {
+        stake_weight
+    } else {
+        0
+    };
+
+    let eligible_stake_weight_before = snapshot.total_eligible_stake_weight()?;
+    let eligible_stake_weight_after = eligible_stake_weight_before
+        .checked_add(new_stake_weight)
+        .ok_or_else(|| anyhow::anyhow!("Stake weight overflow"))?;
+
+    let mut stake_weights: Vec<u128> = eligible_operators
+        .iter()
+        .map(|(_, stake_weight)| *stake_weight)
+        .collect();
+    let nakamoto_coefficient_before = nakamoto_coefficient(&stake_weights);
+    if new_operator_eligible {
+        stake_weights.push(new_stake_weight);
+    }
+    let nakamoto_coefficient_after = nakamoto_coefficient(&stake_weights);
+
+    let operator_fractions = eligible_operators
+        .iter()
+        .map(|(operator, stake_weight)| {
+            Ok(OperatorStakeFraction {
+                operator: *operator,
+                before_bps: stake_fraction_bps(*stake_weight, eligible_stake_weight_before)?,
+                after_bps: stake_fraction_bps(*stake_weight, eligible_stake_weight_after)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(OperatorJoinSimulation {
+        new_operator_eligible,
+        eligible_stake_weight_before,
+        eligible_stake_weight_after,
+        nakamoto_coefficient_before,
+        nakamoto_coefficient_after,
+        new_operator_fraction_bps: stake_fraction_bps(
+            new_stake_weight,
+            eligible_stake_weight_after,
+        )?,
+        operator_fractions,
+    })
+}
+
+/// Reports how an operator joining with `stake_weight` would change the current snapshot's stake
+/// distribution, for capacity planning
+pub async fn simulate_operator_join(
+    handler: &CliHandler,
+    stake_weight: u128,
+) -> Result<OperatorJoinSimulation> {
+    let snapshot = get_snapshot(handler, handler.epoch).await?;
+    let simulation = operator_join_effect(&snapshot, stake_weight)?;
+
+    if !simulation.new_operator_eligible {
+        warn!(
+            "Stake weight {} is below the minimum stake {}, the operator would not be eligible",
+            stake_weight,
+            snapshot.minimum_stake().stake_weight()
+        );
+    }
+
+    for fraction in simulation.operator_fractions.iter() {
+        info!(
+            "Operator {}: {} bps -> {} bps",
+            fraction.operator, fraction.before_bps, fraction.after_bps
+        );
+    }
+
+    Ok(simulation)
+}
+
+/// Every precondition for an operator to take part in votes, see `check_operator_ready`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorReadiness {
+    /// The operator has an ncn operator account
+    pub registered: bool,
+    /// Its G1 and G2 pubkeys are set and belong to the same private key
+    pub valid_bn128_keys: bool,
+    /// Both sides of its NCN link are active in the restaking program
+    pub active_in_restaking: bool,
+    /// Its delegation was snapshotted in the checked epoch
+    pub snapshotted_this_epoch: bool,
+    /// Its snapshotted stake weight meets the minimum stake
+    pub meets_minimum_stake: bool,
+}
+
+impl OperatorReadiness {
+    pub const fn is_ready(&self) -> bool {
+        self.registered
+            && self.valid_bn128_keys
+            && self.active_in_restaking
+            && self.snapshotted_this_epoch
+            && self.meets_minimum_stake
+    }
+}
+
+/// Evaluates the readiness checks of an operator from its accounts, `None` for the ones that do
+/// not exist
+pub fn operator_readiness(
+    ncn_operator_account: Option<&NCNOperatorAccount>,
+    active_in_restaking: bool,
+    operator_snapshot: Option<&OperatorSnapshot>,
+    epoch_length: u64,
+    epoch: u64,
+) -> Result<OperatorReadiness> {
+    let registered = ncn_operator_account.is_some_and(|account| !account.is_empty());
+    let valid_bn128_keys = ncn_operator_account.is_some_and(|account| {
+        account.g1_pubkey() != &[0; 32]
+            && account.g2_pubkey() != &[0; 64]
+            && account.verify_keypair().is_ok()
+    });
+
+    let snapshotted_this_epoch = match operator_snapshot {
+        Some(operator_snapshot) => {
+            operator_snapshot.is_active()
+                && get_epoch(operator_snapshot.last_snapshot_slot(), epoch_length)? >= epoch
+        }
+        None => false,
+    };
+    let meets_minimum_stake = snapshotted_this_epoch
+        && operator_snapshot.is_some_and(OperatorSnapshot::has_minimum_stake);
+
+    Ok(OperatorReadiness {
+        registered,
+        valid_bn128_keys,
+        active_in_restaking,
+        snapshotted_this_epoch,
+        meets_minimum_stake,
+    })
+}
+
+/// Checks whether `operator` is registered with valid BN128 keys, active in restaking,
+/// snapshotted in `epoch` and meets the minimum stake, warning about every failed check
+pub async fn check_operator_ready(
+    handler: &CliHandler,
+    operator: &Pubkey,
+    epoch: u64,
+) -> Result<OperatorReadiness> {
+    let (ncn_operator_account_address, _, _) =
+        NCNOperatorAccount::find_program_address(&handler.ncn_program_id, handler.ncn()?, operator);
+    let ncn_operator_account = match get_account(handler, &ncn_operator_account_address).await? {
+        Some(account) => Some(*NCNOperatorAccount::try_from_slice_unchecked(
+            account.data.as_slice(),
+        )?),
+        None => None,
+    };
+
+    let epoch_length = get_restaking_config(handler).await?.epoch_length();
+    let slot = get_current_slot(handler).await?;
+    let active_in_restaking = match get_ncn_operator_state(handler, operator).await {
+        Ok(ncn_operator_state) => {
+            let ncn_state = ncn_operator_state
+                .ncn_opt_in_state
+                .state(slot, epoch_length)
+                .map_err(|e| anyhow::anyhow!("Failed to read NCN opt in state: {:?}", e))?;
+            let operator_state = ncn_operator_state
+                .operator_opt_in_state
+                .state(slot, epoch_length)
+                .map_err(|e| anyhow::anyhow!("Failed to read operator opt in state: {:?}", e))?;
+            ncn_state == SlotToggleState::Active && operator_state == SlotToggleState::Active
+        }
+        Err(_) => false,
+    };
+
+    let snapshot = get_snapshot(handler, epoch).await.ok();
+    let operator_snapshot = snapshot
+        .as_ref()
+        .and_then(|snapshot| snapshot.find_operator_snapshot(operator));
+
+    let readiness = operator_readiness(
+        ncn_operator_account.as_ref(),
+        active_in_restaking,
+        operator_snapshot,
+        epoch_length,
+        epoch,
+    )?;
+
+    for (check, passed) in [
+        ("registered", readiness.registered),
+        ("valid BN128 keys", readiness.valid_bn128_keys),
+        ("active in restaking", readiness.active_in_restaking),
+        ("snapshotted this epoch", readiness.snapshotted_this_epoch),
+        ("meets minimum stake", readiness.meets_minimum_stake),
+    ] {
+        if !passed {
+            warn!("Operator {} check failed: {}", operator, check);
+        }
+    }
+
+    Ok(readiness)
+}
+
+/// An operator's snapshotted G1 pubkey next to its local one, see `verify_operator_registration`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorRegistrationCheck {
+    pub ncn_operator_index: u64,
+    pub snapshot_g1_pubkey: [u8; 32],
+    pub local_g1_pubkey: [u8; 32],
+}
+
+impl OperatorRegistrationCheck {
+    pub fn matches(&self) -> bool {
+        self.snapshot_g1_pubkey == self.local_g1_pubkey
+    }
+}
+
+/// Compares the G1 pubkey `operator` was snapshotted with against `local_g1_pubkey`. Fails when
+/// the operator is not registered in the snapshot.
+pub fn operator_registration_check(
+    snapshot: &Snapshot,
+    operator: &Pubkey,
+    local_g1_pubkey: &[u8; 32],
+) -> Result<OperatorRegistrationCheck> {
+    let operator_snapshot = snapshot
+        .find_operator_snapshot(operator)
+        .ok_or_else(|| anyhow::anyhow!("Operator snapshot not found for operator: {}", operator))?;
+
+    Ok(OperatorRegistrationCheck {
+        ncn_operator_index: operator_snapshot.ncn_operator_index(),
+        snapshot_g1_pubkey: operator_snapshot.g1_pubkey(),
+        local_g1_pubkey: *local_g1_pubkey,
+    })
+}
+
+/// Checks that `operator` landed in the snapshot with `local_g1_pubkey`, warning on a mismatch
+pub async fn verify_operator_registration(
+    handler: &CliHandler,
+    operator: &Pubkey,
+    local_g1_pubkey: &[u8; 32],
+) -> Result<OperatorRegistrationCheck> {
+    let snapshot = get_snapshot(handler, handler.epoch).await?;
+    let check = operator_registration_check(&snapshot, operator, local_g1_pubkey)?;
+
+    if !check.matches() {
+        warn!(
+            "G1 pubkey of operator {} does not match the snapshot. Snapshot: {}, local: {}",
+            operator,
+            hex::encode(check.snapshot_g1_pubkey),
+            hex::encode(check.local_g1_pubkey)
+        );
+    }
+
+    Ok(check)
+}
+
+/// How one operator changed between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorSnapshotChange {
+    pub operator: Pubkey,
+    pub stake_weight_before: u128,
+    pub stake_weight_after: u128,
+    pub eligible_before: bool,
+    pub eligible_after: bool,
+    pub active_before: bool,
+    pub active_after: bool,
+}
+
+/// Operator changes between two snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub added: Vec<Pubkey>,
+    pub removed: Vec<Pubkey>,
+    /// Operators in both snapshots whose stake weight, minimum stake eligibility or active
+    /// status changed
+    pub changed: Vec<OperatorSnapshotChange>,
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for operator in self.added.iter() {
+            writeln!(f, "Added:   {}", operator)?;
+        }
+        for operator in self.removed.iter() {
+            writeln!(f, "Removed: {}", operator)?;
+        }
+        for change in self.changed.iter() {
+            writeln!(
+                f,
+                "Changed: {} stake weight {} -> {}, eligible {} -> {}, active {} -> {}",
+                change.operator,
+                change.stake_weight_before,
+                change.stake_weight_after,
+                change.eligible_before,
+                change.eligible_after,
+                change.active_before,
+                change.active_after
+            )?;
+        }
+        if self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty() {
+            writeln!(f, "No operator changes")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the operators of `before` and `after`, in `after`'s snapshot order
+pub fn diff_snapshots(before: &Snapshot, after: &Snapshot) -> SnapshotDiff {
+    let mut diff = SnapshotDiff::default();
+
+    for (_, operator_snapshot) in after.registered_operator_snapshots() {
+        let operator = *operator_snapshot.operator();
+        let Some(previous) = before.find_operator_snapshot(&operator) else {
+            diff.added.push(operator);
+            continue;
+        };
+
+        let change = OperatorSnapshotChange {
+            operator,
+            stake_weight_before: previous.stake_weight().stake_weight(),
+            stake_weight_after: operator_snapshot.stake_weight().stake_weight(),
+            eligible_before: previous.has_minimum_stake(),
+            eligible_after: operator_snapshot.has_minimum_stake(),
+            active_before: previous.is_active(),
+            active_after: operator_snapshot.is_active(),
+        };
+        if change.stake_weight_before != change.stake_weight_after
+            || change.eligible_before != change.eligible_after
+            || change.active_before != change.active_after
+        {
+            diff.changed.push(change);
+        }
+    }
+
+    diff.removed = before
+        .registered_operator_snapshots()
+        .map(|(_, operator_snapshot)| *operator_snapshot.operator())
+        .filter(|operator| after.find_operator_snapshot(operator).is_none())
+        .collect();
+
+    diff
+}
+
+/// Path of the snapshot backup of `epoch` under `backup_dir`
+pub fn snapshot_backup_path(backup_dir: &str, epoch: u64) -> PathBuf {
+    Path::new(backup_dir).join(format!("snapshot-{}.bin", epoch))
+}
+
+/// Restores a snapshot from a backup file written by `backup_snapshot`
+pub fn load_snapshot_backup(path: &Path) -> Result<Box<Snapshot>> {
+    let bytes = fs::read(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read snapshot backup {}: {}", path.display(), e))?;
+
+    Snapshot::from_backup_bytes(&bytes).map_err(|e| {
+        anyhow::anyhow!(
+            "Failed to restore snapshot backup {}: {:?}",
+            path.display(),
+            e
+        )
+    })
+}
+
+/// The snapshot as pretty printed JSON, with the registered operators only
+pub fn snapshot_json(snapshot: &Snapshot) -> Result<String> {
+    serde_json::to_string_pretty(snapshot)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize the snapshot: {}", e))
+}
+
+/// Saves the on-chain snapshot as the backup of the current epoch under `backup_dir`
+pub async fn backup_snapshot(handler: &CliHandler, backup_dir: &str) -> Result<PathBuf> {
+    let snapshot = get_snapshot(handler, handler.epoch).await?;
+
+    fs::create_dir_all(backup_dir)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", backup_dir, e))?;
+    let path = snapshot_backup_path(backup_dir, handler.epoch);
+    fs::write(&path, snapshot.to_backup_bytes()).map_err(|e| {
+        anyhow::anyhow!("Failed to write snapshot backup {}: {}", path.display(), e)
+    })?;
+
+    Ok(path)
+}
+
+/// The snapshot of `epoch`: its backup under `backup_dir`, or the on-chain snapshot when `epoch`
+/// is the current epoch and no backup was taken. The program keeps a single snapshot per NCN,
+/// so earlier epochs are only available as backups.
+pub async fn get_snapshot_at_epoch(
+    handler: &CliHandler,
+    backup_dir: &str,
+    epoch: u64,
+) -> Result<Box<Snapshot>> {
+    let path = snapshot_backup_path(backup_dir, epoch);
+    if path.exists() {
+        return load_snapshot_backup(&path);
+    }
+
+    if epoch == handler.epoch {
+        return Ok(Box::new(get_snapshot(handler, epoch).await?));
+    }
+
+    Err(anyhow::anyhow!(
+        "No snapshot backup for epoch {} at {}",
+        epoch,
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{
+        g1_point::G1CompressedPoint, g2_point::G2CompressedPoint, privkey::PrivKey,
+        stake_weight::StakeWeights, vote_counter::vote_signing_payload,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot_transaction_estimate() {
+        // 10 operators, 4 already registered, 1 vault
+        let estimate = SnapshotTransactionEstimate::new(10, 4, 1);
+        assert_eq!(estimate.register_operator_transactions, 6);
+        assert_eq!(estimate.snapshot_delegation_transactions, 10);
+        assert_eq!(estimate.total(), 16);
+
+        // Fully registered NCN only needs delegation snapshots
+        let estimate = SnapshotTransactionEstimate::new(3, 3, 2);
+        assert_eq!(estimate.register_operator_transactions, 0);
+        assert_eq!(estimate.snapshot_delegation_transactions, 6);
+        assert_eq!(estimate.total(), 6);
+
+        // Empty NCN
+        assert_eq!(SnapshotTransactionEstimate::new(0, 0, 1).total(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_transaction_estimate_from_snapshot() {
+        // 5 operators in the NCN, 3 of them registered in the snapshot, 2 vaults
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            0,
+            StakeWeights::new(1),
+        ));
+        for index in 0..3 {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                0,
+                true,
+                index,
+                index,
+                G1CompressedPoint::from_random().0,
+                [0; 64],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 0)
+                .unwrap();
+        }
+
+        let estimate = SnapshotTransactionEstimate::from_snapshot(&snapshot, 5, 2);
+        assert_eq!(estimate.register_operator_transactions, 2);
+        assert_eq!(estimate.snapshot_delegation_transactions, 10);
+        assert_eq!(estimate.total(), 12);
+    }
+
+    #[test]
+    fn test_pending_snapshot_operators_resume_after_batch() {
+        const EPOCH_LENGTH: u64 = 100;
+        const EPOCH: u64 = 3;
+
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            0,
+            StakeWeights::new(1),
+        ));
+        let operators: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        for (index, operator) in operators.iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                operator,
+                0,
+                true,
+                index as u64,
+                index as u64,
+                G1CompressedPoint::from_random().0,
+                [0; 64],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 0)
+                .unwrap();
+        }
+
+        let pending = pending_snapshot_operators(&snapshot, EPOCH_LENGTH, EPOCH).unwrap();
+        assert_eq!(pending.len(), 5);
+
+        // A batch that stopped after the first three operators
+        for index in 0..3 {
+            let mut operator_snapshot = *snapshot.get_operator_snapshot(index).unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    EPOCH * EPOCH_LENGTH,
+                    &StakeWeights::new(1),
+                    &StakeWeights::new(1),
+                    snapshot.minimum_stake(),
+                )
+                .unwrap();
+            snapshot.update_operator_snapshot(index as usize, &operator_snapshot);
+        }
+
+        assert_eq!(
+            pending_snapshot_operators(&snapshot, EPOCH_LENGTH, EPOCH).unwrap(),
+            vec![(3, operators[3]), (4, operators[4])]
+        );
+    }
+
+    #[test]
+    fn test_find_stale_snapshot_keys_after_rotation() {
+        let ncn = Pubkey::new_unique();
+        let mut snapshot = Box::new(Snapshot::new(&ncn, 1, 100, StakeWeights::new(1)));
+        let mut ncn_operator_accounts = Vec::new();
+
+        for index in 0..2 {
+            let operator = Pubkey::new_unique();
+            let privkey = PrivKey::from_random();
+            let g1_pubkey = G1CompressedPoint::try_from(privkey).unwrap().0;
+            let g2_pubkey = G2CompressedPoint::try_from(&privkey).unwrap().0;
+
+            let operator_snapshot =
+                OperatorSnapshot::new(&operator, 100, true, index, index, g1_pubkey, g2_pubkey)
+                    .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+
+            ncn_operator_accounts.push(NCNOperatorAccount::new(
+                &ncn, &operator, &g1_pubkey, &g2_pubkey, index, 100, 255,
+            ));
+        }
+
+        assert!(find_stale_snapshot_keys(&snapshot, &ncn_operator_accounts).is_empty());
+
+        // Rotate the second operator's keys after the snapshot was taken
+        let snapshot_g1_pubkey = *ncn_operator_accounts[1].g1_pubkey();
+        let new_privkey = PrivKey::from_random();
+        let new_g1_pubkey = G1CompressedPoint::try_from(new_privkey).unwrap().0;
+        let new_g2_pubkey = G2CompressedPoint::try_from(&new_privkey).unwrap().0;
+        ncn_operator_accounts[1]
+            .update_keys(&new_g1_pubkey, &new_g2_pubkey, 200)
+            .unwrap();
+
+        assert_eq!(
+            find_stale_snapshot_keys(&snapshot, &ncn_operator_accounts),
+            vec![StaleSnapshotKey {
+                operator: *ncn_operator_accounts[1].operator_pubkey(),
+                snapshot_g1_pubkey,
+                current_g1_pubkey: new_g1_pubkey,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_operator_join_effect_large_operator() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            100,
+            StakeWeights::new(10),
+        ));
+
+        // Three equal operators and one below the minimum stake
+        let operators: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        for (index, stake_weight) in [100, 100, 100, 5].into_iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &operators[index],
+                100,
+                true,
+                index as u64,
+                index as u64,
+                G1CompressedPoint::from_random().0,
+                [0; 64],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(10),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        let simulation = operator_join_effect(&snapshot, 300).unwrap();
+        assert!(simulation.new_operator_eligible);
+        assert_eq!(simulation.eligible_stake_weight_before, 300);
+        assert_eq!(simulation.eligible_stake_weight_after, 600);
+        assert_eq!(simulation.nakamoto_coefficient_before, 2);
+        assert_eq!(simulation.nakamoto_coefficient_after, 1);
+        assert_eq!(simulation.new_operator_fraction_bps, 5_000);
+        assert_eq!(
+            simulation.operator_fractions,
+            operators[..3]
+                .iter()
+                .map(|operator| OperatorStakeFraction {
+                    operator: *operator,
+                    before_bps: 3_333,
+                    after_bps: 1_666,
+                })
+                .collect::<Vec<_>>()
+        );
+
+        // An operator below the minimum stake changes nothing
+        let simulation = operator_join_effect(&snapshot, 5).unwrap();
+        assert!(!simulation.new_operator_eligible);
+        assert_eq!(simulation.eligible_stake_weight_after, 300);
+        assert_eq!(simulation.nakamoto_coefficient_after, 2);
+        assert_eq!(simulation.new_operator_fraction_bps, 0);
+        assert!(simulation
+            .operator_fractions
+            .iter()
+            .all(|fraction| fraction.after_bps == fraction.before_bps));
+    }
+
+    /// Snapshot with the given operators, stake weights and active status, a minimum stake of 10
+    fn diff_fixture_snapshot(operators: &[(Pubkey, u128, bool)]) -> Box<Snapshot> {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            100,
+            StakeWeights::new(10),
+        ));
+
+        for (index, (operator, stake_weight, is_active)) in operators.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                *is_active,
+                index as u64,
+                index as u64,
+                G1CompressedPoint::from_random().0,
+                [0; 64],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(*stake_weight),
+                    &StakeWeights::new(*stake_weight),
+                    &StakeWeights::new(10),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        snapshot
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let operators: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+        let before = diff_fixture_snapshot(&[
+            (operators[0], 100, true),
+            (operators[1], 100, true),
+            (operators[2], 100, true),
+            (operators[3], 100, true),
+        ]);
+        // Operator 0 gains stake, 1 is unchanged, 2 drops below the minimum, 3 leaves and 4 joins
+        let after = diff_fixture_snapshot(&[
+            (operators[0], 150, true),
+            (operators[1], 100, true),
+            (operators[2], 5, true),
+            (operators[4], 50, true),
+        ]);
+
+        // Round trip the earlier snapshot through a backup file, as `diff-snapshots` loads it
+        let backup_dir = tempfile::tempdir().unwrap();
+        let path = snapshot_backup_path(backup_dir.path().to_str().unwrap(), 7);
+        fs::write(&path, before.to_backup_bytes()).unwrap();
+        let before = load_snapshot_backup(&path).unwrap();
+
+        let diff = diff_snapshots(&before, &after);
+        assert_eq!(diff.added, vec![operators[4]]);
+        assert_eq!(diff.removed, vec![operators[3]]);
+        assert_eq!(
+            diff.changed,
+            vec![
+                OperatorSnapshotChange {
+                    operator: operators[0],
+                    stake_weight_before: 100,
+                    stake_weight_after: 150,
+                    eligible_before: true,
+                    eligible_after: true,
+                    active_before: true,
+                    active_after: true,
+                },
+                OperatorSnapshotChange {
+                    operator: operators[2],
+                    stake_weight_before: 100,
+                    stake_weight_after: 5,
+                    eligible_before: true,
+                    eligible_after: false,
+                    active_before: true,
+                    active_after: true,
+                },
+            ]
+        );
+
+        assert_eq!(diff_snapshots(&after, &after), SnapshotDiff::default());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_cache_reuses_snapshot_within_window() {
+        async fn read(
+            cache: &SnapshotCache,
+            ncn: &Pubkey,
+            slot: u64,
+            snapshot: &Snapshot,
+            fetches: &mut usize,
+        ) -> Snapshot {
+            cache
+                .get_or_fetch(ncn, slot, || {
+                    *fetches += 1;
+                    std::future::ready(Ok(*snapshot))
+                })
+                .await
+                .unwrap()
+        }
+
+        let ncn = Pubkey::new_unique();
+        let snapshot = diff_fixture_snapshot(&[(Pubkey::new_unique(), 100, true)]);
+        let cache = SnapshotCache::default();
+        let mut fetches = 0;
+
+        // Two reads within the window only fetch once
+        read(&cache, &ncn, 100, &snapshot, &mut fetches).await;
+        let cached = read(&cache, &ncn, 105, &snapshot, &mut fetches).await;
+        assert_eq!(fetches, 1);
+        assert_eq!(cached.content_hash(), snapshot.content_hash());
+
+        // Past the window, for another NCN and after a transaction it is fetched again
+        read(
+            &cache,
+            &ncn,
+            100 + SNAPSHOT_CACHE_SLOTS,
+            &snapshot,
+            &mut fetches,
+        )
+        .await;
+        assert_eq!(fetches, 2);
+        read(&cache, &Pubkey::new_unique(), 111, &snapshot, &mut fetches).await;
+        assert_eq!(fetches, 3);
+        read(&cache, &ncn, 111, &snapshot, &mut fetches).await;
+        read(&cache, &ncn, 112, &snapshot, &mut fetches).await;
+        assert_eq!(fetches, 4);
+        cache.invalidate();
+        read(&cache, &ncn, 112, &snapshot, &mut fetches).await;
+        assert_eq!(fetches, 5);
+    }
+
+    #[test]
+    fn test_snapshot_json() {
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut snapshot = diff_fixture_snapshot(&[
+            (operators[0], 100, true),
+            (operators[1], 50, true),
+            (operators[2], 5, false),
+        ]);
+        let g1_pubkeys: Vec<[u8; 32]> = snapshot
+            .operator_snapshots()
+            .iter()
+            .take(3)
+            .map(|operator_snapshot| operator_snapshot.g1_pubkey())
+            .collect();
+        for g1_pubkey in g1_pubkeys.iter() {
+            snapshot.register_operator_g1_pubkey(g1_pubkey).unwrap();
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_str(&snapshot_json(&snapshot).unwrap()).unwrap();
+
+        assert_eq!(json["operators_registered"], 3);
+        assert_eq!(json["operators"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            json["total_aggregated_g1_pubkey"],
+            snapshot
+                .total_aggregated_g1_point()
+                .unwrap()
+                .to_hex()
+                .unwrap()
+        );
+
+        let operator = &json["operators"][0];
+        assert_eq!(operator["operator"], operators[0].to_string());
+        assert_eq!(operator["stake_weight"]["stake_weight"], 100);
+        assert_eq!(
+            operator["g1_pubkey"],
+            G1CompressedPoint(snapshot.operator_snapshots()[0].g1_pubkey()).to_hex()
+        );
+    }
+
+    #[test]
+    fn test_operator_readiness() {
+        const EPOCH_LENGTH: u64 = 100;
+        const EPOCH: u64 = 3;
+
+        let ncn = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let privkey = PrivKey::from_random();
+        let g1_pubkey = G1CompressedPoint::try_from(privkey).unwrap().0;
+        let g2_pubkey = G2CompressedPoint::try_from(&privkey).unwrap().0;
+
+        let mut operator_snapshot =
+            OperatorSnapshot::new(&operator, 0, true, 0, 0, g1_pubkey, g2_pubkey).unwrap();
+        operator_snapshot
+            .snapshot_vault_operator_delegation(
+                EPOCH * EPOCH_LENGTH,
+                &StakeWeights::new(100),
+                &StakeWeights::new(100),
+                &StakeWeights::new(10),
+            )
+            .unwrap();
+
+        let ncn_operator_account =
+            NCNOperatorAccount::new(&ncn, &operator, &g1_pubkey, &g2_pubkey, 0, 0, 255);
+        let readiness = operator_readiness(
+            Some(&ncn_operator_account),
+            true,
+            Some(&operator_snapshot),
+            EPOCH_LENGTH,
+            EPOCH,
+        )
+        .unwrap();
+        assert_eq!(
+            readiness,
+            OperatorReadiness {
+                registered: true,
+                valid_bn128_keys: true,
+                active_in_restaking: true,
+                snapshotted_this_epoch: true,
+                meets_minimum_stake: true,
+            }
+        );
+        assert!(readiness.is_ready());
+
+        // Registered without BN128 keys
+        let unkeyed_ncn_operator_account =
+            NCNOperatorAccount::new(&ncn, &operator, &[0; 32], &[0; 64], 0, 0, 255);
+        let readiness = operator_readiness(
+            Some(&unkeyed_ncn_operator_account),
+            true,
+            Some(&operator_snapshot),
+            EPOCH_LENGTH,
+            EPOCH,
+        )
+        .unwrap();
+        assert!(readiness.registered);
+        assert!(!readiness.valid_bn128_keys);
+        assert!(!readiness.is_ready());
+    }
+
+    #[test]
+    fn test_operator_registration_check() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            0,
+            StakeWeights::new(1),
+        ));
+        let operator = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot =
+            OperatorSnapshot::new(&operator, 0, true, 2, 2, g1_pubkey, [0; 64]).unwrap();
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 0)
+            .unwrap();
+
+        let check = operator_registration_check(&snapshot, &operator, &g1_pubkey).unwrap();
+        assert_eq!(check.ncn_operator_index, 2);
+        assert!(check.matches());
+
+        let other_g1_pubkey = G1CompressedPoint::from_random().0;
+        let check = operator_registration_check(&snapshot, &operator, &other_g1_pubkey).unwrap();
+        assert_eq!(check.snapshot_g1_pubkey, g1_pubkey);
+        assert!(!check.matches());
+
+        assert!(operator_registration_check(&snapshot, &Pubkey::new_unique(), &g1_pubkey).is_err());
+    }
+
+    #[test]
+    fn test_nakamoto_coefficient() {
+        assert_eq!(nakamoto_coefficient(&[]), 0);
+        assert_eq!(nakamoto_coefficient(&[0, 0]), 0);
+        assert_eq!(nakamoto_coefficient(&[100]), 1);
+        // Exactly a third is not enough to stall consensus
+        assert_eq!(nakamoto_coefficient(&[100, 100, 100]), 2);
+        assert_eq!(nakamoto_coefficient(&[10, 500, 10, 10]), 1);
+    }
+
+    #[test]
+    fn test_vote_signing_payload_matches_program() {
+        let ncn = Pubkey::new_unique();
+        let epoch_length = 150;
+
+        // Slots on both sides of an epoch boundary, as `CastVote` derives the epoch
+        for slot in [0, 149, 150, 1_000] {
+            let program_epoch = jito_jsm_core::get_epoch(slot, epoch_length).unwrap();
+            assert_eq!(
+                vote_signing_payload_at(&ncn, slot, epoch_length, 7).unwrap(),
+                vote_signing_payload(&ncn, program_epoch, 7, &[])
+            );
+            assert_eq!(
+                vote_signing_payload_at(&ncn, slot, epoch_length, 7).unwrap(),
+                NCNProgramConfig::consensus_message(&ncn, program_epoch, 7)
+            );
+        }
+
+        assert!(vote_signing_payload_at(&ncn, 1_000, 0, 7).is_err());
+    }
+}
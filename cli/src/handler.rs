@@ -4,18 +4,21 @@ use std::{collections::HashMap, mem::size_of, str::FromStr};
 use crate::{
     args::{Args, ProgramCommand},
     getters::{
+        backup_snapshot, check_operator_ready, diff_snapshots, estimate_snapshot_transactions,
         get_account_payer, get_all_ncn_operator_accounts, get_all_operators_in_ncn,
-        get_all_tickets, get_all_vaults_in_ncn, get_ncn, get_ncn_operator_state,
-        get_ncn_program_config, get_ncn_vault_ticket, get_operator_snapshot, get_snapshot,
-        get_total_epoch_rent_cost, get_vault_ncn_ticket, get_vault_operator_delegation,
-        get_vault_registry, get_vote_counter,
+        get_all_tickets, get_all_vaults_in_ncn, get_eligible_voters, get_ncn,
+        get_ncn_operator_state, get_ncn_program_config, get_ncn_vault_ticket,
+        get_operator_snapshot, get_snapshot, get_snapshot_at_epoch, get_total_epoch_rent_cost,
+        get_vault_ncn_ticket, get_vault_operator_delegation, get_vault_registry, get_vote_counter,
+        get_vote_message, get_vote_signing_payload, simulate_operator_join, snapshot_json,
+        verify_operator_registration, verify_snapshot_keys_current, SnapshotCache,
     },
     instructions::{
-        admin_create_config, admin_fund_account_payer, admin_register_st_mint, admin_set_new_admin,
-        admin_set_parameters, crank_register_vaults, crank_snapshot, crank_snapshot_unupdated,
-        create_snapshot, create_vault_registry, create_vote_counter, full_vault_update,
-        register_operator, register_vault, snapshot_vault_operator_delegation,
-        update_operator_ip_port,
+        admin_create_config, admin_fund_account_payer, admin_register_st_mint, admin_repair_config,
+        admin_set_new_admin, admin_set_parameters, crank_register_vaults, crank_snapshot,
+        crank_snapshot_unupdated, crank_until_finalized, create_snapshot, create_vault_registry,
+        create_vote_counter, full_vault_update, register_operator, register_vault,
+        snapshot_vault_operator_delegation, update_operator_ip_port,
     },
     keeper::keeper_loop::startup_ncn_keeper,
 };
@@ -50,6 +53,8 @@ pub struct CliHandler {
     pub rpc_client: RpcClient,
     pub retries: u64,
     pub priority_fee_micro_lamports: u64,
+    /// `None` when the snapshot cache is disabled with `--no-cache`
+    pub snapshot_cache: Option<SnapshotCache>,
 }
 
 impl CliHandler {
@@ -99,6 +104,7 @@ impl CliHandler {
             rpc_client,
             retries: args.transaction_retries,
             priority_fee_micro_lamports: args.priority_fee_micro_lamports,
+            snapshot_cache: (!args.no_cache).then(SnapshotCache::default),
         };
 
         handler.epoch = {
@@ -168,17 +174,47 @@ impl CliHandler {
             ProgramCommand::RunKeeper {
                 loop_timeout_ms,
                 error_timeout_ms,
-            } => startup_ncn_keeper(self, loop_timeout_ms, error_timeout_ms).await,
+                max_epochs,
+                vote_file,
+            } => {
+                startup_ncn_keeper(
+                    self,
+                    loop_timeout_ms,
+                    error_timeout_ms,
+                    max_epochs,
+                    vote_file,
+                )
+                .await
+            }
 
             // Cranks
             ProgramCommand::CrankRegisterVaults {} => crank_register_vaults(self).await,
 
-            ProgramCommand::CrankSnapshot {} => crank_snapshot(self, self.epoch).await,
+            ProgramCommand::CrankSnapshot {} => {
+                let summary = crank_snapshot(self, self.epoch).await?;
+                info!(
+                    "Snapshotted {} vaults, skipped {} missing vaults",
+                    summary.snapshotted_vaults.len(),
+                    summary.skipped_vaults.len()
+                );
+                Ok(())
+            }
 
             ProgramCommand::CrankSnapshotUnupdated { verbose } => {
                 crank_snapshot_unupdated(self, self.epoch, verbose).await
             }
 
+            ProgramCommand::CrankUntilFinalized { max_iterations } => {
+                if !crank_until_finalized(self, self.epoch, max_iterations).await? {
+                    return Err(anyhow!(
+                        "Snapshot for epoch {} did not finalize after {} attempts",
+                        self.epoch,
+                        max_iterations
+                    ));
+                }
+                Ok(())
+            }
+
             // Admin
             ProgramCommand::AdminCreateConfig {
                 ncn_fee_wallet,
@@ -188,6 +224,10 @@ impl CliHandler {
                 epochs_after_consensus_before_close,
                 tie_breaker_admin,
                 minimum_stake,
+                curve_id,
+                minimum_operators,
+                quorum_bps,
+                max_snapshot_age_slots,
             } => {
                 let tie_breaker = if let Some(admin_str) = tie_breaker_admin {
                     Some(
@@ -210,11 +250,37 @@ impl CliHandler {
                     valid_slots_after_consensus,
                     epochs_after_consensus_before_close,
                     minimum_stake,
+                    curve_id,
+                    minimum_operators,
+                    quorum_bps,
+                    max_snapshot_age_slots,
                 )
                 .await
             }
-            ProgramCommand::AdminRegisterStMint {} => {
-                admin_register_st_mint(self, self.vault()).await
+            ProgramCommand::AdminRepairConfig {
+                ncn_fee_wallet,
+                ncn_fee_bps,
+                epochs_before_stall,
+                valid_slots_after_consensus,
+                epochs_after_consensus_before_close,
+                minimum_stake,
+            } => {
+                let ncn_fee_wallet = Pubkey::from_str(&ncn_fee_wallet)
+                    .map_err(|e| anyhow!("Error parsing NCN fee wallet: {}", e))?;
+
+                admin_repair_config(
+                    self,
+                    ncn_fee_wallet,
+                    ncn_fee_bps.try_into()?,
+                    epochs_before_stall,
+                    valid_slots_after_consensus,
+                    epochs_after_consensus_before_close,
+                    minimum_stake,
+                )
+                .await
+            }
+            ProgramCommand::AdminRegisterStMint { weight } => {
+                admin_register_st_mint(self, self.vault(), weight).await
             }
             ProgramCommand::AdminSetTieBreaker { weather_status: _ } => {
                 // Tie breaker functionality has been removed
@@ -226,6 +292,13 @@ impl CliHandler {
                 epochs_after_consensus_before_close,
                 valid_slots_after_consensus,
                 starting_valid_epoch,
+                enforce_signer_order,
+                minimum_operators,
+                reject_duplicate_aggregates,
+                allow_multiple_votes_per_epoch,
+                quorum_bps,
+                max_snapshot_age_slots,
+                allow_ed25519_votes,
             } => {
                 admin_set_parameters(
                     self,
@@ -233,14 +306,28 @@ impl CliHandler {
                     epochs_after_consensus_before_close,
                     valid_slots_after_consensus,
                     starting_valid_epoch,
+                    enforce_signer_order,
+                    minimum_operators,
+                    reject_duplicate_aggregates,
+                    allow_multiple_votes_per_epoch,
+                    quorum_bps,
+                    max_snapshot_age_slots,
+                    allow_ed25519_votes,
                 )
                 .await?;
                 let config = get_ncn_program_config(self).await?;
-                info!("\n\n--- Parameters Set ---\nepochs_before_stall: {}\nepochs_after_consensus_before_close: {}\nvalid_slots_after_consensus: {}\nstarting_valid_epoch: {}\n",
+                info!("\n\n--- Parameters Set ---\nepochs_before_stall: {}\nepochs_after_consensus_before_close: {}\nvalid_slots_after_consensus: {}\nstarting_valid_epoch: {}\nenforce_signer_order: {}\nminimum_operators: {}\nreject_duplicate_aggregates: {}\nallow_multiple_votes_per_epoch: {}\nquorum_bps: {}\nmax_snapshot_age_slots: {}\nallow_ed25519_votes: {}\n",
                     config.epochs_before_stall(),
                     config.epochs_after_consensus_before_close(),
                     config.valid_slots_after_consensus(),
-                    config.starting_valid_epoch()
+                    config.starting_valid_epoch(),
+                    config.enforce_signer_order(),
+                    config.minimum_operators(),
+                    config.reject_duplicate_aggregates(),
+                    config.allow_multiple_votes_per_epoch(),
+                    config.quorum_bps(),
+                    config.max_snapshot_age_slots(),
+                    config.allow_ed25519_votes()
                 );
 
                 Ok(())
@@ -253,8 +340,17 @@ impl CliHandler {
                     .map_err(|e| anyhow!("Error parsing new admin: {}", e))?;
                 admin_set_new_admin(self, &new_admin, set_tie_breaker_admin).await
             }
-            ProgramCommand::AdminFundAccountPayer { amount_in_sol } => {
-                admin_fund_account_payer(self, amount_in_sol).await
+            ProgramCommand::AdminFundAccountPayer {
+                amount_in_sol,
+                account_payer,
+                yes,
+            } => {
+                let account_payer = account_payer
+                    .map(|account_payer| Pubkey::from_str(&account_payer))
+                    .transpose()
+                    .map_err(|e| anyhow!("Invalid account payer address: {}", e))?;
+
+                admin_fund_account_payer(self, amount_in_sol, account_payer, yes).await
             }
 
             // Instructions
@@ -264,55 +360,73 @@ impl CliHandler {
 
             ProgramCommand::RegisterVault {} => register_vault(self, self.vault()).await,
 
+            ProgramCommand::GenerateOperatorKeys { seed } => {
+                use crate::bls_keys::{generate_operator_keys, log_operator_keys};
+
+                let keys = generate_operator_keys(seed)?;
+                log_operator_keys(&keys);
+                Ok(())
+            }
+
             ProgramCommand::RegisterOperator {
                 operator,
                 g1_pubkey,
                 g2_pubkey,
                 signature,
                 keys_file,
+                operator_keystore,
             } => {
                 let operator = Pubkey::from_str(&operator)
                     .map_err(|e| anyhow!("Error parsing operator: {}", e))?;
 
-                // Handle BLS key generation/loading vs manual input
-                let (g1_array, g2_array, sig_array) = match (g1_pubkey, g2_pubkey, signature) {
-                    // All keys provided manually
-                    (Some(g1), Some(g2), Some(sig)) => {
-                        use crate::bls_keys::hex_to_bytes;
-                        (
-                            hex_to_bytes::<32>(&g1)?,
-                            hex_to_bytes::<64>(&g2)?,
-                            hex_to_bytes::<64>(&sig)?,
-                        )
-                    }
-                    // G1 and G2 pubkeys provided, generate signature
-                    (Some(g1), Some(g2), None) => {
-                        use crate::bls_keys::{generate_or_use_keys, generate_signature};
-
-                        let key_set = generate_or_use_keys(
-                            &operator,
-                            &keys_file,
-                            Some(g1.as_str()),
-                            Some(g2.as_str()),
-                        )?;
-                        let signature = generate_signature(&key_set)?;
-
-                        (key_set.g1_pubkey, key_set.g2_pubkey, signature)
-                    }
-                    // No keys provided - generate random keys
-                    (None, None, None) => {
-                        use crate::bls_keys::{generate_or_use_keys, generate_signature};
+                // Handle BLS key loading from a keystore, generation/loading vs manual input
+                let (g1_array, g2_array, sig_array) = if let Some(operator_keystore) =
+                    operator_keystore
+                {
+                    use crate::bls_keys::load_operator_keystore;
+
+                    let keys = load_operator_keystore(&operator_keystore)?;
+                    (keys.g1_pubkey, keys.g2_pubkey, keys.signature)
+                } else {
+                    match (g1_pubkey, g2_pubkey, signature) {
+                        // All keys provided manually
+                        (Some(g1), Some(g2), Some(sig)) => {
+                            use crate::bls_keys::hex_to_bytes;
+                            (
+                                hex_to_bytes::<32>(&g1)?,
+                                hex_to_bytes::<64>(&g2)?,
+                                hex_to_bytes::<64>(&sig)?,
+                            )
+                        }
+                        // G1 and G2 pubkeys provided, generate signature
+                        (Some(g1), Some(g2), None) => {
+                            use crate::bls_keys::{generate_or_use_keys, generate_signature};
+
+                            let key_set = generate_or_use_keys(
+                                &operator,
+                                &keys_file,
+                                Some(g1.as_str()),
+                                Some(g2.as_str()),
+                            )?;
+                            let signature = generate_signature(&key_set)?;
+
+                            (key_set.g1_pubkey, key_set.g2_pubkey, signature)
+                        }
+                        // No keys provided - generate random keys
+                        (None, None, None) => {
+                            use crate::bls_keys::{generate_or_use_keys, generate_signature};
 
-                        let key_set = generate_or_use_keys(&operator, &keys_file, None, None)?;
-                        let signature = generate_signature(&key_set)?;
+                            let key_set = generate_or_use_keys(&operator, &keys_file, None, None)?;
+                            let signature = generate_signature(&key_set)?;
 
-                        (key_set.g1_pubkey, key_set.g2_pubkey, signature)
-                    }
-                    // Partial keys provided - not allowed
-                    _ => {
-                        return Err(anyhow!(
-                            "Either provide G1 and G2 pubkeys (--g1-pubkey, --g2-pubkey) or none for auto-generation. Signature is auto-generated."
-                        ));
+                            (key_set.g1_pubkey, key_set.g2_pubkey, signature)
+                        }
+                        // Partial keys provided - not allowed
+                        _ => {
+                            return Err(anyhow!(
+                                "Either provide G1 and G2 pubkeys (--g1-pubkey, --g2-pubkey) or none for auto-generation. Signature is auto-generated."
+                            ));
+                        }
                     }
                 };
 
@@ -342,6 +456,8 @@ impl CliHandler {
                 aggregated_g2,
                 signers_bitmap,
                 message,
+                quorum_bps,
+                priority_fee,
             } => {
                 use crate::bls_keys::hex_to_bytes;
                 use crate::instructions::cast_vote;
@@ -354,37 +470,92 @@ impl CliHandler {
                 let message_bytes = if let Some(msg) = message {
                     hex_to_bytes::<32>(&msg)?
                 } else {
-                    // Get current vote counter as message
-                    let vote_counter = get_vote_counter(self).await?;
-                    let counter_bytes = vote_counter.count().to_le_bytes();
-                    let mut message_32 = [0u8; 32];
-                    message_32[..8].copy_from_slice(&counter_bytes);
-                    message_32
+                    // Sign the current vote counter value in the current epoch
+                    get_vote_signing_payload(self).await?
                 };
 
-                cast_vote(self, self.epoch, agg_sig, apk2, bitmap_bytes, message_bytes).await
+                cast_vote(
+                    self,
+                    self.epoch,
+                    agg_sig,
+                    apk2,
+                    bitmap_bytes,
+                    message_bytes,
+                    quorum_bps,
+                    priority_fee,
+                )
+                .await
+            }
+
+            ProgramCommand::SimulateVote {
+                operators,
+                signatures,
+                message,
+                quorum_bps,
+            } => {
+                use crate::bls_keys::{hex_to_bytes, simulate_vote};
+
+                let operators: Vec<Pubkey> = operators
+                    .split(',')
+                    .map(|operator| {
+                        Pubkey::from_str(operator.trim())
+                            .map_err(|e| anyhow!("Error parsing operator '{}': {}", operator, e))
+                    })
+                    .collect::<Result<_>>()?;
+                let signatures: Vec<[u8; 64]> = signatures
+                    .split(',')
+                    .map(|signature| hex_to_bytes::<64>(signature.trim()))
+                    .collect::<Result<_>>()?;
+                if operators.len() != signatures.len() {
+                    return Err(anyhow!("Number of operators and signatures must match"));
+                }
+
+                let message_bytes = if let Some(msg) = message {
+                    hex_to_bytes::<32>(&msg)?
+                } else {
+                    // Sign the current vote counter value in the current epoch
+                    get_vote_signing_payload(self).await?
+                };
+
+                let snapshot = get_snapshot(self, self.epoch).await?;
+                let signers: Vec<(Pubkey, [u8; 64])> =
+                    operators.into_iter().zip(signatures).collect();
+                let simulation = simulate_vote(&snapshot, &signers, &message_bytes, quorum_bps)?;
+
+                info!(
+                    "Signers bitmap: {}",
+                    hex::encode(&simulation.signers_bitmap)
+                );
+                info!("Signature valid: {}", simulation.signature_valid);
+                info!("Quorum met: {}", simulation.quorum_met);
+                info!(
+                    "Signer stake weight: {} of {} eligible",
+                    simulation.signer_stake_weight, simulation.eligible_stake_weight
+                );
+
+                if !simulation.passed() {
+                    return Err(anyhow!("Vote simulation failed"));
+                }
+                info!("Vote simulation passed");
+                Ok(())
             }
 
             ProgramCommand::GenerateVoteSignature {
                 private_key,
                 message,
             } => {
-                use crate::bls_keys::{generate_signature_from_private_key, hex_to_bytes};
+                use crate::bls_keys::{generate_vote_signature, hex_to_bytes};
 
                 let priv_key_bytes = hex_to_bytes::<32>(&private_key)?;
                 let message_bytes = if let Some(msg) = message {
                     hex_to_bytes::<32>(&msg)?
                 } else {
-                    // Get current vote counter as message
-                    let vote_counter = get_vote_counter(self).await?;
-                    let counter_bytes = vote_counter.count().to_le_bytes();
-                    let mut message_32 = [0u8; 32];
-                    message_32[..8].copy_from_slice(&counter_bytes);
-                    message_32
+                    // Sign the current vote counter value in the current epoch
+                    get_vote_signing_payload(self).await?
                 };
 
                 let signature =
-                    generate_signature_from_private_key(&priv_key_bytes, &message_bytes)?;
+                    generate_vote_signature(&priv_key_bytes, self.ncn()?, &message_bytes)?;
                 info!("Generated signature: {}", hex::encode(signature));
                 Ok(())
             }
@@ -416,6 +587,40 @@ impl CliHandler {
                 Ok(())
             }
 
+            ProgramCommand::AggregateAndVote {
+                signatures_dir,
+                quorum_bps,
+                priority_fee,
+            } => {
+                use crate::bls_keys::{aggregate_vote, load_partial_signatures};
+                use crate::instructions::cast_vote;
+
+                let signers = load_partial_signatures(&signatures_dir)?;
+                let snapshot = get_snapshot(self, self.epoch).await?;
+                let aggregation = aggregate_vote(&snapshot, &signers)?;
+                info!(
+                    "{} of {} operators signed, signers bitmap: {}",
+                    signers.len(),
+                    snapshot.operators_registered(),
+                    hex::encode(&aggregation.signers_bitmap)
+                );
+
+                // The operators sign the current vote counter value in the current epoch
+                let message = get_vote_signing_payload(self).await?;
+
+                cast_vote(
+                    self,
+                    self.epoch,
+                    aggregation.aggregated_signature,
+                    aggregation.aggregated_g2,
+                    aggregation.signers_bitmap,
+                    message,
+                    quorum_bps,
+                    priority_fee,
+                )
+                .await
+            }
+
             // Getters
             ProgramCommand::GetNcn {} => {
                 let ncn = get_ncn(self).await?;
@@ -506,6 +711,16 @@ impl CliHandler {
                 info!("Vote Counter: {:?}", vote_counter);
                 Ok(())
             }
+            ProgramCommand::ShowVoteCounter {} => {
+                let vote_counter = get_vote_counter(self).await?;
+                info!("{}", vote_counter);
+                Ok(())
+            }
+            ProgramCommand::VoteMessage { vote_counter } => {
+                let message = get_vote_message(self, self.epoch, vote_counter).await?;
+                println!("{}", hex::encode(message));
+                Ok(())
+            }
 
             ProgramCommand::GetSnapshot {} => {
                 let snapshot = get_snapshot(self, self.epoch).await?;
@@ -519,6 +734,53 @@ impl CliHandler {
                 info!("{}", operator_snapshot);
                 Ok(())
             }
+            ProgramCommand::ExportOperatorSnapshot { operator, output } => {
+                let operator = Pubkey::from_str(&operator)
+                    .map_err(|e| anyhow!("Error parsing operator: {}", e))?;
+                let operator_snapshot = get_operator_snapshot(self, &operator, self.epoch).await?;
+                let bytes = operator_snapshot.to_bytes();
+                std::fs::write(&output, &bytes)
+                    .map_err(|e| anyhow!("Failed to write {}: {}", output, e))?;
+                info!(
+                    "Operator snapshot of {} ({} bytes) written to {}",
+                    operator,
+                    bytes.len(),
+                    output
+                );
+                Ok(())
+            }
+            ProgramCommand::ListEligibleVoters {} => {
+                let eligible_voters = get_eligible_voters(self).await?;
+                info!(
+                    "\n\n--- Eligible Voters ---\nCount: {}\n",
+                    eligible_voters.len()
+                );
+                for operator_snapshot in eligible_voters.iter() {
+                    info!(
+                        "Operator {}: {}",
+                        operator_snapshot.operator(),
+                        operator_snapshot.stake_weight().stake_weight()
+                    );
+                }
+                Ok(())
+            }
+            ProgramCommand::RankOperators {} => {
+                let snapshot = get_snapshot(self, self.epoch).await?;
+                let ranked_operators = snapshot.operators_by_stake_weight();
+                info!(
+                    "\n\n--- Operators By Stake Weight ---\nCount: {}\n",
+                    ranked_operators.len()
+                );
+                for (rank, operator_snapshot) in ranked_operators.iter().enumerate() {
+                    info!(
+                        "{}. Operator {}: {}",
+                        rank + 1,
+                        operator_snapshot.operator(),
+                        operator_snapshot.stake_weight().stake_weight()
+                    );
+                }
+                Ok(())
+            }
             ProgramCommand::GetAccountPayer {} => {
                 let account_payer = get_account_payer(self).await?;
                 let (account_payer_address, _, _) =
@@ -538,6 +800,158 @@ impl CliHandler {
                 );
                 Ok(())
             }
+            ProgramCommand::GetSnapshotTransactionEstimate {} => {
+                let estimate = estimate_snapshot_transactions(self).await?;
+                info!(
+                    "\n\n--- Snapshot Transaction Estimate ---\nRegister Operator: {}\nSnapshot Delegation: {}\nTotal: {}\n",
+                    estimate.register_operator_transactions,
+                    estimate.snapshot_delegation_transactions,
+                    estimate.total()
+                );
+                Ok(())
+            }
+            ProgramCommand::VerifySnapshotKeys {} => {
+                let stale_keys = verify_snapshot_keys_current(self).await?;
+                info!(
+                    "\n\n--- Snapshot Keys ---\nOutdated: {}\n",
+                    stale_keys.len()
+                );
+                Ok(())
+            }
+            ProgramCommand::SnapshotHash {} => {
+                let snapshot = get_snapshot(self, self.epoch).await?;
+                info!(
+                    "\n\n--- Snapshot Hash ---\n{}\n",
+                    hex::encode(snapshot.content_hash())
+                );
+                Ok(())
+            }
+            ProgramCommand::SnapshotJson { backup_dir } => {
+                let snapshot = get_snapshot_at_epoch(self, &backup_dir, self.epoch).await?;
+                println!("{}", snapshot_json(&snapshot)?);
+                Ok(())
+            }
+            ProgramCommand::TotalStake {} => {
+                let snapshot = get_snapshot(self, self.epoch).await?;
+                info!(
+                    "\n\n--- Total Eligible Stake Weight ---\n{}\n",
+                    snapshot.total_eligible_stake_weight_saturating()
+                );
+                Ok(())
+            }
+            ProgramCommand::BackupSnapshot { backup_dir } => {
+                let path = backup_snapshot(self, &backup_dir).await?;
+                info!(
+                    "Snapshot of epoch {} saved to {}",
+                    self.epoch,
+                    path.display()
+                );
+                Ok(())
+            }
+            ProgramCommand::DiffSnapshots {
+                epoch_a,
+                epoch_b,
+                backup_dir,
+            } => {
+                let before = get_snapshot_at_epoch(self, &backup_dir, epoch_a).await?;
+                let after = get_snapshot_at_epoch(self, &backup_dir, epoch_b).await?;
+                info!(
+                    "\n\n--- Snapshot Diff {} -> {} ---\n{}",
+                    epoch_a,
+                    epoch_b,
+                    diff_snapshots(&before, &after)
+                );
+                Ok(())
+            }
+            ProgramCommand::ListNonSigners {
+                signers_bitmap,
+                backup_dir,
+            } => {
+                let bitmap_bytes = hex::decode(&signers_bitmap)
+                    .map_err(|e| anyhow!("Error parsing signers bitmap: {}", e))?;
+                let snapshot = get_snapshot_at_epoch(self, &backup_dir, self.epoch).await?;
+                let non_signers = snapshot.non_signers(&bitmap_bytes);
+                info!(
+                    "\n\n--- Non-Signers (epoch {}) ---\nCount: {}\n",
+                    self.epoch,
+                    non_signers.len()
+                );
+                for operator_snapshot in non_signers.iter() {
+                    info!(
+                        "Operator {} (index {}): {}",
+                        operator_snapshot.operator(),
+                        operator_snapshot.ncn_operator_index(),
+                        operator_snapshot.stake_weight().stake_weight()
+                    );
+                }
+                Ok(())
+            }
+            ProgramCommand::SimulateOperatorJoin { stake_weight } => {
+                let simulation = simulate_operator_join(self, stake_weight).await?;
+                info!(
+                    "\n\n--- Operator Join Simulation ---\nEligible: {}\nEligible Stake Weight: {} -> {}\nNakamoto Coefficient: {} -> {}\nNew Operator Fraction: {} bps\n",
+                    simulation.new_operator_eligible,
+                    simulation.eligible_stake_weight_before,
+                    simulation.eligible_stake_weight_after,
+                    simulation.nakamoto_coefficient_before,
+                    simulation.nakamoto_coefficient_after,
+                    simulation.new_operator_fraction_bps
+                );
+                Ok(())
+            }
+            ProgramCommand::CheckOperatorReady { operator } => {
+                let operator = Pubkey::from_str(&operator)
+                    .map_err(|e| anyhow!("Error parsing operator: {}", e))?;
+                let readiness = check_operator_ready(self, &operator, self.epoch).await?;
+                info!(
+                    "\n\n--- Operator Readiness ---\nOperator: {}\nRegistered: {}\nValid BN128 Keys: {}\nActive In Restaking: {}\nSnapshotted This Epoch: {}\nMeets Minimum Stake: {}\nReady: {}\n",
+                    operator,
+                    readiness.registered,
+                    readiness.valid_bn128_keys,
+                    readiness.active_in_restaking,
+                    readiness.snapshotted_this_epoch,
+                    readiness.meets_minimum_stake,
+                    readiness.is_ready()
+                );
+                Ok(())
+            }
+            ProgramCommand::VerifyOperatorRegistration {
+                operator,
+                g1_pubkey,
+                operator_keystore,
+            } => {
+                let operator = Pubkey::from_str(&operator)
+                    .map_err(|e| anyhow!("Error parsing operator: {}", e))?;
+
+                let local_g1_pubkey = match (g1_pubkey, operator_keystore) {
+                    (Some(g1_pubkey), _) => {
+                        use crate::bls_keys::hex_to_bytes;
+
+                        hex_to_bytes::<32>(&g1_pubkey)?
+                    }
+                    (None, Some(operator_keystore)) => {
+                        use crate::bls_keys::load_operator_keystore;
+
+                        load_operator_keystore(&operator_keystore)?.g1_pubkey
+                    }
+                    (None, None) => {
+                        return Err(anyhow!(
+                            "Either --g1-pubkey or --operator-keystore must be provided"
+                        ))
+                    }
+                };
+
+                let check = verify_operator_registration(self, &operator, &local_g1_pubkey).await?;
+                info!(
+                    "\n\n--- Operator Registration ---\nOperator: {}\nNCN Operator Index: {}\nSnapshot G1 Pubkey: {}\nLocal G1 Pubkey: {}\nMatch: {}\n",
+                    operator,
+                    check.ncn_operator_index,
+                    hex::encode(check.snapshot_g1_pubkey),
+                    hex::encode(check.local_g1_pubkey),
+                    check.matches()
+                );
+                Ok(())
+            }
 
             ProgramCommand::GetOperatorStakes {} => {
                 // Get snapshot for total stake
@@ -1,11 +1,11 @@
-use std::time::Duration;
+use std::{future::Future, io::Write, time::Duration};
 
 use crate::{
     getters::{
         get_account, get_all_operators_in_ncn, get_all_sorted_operators_for_vault,
-        get_all_vaults_in_ncn, get_current_slot, get_operator_snapshot,
-        get_or_create_vault_registry, get_restaking_config, get_snapshot, get_vault,
-        get_vault_config, get_vault_registry, get_vault_update_state_tracker,
+        get_all_vaults_in_ncn, get_current_slot, get_or_create_vault_registry,
+        get_restaking_config, get_snapshot, get_vault, get_vault_config, get_vault_registry,
+        get_vault_update_state_tracker, is_snapshot_finalized, pending_snapshot_operators,
     },
     handler::CliHandler,
     log::boring_progress_bar,
@@ -27,21 +27,27 @@ use jito_vault_core::{
     vault_operator_delegation::VaultOperatorDelegation,
     vault_update_state_tracker::VaultUpdateStateTracker,
 };
-use log::info;
+use log::{info, warn};
 use ncn_program_client::{
     instructions::{
-        AdminRegisterStMintBuilder, AdminSetNewAdminBuilder, AdminSetParametersBuilder,
-        CastVoteBuilder, InitializeConfigBuilder as InitializeNCNProgramConfigBuilder,
-        InitializeSnapshotBuilder, InitializeVaultRegistryBuilder, InitializeVoteCounterBuilder,
-        ReallocSnapshotBuilder, RegisterOperatorBuilder, RegisterVaultBuilder,
+        AdminRegisterStMintBuilder, AdminRegisterStMintWithWeightBuilder, AdminRepairConfigBuilder,
+        AdminSetNewAdminBuilder, AdminSetParametersBuilder, CastVoteBuilder,
+        InitializeConfigBuilder as InitializeNCNProgramConfigBuilder, InitializeSnapshotBuilder,
+        InitializeVaultRegistryBuilder, InitializeVoteCounterBuilder, ReallocSnapshotBuilder,
+        RegisterOperatorBuilder, RegisterVaultBuilder, SnapshotVaultOperatorDelegationBatchBuilder,
         SnapshotVaultOperatorDelegationBuilder, UpdateOperatorIpPortBuilder,
     },
     types::ConfigAdminRole,
 };
 use ncn_program_core::{
-    account_payer::AccountPayer, config::Config as NCNProgramConfig, constants::MAX_REALLOC_BYTES,
-    ncn_operator_account::NCNOperatorAccount, snapshot::Snapshot, utils::get_epoch,
-    vault_registry::VaultRegistry, vote_counter::VoteCounter,
+    account_payer::AccountPayer,
+    config::Config as NCNProgramConfig,
+    constants::{MAX_REALLOC_BYTES, MAX_SNAPSHOT_BATCH_OPERATORS},
+    ncn_operator_account::NCNOperatorAccount,
+    snapshot::Snapshot,
+    utils::{bitmap_iter, get_epoch},
+    vault_registry::VaultRegistry,
+    vote_counter::VoteCounter,
 };
 use solana_client::rpc_config::RpcSendTransactionConfig;
 
@@ -49,7 +55,7 @@ use hex;
 
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
-    instruction::Instruction,
+    instruction::{AccountMeta, Instruction},
     msg,
     native_token::sol_to_lamports,
     pubkey::Pubkey,
@@ -72,6 +78,10 @@ pub async fn admin_create_config(
     valid_slots_after_consensus: u64,
     epochs_after_consensus_before_close: u64,
     minimum_stake: u128,
+    curve_id: Option<u8>,
+    minimum_operators: Option<u64>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
 ) -> Result<()> {
     let keypair = handler.keypair()?;
     let client = handler.rpc_client();
@@ -84,8 +94,11 @@ pub async fn admin_create_config(
     println!("Account Payer: {}", account_payer);
 
     let tie_breaker_admin = tie_breaker_admin.unwrap_or_else(|| keypair.pubkey());
+    let (restaking_config, _, _) =
+        RestakingConfig::find_program_address(&handler.restaking_program_id);
 
-    let initialize_config_ix = InitializeNCNProgramConfigBuilder::new()
+    let mut initialize_config_builder = InitializeNCNProgramConfigBuilder::new();
+    initialize_config_builder
         .config(config)
         .ncn(ncn)
         .ncn_fee_wallet(ncn_fee_wallet)
@@ -93,12 +106,30 @@ pub async fn admin_create_config(
         .tie_breaker_admin(tie_breaker_admin)
         .account_payer(account_payer)
         .system_program(system_program::id())
+        .restaking_config(restaking_config)
         .epochs_before_stall(epochs_before_stall)
         .epochs_after_consensus_before_close(epochs_after_consensus_before_close)
         .valid_slots_after_consensus(valid_slots_after_consensus)
         .minimum_stake(minimum_stake)
-        .ncn_fee_bps(ncn_fee_bps)
-        .instruction();
+        .ncn_fee_bps(ncn_fee_bps);
+
+    if let Some(curve_id) = curve_id {
+        initialize_config_builder.curve_id(curve_id);
+    }
+
+    if let Some(minimum_operators) = minimum_operators {
+        initialize_config_builder.minimum_operators(minimum_operators);
+    }
+
+    if let Some(quorum_bps) = quorum_bps {
+        initialize_config_builder.quorum_bps(quorum_bps);
+    }
+
+    if let Some(max_snapshot_age_slots) = max_snapshot_age_slots {
+        initialize_config_builder.max_snapshot_age_slots(max_snapshot_age_slots);
+    }
+
+    let initialize_config_ix = initialize_config_builder.instruction();
 
     let program = client.get_account(&handler.ncn_program_id).await?;
 
@@ -127,27 +158,91 @@ pub async fn admin_create_config(
     Ok(())
 }
 
-pub async fn admin_register_st_mint(handler: &CliHandler, vault: &Pubkey) -> Result<()> {
+pub async fn admin_repair_config(
+    handler: &CliHandler,
+    ncn_fee_wallet: Pubkey,
+    ncn_fee_bps: u16,
+    epochs_before_stall: u64,
+    valid_slots_after_consensus: u64,
+    epochs_after_consensus_before_close: u64,
+    minimum_stake: u128,
+) -> Result<()> {
     let keypair = handler.keypair()?;
 
     let ncn = *handler.ncn()?;
 
     let (config, _, _) = NCNProgramConfig::find_program_address(&handler.ncn_program_id, &ncn);
+    let (restaking_config, _, _) =
+        RestakingConfig::find_program_address(&handler.restaking_program_id);
+    let (account_payer, _, _) = AccountPayer::find_program_address(&handler.ncn_program_id, &ncn);
 
-    let (vault_registry, _, _) = VaultRegistry::find_program_address(&handler.ncn_program_id, &ncn);
+    let repair_config_ix = AdminRepairConfigBuilder::new()
+        .config(config)
+        .ncn(ncn)
+        .ncn_fee_wallet(ncn_fee_wallet)
+        .ncn_admin(keypair.pubkey())
+        .restaking_config(restaking_config)
+        .account_payer(account_payer)
+        .system_program(system_program::id())
+        .epochs_before_stall(epochs_before_stall)
+        .epochs_after_consensus_before_close(epochs_after_consensus_before_close)
+        .valid_slots_after_consensus(valid_slots_after_consensus)
+        .minimum_stake(minimum_stake)
+        .ncn_fee_bps(ncn_fee_bps)
+        .instruction();
 
-    let vault_account = get_vault(handler, vault).await?;
+    send_and_log_transaction(
+        handler,
+        &[repair_config_ix],
+        &[],
+        "Repaired NCN Program Config",
+        &[
+            format!("NCN: {:?}", ncn),
+            format!("Ncn Admin: {:?}", keypair.pubkey()),
+            format!(
+                "Valid Slots After Consensus: {:?}",
+                valid_slots_after_consensus
+            ),
+        ],
+    )
+    .await?;
 
-    let mut register_st_mint_builder = AdminRegisterStMintBuilder::new();
+    Ok(())
+}
 
-    register_st_mint_builder
-        .config(config)
-        .admin(keypair.pubkey())
-        .vault_registry(vault_registry)
-        .ncn(ncn)
-        .st_mint(vault_account.supported_mint);
+pub async fn admin_register_st_mint(
+    handler: &CliHandler,
+    vault: &Pubkey,
+    weight: Option<u128>,
+) -> Result<()> {
+    let keypair = handler.keypair()?;
+
+    let ncn = *handler.ncn()?;
+
+    let (config, _, _) = NCNProgramConfig::find_program_address(&handler.ncn_program_id, &ncn);
 
-    let register_st_mint_ix = register_st_mint_builder.instruction();
+    let (vault_registry, _, _) = VaultRegistry::find_program_address(&handler.ncn_program_id, &ncn);
+
+    let vault_account = get_vault(handler, vault).await?;
+
+    let register_st_mint_ix = if let Some(weight) = weight {
+        AdminRegisterStMintWithWeightBuilder::new()
+            .config(config)
+            .admin(keypair.pubkey())
+            .vault_registry(vault_registry)
+            .ncn(ncn)
+            .st_mint(vault_account.supported_mint)
+            .weight(weight)
+            .instruction()
+    } else {
+        AdminRegisterStMintBuilder::new()
+            .config(config)
+            .admin(keypair.pubkey())
+            .vault_registry(vault_registry)
+            .ncn(ncn)
+            .st_mint(vault_account.supported_mint)
+            .instruction()
+    };
 
     send_and_log_transaction(
         handler,
@@ -157,6 +252,7 @@ pub async fn admin_register_st_mint(handler: &CliHandler, vault: &Pubkey) -> Res
         &[
             format!("NCN: {:?}", ncn),
             format!("ST Mint: {:?}", vault_account.supported_mint),
+            format!("Weight: {:?}", weight),
         ],
     )
     .await?;
@@ -211,14 +307,30 @@ pub async fn admin_set_parameters(
     epochs_after_consensus_before_close: Option<u64>,
     valid_slots_after_consensus: Option<u64>,
     starting_valid_epoch: Option<u64>,
+    enforce_signer_order: Option<bool>,
+    minimum_operators: Option<u64>,
+    reject_duplicate_aggregates: Option<bool>,
+    allow_multiple_votes_per_epoch: Option<bool>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
+    allow_ed25519_votes: Option<bool>,
 ) -> Result<()> {
     let keypair = handler.keypair()?;
     let ncn = *handler.ncn()?;
 
     let config_pda = NCNProgramConfig::find_program_address(&handler.ncn_program_id, &ncn).0;
+    let (restaking_config, _, _) =
+        RestakingConfig::find_program_address(&handler.restaking_program_id);
+    let (snapshot, _, _) = Snapshot::find_program_address(&handler.ncn_program_id, &ncn);
+    let (vote_counter, _, _) = VoteCounter::find_program_address(&handler.ncn_program_id, &ncn);
 
     let mut ix = AdminSetParametersBuilder::new();
-    ix.config(config_pda).ncn(ncn).ncn_admin(keypair.pubkey());
+    ix.config(config_pda)
+        .ncn(ncn)
+        .ncn_admin(keypair.pubkey())
+        .restaking_config(restaking_config)
+        .snapshot(snapshot)
+        .vote_counter(vote_counter);
 
     if let Some(epochs) = epochs_before_stall {
         ix.epochs_before_stall(epochs);
@@ -236,6 +348,34 @@ pub async fn admin_set_parameters(
         ix.starting_valid_epoch(epoch);
     }
 
+    if let Some(enforce) = enforce_signer_order {
+        ix.enforce_signer_order(enforce);
+    }
+
+    if let Some(minimum_operators) = minimum_operators {
+        ix.minimum_operators(minimum_operators);
+    }
+
+    if let Some(reject) = reject_duplicate_aggregates {
+        ix.reject_duplicate_aggregates(reject);
+    }
+
+    if let Some(allow) = allow_multiple_votes_per_epoch {
+        ix.allow_multiple_votes_per_epoch(allow);
+    }
+
+    if let Some(quorum_bps) = quorum_bps {
+        ix.quorum_bps(quorum_bps);
+    }
+
+    if let Some(max_snapshot_age_slots) = max_snapshot_age_slots {
+        ix.max_snapshot_age_slots(max_snapshot_age_slots);
+    }
+
+    if let Some(allow) = allow_ed25519_votes {
+        ix.allow_ed25519_votes(allow);
+    }
+
     send_and_log_transaction(
         handler,
         &[ix.instruction()],
@@ -252,6 +392,19 @@ pub async fn admin_set_parameters(
                 "Valid Slots After Consensus: {:?}",
                 valid_slots_after_consensus
             ),
+            format!("Enforce Signer Order: {:?}", enforce_signer_order),
+            format!("Minimum Operators: {:?}", minimum_operators),
+            format!(
+                "Reject Duplicate Aggregates: {:?}",
+                reject_duplicate_aggregates
+            ),
+            format!(
+                "Allow Multiple Votes Per Epoch: {:?}",
+                allow_multiple_votes_per_epoch
+            ),
+            format!("Quorum Bps: {:?}", quorum_bps),
+            format!("Max Snapshot Age Slots: {:?}", max_snapshot_age_slots),
+            format!("Allow Ed25519 Votes: {:?}", allow_ed25519_votes),
         ],
     )
     .await?;
@@ -259,12 +412,51 @@ pub async fn admin_set_parameters(
     Ok(())
 }
 
-pub async fn admin_fund_account_payer(handler: &CliHandler, amount: f64) -> Result<()> {
+/// Re-derives the account payer PDA of `ncn` and refuses `account_payer` if it differs, so SOL is
+/// never sent to a mistyped address
+pub fn verify_account_payer_address(
+    program_id: &Pubkey,
+    ncn: &Pubkey,
+    account_payer: &Pubkey,
+) -> Result<()> {
+    let (expected_account_payer, _, _) = AccountPayer::find_program_address(program_id, ncn);
+
+    if expected_account_payer != *account_payer {
+        return Err(anyhow!(
+            "Account payer {} does not match the PDA {} derived for NCN {}",
+            account_payer,
+            expected_account_payer,
+            ncn
+        ));
+    }
+
+    Ok(())
+}
+
+pub async fn admin_fund_account_payer(
+    handler: &CliHandler,
+    amount: f64,
+    expected_account_payer: Option<Pubkey>,
+    skip_confirmation: bool,
+) -> Result<()> {
     let keypair = handler.keypair()?;
     let ncn = *handler.ncn()?;
 
     let (account_payer, _, _) = AccountPayer::find_program_address(&handler.ncn_program_id, &ncn);
 
+    if let Some(expected_account_payer) = expected_account_payer {
+        verify_account_payer_address(&handler.ncn_program_id, &ncn, &expected_account_payer)?;
+    }
+
+    if !skip_confirmation
+        && !confirm(&format!(
+            "Transfer {} SOL to account payer {} of NCN {}?",
+            amount, account_payer, ncn
+        ))?
+    {
+        return Err(anyhow!("Funding the account payer was cancelled"));
+    }
+
     let transfer_ix = transfer(&keypair.pubkey(), &account_payer, sol_to_lamports(amount));
 
     send_and_log_transaction(
@@ -637,6 +829,8 @@ pub async fn snapshot_vault_operator_delegation(
 
     let (snapshot, _, _) = Snapshot::find_program_address(&handler.ncn_program_id, &ncn);
 
+    let (vault_registry, _, _) = VaultRegistry::find_program_address(&handler.ncn_program_id, &ncn);
+
     let snapshot_vault_operator_delegation_ix = SnapshotVaultOperatorDelegationBuilder::new()
         .restaking_config(restaking_config)
         .config(config)
@@ -648,6 +842,7 @@ pub async fn snapshot_vault_operator_delegation(
         .ncn_vault_ticket(ncn_vault_ticket)
         .vault_operator_delegation(vault_operator_delegation)
         .snapshot(snapshot)
+        .vault_registry(vault_registry)
         .instruction();
 
     send_and_log_transaction(
@@ -667,6 +862,86 @@ pub async fn snapshot_vault_operator_delegation(
     Ok(())
 }
 
+/// Snapshots the delegations from `vault` to the `(index, operator)` pairs in one transaction.
+/// The program may stop before the last operator when the compute budget runs low.
+pub async fn snapshot_vault_operator_delegation_batch(
+    handler: &CliHandler,
+    vault: &Pubkey,
+    operators: &[(u64, Pubkey)],
+    epoch: u64,
+) -> Result<()> {
+    let ncn = *handler.ncn()?;
+
+    let vault = *vault;
+
+    let (config, _, _) = NCNProgramConfig::find_program_address(&handler.ncn_program_id, &ncn);
+
+    let (restaking_config, _, _) =
+        RestakingConfig::find_program_address(&handler.restaking_program_id);
+
+    let (vault_ncn_ticket, _, _) =
+        VaultNcnTicket::find_program_address(&handler.vault_program_id, &vault, &ncn);
+
+    let (ncn_vault_ticket, _, _) =
+        NcnVaultTicket::find_program_address(&handler.restaking_program_id, &ncn, &vault);
+
+    let (snapshot, _, _) = Snapshot::find_program_address(&handler.ncn_program_id, &ncn);
+
+    let (vault_registry, _, _) = VaultRegistry::find_program_address(&handler.ncn_program_id, &ncn);
+
+    let mut operator_accounts = Vec::with_capacity(operators.len() * 3);
+    for (_, operator) in operators.iter() {
+        let (ncn_operator_state, _, _) =
+            NcnOperatorState::find_program_address(&handler.restaking_program_id, &ncn, operator);
+
+        let (vault_operator_delegation, _, _) = VaultOperatorDelegation::find_program_address(
+            &handler.vault_program_id,
+            &vault,
+            operator,
+        );
+
+        operator_accounts.push(AccountMeta::new_readonly(*operator, false));
+        operator_accounts.push(AccountMeta::new_readonly(ncn_operator_state, false));
+        operator_accounts.push(AccountMeta::new_readonly(vault_operator_delegation, false));
+    }
+
+    let snapshot_vault_operator_delegation_batch_ix =
+        SnapshotVaultOperatorDelegationBatchBuilder::new()
+            .config(config)
+            .restaking_config(restaking_config)
+            .ncn(ncn)
+            .vault(vault)
+            .vault_ncn_ticket(vault_ncn_ticket)
+            .ncn_vault_ticket(ncn_vault_ticket)
+            .snapshot(snapshot)
+            .vault_registry(vault_registry)
+            .operator_indices(operators.iter().map(|(index, _)| *index).collect())
+            .add_remaining_accounts(&operator_accounts)
+            .instruction();
+
+    send_and_log_transaction(
+        handler,
+        &[snapshot_vault_operator_delegation_batch_ix],
+        &[],
+        "Snapshotted Vault Operator Delegation Batch",
+        &[
+            format!("NCN: {:?}", ncn),
+            format!("Vault: {:?}", vault),
+            format!(
+                "Operator indices: {:?}",
+                operators
+                    .iter()
+                    .map(|(index, _)| *index)
+                    .collect::<Vec<_>>()
+            ),
+            format!("Epoch: {:?}", epoch),
+        ],
+    )
+    .await?;
+
+    Ok(())
+}
+
 // --------------------- operator ------------------------------
 
 pub async fn cast_vote(
@@ -676,6 +951,8 @@ pub async fn cast_vote(
     apk2: [u8; 64],
     signers_bitmap: Vec<u8>,
     message: [u8; 32],
+    quorum_bps: Option<u16>,
+    priority_fee_micro_lamports: Option<u64>,
 ) -> Result<()> {
     let ncn = *handler.ncn()?;
 
@@ -688,7 +965,28 @@ pub async fn cast_vote(
 
     let (vote_counter, _, _) = VoteCounter::find_program_address(&handler.ncn_program_id, &ncn);
 
-    let cast_vote_ix = CastVoteBuilder::new()
+    // Each signer's NCN link is re-checked on-chain, in operator index order
+    let snapshot_account = get_snapshot(handler, epoch).await?;
+    let ncn_operator_states: Vec<AccountMeta> = snapshot_account
+        .operator_snapshots()
+        .iter()
+        .zip(bitmap_iter(
+            &signers_bitmap,
+            snapshot_account.operator_slot_count(),
+        ))
+        .filter(|(_, signed)| *signed)
+        .map(|(operator_snapshot, _)| {
+            let (ncn_operator_state, _, _) = NcnOperatorState::find_program_address(
+                &handler.restaking_program_id,
+                &ncn,
+                operator_snapshot.operator(),
+            );
+            AccountMeta::new_readonly(ncn_operator_state, false)
+        })
+        .collect();
+
+    let mut cast_vote_builder = CastVoteBuilder::new();
+    cast_vote_builder
         .config(config)
         .ncn(ncn)
         .snapshot(snapshot)
@@ -697,20 +995,38 @@ pub async fn cast_vote(
         .aggregated_signature(agg_sig)
         .aggregated_g2(apk2)
         .operators_signature_bitmap(signers_bitmap)
-        .instruction();
+        .add_remaining_accounts(&ncn_operator_states);
 
-    send_and_log_transaction(
+    if let Some(quorum_bps) = quorum_bps {
+        cast_vote_builder.quorum_bps(quorum_bps);
+    }
+
+    let cast_vote_ix = cast_vote_builder.instruction();
+
+    let priority_fee_micro_lamports =
+        priority_fee_micro_lamports.unwrap_or(handler.priority_fee_micro_lamports);
+
+    sleep(Duration::from_secs(1)).await;
+
+    let signature = send_transactions_with_priority_fee(
         handler,
         &[cast_vote_ix],
         &[],
+        priority_fee_micro_lamports,
+    )
+    .await?;
+
+    log_transaction(
         "Cast Vote",
+        signature,
         &[
             format!("NCN: {:?}", ncn),
             format!("Epoch: {:?}", epoch),
             format!("Message: {}", hex::encode(message)),
+            format!("Quorum BPS: {:?}", quorum_bps),
+            format!("Priority Fee: {}", priority_fee_micro_lamports),
         ],
-    )
-    .await?;
+    );
 
     Ok(())
 }
@@ -952,7 +1268,42 @@ pub async fn crank_register_vaults(handler: &CliHandler) -> Result<()> {
     Ok(())
 }
 
-pub async fn crank_snapshot(handler: &CliHandler, epoch: u64) -> Result<()> {
+/// What `crank_snapshot` did with each registered vault
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SnapshotCrankSummary {
+    /// Vaults whose delegations were snapshotted
+    pub snapshotted_vaults: Vec<Pubkey>,
+    /// Vaults skipped because their account no longer exists
+    pub skipped_vaults: Vec<Pubkey>,
+}
+
+/// Runs `snapshot_vault` for every `(vault, exists)` pair whose vault account still exists. A
+/// closed vault is skipped with a warning instead of failing the whole crank
+pub async fn snapshot_existing_vaults<F, Fut>(
+    vaults: &[(Pubkey, bool)],
+    mut snapshot_vault: F,
+) -> Result<SnapshotCrankSummary>
+where
+    F: FnMut(Pubkey) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut summary = SnapshotCrankSummary::default();
+
+    for (vault, exists) in vaults.iter() {
+        if !exists {
+            warn!("Skipping vault {} as its account no longer exists", vault);
+            summary.skipped_vaults.push(*vault);
+            continue;
+        }
+
+        snapshot_vault(*vault).await?;
+        summary.snapshotted_vaults.push(*vault);
+    }
+
+    Ok(summary)
+}
+
+pub async fn crank_snapshot(handler: &CliHandler, epoch: u64) -> Result<SnapshotCrankSummary> {
     let vault_registry = get_vault_registry(handler).await?;
 
     let operators = get_all_operators_in_ncn(handler).await?;
@@ -967,7 +1318,45 @@ pub async fn crank_snapshot(handler: &CliHandler, epoch: u64) -> Result<()> {
     // Initialize operator snapshot progress tracking in epoch state
     let _ncn = *handler.ncn()?;
 
-    let vault = &all_vaults[0];
+    {
+        let snapshot = get_snapshot(handler, epoch).await?;
+        for operator in operators.iter() {
+            if snapshot.find_operator_snapshot(operator).is_none() {
+                log::error!(
+                    "Operator snapshot not found for operator: {:?} in epoch: {:?}",
+                    operator,
+                    epoch
+                );
+            }
+        }
+    }
+
+    let mut vaults = Vec::with_capacity(all_vaults.len());
+    for vault in all_vaults {
+        let exists = get_account(handler, &vault).await?.is_some();
+        vaults.push((vault, exists));
+    }
+
+    let summary = snapshot_existing_vaults(&vaults, |vault| async move {
+        crank_vault_snapshot(handler, &vault, epoch).await
+    })
+    .await?;
+
+    if !summary.skipped_vaults.is_empty() {
+        warn!(
+            "Skipped {} missing vaults in epoch {}: {:?}",
+            summary.skipped_vaults.len(),
+            epoch,
+            summary.skipped_vaults
+        );
+    }
+
+    Ok(summary)
+}
+
+/// Updates `vault` and snapshots its delegations in batches, each resuming from the first
+/// operator the previous one did not reach
+async fn crank_vault_snapshot(handler: &CliHandler, vault: &Pubkey, epoch: u64) -> Result<()> {
     let result = full_vault_update(handler, vault).await;
     if let Err(err) = result {
         log::error!(
@@ -977,31 +1366,37 @@ pub async fn crank_snapshot(handler: &CliHandler, epoch: u64) -> Result<()> {
         );
     }
 
-    for operator in operators.iter() {
-        // Create Vault Operator Delegation
-        {
-            let result = get_operator_snapshot(handler, operator, epoch).await;
+    let epoch_length = get_restaking_config(handler).await?.epoch_length();
 
-            if result.is_err() {
-                log::error!(
-                "Failed to get or create operator snapshot for operator: {:?} in epoch: {:?} with error: {:?}",
-                operator,
-                epoch,
-                result.err().unwrap()
+    let mut pending_count = usize::MAX;
+    loop {
+        let snapshot = get_snapshot(handler, epoch).await?;
+        let pending = pending_snapshot_operators(&snapshot, epoch_length, epoch)?;
+        if pending.is_empty() {
+            break;
+        }
+
+        if pending.len() >= pending_count {
+            log::error!(
+                "Snapshot batch made no progress, {} operators left to snapshot in epoch: {:?}",
+                pending.len(),
+                epoch
             );
-                continue;
-            };
+            break;
         }
+        pending_count = pending.len();
 
-        let result = snapshot_vault_operator_delegation(handler, vault, operator, epoch).await;
+        let batch = &pending[..pending.len().min(MAX_SNAPSHOT_BATCH_OPERATORS)];
+        let result = snapshot_vault_operator_delegation_batch(handler, vault, batch, epoch).await;
         if let Err(err) = result {
             log::error!(
-                    "Failed to snapshot vault operator delegation for vault: {:?} and operator: {:?} in epoch: {:?} with error: {:?}",
-                    vault,
-                    operator,
-                    epoch,
-                    err
-                );
+                "Failed to snapshot vault operator delegations for vault: {:?} from operator index: {:?} in epoch: {:?} with error: {:?}",
+                vault,
+                batch[0].0,
+                epoch,
+                err
+            );
+            break;
         }
     }
 
@@ -1117,8 +1512,83 @@ pub async fn crank_snapshot_unupdated(
     Ok(())
 }
 
+pub const CRANK_BACKOFF_MS: u64 = 1000;
+
+/// Cranks the snapshot of `epoch` until every operator is registered and snapshotted, backing off
+/// between attempts. Returns whether the snapshot finalized within `max_iterations` attempts
+pub async fn crank_until_finalized(
+    handler: &CliHandler,
+    epoch: u64,
+    max_iterations: u64,
+) -> Result<bool> {
+    let operator_count = get_all_operators_in_ncn(handler).await?.len() as u64;
+    let epoch_length = get_restaking_config(handler).await?.epoch_length();
+
+    let finalized = retry_with_backoff(max_iterations, CRANK_BACKOFF_MS, |iteration| async move {
+        let result = crank_snapshot(handler, epoch).await;
+        if let Err(err) = result {
+            log::error!(
+                "Failed to crank snapshot in epoch {:?} on attempt {} with error: {:?}",
+                epoch,
+                iteration + 1,
+                err
+            );
+        }
+
+        let snapshot = get_snapshot(handler, epoch).await?;
+        is_snapshot_finalized(&snapshot, operator_count, epoch_length, epoch)
+    })
+    .await?;
+
+    if finalized {
+        info!("Snapshot for epoch {} is finalized", epoch);
+    } else {
+        info!(
+            "Snapshot for epoch {} is not finalized after {} attempts",
+            epoch, max_iterations
+        );
+    }
+
+    Ok(finalized)
+}
+
 // --------------------- HELPERS -------------------------
 
+/// Asks a yes/no question on stdin, anything but `y` or `yes` is a no
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Runs `attempt` until it reports success or `max_iterations` attempts were made, sleeping a
+/// linearly growing multiple of `backoff_ms` in between. Returns whether an attempt succeeded
+pub async fn retry_with_backoff<F, Fut>(
+    max_iterations: u64,
+    backoff_ms: u64,
+    mut attempt: F,
+) -> Result<bool>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    for iteration in 0..max_iterations {
+        if attempt(iteration).await? {
+            return Ok(true);
+        }
+
+        if iteration + 1 < max_iterations {
+            sleep(Duration::from_millis(backoff_ms * (iteration + 1))).await;
+        }
+    }
+
+    Ok(false)
+}
+
 pub async fn send_and_log_transaction(
     handler: &CliHandler,
     instructions: &[Instruction],
@@ -1139,19 +1609,47 @@ pub async fn send_transactions(
     handler: &CliHandler,
     instructions: &[Instruction],
     signing_keypairs: &[&Keypair],
+) -> Result<Signature> {
+    send_transactions_with_priority_fee(
+        handler,
+        instructions,
+        signing_keypairs,
+        handler.priority_fee_micro_lamports,
+    )
+    .await
+}
+
+/// Prepends the compute unit price instruction to the transaction instructions
+pub fn with_compute_unit_price(
+    instructions: &[Instruction],
+    priority_fee_micro_lamports: u64,
+) -> Vec<Instruction> {
+    let mut all_instructions = vec![ComputeBudgetInstruction::set_compute_unit_price(
+        priority_fee_micro_lamports,
+    )];
+
+    all_instructions.extend_from_slice(instructions);
+
+    all_instructions
+}
+
+/// Same as `send_transactions`, with a priority fee replacing the handler default
+pub async fn send_transactions_with_priority_fee(
+    handler: &CliHandler,
+    instructions: &[Instruction],
+    signing_keypairs: &[&Keypair],
+    priority_fee_micro_lamports: u64,
 ) -> Result<Signature> {
     let client = handler.rpc_client();
     let keypair = handler.keypair()?;
     let retries = handler.retries;
-    let priority_fee_micro_lamports = handler.priority_fee_micro_lamports;
-
-    let mut all_instructions = vec![];
 
-    all_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
-        priority_fee_micro_lamports,
-    ));
+    // The transaction may change the snapshot even when confirming it fails
+    if let Some(snapshot_cache) = &handler.snapshot_cache {
+        snapshot_cache.invalidate();
+    }
 
-    all_instructions.extend_from_slice(instructions);
+    let all_instructions = with_compute_unit_price(instructions, priority_fee_micro_lamports);
 
     for iteration in 0..retries {
         let blockhash = client.get_latest_blockhash().await?;
@@ -1198,7 +1696,7 @@ pub async fn send_transactions(
     all_signers.extend(signing_keypairs.iter());
 
     let tx = Transaction::new_signed_with_payer(
-        instructions,
+        &all_instructions,
         Some(&keypair.pubkey()),
         &all_signers, // Pass the reference to the vector of keypair references
         blockhash,
@@ -1228,3 +1726,164 @@ pub fn log_transaction(title: &str, signature: Signature, log_items: &[String])
     log_message.push('\n');
     info!("{}", log_message);
 }
+
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{
+        constants::G2_COMPRESSED_POINT_SIZE, g1_point::G1CompressedPoint,
+        snapshot::OperatorSnapshot, stake_weight::StakeWeights,
+    };
+
+    use super::*;
+
+    const EPOCH_LENGTH: u64 = 100;
+    const EPOCH: u64 = 3;
+    const OPERATOR_COUNT: u64 = 3;
+
+    fn multi_operator_snapshot() -> Box<Snapshot> {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            0,
+            StakeWeights::new(1),
+        ));
+
+        for index in 0..OPERATOR_COUNT {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                0,
+                true,
+                index,
+                index,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 0)
+                .unwrap();
+        }
+
+        snapshot
+    }
+
+    /// Stands in for `crank_snapshot`, snapshotting one more operator's delegation per call
+    fn crank_one_operator(snapshot: &mut Snapshot) -> Result<bool> {
+        let slot = EPOCH * EPOCH_LENGTH;
+        let next_index = (0..OPERATOR_COUNT).find(|index| {
+            snapshot
+                .get_operator_snapshot(*index)
+                .is_some_and(|operator_snapshot| operator_snapshot.last_snapshot_slot() < slot)
+        });
+
+        if let Some(index) = next_index {
+            let mut operator_snapshot = *snapshot.get_operator_snapshot(index).unwrap();
+            operator_snapshot.snapshot_vault_operator_delegation(
+                slot,
+                &StakeWeights::new(1),
+                &StakeWeights::new(1),
+                snapshot.minimum_stake(),
+            )?;
+            snapshot.update_operator_snapshot(index as usize, &operator_snapshot);
+        }
+
+        is_snapshot_finalized(snapshot, OPERATOR_COUNT, EPOCH_LENGTH, EPOCH)
+    }
+
+    #[tokio::test]
+    async fn test_crank_finalizes_multi_operator_snapshot_within_cap() {
+        let mut snapshot = multi_operator_snapshot();
+        assert!(!is_snapshot_finalized(&snapshot, OPERATOR_COUNT, EPOCH_LENGTH, EPOCH).unwrap());
+
+        let mut attempts = 0;
+        let finalized = retry_with_backoff(5, 0, |_| {
+            attempts += 1;
+            std::future::ready(crank_one_operator(&mut snapshot))
+        })
+        .await
+        .unwrap();
+
+        assert!(finalized);
+        assert_eq!(attempts, OPERATOR_COUNT);
+
+        // Hitting the cap first reports the snapshot as not finalized
+        let mut snapshot = multi_operator_snapshot();
+        let finalized = retry_with_backoff(OPERATOR_COUNT - 1, 0, |_| {
+            std::future::ready(crank_one_operator(&mut snapshot))
+        })
+        .await
+        .unwrap();
+
+        assert!(!finalized);
+    }
+
+    #[tokio::test]
+    async fn test_crank_skips_missing_vault() {
+        let missing_vault = Pubkey::new_unique();
+        let vaults = [
+            (Pubkey::new_unique(), true),
+            (missing_vault, false),
+            (Pubkey::new_unique(), true),
+        ];
+
+        let mut cranked_vaults = Vec::new();
+        let summary = snapshot_existing_vaults(&vaults, |vault| {
+            cranked_vaults.push(vault);
+            std::future::ready(Ok(()))
+        })
+        .await
+        .unwrap();
+
+        // The vaults after the missing one are still snapshotted
+        assert_eq!(cranked_vaults, vec![vaults[0].0, vaults[2].0]);
+        assert_eq!(summary.snapshotted_vaults, cranked_vaults);
+        assert_eq!(summary.skipped_vaults, vec![missing_vault]);
+    }
+
+    #[test]
+    fn test_verify_account_payer_address() {
+        let program_id = Pubkey::new_unique();
+        let ncn = Pubkey::new_unique();
+        let other_ncn = Pubkey::new_unique();
+
+        let (account_payer, _, _) = AccountPayer::find_program_address(&program_id, &ncn);
+        let (other_account_payer, _, _) =
+            AccountPayer::find_program_address(&program_id, &other_ncn);
+        assert_ne!(account_payer, other_account_payer);
+
+        assert!(verify_account_payer_address(&program_id, &ncn, &account_payer).is_ok());
+        assert!(verify_account_payer_address(&program_id, &ncn, &other_account_payer).is_err());
+
+        // The derivation also depends on the program
+        assert!(verify_account_payer_address(&Pubkey::new_unique(), &ncn, &account_payer).is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_compute_unit_price() {
+        let cast_vote_ix = CastVoteBuilder::new()
+            .config(Pubkey::new_unique())
+            .ncn(Pubkey::new_unique())
+            .snapshot(Pubkey::new_unique())
+            .restaking_config(Pubkey::new_unique())
+            .vote_counter(Pubkey::new_unique())
+            .aggregated_signature([1; 32])
+            .aggregated_g2([2; 64])
+            .operators_signature_bitmap(vec![0xff])
+            .instruction();
+
+        let instructions = with_compute_unit_price(&[cast_vote_ix.clone()], 5_000);
+
+        // The override is the compute unit price, ahead of the untouched vote
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[0],
+            ComputeBudgetInstruction::set_compute_unit_price(5_000)
+        );
+        assert_eq!(instructions[1], cast_vote_ix);
+    }
+}
@@ -1,16 +1,24 @@
 use std::time::Duration;
 
 use crate::{
-    getters::{get_guaranteed_epoch_and_slot, get_or_create_vote_counter},
+    bls_keys::load_aggregation_from_file,
+    getters::{
+        get_guaranteed_epoch_and_slot, get_or_create_vote_counter, get_restaking_config,
+        get_vote_signing_payload,
+    },
     handler::CliHandler,
-    instructions::{crank_snapshot_unupdated, get_or_create_snapshot},
+    instructions::{
+        cast_vote, crank_register_vaults, crank_snapshot_unupdated, crank_until_finalized,
+        get_or_create_snapshot,
+    },
     keeper::{
         keeper_metrics::{emit_error, emit_heartbeat},
         keeper_state::KeeperState,
     },
 };
 use anyhow::Result;
-use log::info;
+use log::{info, warn};
+use ncn_program_core::utils::get_epoch;
 use solana_metrics::set_host_id;
 use std::process::Command;
 use tokio::time::sleep;
@@ -23,19 +31,29 @@ use tokio::time::sleep;
 /// 3. Determining which operators need snapshotting
 /// 4. Taking snapshots for operators that need updates
 ///
+/// Once per NCN epoch it also registers new vaults, cranks the snapshot until
+/// it is finalized and, when an aggregated signature is available in
+/// `vote_file`, casts the vote.
+///
 /// The keeper runs in a continuous loop, monitoring the snapshot state and
-/// taking snapshots when operators are due for updates.
+/// taking snapshots when operators are due for updates, until `max_epochs`
+/// epochs were completed.
 ///
 /// # Arguments
 /// * `handler` - CLI handler containing RPC client and configuration
 /// * `loop_timeout_ms` - Timeout between main loop iterations when stalled
 /// * `error_timeout_ms` - Timeout after errors before retrying
+/// * `max_epochs` - Number of epochs to complete before exiting, runs forever when `None`
+/// * `vote_file` - JSON file holding the aggregated signature to vote with each epoch
 pub async fn startup_ncn_keeper(
     handler: &CliHandler,
     loop_timeout_ms: u64,
     error_timeout_ms: u64,
+    max_epochs: Option<u64>,
+    vote_file: Option<String>,
 ) -> Result<()> {
     let mut state: KeeperState = KeeperState::default();
+    let mut progress = KeeperEpochProgress::default();
     let mut current_keeper_epoch = handler.epoch;
     let mut tick = 0;
 
@@ -80,6 +98,8 @@ pub async fn startup_ncn_keeper(
         return Err(anyhow::anyhow!("Failed to create vote counter"));
     }
 
+    let epoch_length = get_restaking_config(handler).await?.epoch_length();
+
     info!("Required accounts initialized successfully");
 
     loop {
@@ -90,7 +110,8 @@ pub async fn startup_ncn_keeper(
                 "\n\n1. Check Epoch Progression - {}\n",
                 current_keeper_epoch
             );
-            let (current_epoch, _) = get_guaranteed_epoch_and_slot(handler).await;
+            let (_, current_slot) = get_guaranteed_epoch_and_slot(handler).await;
+            let current_epoch = get_epoch(current_slot, epoch_length)?;
 
             if current_epoch != current_keeper_epoch {
                 info!(
@@ -179,6 +200,73 @@ pub async fn startup_ncn_keeper(
             }
         }
 
+        // PHASE 4: EPOCH CRANKS
+        // Register new vaults, finalize the snapshot and vote, once per epoch
+        if progress.needs_epoch_work(current_keeper_epoch) {
+            info!(
+                "\n\n4. Register Vaults, Finalize Snapshot and Vote - {}\n",
+                current_keeper_epoch
+            );
+
+            let result = crank_register_vaults(handler).await;
+            if check_and_timeout_error(
+                "Register Vaults".to_string(),
+                &result,
+                error_timeout_ms,
+                current_keeper_epoch,
+            )
+            .await
+            {
+                continue;
+            }
+
+            let result =
+                crank_until_finalized(handler, current_keeper_epoch, FINALIZE_MAX_ITERATIONS).await;
+            if check_and_timeout_error(
+                "Finalize Snapshot".to_string(),
+                &result,
+                error_timeout_ms,
+                current_keeper_epoch,
+            )
+            .await
+            {
+                continue;
+            }
+
+            if matches!(result, Ok(true)) {
+                if let Some(vote_file) = &vote_file {
+                    let result =
+                        cast_vote_from_file(handler, current_keeper_epoch, vote_file).await;
+                    if check_and_timeout_error(
+                        "Cast Vote".to_string(),
+                        &result,
+                        error_timeout_ms,
+                        current_keeper_epoch,
+                    )
+                    .await
+                    {
+                        continue;
+                    }
+                }
+
+                progress.complete_epoch(current_keeper_epoch);
+                info!(
+                    "Epoch {} completed ({} so far)",
+                    current_keeper_epoch, progress.epochs_completed
+                );
+
+                if progress.is_done(max_epochs) {
+                    info!(
+                        "Completed {} epochs, stopping keeper",
+                        progress.epochs_completed
+                    );
+                    return Ok(());
+                }
+            } else {
+                info!("Snapshot not finalized yet - retrying on the next loop");
+            }
+        }
+
         // MAIN LOOP TIMEOUT
         // Wait before the next iteration and emit a heartbeat
         info!("\n\n -- Loop Timeout -- {}\n", current_keeper_epoch);
@@ -188,6 +276,63 @@ pub async fn startup_ncn_keeper(
     }
 }
 
+/// Attempts the snapshot gets per epoch to finalize before the keeper retries on its next loop
+const FINALIZE_MAX_ITERATIONS: u64 = 10;
+
+/// Tracks which NCN epochs the keeper has fully cranked and voted in
+#[derive(Default, Debug)]
+pub struct KeeperEpochProgress {
+    /// The last epoch whose cranks and vote completed
+    pub last_completed_epoch: Option<u64>,
+    /// Number of epochs completed since the keeper started
+    pub epochs_completed: u64,
+}
+
+impl KeeperEpochProgress {
+    /// Whether the cranks and vote still have to run for `epoch`
+    pub fn needs_epoch_work(&self, epoch: u64) -> bool {
+        self.last_completed_epoch != Some(epoch)
+    }
+
+    pub fn complete_epoch(&mut self, epoch: u64) {
+        if self.needs_epoch_work(epoch) {
+            self.last_completed_epoch = Some(epoch);
+            self.epochs_completed += 1;
+        }
+    }
+
+    /// Whether the keeper completed `max_epochs` epochs, never when there is no limit
+    pub fn is_done(&self, max_epochs: Option<u64>) -> bool {
+        max_epochs.is_some_and(|max_epochs| self.epochs_completed >= max_epochs)
+    }
+}
+
+/// Casts the vote of `epoch` with the aggregated signature in `vote_file`, skipping the vote
+/// when the file does not exist yet
+async fn cast_vote_from_file(handler: &CliHandler, epoch: u64, vote_file: &str) -> Result<()> {
+    let Some(aggregation) = load_aggregation_from_file(vote_file)? else {
+        warn!(
+            "No aggregated signature in {}, skipping the vote",
+            vote_file
+        );
+        return Ok(());
+    };
+
+    let message = get_vote_signing_payload(handler).await?;
+
+    cast_vote(
+        handler,
+        epoch,
+        aggregation.aggregated_signature,
+        aggregation.aggregated_g2,
+        aggregation.signers_bitmap,
+        message,
+        None,
+        None,
+    )
+    .await
+}
+
 /// Handles errors consistently across the keeper loop
 ///
 /// This function:
@@ -247,3 +392,62 @@ async fn timeout_keeper(duration_ms: u64) {
     info!("Keeper Timeout for {}s", duration_ms as f64 / 1000.0);
     sleep(Duration::from_millis(duration_ms)).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::NamedTempFile;
+
+    use crate::bls_keys::AggregationResult;
+
+    use super::*;
+
+    #[test]
+    fn test_keeper_runs_two_simulated_epochs() {
+        let mut progress = KeeperEpochProgress::default();
+        let max_epochs = Some(2);
+        let mut worked_epochs = Vec::new();
+
+        // The keeper polls several times per epoch, the work only runs once in each
+        for polled_epoch in [5, 5, 6, 6, 6, 7] {
+            if progress.is_done(max_epochs) {
+                break;
+            }
+
+            if progress.needs_epoch_work(polled_epoch) {
+                worked_epochs.push(polled_epoch);
+                progress.complete_epoch(polled_epoch);
+            }
+        }
+
+        assert_eq!(worked_epochs, vec![5, 6]);
+        assert_eq!(progress.epochs_completed, 2);
+        assert!(progress.is_done(max_epochs));
+
+        // Without a limit the keeper never stops
+        assert!(!progress.is_done(None));
+    }
+
+    #[test]
+    fn test_load_aggregation_from_vote_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let aggregation = AggregationResult {
+            aggregated_signature: [1; 32],
+            aggregated_g2: [2; 64],
+            signers_bitmap: vec![0b0000_0111],
+        };
+        fs::write(
+            temp_file.path(),
+            serde_json::to_string(&aggregation).unwrap(),
+        )
+        .unwrap();
+
+        let loaded = load_aggregation_from_file(temp_file.path()).unwrap();
+        assert_eq!(loaded, Some(aggregation));
+
+        // A vote file that was not written yet skips the vote
+        let missing = temp_file.path().with_extension("missing");
+        assert_eq!(load_aggregation_from_file(missing).unwrap(), None);
+    }
+}
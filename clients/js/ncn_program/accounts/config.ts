@@ -15,8 +15,14 @@ import {
   fetchEncodedAccounts,
   getAddressDecoder,
   getAddressEncoder,
+  getArrayDecoder,
+  getArrayEncoder,
+  getBoolDecoder,
+  getBoolEncoder,
   getStructDecoder,
   getStructEncoder,
+  getU16Decoder,
+  getU16Encoder,
   getU64Decoder,
   getU64Encoder,
   getU8Decoder,
@@ -54,6 +60,16 @@ export type Config = {
   feeConfig: FeeConfig;
   bump: number;
   minimumStake: StakeWeights;
+  enforceSignerOrder: boolean;
+  curveId: number;
+  minimumOperators: bigint;
+  rejectDuplicateAggregates: boolean;
+  allowMultipleVotesPerEpoch: boolean;
+  epochLength: bigint;
+  quorumBps: number;
+  maxSnapshotAgeSlots: bigint;
+  allowEd25519Votes: boolean;
+  reserved: Array<number>;
 };
 
 export type ConfigArgs = {
@@ -67,6 +83,16 @@ export type ConfigArgs = {
   feeConfig: FeeConfigArgs;
   bump: number;
   minimumStake: StakeWeightsArgs;
+  enforceSignerOrder: boolean;
+  curveId: number;
+  minimumOperators: number | bigint;
+  rejectDuplicateAggregates: boolean;
+  allowMultipleVotesPerEpoch: boolean;
+  epochLength: number | bigint;
+  quorumBps: number;
+  maxSnapshotAgeSlots: number | bigint;
+  allowEd25519Votes: boolean;
+  reserved: Array<number>;
 };
 
 export function getConfigEncoder(): Encoder<ConfigArgs> {
@@ -81,6 +107,16 @@ export function getConfigEncoder(): Encoder<ConfigArgs> {
     ['feeConfig', getFeeConfigEncoder()],
     ['bump', getU8Encoder()],
     ['minimumStake', getStakeWeightsEncoder()],
+    ['enforceSignerOrder', getBoolEncoder()],
+    ['curveId', getU8Encoder()],
+    ['minimumOperators', getU64Encoder()],
+    ['rejectDuplicateAggregates', getBoolEncoder()],
+    ['allowMultipleVotesPerEpoch', getBoolEncoder()],
+    ['epochLength', getU64Encoder()],
+    ['quorumBps', getU16Encoder()],
+    ['maxSnapshotAgeSlots', getU64Encoder()],
+    ['allowEd25519Votes', getBoolEncoder()],
+    ['reserved', getArrayEncoder(getU8Encoder(), { size: 99 })],
   ]);
 }
 
@@ -96,6 +132,16 @@ export function getConfigDecoder(): Decoder<Config> {
     ['feeConfig', getFeeConfigDecoder()],
     ['bump', getU8Decoder()],
     ['minimumStake', getStakeWeightsDecoder()],
+    ['enforceSignerOrder', getBoolDecoder()],
+    ['curveId', getU8Decoder()],
+    ['minimumOperators', getU64Decoder()],
+    ['rejectDuplicateAggregates', getBoolDecoder()],
+    ['allowMultipleVotesPerEpoch', getBoolDecoder()],
+    ['epochLength', getU64Decoder()],
+    ['quorumBps', getU16Decoder()],
+    ['maxSnapshotAgeSlots', getU64Decoder()],
+    ['allowEd25519Votes', getBoolDecoder()],
+    ['reserved', getArrayDecoder(getU8Decoder(), { size: 99 })],
   ]);
 }
 
@@ -54,6 +54,7 @@ export type Snapshot = {
   discriminator: bigint;
   ncn: Address;
   bump: number;
+  version: number;
   slotCreated: bigint;
   operatorsRegistered: bigint;
   operatorsCanVoteCount: bigint;
@@ -61,12 +62,16 @@ export type Snapshot = {
   operatorSnapshots: Array<OperatorSnapshot>;
   minimumStake: StakeWeights;
   lastSnapshotSlot: bigint;
+  lastRolloverEpoch: bigint;
+  slotFinalized: bigint;
+  deregisteredOperators: ReadonlyUint8Array;
 };
 
 export type SnapshotArgs = {
   discriminator: number | bigint;
   ncn: Address;
   bump: number;
+  version: number;
   slotCreated: number | bigint;
   operatorsRegistered: number | bigint;
   operatorsCanVoteCount: number | bigint;
@@ -74,6 +79,9 @@ export type SnapshotArgs = {
   operatorSnapshots: Array<OperatorSnapshotArgs>;
   minimumStake: StakeWeightsArgs;
   lastSnapshotSlot: number | bigint;
+  lastRolloverEpoch: number | bigint;
+  slotFinalized: number | bigint;
+  deregisteredOperators: ReadonlyUint8Array;
 };
 
 export function getSnapshotEncoder(): Encoder<SnapshotArgs> {
@@ -81,6 +89,7 @@ export function getSnapshotEncoder(): Encoder<SnapshotArgs> {
     ['discriminator', getU64Encoder()],
     ['ncn', getAddressEncoder()],
     ['bump', getU8Encoder()],
+    ['version', getU8Encoder()],
     ['slotCreated', getU64Encoder()],
     ['operatorsRegistered', getU64Encoder()],
     ['operatorsCanVoteCount', getU64Encoder()],
@@ -91,6 +100,9 @@ export function getSnapshotEncoder(): Encoder<SnapshotArgs> {
     ],
     ['minimumStake', getStakeWeightsEncoder()],
     ['lastSnapshotSlot', getU64Encoder()],
+    ['lastRolloverEpoch', getU64Encoder()],
+    ['slotFinalized', getU64Encoder()],
+    ['deregisteredOperators', fixEncoderSize(getBytesEncoder(), 32)],
   ]);
 }
 
@@ -99,6 +111,7 @@ export function getSnapshotDecoder(): Decoder<Snapshot> {
     ['discriminator', getU64Decoder()],
     ['ncn', getAddressDecoder()],
     ['bump', getU8Decoder()],
+    ['version', getU8Decoder()],
     ['slotCreated', getU64Decoder()],
     ['operatorsRegistered', getU64Decoder()],
     ['operatorsCanVoteCount', getU64Decoder()],
@@ -109,6 +122,9 @@ export function getSnapshotDecoder(): Decoder<Snapshot> {
     ],
     ['minimumStake', getStakeWeightsDecoder()],
     ['lastSnapshotSlot', getU64Decoder()],
+    ['lastRolloverEpoch', getU64Decoder()],
+    ['slotFinalized', getU64Decoder()],
+    ['deregisteredOperators', fixDecoderSize(getBytesDecoder(), 32)],
   ]);
 }
 
@@ -19,6 +19,8 @@ import {
   getArrayEncoder,
   getStructDecoder,
   getStructEncoder,
+  getU128Decoder,
+  getU128Encoder,
   getU64Decoder,
   getU64Encoder,
   getU8Decoder,
@@ -34,6 +36,12 @@ import {
   type MaybeAccount,
   type MaybeEncodedAccount,
 } from '@solana/web3.js';
+import {
+  getConsensusRecordDecoder,
+  getConsensusRecordEncoder,
+  type ConsensusRecord,
+  type ConsensusRecordArgs,
+} from '../types';
 
 export type VoteCounter = {
   discriminator: bigint;
@@ -41,6 +49,16 @@ export type VoteCounter = {
   count: bigint;
   bump: number;
   reserved: Array<number>;
+  consensusRecordsWritten: bigint;
+  consensusRecords: Array<ConsensusRecord>;
+  lastVoteSlot: bigint;
+  lastVoteEpoch: bigint;
+  votesThisEpoch: bigint;
+  lastAggregateHash: Array<number>;
+  messagesRoot: Array<number>;
+  ed25519VoteEpoch: bigint;
+  ed25519StakeWeight: bigint;
+  ed25519Voters: Array<number>;
 };
 
 export type VoteCounterArgs = {
@@ -49,6 +67,16 @@ export type VoteCounterArgs = {
   count: number | bigint;
   bump: number;
   reserved: Array<number>;
+  consensusRecordsWritten: number | bigint;
+  consensusRecords: Array<ConsensusRecordArgs>;
+  lastVoteSlot: number | bigint;
+  lastVoteEpoch: number | bigint;
+  votesThisEpoch: number | bigint;
+  lastAggregateHash: Array<number>;
+  messagesRoot: Array<number>;
+  ed25519VoteEpoch: number | bigint;
+  ed25519StakeWeight: number | bigint;
+  ed25519Voters: Array<number>;
 };
 
 export function getVoteCounterEncoder(): Encoder<VoteCounterArgs> {
@@ -58,6 +86,19 @@ export function getVoteCounterEncoder(): Encoder<VoteCounterArgs> {
     ['count', getU64Encoder()],
     ['bump', getU8Encoder()],
     ['reserved', getArrayEncoder(getU8Encoder(), { size: 7 })],
+    ['consensusRecordsWritten', getU64Encoder()],
+    [
+      'consensusRecords',
+      getArrayEncoder(getConsensusRecordEncoder(), { size: 8 }),
+    ],
+    ['lastVoteSlot', getU64Encoder()],
+    ['lastVoteEpoch', getU64Encoder()],
+    ['votesThisEpoch', getU64Encoder()],
+    ['lastAggregateHash', getArrayEncoder(getU8Encoder(), { size: 32 })],
+    ['messagesRoot', getArrayEncoder(getU8Encoder(), { size: 32 })],
+    ['ed25519VoteEpoch', getU64Encoder()],
+    ['ed25519StakeWeight', getU128Encoder()],
+    ['ed25519Voters', getArrayEncoder(getU8Encoder(), { size: 32 })],
   ]);
 }
 
@@ -68,6 +109,19 @@ export function getVoteCounterDecoder(): Decoder<VoteCounter> {
     ['count', getU64Decoder()],
     ['bump', getU8Decoder()],
     ['reserved', getArrayDecoder(getU8Decoder(), { size: 7 })],
+    ['consensusRecordsWritten', getU64Decoder()],
+    [
+      'consensusRecords',
+      getArrayDecoder(getConsensusRecordDecoder(), { size: 8 }),
+    ],
+    ['lastVoteSlot', getU64Decoder()],
+    ['lastVoteEpoch', getU64Decoder()],
+    ['votesThisEpoch', getU64Decoder()],
+    ['lastAggregateHash', getArrayDecoder(getU8Decoder(), { size: 32 })],
+    ['messagesRoot', getArrayDecoder(getU8Decoder(), { size: 32 })],
+    ['ed25519VoteEpoch', getU64Decoder()],
+    ['ed25519StakeWeight', getU128Decoder()],
+    ['ed25519Voters', getArrayDecoder(getU8Decoder(), { size: 32 })],
   ]);
 }
 
@@ -244,9 +244,70 @@ export const NCN_PROGRAM_ERROR__N_C_N_OPERATOR_ACCOUNT_DOSENT_EXIST = 0x2263; //
 export const NCN_PROGRAM_ERROR__NO_OPERATORS_REGISTERED = 0x2264; // 8804
 /** InvalidOperatorCount: Invalid operator count */
 export const NCN_PROGRAM_ERROR__INVALID_OPERATOR_COUNT = 0x2265; // 8805
+/** InvalidSnapshotBackup: Invalid snapshot backup */
+export const NCN_PROGRAM_ERROR__INVALID_SNAPSHOT_BACKUP = 0x2266; // 8806
+/** UnsupportedSnapshotBackupVersion: Unsupported snapshot backup version */
+export const NCN_PROGRAM_ERROR__UNSUPPORTED_SNAPSHOT_BACKUP_VERSION = 0x2267; // 8807
+/** OperatorLinkInactive: Operator NCN link is not active */
+export const NCN_PROGRAM_ERROR__OPERATOR_LINK_INACTIVE = 0x2268; // 8808
+/** SignerOrderViolation: Signer accounts are not in ascending operator index order */
+export const NCN_PROGRAM_ERROR__SIGNER_ORDER_VIOLATION = 0x2269; // 8809
+/** BitmapTooLarge: Operator signature bitmap exceeds the maximum size */
+export const NCN_PROGRAM_ERROR__BITMAP_TOO_LARGE = 0x226a; // 8810
+/** SignerStakeQuorumNotMet: Signers stake weight is below the requested quorum */
+export const NCN_PROGRAM_ERROR__SIGNER_STAKE_QUORUM_NOT_MET = 0x226b; // 8811
+/** UnsupportedCurve: Unsupported curve */
+export const NCN_PROGRAM_ERROR__UNSUPPORTED_CURVE = 0x226c; // 8812
+/** InvalidG2Pubkey: Invalid G2 Pubkey */
+export const NCN_PROGRAM_ERROR__INVALID_G2_PUBKEY = 0x226d; // 8813
+/** SnapshotAlreadyRolledOver: Snapshot already rolled over this epoch */
+export const NCN_PROGRAM_ERROR__SNAPSHOT_ALREADY_ROLLED_OVER = 0x226e; // 8814
+/** NotEnoughOperatorsRegistered: Not enough operators registered for consensus */
+export const NCN_PROGRAM_ERROR__NOT_ENOUGH_OPERATORS_REGISTERED = 0x226f; // 8815
+/** InsufficientOperators: Not enough operators with the minimum stake for consensus */
+export const NCN_PROGRAM_ERROR__INSUFFICIENT_OPERATORS = 0x2270; // 8816
+/** OperatorSignedMessage: Operator signed the message */
+export const NCN_PROGRAM_ERROR__OPERATOR_SIGNED_MESSAGE = 0x2271; // 8817
+/** DuplicateAggregateSubmission: Aggregate signature was already submitted */
+export const NCN_PROGRAM_ERROR__DUPLICATE_AGGREGATE_SUBMISSION = 0x2272; // 8818
+/** VoteMessageMismatch: Vote message mismatch */
+export const NCN_PROGRAM_ERROR__VOTE_MESSAGE_MISMATCH = 0x2273; // 8819
+/** VaultOperatorDelegationNotUpdated: Vault operator delegation was not updated this epoch */
+export const NCN_PROGRAM_ERROR__VAULT_OPERATOR_DELEGATION_NOT_UPDATED = 0x2274; // 8820
+/** DuplicateOperatorIndex: Duplicate operator index */
+export const NCN_PROGRAM_ERROR__DUPLICATE_OPERATOR_INDEX = 0x2275; // 8821
+/** NoSignersInBitmap: No signers in bitmap */
+export const NCN_PROGRAM_ERROR__NO_SIGNERS_IN_BITMAP = 0x2276; // 8822
+/** SnapshotVersionMismatch: Snapshot version mismatch */
+export const NCN_PROGRAM_ERROR__SNAPSHOT_VERSION_MISMATCH = 0x2277; // 8823
+/** DuplicateOperatorG1Pubkey: G1 pubkey already registered by another operator */
+export const NCN_PROGRAM_ERROR__DUPLICATE_OPERATOR_G1_PUBKEY = 0x2278; // 8824
+/** ParameterChangeNotAllowed: Parameter change not allowed while a vote is pending */
+export const NCN_PROGRAM_ERROR__PARAMETER_CHANGE_NOT_ALLOWED = 0x2279; // 8825
+/** AlreadyVotedThisEpoch: A vote was already cast this epoch */
+export const NCN_PROGRAM_ERROR__ALREADY_VOTED_THIS_EPOCH = 0x227a; // 8826
+/** InvalidQuorumBps: Quorum bps exceeds 10000 */
+export const NCN_PROGRAM_ERROR__INVALID_QUORUM_BPS = 0x227b; // 8827
+/** G1PubkeyNotInAggregate: G1 pubkey is not part of the total aggregated pubkey */
+export const NCN_PROGRAM_ERROR__G1_PUBKEY_NOT_IN_AGGREGATE = 0x227c; // 8828
+/** InvalidOperatorSnapshotBytes: Invalid operator snapshot bytes */
+export const NCN_PROGRAM_ERROR__INVALID_OPERATOR_SNAPSHOT_BYTES = 0x227d; // 8829
+/** SnapshotTooStale: Snapshot too stale */
+export const NCN_PROGRAM_ERROR__SNAPSHOT_TOO_STALE = 0x227e; // 8830
+/** G2AggregateMismatch: G2 aggregate mismatch */
+export const NCN_PROGRAM_ERROR__G2_AGGREGATE_MISMATCH = 0x227f; // 8831
+/** Ed25519VotesDisabled: Ed25519 votes are disabled */
+export const NCN_PROGRAM_ERROR__ED25519_VOTES_DISABLED = 0x2280; // 8832
+/** InvalidEd25519Instruction: Invalid ed25519 instruction */
+export const NCN_PROGRAM_ERROR__INVALID_ED25519_INSTRUCTION = 0x2281; // 8833
+/** Ed25519AlreadyVoted: Operator already cast an ed25519 vote this epoch */
+export const NCN_PROGRAM_ERROR__ED25519_ALREADY_VOTED = 0x2282; // 8834
+/** NcnOperatorIndexMismatch: NCN operator index does not match the operator's NCN operator state */
+export const NCN_PROGRAM_ERROR__NCN_OPERATOR_INDEX_MISMATCH = 0x2283; // 8835
 
 export type NcnProgramError =
   | typeof NCN_PROGRAM_ERROR__ACCOUNT_ALREADY_INITIALIZED
+  | typeof NCN_PROGRAM_ERROR__ALREADY_VOTED_THIS_EPOCH
   | typeof NCN_PROGRAM_ERROR__ALT_B_N128_ADD_ERROR
   | typeof NCN_PROGRAM_ERROR__ALT_B_N128_MUL_ERROR
   | typeof NCN_PROGRAM_ERROR__ALT_B_N128_PAIRING_ERROR
@@ -257,6 +318,7 @@ export type NcnProgramError =
   | typeof NCN_PROGRAM_ERROR__BALLOT_TALLY_FULL
   | typeof NCN_PROGRAM_ERROR__BALLOT_TALLY_NOT_EMPTY
   | typeof NCN_PROGRAM_ERROR__BALLOT_TALLY_NOT_FOUND_FULL
+  | typeof NCN_PROGRAM_ERROR__BITMAP_TOO_LARGE
   | typeof NCN_PROGRAM_ERROR__B_L_S_SIGNING_ERROR
   | typeof NCN_PROGRAM_ERROR__B_L_S_VERIFICATION_ERROR
   | typeof NCN_PROGRAM_ERROR__CANNOT_CLOSE_ACCOUNT
@@ -278,15 +340,22 @@ export type NcnProgramError =
   | typeof NCN_PROGRAM_ERROR__DEFAULT_PROTOCOL_WALLET
   | typeof NCN_PROGRAM_ERROR__DENOMINATOR_IS_ZERO
   | typeof NCN_PROGRAM_ERROR__DESTINATION_MISMATCH
+  | typeof NCN_PROGRAM_ERROR__DUPLICATE_AGGREGATE_SUBMISSION
   | typeof NCN_PROGRAM_ERROR__DUPLICATE_MINTS_IN_TABLE
+  | typeof NCN_PROGRAM_ERROR__DUPLICATE_OPERATOR_G1_PUBKEY
+  | typeof NCN_PROGRAM_ERROR__DUPLICATE_OPERATOR_INDEX
   | typeof NCN_PROGRAM_ERROR__DUPLICATE_VAULT_OPERATOR_DELEGATION
   | typeof NCN_PROGRAM_ERROR__DUPLICATE_VOTE_CAST
+  | typeof NCN_PROGRAM_ERROR__ED25519_ALREADY_VOTED
+  | typeof NCN_PROGRAM_ERROR__ED25519_VOTES_DISABLED
   | typeof NCN_PROGRAM_ERROR__EPOCH_IS_CLOSING_DOWN
   | typeof NCN_PROGRAM_ERROR__FEE_CAP_EXCEEDED
   | typeof NCN_PROGRAM_ERROR__FEE_NOT_ACTIVE
   | typeof NCN_PROGRAM_ERROR__G1_POINT_ADDITION_ERROR
   | typeof NCN_PROGRAM_ERROR__G1_POINT_COMPRESSION_ERROR
   | typeof NCN_PROGRAM_ERROR__G1_POINT_DECOMPRESSION_ERROR
+  | typeof NCN_PROGRAM_ERROR__G1_PUBKEY_NOT_IN_AGGREGATE
+  | typeof NCN_PROGRAM_ERROR__G2_AGGREGATE_MISMATCH
   | typeof NCN_PROGRAM_ERROR__G2_POINT_ADDITION_ERROR
   | typeof NCN_PROGRAM_ERROR__G2_POINT_COMPRESSION_ERROR
   | typeof NCN_PROGRAM_ERROR__G2_POINT_DECOMPRESSION_ERROR
@@ -295,12 +364,15 @@ export type NcnProgramError =
   | typeof NCN_PROGRAM_ERROR__INCORRECT_NCN
   | typeof NCN_PROGRAM_ERROR__INCORRECT_NCN_ADMIN
   | typeof NCN_PROGRAM_ERROR__INCORRECT_WEIGHT_TABLE_ADMIN
+  | typeof NCN_PROGRAM_ERROR__INSUFFICIENT_OPERATORS
   | typeof NCN_PROGRAM_ERROR__INVALID_ACCOUNT_STATUS
   | typeof NCN_PROGRAM_ERROR__INVALID_ACCOUNT_TO_CLOSE_DISCRIMINATOR
   | typeof NCN_PROGRAM_ERROR__INVALID_BASE_FEE_GROUP
+  | typeof NCN_PROGRAM_ERROR__INVALID_ED25519_INSTRUCTION
   | typeof NCN_PROGRAM_ERROR__INVALID_EPOCHS_BEFORE_CLOSE
   | typeof NCN_PROGRAM_ERROR__INVALID_EPOCHS_BEFORE_STALL
   | typeof NCN_PROGRAM_ERROR__INVALID_G1_PUBKEY
+  | typeof NCN_PROGRAM_ERROR__INVALID_G2_PUBKEY
   | typeof NCN_PROGRAM_ERROR__INVALID_INPUT_LENGTH
   | typeof NCN_PROGRAM_ERROR__INVALID_MERKLE_PROOF
   | typeof NCN_PROGRAM_ERROR__INVALID_MINIMUM_STAKE_WEIGHT
@@ -308,38 +380,52 @@ export type NcnProgramError =
   | typeof NCN_PROGRAM_ERROR__INVALID_NCN_FEE_GROUP
   | typeof NCN_PROGRAM_ERROR__INVALID_N_C_N_FEE_WALLET
   | typeof NCN_PROGRAM_ERROR__INVALID_OPERATOR_COUNT
+  | typeof NCN_PROGRAM_ERROR__INVALID_OPERATOR_SNAPSHOT_BYTES
   | typeof NCN_PROGRAM_ERROR__INVALID_OPERATOR_VOTER
+  | typeof NCN_PROGRAM_ERROR__INVALID_QUORUM_BPS
   | typeof NCN_PROGRAM_ERROR__INVALID_SLOTS_AFTER_CONSENSUS
+  | typeof NCN_PROGRAM_ERROR__INVALID_SNAPSHOT_BACKUP
   | typeof NCN_PROGRAM_ERROR__MARKER_EXISTS
   | typeof NCN_PROGRAM_ERROR__MINT_ENTRY_NOT_FOUND
   | typeof NCN_PROGRAM_ERROR__MINT_IN_TABLE
   | typeof NCN_PROGRAM_ERROR__MISSING_REQUIRED_SIGNATURE
   | typeof NCN_PROGRAM_ERROR__MODULO_OVERFLOW
   | typeof NCN_PROGRAM_ERROR__N_C_N_OPERATOR_ACCOUNT_DOSENT_EXIST
+  | typeof NCN_PROGRAM_ERROR__NCN_OPERATOR_INDEX_MISMATCH
   | typeof NCN_PROGRAM_ERROR__N_C_N_OPERATOR_OPT_IN_INACTIVE
   | typeof NCN_PROGRAM_ERROR__NEW_PRECISE_NUMBER_ERROR
   | typeof NCN_PROGRAM_ERROR__NO_MINTS_IN_TABLE
   | typeof NCN_PROGRAM_ERROR__NO_NON_SIGNERS_AGGREGATED_PUBKEY
   | typeof NCN_PROGRAM_ERROR__NO_OPERATORS
   | typeof NCN_PROGRAM_ERROR__NO_OPERATORS_REGISTERED
+  | typeof NCN_PROGRAM_ERROR__NO_SIGNERS_IN_BITMAP
+  | typeof NCN_PROGRAM_ERROR__NOT_ENOUGH_OPERATORS_REGISTERED
   | typeof NCN_PROGRAM_ERROR__NO_VALID_BALLOTS
   | typeof NCN_PROGRAM_ERROR__NO_VAULTS_IN_REGISTRY
   | typeof NCN_PROGRAM_ERROR__OPERATOR_ALREADY_VOTED
   | typeof NCN_PROGRAM_ERROR__OPERATOR_HAS_NO_MINIMUM_STAKE
   | typeof NCN_PROGRAM_ERROR__OPERATOR_IS_NOT_IN_SNAPSHOT
+  | typeof NCN_PROGRAM_ERROR__OPERATOR_LINK_INACTIVE
   | typeof NCN_PROGRAM_ERROR__OPERATOR_NCN_CONNECTION_NOT_ACTIVE
   | typeof NCN_PROGRAM_ERROR__OPERATOR_REGISTRY_LIST_FULL
+  | typeof NCN_PROGRAM_ERROR__OPERATOR_SIGNED_MESSAGE
   | typeof NCN_PROGRAM_ERROR__OPERATOR_SNAPSHOT_ALREADY_TAKED
   | typeof NCN_PROGRAM_ERROR__OPERATOR_SNAPSHOT_IS_NOT_ACTIVE
   | typeof NCN_PROGRAM_ERROR__OPERATOR_SNAPSHOT_OUTDATED
   | typeof NCN_PROGRAM_ERROR__OPERATOR_VOTES_FULL
+  | typeof NCN_PROGRAM_ERROR__PARAMETER_CHANGE_NOT_ALLOWED
   | typeof NCN_PROGRAM_ERROR__QUORUM_NOT_MET
   | typeof NCN_PROGRAM_ERROR__REGISTRY_NOT_INITIALIZED
   | typeof NCN_PROGRAM_ERROR__SECRET_KEY_ERROR
   | typeof NCN_PROGRAM_ERROR__SERIALIZATION_ERROR
   | typeof NCN_PROGRAM_ERROR__SIGNATURE_VERIFICATION_FAILED
+  | typeof NCN_PROGRAM_ERROR__SIGNER_ORDER_VIOLATION
+  | typeof NCN_PROGRAM_ERROR__SIGNER_STAKE_QUORUM_NOT_MET
   | typeof NCN_PROGRAM_ERROR__SNAPSHOT_ALREADY_FINALIZED
+  | typeof NCN_PROGRAM_ERROR__SNAPSHOT_ALREADY_ROLLED_OVER
   | typeof NCN_PROGRAM_ERROR__SNAPSHOT_NOT_FINALIZED
+  | typeof NCN_PROGRAM_ERROR__SNAPSHOT_TOO_STALE
+  | typeof NCN_PROGRAM_ERROR__SNAPSHOT_VERSION_MISMATCH
   | typeof NCN_PROGRAM_ERROR__TABLE_NOT_INITIALIZED
   | typeof NCN_PROGRAM_ERROR__TIE_BREAKER_ADMIN_INVALID
   | typeof NCN_PROGRAM_ERROR__TIE_BREAKER_NOT_IN_PRIOR_VOTES
@@ -347,12 +433,16 @@ export type NcnProgramError =
   | typeof NCN_PROGRAM_ERROR__TOO_MANY_VAULT_OPERATOR_DELEGATIONS
   | typeof NCN_PROGRAM_ERROR__TOO_MANY_VAULTS_FOR_REGISTRY
   | typeof NCN_PROGRAM_ERROR__TOTAL_FEES_CANNOT_BE_ZERO
+  | typeof NCN_PROGRAM_ERROR__UNSUPPORTED_CURVE
+  | typeof NCN_PROGRAM_ERROR__UNSUPPORTED_SNAPSHOT_BACKUP_VERSION
   | typeof NCN_PROGRAM_ERROR__VAULT_INDEX_ALREADY_IN_USE
   | typeof NCN_PROGRAM_ERROR__VAULT_NEEDS_UPDATE
   | typeof NCN_PROGRAM_ERROR__VAULT_NOT_IN_REGISTRY
   | typeof NCN_PROGRAM_ERROR__VAULT_OPERATOR_DELEGATION_FINALIZED
+  | typeof NCN_PROGRAM_ERROR__VAULT_OPERATOR_DELEGATION_NOT_UPDATED
   | typeof NCN_PROGRAM_ERROR__VAULT_REGISTRY_LIST_FULL
   | typeof NCN_PROGRAM_ERROR__VAULT_REGISTRY_VAULT_LOCKED
+  | typeof NCN_PROGRAM_ERROR__VOTE_MESSAGE_MISMATCH
   | typeof NCN_PROGRAM_ERROR__VOTING_NOT_FINALIZED
   | typeof NCN_PROGRAM_ERROR__VOTING_NOT_VALID
   | typeof NCN_PROGRAM_ERROR__WEIGHT_MINTS_DO_NOT_MATCH_LENGTH
@@ -366,6 +456,7 @@ let ncnProgramErrorMessages: Record<NcnProgramError, string> | undefined;
 if (process.env.NODE_ENV !== 'production') {
   ncnProgramErrorMessages = {
     [NCN_PROGRAM_ERROR__ACCOUNT_ALREADY_INITIALIZED]: `Account already initialized`,
+    [NCN_PROGRAM_ERROR__ALREADY_VOTED_THIS_EPOCH]: `A vote was already cast this epoch`,
     [NCN_PROGRAM_ERROR__ALT_B_N128_ADD_ERROR]: `AltBN128 add error`,
     [NCN_PROGRAM_ERROR__ALT_B_N128_MUL_ERROR]: `AltBN128 mul error`,
     [NCN_PROGRAM_ERROR__ALT_B_N128_PAIRING_ERROR]: `AltBN128 pairing error`,
@@ -376,6 +467,7 @@ if (process.env.NODE_ENV !== 'production') {
     [NCN_PROGRAM_ERROR__BALLOT_TALLY_FULL]: `Merkle root tally full`,
     [NCN_PROGRAM_ERROR__BALLOT_TALLY_NOT_EMPTY]: `Ballot tally not empty`,
     [NCN_PROGRAM_ERROR__BALLOT_TALLY_NOT_FOUND_FULL]: `Ballot tally not found`,
+    [NCN_PROGRAM_ERROR__BITMAP_TOO_LARGE]: `Operator signature bitmap exceeds the maximum size`,
     [NCN_PROGRAM_ERROR__B_L_S_SIGNING_ERROR]: `BLSSigning error`,
     [NCN_PROGRAM_ERROR__B_L_S_VERIFICATION_ERROR]: `BLSVerification error`,
     [NCN_PROGRAM_ERROR__CANNOT_CLOSE_ACCOUNT]: `Cannot close account`,
@@ -397,15 +489,22 @@ if (process.env.NODE_ENV !== 'production') {
     [NCN_PROGRAM_ERROR__DEFAULT_PROTOCOL_WALLET]: `Protocol wallet cannot be default`,
     [NCN_PROGRAM_ERROR__DENOMINATOR_IS_ZERO]: `Zero in the denominator`,
     [NCN_PROGRAM_ERROR__DESTINATION_MISMATCH]: `Destination mismatch`,
+    [NCN_PROGRAM_ERROR__DUPLICATE_AGGREGATE_SUBMISSION]: `Aggregate signature was already submitted`,
     [NCN_PROGRAM_ERROR__DUPLICATE_MINTS_IN_TABLE]: `Duplicate mints in table`,
+    [NCN_PROGRAM_ERROR__DUPLICATE_OPERATOR_G1_PUBKEY]: `G1 pubkey already registered by another operator`,
+    [NCN_PROGRAM_ERROR__DUPLICATE_OPERATOR_INDEX]: `Duplicate operator index`,
     [NCN_PROGRAM_ERROR__DUPLICATE_VAULT_OPERATOR_DELEGATION]: `Duplicate vault operator delegation`,
     [NCN_PROGRAM_ERROR__DUPLICATE_VOTE_CAST]: `Duplicate Vote Cast`,
+    [NCN_PROGRAM_ERROR__ED25519_ALREADY_VOTED]: `Operator already cast an ed25519 vote this epoch`,
+    [NCN_PROGRAM_ERROR__ED25519_VOTES_DISABLED]: `Ed25519 votes are disabled`,
     [NCN_PROGRAM_ERROR__EPOCH_IS_CLOSING_DOWN]: `Epoch is closing down`,
     [NCN_PROGRAM_ERROR__FEE_CAP_EXCEEDED]: `Fee cap exceeded`,
     [NCN_PROGRAM_ERROR__FEE_NOT_ACTIVE]: `Fee not active`,
     [NCN_PROGRAM_ERROR__G1_POINT_ADDITION_ERROR]: `G1 Point Addition Error`,
     [NCN_PROGRAM_ERROR__G1_POINT_COMPRESSION_ERROR]: `G1 point compression error`,
     [NCN_PROGRAM_ERROR__G1_POINT_DECOMPRESSION_ERROR]: `G1 point decompression error`,
+    [NCN_PROGRAM_ERROR__G1_PUBKEY_NOT_IN_AGGREGATE]: `G1 pubkey is not part of the total aggregated pubkey`,
+    [NCN_PROGRAM_ERROR__G2_AGGREGATE_MISMATCH]: `G2 aggregate mismatch`,
     [NCN_PROGRAM_ERROR__G2_POINT_ADDITION_ERROR]: `G2 Point Addition Error`,
     [NCN_PROGRAM_ERROR__G2_POINT_COMPRESSION_ERROR]: `G2 point compression error`,
     [NCN_PROGRAM_ERROR__G2_POINT_DECOMPRESSION_ERROR]: `G2 point decompression error`,
@@ -414,12 +513,15 @@ if (process.env.NODE_ENV !== 'production') {
     [NCN_PROGRAM_ERROR__INCORRECT_NCN]: `Incorrect NCN`,
     [NCN_PROGRAM_ERROR__INCORRECT_NCN_ADMIN]: `Incorrect NCN Admin`,
     [NCN_PROGRAM_ERROR__INCORRECT_WEIGHT_TABLE_ADMIN]: `Incorrect weight table admin`,
+    [NCN_PROGRAM_ERROR__INSUFFICIENT_OPERATORS]: `Not enough operators with the minimum stake for consensus`,
     [NCN_PROGRAM_ERROR__INVALID_ACCOUNT_STATUS]: `Invalid Account Status`,
     [NCN_PROGRAM_ERROR__INVALID_ACCOUNT_TO_CLOSE_DISCRIMINATOR]: `Invalid account_to_close Discriminator`,
     [NCN_PROGRAM_ERROR__INVALID_BASE_FEE_GROUP]: `Not a valid base fee group`,
+    [NCN_PROGRAM_ERROR__INVALID_ED25519_INSTRUCTION]: `Invalid ed25519 instruction`,
     [NCN_PROGRAM_ERROR__INVALID_EPOCHS_BEFORE_CLOSE]: `Invalid epochs before accounts can close`,
     [NCN_PROGRAM_ERROR__INVALID_EPOCHS_BEFORE_STALL]: `Invalid epochs before stall`,
     [NCN_PROGRAM_ERROR__INVALID_G1_PUBKEY]: `Invalid G1 Pubkey`,
+    [NCN_PROGRAM_ERROR__INVALID_G2_PUBKEY]: `Invalid G2 Pubkey`,
     [NCN_PROGRAM_ERROR__INVALID_INPUT_LENGTH]: `Invalid input length`,
     [NCN_PROGRAM_ERROR__INVALID_MERKLE_PROOF]: `Invalid merkle proof`,
     [NCN_PROGRAM_ERROR__INVALID_MINIMUM_STAKE_WEIGHT]: `Invalid Minimum Stake Weight`,
@@ -427,38 +529,52 @@ if (process.env.NODE_ENV !== 'production') {
     [NCN_PROGRAM_ERROR__INVALID_NCN_FEE_GROUP]: `Not a valid NCN fee group`,
     [NCN_PROGRAM_ERROR__INVALID_N_C_N_FEE_WALLET]: `Invalid NCN Fee wallet`,
     [NCN_PROGRAM_ERROR__INVALID_OPERATOR_COUNT]: `Invalid operator count`,
+    [NCN_PROGRAM_ERROR__INVALID_OPERATOR_SNAPSHOT_BYTES]: `Invalid operator snapshot bytes`,
     [NCN_PROGRAM_ERROR__INVALID_OPERATOR_VOTER]: `Operator voter needs to sign its vote`,
+    [NCN_PROGRAM_ERROR__INVALID_QUORUM_BPS]: `Quorum bps exceeds 10000`,
     [NCN_PROGRAM_ERROR__INVALID_SLOTS_AFTER_CONSENSUS]: `Invalid slots after consensus`,
+    [NCN_PROGRAM_ERROR__INVALID_SNAPSHOT_BACKUP]: `Invalid snapshot backup`,
     [NCN_PROGRAM_ERROR__MARKER_EXISTS]: `Marker exists`,
     [NCN_PROGRAM_ERROR__MINT_ENTRY_NOT_FOUND]: `Mint Entry not found`,
     [NCN_PROGRAM_ERROR__MINT_IN_TABLE]: `Mint is already in the table`,
     [NCN_PROGRAM_ERROR__MISSING_REQUIRED_SIGNATURE]: `Missing required signature`,
     [NCN_PROGRAM_ERROR__MODULO_OVERFLOW]: `Modulo Overflow`,
     [NCN_PROGRAM_ERROR__N_C_N_OPERATOR_ACCOUNT_DOSENT_EXIST]: `NCN Operator Account Doesn't exist`,
+    [NCN_PROGRAM_ERROR__NCN_OPERATOR_INDEX_MISMATCH]: `NCN operator index does not match the operator's NCN operator state`,
     [NCN_PROGRAM_ERROR__N_C_N_OPERATOR_OPT_IN_INACTIVE]: `NCN Operator Opt In Inactive`,
     [NCN_PROGRAM_ERROR__NEW_PRECISE_NUMBER_ERROR]: `New precise number error`,
     [NCN_PROGRAM_ERROR__NO_MINTS_IN_TABLE]: `There are no mints in the table`,
     [NCN_PROGRAM_ERROR__NO_NON_SIGNERS_AGGREGATED_PUBKEY]: `No non signers aggregated pubkey`,
     [NCN_PROGRAM_ERROR__NO_OPERATORS]: `No operators in ncn`,
     [NCN_PROGRAM_ERROR__NO_OPERATORS_REGISTERED]: `No operators registered`,
+    [NCN_PROGRAM_ERROR__NO_SIGNERS_IN_BITMAP]: `No signers in bitmap`,
+    [NCN_PROGRAM_ERROR__NOT_ENOUGH_OPERATORS_REGISTERED]: `Not enough operators registered for consensus`,
     [NCN_PROGRAM_ERROR__NO_VALID_BALLOTS]: `No valid Ballot`,
     [NCN_PROGRAM_ERROR__NO_VAULTS_IN_REGISTRY]: `There are no vaults in the registry`,
     [NCN_PROGRAM_ERROR__OPERATOR_ALREADY_VOTED]: `Operator Already Voted`,
     [NCN_PROGRAM_ERROR__OPERATOR_HAS_NO_MINIMUM_STAKE]: `Operator has no minimum stake`,
     [NCN_PROGRAM_ERROR__OPERATOR_IS_NOT_IN_SNAPSHOT]: `Operator is not in snapshot`,
+    [NCN_PROGRAM_ERROR__OPERATOR_LINK_INACTIVE]: `Operator NCN link is not active`,
     [NCN_PROGRAM_ERROR__OPERATOR_NCN_CONNECTION_NOT_ACTIVE]: `Operator <> NCN connection is not active`,
     [NCN_PROGRAM_ERROR__OPERATOR_REGISTRY_LIST_FULL]: `Operator Registry operators are at capacity`,
+    [NCN_PROGRAM_ERROR__OPERATOR_SIGNED_MESSAGE]: `Operator signed the message`,
     [NCN_PROGRAM_ERROR__OPERATOR_SNAPSHOT_ALREADY_TAKED]: `Operator snapshot is already taken`,
     [NCN_PROGRAM_ERROR__OPERATOR_SNAPSHOT_IS_NOT_ACTIVE]: `Operator snapshot is not active`,
     [NCN_PROGRAM_ERROR__OPERATOR_SNAPSHOT_OUTDATED]: `Operator snapshot is outdated`,
     [NCN_PROGRAM_ERROR__OPERATOR_VOTES_FULL]: `Operator votes full`,
+    [NCN_PROGRAM_ERROR__PARAMETER_CHANGE_NOT_ALLOWED]: `Parameter change not allowed while a vote is pending`,
     [NCN_PROGRAM_ERROR__QUORUM_NOT_MET]: `Quorum not met`,
     [NCN_PROGRAM_ERROR__REGISTRY_NOT_INITIALIZED]: `Registry not initialized`,
     [NCN_PROGRAM_ERROR__SECRET_KEY_ERROR]: `Secret key error`,
     [NCN_PROGRAM_ERROR__SERIALIZATION_ERROR]: `Serialization error`,
     [NCN_PROGRAM_ERROR__SIGNATURE_VERIFICATION_FAILED]: `Signature verification failed`,
+    [NCN_PROGRAM_ERROR__SIGNER_ORDER_VIOLATION]: `Signer accounts are not in ascending operator index order`,
+    [NCN_PROGRAM_ERROR__SIGNER_STAKE_QUORUM_NOT_MET]: `Signers stake weight is below the requested quorum`,
     [NCN_PROGRAM_ERROR__SNAPSHOT_ALREADY_FINALIZED]: `snapshot already finalized`,
+    [NCN_PROGRAM_ERROR__SNAPSHOT_ALREADY_ROLLED_OVER]: `Snapshot already rolled over this epoch`,
     [NCN_PROGRAM_ERROR__SNAPSHOT_NOT_FINALIZED]: `Snapshot not finalized`,
+    [NCN_PROGRAM_ERROR__SNAPSHOT_TOO_STALE]: `Snapshot too stale`,
+    [NCN_PROGRAM_ERROR__SNAPSHOT_VERSION_MISMATCH]: `Snapshot version mismatch`,
     [NCN_PROGRAM_ERROR__TABLE_NOT_INITIALIZED]: `Table not initialized`,
     [NCN_PROGRAM_ERROR__TIE_BREAKER_ADMIN_INVALID]: `Tie breaker admin invalid`,
     [NCN_PROGRAM_ERROR__TIE_BREAKER_NOT_IN_PRIOR_VOTES]: `Tie breaking ballot must be one of the prior votes`,
@@ -466,12 +582,16 @@ if (process.env.NODE_ENV !== 'production') {
     [NCN_PROGRAM_ERROR__TOO_MANY_VAULT_OPERATOR_DELEGATIONS]: `Too many vault operator delegations`,
     [NCN_PROGRAM_ERROR__TOO_MANY_VAULTS_FOR_REGISTRY]: `Too many vaults for registry`,
     [NCN_PROGRAM_ERROR__TOTAL_FEES_CANNOT_BE_ZERO]: `Total fees cannot be 0`,
+    [NCN_PROGRAM_ERROR__UNSUPPORTED_CURVE]: `Unsupported curve`,
+    [NCN_PROGRAM_ERROR__UNSUPPORTED_SNAPSHOT_BACKUP_VERSION]: `Unsupported snapshot backup version`,
     [NCN_PROGRAM_ERROR__VAULT_INDEX_ALREADY_IN_USE]: `Vault index already in use by a different mint`,
     [NCN_PROGRAM_ERROR__VAULT_NEEDS_UPDATE]: `Vault needs to be updated`,
     [NCN_PROGRAM_ERROR__VAULT_NOT_IN_REGISTRY]: `Vault not in weight table registry`,
     [NCN_PROGRAM_ERROR__VAULT_OPERATOR_DELEGATION_FINALIZED]: `Vault operator delegation is already finalized - should not happen`,
+    [NCN_PROGRAM_ERROR__VAULT_OPERATOR_DELEGATION_NOT_UPDATED]: `Vault operator delegation was not updated this epoch`,
     [NCN_PROGRAM_ERROR__VAULT_REGISTRY_LIST_FULL]: `Vault Registry mints are at capacity`,
     [NCN_PROGRAM_ERROR__VAULT_REGISTRY_VAULT_LOCKED]: `Vault registry are locked for the epoch`,
+    [NCN_PROGRAM_ERROR__VOTE_MESSAGE_MISMATCH]: `Vote message mismatch`,
     [NCN_PROGRAM_ERROR__VOTING_NOT_FINALIZED]: `Voting not finalized`,
     [NCN_PROGRAM_ERROR__VOTING_NOT_VALID]: `Voting not valid, too many slots after consensus reached`,
     [NCN_PROGRAM_ERROR__WEIGHT_MINTS_DO_NOT_MATCH_LENGTH]: `Weight mints do not match - length`,
@@ -8,12 +8,16 @@
 
 import {
   combineCodec,
+  getBoolDecoder,
+  getBoolEncoder,
   getOptionDecoder,
   getOptionEncoder,
   getStructDecoder,
   getStructEncoder,
   getU128Decoder,
   getU128Encoder,
+  getU16Decoder,
+  getU16Encoder,
   getU64Decoder,
   getU64Encoder,
   getU8Decoder,
@@ -49,6 +53,9 @@ export type AdminSetParametersInstruction<
   TAccountConfig extends string | IAccountMeta<string> = string,
   TAccountNcn extends string | IAccountMeta<string> = string,
   TAccountNcnAdmin extends string | IAccountMeta<string> = string,
+  TAccountRestakingConfig extends string | IAccountMeta<string> = string,
+  TAccountSnapshot extends string | IAccountMeta<string> = string,
+  TAccountVoteCounter extends string | IAccountMeta<string> = string,
   TRemainingAccounts extends readonly IAccountMeta<string>[] = [],
 > = IInstruction<TProgram> &
   IInstructionWithData<Uint8Array> &
@@ -62,6 +69,15 @@ export type AdminSetParametersInstruction<
         ? ReadonlySignerAccount<TAccountNcnAdmin> &
             IAccountSignerMeta<TAccountNcnAdmin>
         : TAccountNcnAdmin,
+      TAccountRestakingConfig extends string
+        ? ReadonlyAccount<TAccountRestakingConfig>
+        : TAccountRestakingConfig,
+      TAccountSnapshot extends string
+        ? ReadonlyAccount<TAccountSnapshot>
+        : TAccountSnapshot,
+      TAccountVoteCounter extends string
+        ? ReadonlyAccount<TAccountVoteCounter>
+        : TAccountVoteCounter,
       ...TRemainingAccounts,
     ]
   >;
@@ -73,6 +89,13 @@ export type AdminSetParametersInstructionData = {
   epochsAfterConsensusBeforeClose: Option<bigint>;
   validSlotsAfterConsensus: Option<bigint>;
   minimumStake: Option<bigint>;
+  enforceSignerOrder: Option<boolean>;
+  minimumOperators: Option<bigint>;
+  rejectDuplicateAggregates: Option<boolean>;
+  allowMultipleVotesPerEpoch: Option<boolean>;
+  quorumBps: Option<number>;
+  maxSnapshotAgeSlots: Option<bigint>;
+  allowEd25519Votes: Option<boolean>;
 };
 
 export type AdminSetParametersInstructionDataArgs = {
@@ -81,6 +104,13 @@ export type AdminSetParametersInstructionDataArgs = {
   epochsAfterConsensusBeforeClose: OptionOrNullable<number | bigint>;
   validSlotsAfterConsensus: OptionOrNullable<number | bigint>;
   minimumStake: OptionOrNullable<number | bigint>;
+  enforceSignerOrder: OptionOrNullable<boolean>;
+  minimumOperators: OptionOrNullable<number | bigint>;
+  rejectDuplicateAggregates: OptionOrNullable<boolean>;
+  allowMultipleVotesPerEpoch: OptionOrNullable<boolean>;
+  quorumBps: OptionOrNullable<number>;
+  maxSnapshotAgeSlots: OptionOrNullable<number | bigint>;
+  allowEd25519Votes: OptionOrNullable<boolean>;
 };
 
 export function getAdminSetParametersInstructionDataEncoder(): Encoder<AdminSetParametersInstructionDataArgs> {
@@ -92,6 +122,13 @@ export function getAdminSetParametersInstructionDataEncoder(): Encoder<AdminSetP
       ['epochsAfterConsensusBeforeClose', getOptionEncoder(getU64Encoder())],
       ['validSlotsAfterConsensus', getOptionEncoder(getU64Encoder())],
       ['minimumStake', getOptionEncoder(getU128Encoder())],
+      ['enforceSignerOrder', getOptionEncoder(getBoolEncoder())],
+      ['minimumOperators', getOptionEncoder(getU64Encoder())],
+      ['rejectDuplicateAggregates', getOptionEncoder(getBoolEncoder())],
+      ['allowMultipleVotesPerEpoch', getOptionEncoder(getBoolEncoder())],
+      ['quorumBps', getOptionEncoder(getU16Encoder())],
+      ['maxSnapshotAgeSlots', getOptionEncoder(getU64Encoder())],
+      ['allowEd25519Votes', getOptionEncoder(getBoolEncoder())],
     ]),
     (value) => ({ ...value, discriminator: ADMIN_SET_PARAMETERS_DISCRIMINATOR })
   );
@@ -105,6 +142,13 @@ export function getAdminSetParametersInstructionDataDecoder(): Decoder<AdminSetP
     ['epochsAfterConsensusBeforeClose', getOptionDecoder(getU64Decoder())],
     ['validSlotsAfterConsensus', getOptionDecoder(getU64Decoder())],
     ['minimumStake', getOptionDecoder(getU128Decoder())],
+    ['enforceSignerOrder', getOptionDecoder(getBoolDecoder())],
+    ['minimumOperators', getOptionDecoder(getU64Decoder())],
+    ['rejectDuplicateAggregates', getOptionDecoder(getBoolDecoder())],
+    ['allowMultipleVotesPerEpoch', getOptionDecoder(getBoolDecoder())],
+    ['quorumBps', getOptionDecoder(getU16Decoder())],
+    ['maxSnapshotAgeSlots', getOptionDecoder(getU64Decoder())],
+    ['allowEd25519Votes', getOptionDecoder(getBoolDecoder())],
   ]);
 }
 
@@ -122,30 +166,56 @@ export type AdminSetParametersInput<
   TAccountConfig extends string = string,
   TAccountNcn extends string = string,
   TAccountNcnAdmin extends string = string,
+  TAccountRestakingConfig extends string = string,
+  TAccountSnapshot extends string = string,
+  TAccountVoteCounter extends string = string,
 > = {
   config: Address<TAccountConfig>;
   ncn: Address<TAccountNcn>;
   ncnAdmin: TransactionSigner<TAccountNcnAdmin>;
+  restakingConfig: Address<TAccountRestakingConfig>;
+  snapshot: Address<TAccountSnapshot>;
+  voteCounter: Address<TAccountVoteCounter>;
   startingValidEpoch: AdminSetParametersInstructionDataArgs['startingValidEpoch'];
   epochsBeforeStall: AdminSetParametersInstructionDataArgs['epochsBeforeStall'];
   epochsAfterConsensusBeforeClose: AdminSetParametersInstructionDataArgs['epochsAfterConsensusBeforeClose'];
   validSlotsAfterConsensus: AdminSetParametersInstructionDataArgs['validSlotsAfterConsensus'];
   minimumStake: AdminSetParametersInstructionDataArgs['minimumStake'];
+  enforceSignerOrder: AdminSetParametersInstructionDataArgs['enforceSignerOrder'];
+  minimumOperators: AdminSetParametersInstructionDataArgs['minimumOperators'];
+  rejectDuplicateAggregates: AdminSetParametersInstructionDataArgs['rejectDuplicateAggregates'];
+  allowMultipleVotesPerEpoch: AdminSetParametersInstructionDataArgs['allowMultipleVotesPerEpoch'];
+  quorumBps: AdminSetParametersInstructionDataArgs['quorumBps'];
+  maxSnapshotAgeSlots: AdminSetParametersInstructionDataArgs['maxSnapshotAgeSlots'];
+  allowEd25519Votes: AdminSetParametersInstructionDataArgs['allowEd25519Votes'];
 };
 
 export function getAdminSetParametersInstruction<
   TAccountConfig extends string,
   TAccountNcn extends string,
   TAccountNcnAdmin extends string,
+  TAccountRestakingConfig extends string,
+  TAccountSnapshot extends string,
+  TAccountVoteCounter extends string,
   TProgramAddress extends Address = typeof NCN_PROGRAM_PROGRAM_ADDRESS,
 >(
-  input: AdminSetParametersInput<TAccountConfig, TAccountNcn, TAccountNcnAdmin>,
+  input: AdminSetParametersInput<
+    TAccountConfig,
+    TAccountNcn,
+    TAccountNcnAdmin,
+    TAccountRestakingConfig,
+    TAccountSnapshot,
+    TAccountVoteCounter
+  >,
   config?: { programAddress?: TProgramAddress }
 ): AdminSetParametersInstruction<
   TProgramAddress,
   TAccountConfig,
   TAccountNcn,
-  TAccountNcnAdmin
+  TAccountNcnAdmin,
+  TAccountRestakingConfig,
+  TAccountSnapshot,
+  TAccountVoteCounter
 > {
   // Program address.
   const programAddress = config?.programAddress ?? NCN_PROGRAM_PROGRAM_ADDRESS;
@@ -155,6 +225,12 @@ export function getAdminSetParametersInstruction<
     config: { value: input.config ?? null, isWritable: true },
     ncn: { value: input.ncn ?? null, isWritable: false },
     ncnAdmin: { value: input.ncnAdmin ?? null, isWritable: false },
+    restakingConfig: {
+      value: input.restakingConfig ?? null,
+      isWritable: false,
+    },
+    snapshot: { value: input.snapshot ?? null, isWritable: false },
+    voteCounter: { value: input.voteCounter ?? null, isWritable: false },
   };
   const accounts = originalAccounts as Record<
     keyof typeof originalAccounts,
@@ -170,6 +246,9 @@ export function getAdminSetParametersInstruction<
       getAccountMeta(accounts.config),
       getAccountMeta(accounts.ncn),
       getAccountMeta(accounts.ncnAdmin),
+      getAccountMeta(accounts.restakingConfig),
+      getAccountMeta(accounts.snapshot),
+      getAccountMeta(accounts.voteCounter),
     ],
     programAddress,
     data: getAdminSetParametersInstructionDataEncoder().encode(
@@ -179,7 +258,10 @@ export function getAdminSetParametersInstruction<
     TProgramAddress,
     TAccountConfig,
     TAccountNcn,
-    TAccountNcnAdmin
+    TAccountNcnAdmin,
+    TAccountRestakingConfig,
+    TAccountSnapshot,
+    TAccountVoteCounter
   >;
 
   return instruction;
@@ -194,6 +276,9 @@ export type ParsedAdminSetParametersInstruction<
     config: TAccountMetas[0];
     ncn: TAccountMetas[1];
     ncnAdmin: TAccountMetas[2];
+    restakingConfig: TAccountMetas[3];
+    snapshot: TAccountMetas[4];
+    voteCounter: TAccountMetas[5];
   };
   data: AdminSetParametersInstructionData;
 };
@@ -206,7 +291,7 @@ export function parseAdminSetParametersInstruction<
     IInstructionWithAccounts<TAccountMetas> &
     IInstructionWithData<Uint8Array>
 ): ParsedAdminSetParametersInstruction<TProgram, TAccountMetas> {
-  if (instruction.accounts.length < 3) {
+  if (instruction.accounts.length < 6) {
     // TODO: Coded error.
     throw new Error('Not enough accounts');
   }
@@ -222,6 +307,9 @@ export function parseAdminSetParametersInstruction<
       config: getNextAccount(),
       ncn: getNextAccount(),
       ncnAdmin: getNextAccount(),
+      restakingConfig: getNextAccount(),
+      snapshot: getNextAccount(),
+      voteCounter: getNextAccount(),
     },
     data: getAdminSetParametersInstructionDataDecoder().decode(
       instruction.data
@@ -14,8 +14,12 @@ import {
   fixEncoderSize,
   getBytesDecoder,
   getBytesEncoder,
+  getOptionDecoder,
+  getOptionEncoder,
   getStructDecoder,
   getStructEncoder,
+  getU16Decoder,
+  getU16Encoder,
   getU32Decoder,
   getU32Encoder,
   getU8Decoder,
@@ -29,6 +33,8 @@ import {
   type IInstruction,
   type IInstructionWithAccounts,
   type IInstructionWithData,
+  type Option,
+  type OptionOrNullable,
   type ReadonlyAccount,
   type ReadonlyUint8Array,
   type WritableAccount,
@@ -76,12 +82,14 @@ export type CastVoteInstructionData = {
   aggregatedSignature: ReadonlyUint8Array;
   aggregatedG2: ReadonlyUint8Array;
   operatorsSignatureBitmap: ReadonlyUint8Array;
+  quorumBps: Option<number>;
 };
 
 export type CastVoteInstructionDataArgs = {
   aggregatedSignature: ReadonlyUint8Array;
   aggregatedG2: ReadonlyUint8Array;
   operatorsSignatureBitmap: ReadonlyUint8Array;
+  quorumBps: OptionOrNullable<number>;
 };
 
 export function getCastVoteInstructionDataEncoder(): Encoder<CastVoteInstructionDataArgs> {
@@ -94,6 +102,7 @@ export function getCastVoteInstructionDataEncoder(): Encoder<CastVoteInstruction
         'operatorsSignatureBitmap',
         addEncoderSizePrefix(getBytesEncoder(), getU32Encoder()),
       ],
+      ['quorumBps', getOptionEncoder(getU16Encoder())],
     ]),
     (value) => ({ ...value, discriminator: CAST_VOTE_DISCRIMINATOR })
   );
@@ -108,6 +117,7 @@ export function getCastVoteInstructionDataDecoder(): Decoder<CastVoteInstruction
       'operatorsSignatureBitmap',
       addDecoderSizePrefix(getBytesDecoder(), getU32Decoder()),
     ],
+    ['quorumBps', getOptionDecoder(getU16Decoder())],
   ]);
 }
 
@@ -136,6 +146,7 @@ export type CastVoteInput<
   aggregatedSignature: CastVoteInstructionDataArgs['aggregatedSignature'];
   aggregatedG2: CastVoteInstructionDataArgs['aggregatedG2'];
   operatorsSignatureBitmap: CastVoteInstructionDataArgs['operatorsSignatureBitmap'];
+  quorumBps: CastVoteInstructionDataArgs['quorumBps'];
 };
 
 export function getCastVoteInstruction<
@@ -8,6 +8,8 @@
 
 import {
   combineCodec,
+  getOptionDecoder,
+  getOptionEncoder,
   getStructDecoder,
   getStructEncoder,
   getU128Decoder,
@@ -28,6 +30,8 @@ import {
   type IInstruction,
   type IInstructionWithAccounts,
   type IInstructionWithData,
+  type Option,
+  type OptionOrNullable,
   type ReadonlyAccount,
   type ReadonlySignerAccount,
   type TransactionSigner,
@@ -53,6 +57,7 @@ export type InitializeConfigInstruction<
   TAccountSystemProgram extends
     | string
     | IAccountMeta<string> = '11111111111111111111111111111111',
+  TAccountRestakingConfig extends string | IAccountMeta<string> = string,
   TRemainingAccounts extends readonly IAccountMeta<string>[] = [],
 > = IInstruction<TProgram> &
   IInstructionWithData<Uint8Array> &
@@ -78,6 +83,9 @@ export type InitializeConfigInstruction<
       TAccountSystemProgram extends string
         ? ReadonlyAccount<TAccountSystemProgram>
         : TAccountSystemProgram,
+      TAccountRestakingConfig extends string
+        ? ReadonlyAccount<TAccountRestakingConfig>
+        : TAccountRestakingConfig,
       ...TRemainingAccounts,
     ]
   >;
@@ -89,6 +97,10 @@ export type InitializeConfigInstructionData = {
   validSlotsAfterConsensus: bigint;
   minimumStake: bigint;
   ncnFeeBps: number;
+  curveId: Option<number>;
+  minimumOperators: Option<bigint>;
+  quorumBps: Option<number>;
+  maxSnapshotAgeSlots: Option<bigint>;
 };
 
 export type InitializeConfigInstructionDataArgs = {
@@ -97,6 +109,10 @@ export type InitializeConfigInstructionDataArgs = {
   validSlotsAfterConsensus: number | bigint;
   minimumStake: number | bigint;
   ncnFeeBps: number;
+  curveId: OptionOrNullable<number>;
+  minimumOperators: OptionOrNullable<number | bigint>;
+  quorumBps: OptionOrNullable<number>;
+  maxSnapshotAgeSlots: OptionOrNullable<number | bigint>;
 };
 
 export function getInitializeConfigInstructionDataEncoder(): Encoder<InitializeConfigInstructionDataArgs> {
@@ -108,6 +124,10 @@ export function getInitializeConfigInstructionDataEncoder(): Encoder<InitializeC
       ['validSlotsAfterConsensus', getU64Encoder()],
       ['minimumStake', getU128Encoder()],
       ['ncnFeeBps', getU16Encoder()],
+      ['curveId', getOptionEncoder(getU8Encoder())],
+      ['minimumOperators', getOptionEncoder(getU64Encoder())],
+      ['quorumBps', getOptionEncoder(getU16Encoder())],
+      ['maxSnapshotAgeSlots', getOptionEncoder(getU64Encoder())],
     ]),
     (value) => ({ ...value, discriminator: INITIALIZE_CONFIG_DISCRIMINATOR })
   );
@@ -121,6 +141,10 @@ export function getInitializeConfigInstructionDataDecoder(): Decoder<InitializeC
     ['validSlotsAfterConsensus', getU64Decoder()],
     ['minimumStake', getU128Decoder()],
     ['ncnFeeBps', getU16Decoder()],
+    ['curveId', getOptionDecoder(getU8Decoder())],
+    ['minimumOperators', getOptionDecoder(getU64Decoder())],
+    ['quorumBps', getOptionDecoder(getU16Decoder())],
+    ['maxSnapshotAgeSlots', getOptionDecoder(getU64Decoder())],
   ]);
 }
 
@@ -142,6 +166,7 @@ export type InitializeConfigInput<
   TAccountTieBreakerAdmin extends string = string,
   TAccountAccountPayer extends string = string,
   TAccountSystemProgram extends string = string,
+  TAccountRestakingConfig extends string = string,
 > = {
   config: Address<TAccountConfig>;
   ncn: Address<TAccountNcn>;
@@ -150,11 +175,16 @@ export type InitializeConfigInput<
   tieBreakerAdmin: Address<TAccountTieBreakerAdmin>;
   accountPayer: Address<TAccountAccountPayer>;
   systemProgram?: Address<TAccountSystemProgram>;
+  restakingConfig: Address<TAccountRestakingConfig>;
   epochsBeforeStall: InitializeConfigInstructionDataArgs['epochsBeforeStall'];
   epochsAfterConsensusBeforeClose: InitializeConfigInstructionDataArgs['epochsAfterConsensusBeforeClose'];
   validSlotsAfterConsensus: InitializeConfigInstructionDataArgs['validSlotsAfterConsensus'];
   minimumStake: InitializeConfigInstructionDataArgs['minimumStake'];
   ncnFeeBps: InitializeConfigInstructionDataArgs['ncnFeeBps'];
+  curveId: InitializeConfigInstructionDataArgs['curveId'];
+  minimumOperators: InitializeConfigInstructionDataArgs['minimumOperators'];
+  quorumBps: InitializeConfigInstructionDataArgs['quorumBps'];
+  maxSnapshotAgeSlots: InitializeConfigInstructionDataArgs['maxSnapshotAgeSlots'];
 };
 
 export function getInitializeConfigInstruction<
@@ -165,6 +195,7 @@ export function getInitializeConfigInstruction<
   TAccountTieBreakerAdmin extends string,
   TAccountAccountPayer extends string,
   TAccountSystemProgram extends string,
+  TAccountRestakingConfig extends string,
   TProgramAddress extends Address = typeof NCN_PROGRAM_PROGRAM_ADDRESS,
 >(
   input: InitializeConfigInput<
@@ -174,7 +205,8 @@ export function getInitializeConfigInstruction<
     TAccountNcnAdmin,
     TAccountTieBreakerAdmin,
     TAccountAccountPayer,
-    TAccountSystemProgram
+    TAccountSystemProgram,
+    TAccountRestakingConfig
   >,
   config?: { programAddress?: TProgramAddress }
 ): InitializeConfigInstruction<
@@ -185,7 +217,8 @@ export function getInitializeConfigInstruction<
   TAccountNcnAdmin,
   TAccountTieBreakerAdmin,
   TAccountAccountPayer,
-  TAccountSystemProgram
+  TAccountSystemProgram,
+  TAccountRestakingConfig
 > {
   // Program address.
   const programAddress = config?.programAddress ?? NCN_PROGRAM_PROGRAM_ADDRESS;
@@ -202,6 +235,10 @@ export function getInitializeConfigInstruction<
     },
     accountPayer: { value: input.accountPayer ?? null, isWritable: true },
     systemProgram: { value: input.systemProgram ?? null, isWritable: false },
+    restakingConfig: {
+      value: input.restakingConfig ?? null,
+      isWritable: false,
+    },
   };
   const accounts = originalAccounts as Record<
     keyof typeof originalAccounts,
@@ -227,6 +264,7 @@ export function getInitializeConfigInstruction<
       getAccountMeta(accounts.tieBreakerAdmin),
       getAccountMeta(accounts.accountPayer),
       getAccountMeta(accounts.systemProgram),
+      getAccountMeta(accounts.restakingConfig),
     ],
     programAddress,
     data: getInitializeConfigInstructionDataEncoder().encode(
@@ -240,7 +278,8 @@ export function getInitializeConfigInstruction<
     TAccountNcnAdmin,
     TAccountTieBreakerAdmin,
     TAccountAccountPayer,
-    TAccountSystemProgram
+    TAccountSystemProgram,
+    TAccountRestakingConfig
   >;
 
   return instruction;
@@ -259,6 +298,7 @@ export type ParsedInitializeConfigInstruction<
     tieBreakerAdmin: TAccountMetas[4];
     accountPayer: TAccountMetas[5];
     systemProgram: TAccountMetas[6];
+    restakingConfig: TAccountMetas[7];
   };
   data: InitializeConfigInstructionData;
 };
@@ -271,7 +311,7 @@ export function parseInitializeConfigInstruction<
     IInstructionWithAccounts<TAccountMetas> &
     IInstructionWithData<Uint8Array>
 ): ParsedInitializeConfigInstruction<TProgram, TAccountMetas> {
-  if (instruction.accounts.length < 7) {
+  if (instruction.accounts.length < 8) {
     // TODO: Coded error.
     throw new Error('Not enough accounts');
   }
@@ -291,6 +331,7 @@ export function parseInitializeConfigInstruction<
       tieBreakerAdmin: getNextAccount(),
       accountPayer: getNextAccount(),
       systemProgram: getNextAccount(),
+      restakingConfig: getNextAccount(),
     },
     data: getInitializeConfigInstructionDataDecoder().decode(instruction.data),
   };
@@ -0,0 +1,53 @@
+/**
+ * This code was AUTOGENERATED using the kinobi library.
+ * Please DO NOT EDIT THIS FILE, instead use visitors
+ * to add features, then rerun kinobi to update it.
+ *
+ * @see https://github.com/kinobi-so/kinobi
+ */
+
+import {
+  combineCodec,
+  getStructDecoder,
+  getStructEncoder,
+  getU64Decoder,
+  getU64Encoder,
+  type Codec,
+  type Decoder,
+  type Encoder,
+} from '@solana/web3.js';
+
+export type ConsensusRecord = {
+  epoch: bigint;
+  snapshotSlot: bigint;
+  consensusSlot: bigint;
+};
+
+export type ConsensusRecordArgs = {
+  epoch: number | bigint;
+  snapshotSlot: number | bigint;
+  consensusSlot: number | bigint;
+};
+
+export function getConsensusRecordEncoder(): Encoder<ConsensusRecordArgs> {
+  return getStructEncoder([
+    ['epoch', getU64Encoder()],
+    ['snapshotSlot', getU64Encoder()],
+    ['consensusSlot', getU64Encoder()],
+  ]);
+}
+
+export function getConsensusRecordDecoder(): Decoder<ConsensusRecord> {
+  return getStructDecoder([
+    ['epoch', getU64Decoder()],
+    ['snapshotSlot', getU64Decoder()],
+    ['consensusSlot', getU64Decoder()],
+  ]);
+}
+
+export function getConsensusRecordCodec(): Codec<
+  ConsensusRecordArgs,
+  ConsensusRecord
+> {
+  return combineCodec(getConsensusRecordEncoder(), getConsensusRecordDecoder());
+}
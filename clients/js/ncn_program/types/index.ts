@@ -7,6 +7,7 @@
  */
 
 export * from './configAdminRole';
+export * from './consensusRecord';
 export * from './fee';
 export * from './feeConfig';
 export * from './fees';
@@ -12,6 +12,8 @@ import {
   fixEncoderSize,
   getAddressDecoder,
   getAddressEncoder,
+  getArrayDecoder,
+  getArrayEncoder,
   getBoolDecoder,
   getBoolEncoder,
   getBytesDecoder,
@@ -45,6 +47,9 @@ export type OperatorSnapshot = {
   hasMinimumStakeNextEpoch: number;
   stakeWeight: StakeWeights;
   nextEpochStakeWeight: StakeWeights;
+  g2Pubkey: ReadonlyUint8Array;
+  missedLastVote: number;
+  vaultsDelegated: Array<Address>;
 };
 
 export type OperatorSnapshotArgs = {
@@ -59,6 +64,9 @@ export type OperatorSnapshotArgs = {
   hasMinimumStakeNextEpoch: number;
   stakeWeight: StakeWeightsArgs;
   nextEpochStakeWeight: StakeWeightsArgs;
+  g2Pubkey: ReadonlyUint8Array;
+  missedLastVote: number;
+  vaultsDelegated: Array<Address>;
 };
 
 export function getOperatorSnapshotEncoder(): Encoder<OperatorSnapshotArgs> {
@@ -74,6 +82,9 @@ export function getOperatorSnapshotEncoder(): Encoder<OperatorSnapshotArgs> {
     ['hasMinimumStakeNextEpoch', getBoolEncoder()],
     ['stakeWeight', getStakeWeightsEncoder()],
     ['nextEpochStakeWeight', getStakeWeightsEncoder()],
+    ['g2Pubkey', fixEncoderSize(getBytesEncoder(), 64)],
+    ['missedLastVote', getBoolEncoder()],
+    ['vaultsDelegated', getArrayEncoder(getAddressEncoder(), { size: 1 })],
   ]);
 }
 
@@ -90,6 +101,9 @@ export function getOperatorSnapshotDecoder(): Decoder<OperatorSnapshot> {
     ['hasMinimumStakeNextEpoch', getBoolDecoder()],
     ['stakeWeight', getStakeWeightsDecoder()],
     ['nextEpochStakeWeight', getStakeWeightsDecoder()],
+    ['g2Pubkey', fixDecoderSize(getBytesDecoder(), 64)],
+    ['missedLastVote', getBoolDecoder()],
+    ['vaultsDelegated', getArrayDecoder(getAddressDecoder(), { size: 1 })],
   ]);
 }
 
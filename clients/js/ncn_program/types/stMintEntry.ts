@@ -16,6 +16,8 @@ import {
   getBytesEncoder,
   getStructDecoder,
   getStructEncoder,
+  getU128Decoder,
+  getU128Encoder,
   type Address,
   type Codec,
   type Decoder,
@@ -26,14 +28,20 @@ import {
 export type StMintEntry = {
   stMint: Address;
   reserveSwitchboardFeed: ReadonlyUint8Array;
+  weight: bigint;
 };
 
-export type StMintEntryArgs = StMintEntry;
+export type StMintEntryArgs = {
+  stMint: Address;
+  reserveSwitchboardFeed: ReadonlyUint8Array;
+  weight: number | bigint;
+};
 
 export function getStMintEntryEncoder(): Encoder<StMintEntryArgs> {
   return getStructEncoder([
     ['stMint', getAddressEncoder()],
     ['reserveSwitchboardFeed', fixEncoderSize(getBytesEncoder(), 32)],
+    ['weight', getU128Encoder()],
   ]);
 }
 
@@ -41,6 +49,7 @@ export function getStMintEntryDecoder(): Decoder<StMintEntry> {
   return getStructDecoder([
     ['stMint', getAddressDecoder()],
     ['reserveSwitchboardFeed', fixDecoderSize(getBytesDecoder(), 32)],
+    ['weight', getU128Decoder()],
   ]);
 }
 
@@ -32,6 +32,17 @@ pub struct Config {
     pub fee_config: FeeConfig,
     pub bump: u8,
     pub minimum_stake: StakeWeights,
+    pub enforce_signer_order: bool,
+    pub curve_id: u8,
+    pub minimum_operators: u64,
+    pub reject_duplicate_aggregates: bool,
+    pub allow_multiple_votes_per_epoch: bool,
+    pub epoch_length: u64,
+    pub quorum_bps: u16,
+    pub max_snapshot_age_slots: u64,
+    pub allow_ed25519_votes: bool,
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::Bytes>"))]
+    pub reserved: [u8; 99],
 }
 
 impl Config {
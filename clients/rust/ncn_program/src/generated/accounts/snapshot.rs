@@ -21,6 +21,7 @@ pub struct Snapshot {
     )]
     pub ncn: Pubkey,
     pub bump: u8,
+    pub version: u8,
     pub slot_created: u64,
     pub operators_registered: u64,
     pub operators_can_vote_count: u64,
@@ -29,6 +30,9 @@ pub struct Snapshot {
     pub operator_snapshots: [OperatorSnapshot; 256],
     pub minimum_stake: StakeWeights,
     pub last_snapshot_slot: u64,
+    pub last_rollover_epoch: u64,
+    pub slot_finalized: u64,
+    pub deregistered_operators: [u8; 32],
 }
 
 impl Snapshot {
@@ -5,6 +5,7 @@
 //! <https://github.com/kinobi-so/kinobi>
 //!
 
+use crate::generated::types::ConsensusRecord;
 use borsh::BorshDeserialize;
 use borsh::BorshSerialize;
 use solana_program::pubkey::Pubkey;
@@ -21,6 +22,16 @@ pub struct VoteCounter {
     pub count: u64,
     pub bump: u8,
     pub reserved: [u8; 7],
+    pub consensus_records_written: u64,
+    pub consensus_records: [ConsensusRecord; 8],
+    pub last_vote_slot: u64,
+    pub last_vote_epoch: u64,
+    pub votes_this_epoch: u64,
+    pub last_aggregate_hash: [u8; 32],
+    pub messages_root: [u8; 32],
+    pub ed25519_vote_epoch: u64,
+    pub ed25519_stake_weight: u128,
+    pub ed25519_voters: [u8; 32],
 }
 
 impl VoteCounter {
@@ -355,6 +355,96 @@ pub enum NcnProgramError {
     /// 8805 - Invalid operator count
     #[error("Invalid operator count")]
     InvalidOperatorCount = 0x2265,
+    /// 8806 - Invalid snapshot backup
+    #[error("Invalid snapshot backup")]
+    InvalidSnapshotBackup = 0x2266,
+    /// 8807 - Unsupported snapshot backup version
+    #[error("Unsupported snapshot backup version")]
+    UnsupportedSnapshotBackupVersion = 0x2267,
+    /// 8808 - Operator NCN link is not active
+    #[error("Operator NCN link is not active")]
+    OperatorLinkInactive = 0x2268,
+    /// 8809 - Signer accounts are not in ascending operator index order
+    #[error("Signer accounts are not in ascending operator index order")]
+    SignerOrderViolation = 0x2269,
+    /// 8810 - Operator signature bitmap exceeds the maximum size
+    #[error("Operator signature bitmap exceeds the maximum size")]
+    BitmapTooLarge = 0x226A,
+    /// 8811 - Signers stake weight is below the requested quorum
+    #[error("Signers stake weight is below the requested quorum")]
+    SignerStakeQuorumNotMet = 0x226B,
+    /// 8812 - Unsupported curve
+    #[error("Unsupported curve")]
+    UnsupportedCurve = 0x226C,
+    /// 8813 - Invalid G2 Pubkey
+    #[error("Invalid G2 Pubkey")]
+    InvalidG2Pubkey = 0x226D,
+    /// 8814 - Snapshot already rolled over this epoch
+    #[error("Snapshot already rolled over this epoch")]
+    SnapshotAlreadyRolledOver = 0x226E,
+    /// 8815 - Not enough operators registered for consensus
+    #[error("Not enough operators registered for consensus")]
+    NotEnoughOperatorsRegistered = 0x226F,
+    /// 8816 - Not enough operators with the minimum stake for consensus
+    #[error("Not enough operators with the minimum stake for consensus")]
+    InsufficientOperators = 0x2270,
+    /// 8817 - Operator signed the message
+    #[error("Operator signed the message")]
+    OperatorSignedMessage = 0x2271,
+    /// 8818 - Aggregate signature was already submitted
+    #[error("Aggregate signature was already submitted")]
+    DuplicateAggregateSubmission = 0x2272,
+    /// 8819 - Vote message mismatch
+    #[error("Vote message mismatch")]
+    VoteMessageMismatch = 0x2273,
+    /// 8820 - Vault operator delegation was not updated this epoch
+    #[error("Vault operator delegation was not updated this epoch")]
+    VaultOperatorDelegationNotUpdated = 0x2274,
+    /// 8821 - Duplicate operator index
+    #[error("Duplicate operator index")]
+    DuplicateOperatorIndex = 0x2275,
+    /// 8822 - No signers in bitmap
+    #[error("No signers in bitmap")]
+    NoSignersInBitmap = 0x2276,
+    /// 8823 - Snapshot version mismatch
+    #[error("Snapshot version mismatch")]
+    SnapshotVersionMismatch = 0x2277,
+    /// 8824 - G1 pubkey already registered by another operator
+    #[error("G1 pubkey already registered by another operator")]
+    DuplicateOperatorG1Pubkey = 0x2278,
+    /// 8825 - Parameter change not allowed while a vote is pending
+    #[error("Parameter change not allowed while a vote is pending")]
+    ParameterChangeNotAllowed = 0x2279,
+    /// 8826 - A vote was already cast this epoch
+    #[error("A vote was already cast this epoch")]
+    AlreadyVotedThisEpoch = 0x227A,
+    /// 8827 - Quorum bps exceeds 10000
+    #[error("Quorum bps exceeds 10000")]
+    InvalidQuorumBps = 0x227B,
+    /// 8828 - G1 pubkey is not part of the total aggregated pubkey
+    #[error("G1 pubkey is not part of the total aggregated pubkey")]
+    G1PubkeyNotInAggregate = 0x227C,
+    /// 8829 - Invalid operator snapshot bytes
+    #[error("Invalid operator snapshot bytes")]
+    InvalidOperatorSnapshotBytes = 0x227D,
+    /// 8830 - Snapshot too stale
+    #[error("Snapshot too stale")]
+    SnapshotTooStale = 0x227E,
+    /// 8831 - G2 aggregate mismatch
+    #[error("G2 aggregate mismatch")]
+    G2AggregateMismatch = 0x227F,
+    /// 8832 - Ed25519 votes are disabled
+    #[error("Ed25519 votes are disabled")]
+    Ed25519VotesDisabled = 0x2280,
+    /// 8833 - Invalid ed25519 instruction
+    #[error("Invalid ed25519 instruction")]
+    InvalidEd25519Instruction = 0x2281,
+    /// 8834 - Operator already cast an ed25519 vote this epoch
+    #[error("Operator already cast an ed25519 vote this epoch")]
+    Ed25519AlreadyVoted = 0x2282,
+    /// 8835 - NCN operator index does not match the operator's NCN operator state
+    #[error("NCN operator index does not match the operator's NCN operator state")]
+    NcnOperatorIndexMismatch = 0x2283,
 }
 
 impl solana_program::program_error::PrintProgramError for NcnProgramError {
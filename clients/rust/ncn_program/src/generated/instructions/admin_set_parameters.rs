@@ -15,6 +15,12 @@ pub struct AdminSetParameters {
     pub ncn: solana_program::pubkey::Pubkey,
 
     pub ncn_admin: solana_program::pubkey::Pubkey,
+
+    pub restaking_config: solana_program::pubkey::Pubkey,
+
+    pub snapshot: solana_program::pubkey::Pubkey,
+
+    pub vote_counter: solana_program::pubkey::Pubkey,
 }
 
 impl AdminSetParameters {
@@ -30,7 +36,7 @@ impl AdminSetParameters {
         args: AdminSetParametersInstructionArgs,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(6 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             self.config,
             false,
@@ -42,6 +48,18 @@ impl AdminSetParameters {
             self.ncn_admin,
             true,
         ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.restaking_config,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.snapshot,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.vote_counter,
+            false,
+        ));
         accounts.extend_from_slice(remaining_accounts);
         let mut data = AdminSetParametersInstructionData::new()
             .try_to_vec()
@@ -82,6 +100,13 @@ pub struct AdminSetParametersInstructionArgs {
     pub epochs_after_consensus_before_close: Option<u64>,
     pub valid_slots_after_consensus: Option<u64>,
     pub minimum_stake: Option<u128>,
+    pub enforce_signer_order: Option<bool>,
+    pub minimum_operators: Option<u64>,
+    pub reject_duplicate_aggregates: Option<bool>,
+    pub allow_multiple_votes_per_epoch: Option<bool>,
+    pub quorum_bps: Option<u16>,
+    pub max_snapshot_age_slots: Option<u64>,
+    pub allow_ed25519_votes: Option<bool>,
 }
 
 /// Instruction builder for `AdminSetParameters`.
@@ -91,16 +116,29 @@ pub struct AdminSetParametersInstructionArgs {
 ///   0. `[writable]` config
 ///   1. `[]` ncn
 ///   2. `[signer]` ncn_admin
+///   3. `[]` restaking_config
+///   4. `[]` snapshot
+///   5. `[]` vote_counter
 #[derive(Clone, Debug, Default)]
 pub struct AdminSetParametersBuilder {
     config: Option<solana_program::pubkey::Pubkey>,
     ncn: Option<solana_program::pubkey::Pubkey>,
     ncn_admin: Option<solana_program::pubkey::Pubkey>,
+    restaking_config: Option<solana_program::pubkey::Pubkey>,
+    snapshot: Option<solana_program::pubkey::Pubkey>,
+    vote_counter: Option<solana_program::pubkey::Pubkey>,
     starting_valid_epoch: Option<u64>,
     epochs_before_stall: Option<u64>,
     epochs_after_consensus_before_close: Option<u64>,
     valid_slots_after_consensus: Option<u64>,
     minimum_stake: Option<u128>,
+    enforce_signer_order: Option<bool>,
+    minimum_operators: Option<u64>,
+    reject_duplicate_aggregates: Option<bool>,
+    allow_multiple_votes_per_epoch: Option<bool>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
+    allow_ed25519_votes: Option<bool>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -123,6 +161,24 @@ impl AdminSetParametersBuilder {
         self.ncn_admin = Some(ncn_admin);
         self
     }
+    #[inline(always)]
+    pub fn restaking_config(
+        &mut self,
+        restaking_config: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.restaking_config = Some(restaking_config);
+        self
+    }
+    #[inline(always)]
+    pub fn snapshot(&mut self, snapshot: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+    #[inline(always)]
+    pub fn vote_counter(&mut self, vote_counter: solana_program::pubkey::Pubkey) -> &mut Self {
+        self.vote_counter = Some(vote_counter);
+        self
+    }
     /// `[optional argument]`
     #[inline(always)]
     pub fn starting_valid_epoch(&mut self, starting_valid_epoch: u64) -> &mut Self {
@@ -156,6 +212,51 @@ impl AdminSetParametersBuilder {
         self.minimum_stake = Some(minimum_stake);
         self
     }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn enforce_signer_order(&mut self, enforce_signer_order: bool) -> &mut Self {
+        self.enforce_signer_order = Some(enforce_signer_order);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn minimum_operators(&mut self, minimum_operators: u64) -> &mut Self {
+        self.minimum_operators = Some(minimum_operators);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn reject_duplicate_aggregates(&mut self, reject_duplicate_aggregates: bool) -> &mut Self {
+        self.reject_duplicate_aggregates = Some(reject_duplicate_aggregates);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn allow_multiple_votes_per_epoch(
+        &mut self,
+        allow_multiple_votes_per_epoch: bool,
+    ) -> &mut Self {
+        self.allow_multiple_votes_per_epoch = Some(allow_multiple_votes_per_epoch);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn quorum_bps(&mut self, quorum_bps: u16) -> &mut Self {
+        self.quorum_bps = Some(quorum_bps);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn max_snapshot_age_slots(&mut self, max_snapshot_age_slots: u64) -> &mut Self {
+        self.max_snapshot_age_slots = Some(max_snapshot_age_slots);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn allow_ed25519_votes(&mut self, allow_ed25519_votes: bool) -> &mut Self {
+        self.allow_ed25519_votes = Some(allow_ed25519_votes);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -180,6 +281,9 @@ impl AdminSetParametersBuilder {
             config: self.config.expect("config is not set"),
             ncn: self.ncn.expect("ncn is not set"),
             ncn_admin: self.ncn_admin.expect("ncn_admin is not set"),
+            restaking_config: self.restaking_config.expect("restaking_config is not set"),
+            snapshot: self.snapshot.expect("snapshot is not set"),
+            vote_counter: self.vote_counter.expect("vote_counter is not set"),
         };
         let args = AdminSetParametersInstructionArgs {
             starting_valid_epoch: self.starting_valid_epoch.clone(),
@@ -187,6 +291,13 @@ impl AdminSetParametersBuilder {
             epochs_after_consensus_before_close: self.epochs_after_consensus_before_close.clone(),
             valid_slots_after_consensus: self.valid_slots_after_consensus.clone(),
             minimum_stake: self.minimum_stake.clone(),
+            enforce_signer_order: self.enforce_signer_order.clone(),
+            minimum_operators: self.minimum_operators.clone(),
+            reject_duplicate_aggregates: self.reject_duplicate_aggregates.clone(),
+            allow_multiple_votes_per_epoch: self.allow_multiple_votes_per_epoch.clone(),
+            quorum_bps: self.quorum_bps.clone(),
+            max_snapshot_age_slots: self.max_snapshot_age_slots.clone(),
+            allow_ed25519_votes: self.allow_ed25519_votes.clone(),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -200,6 +311,12 @@ pub struct AdminSetParametersCpiAccounts<'a, 'b> {
     pub ncn: &'b solana_program::account_info::AccountInfo<'a>,
 
     pub ncn_admin: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub restaking_config: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub snapshot: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub vote_counter: &'b solana_program::account_info::AccountInfo<'a>,
 }
 
 /// `admin_set_parameters` CPI instruction.
@@ -212,6 +329,12 @@ pub struct AdminSetParametersCpi<'a, 'b> {
     pub ncn: &'b solana_program::account_info::AccountInfo<'a>,
 
     pub ncn_admin: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub restaking_config: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub snapshot: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub vote_counter: &'b solana_program::account_info::AccountInfo<'a>,
     /// The arguments for the instruction.
     pub __args: AdminSetParametersInstructionArgs,
 }
@@ -227,6 +350,9 @@ impl<'a, 'b> AdminSetParametersCpi<'a, 'b> {
             config: accounts.config,
             ncn: accounts.ncn,
             ncn_admin: accounts.ncn_admin,
+            restaking_config: accounts.restaking_config,
+            snapshot: accounts.snapshot,
+            vote_counter: accounts.vote_counter,
             __args: args,
         }
     }
@@ -263,7 +389,7 @@ impl<'a, 'b> AdminSetParametersCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(3 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(6 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             *self.config.key,
             false,
@@ -276,6 +402,18 @@ impl<'a, 'b> AdminSetParametersCpi<'a, 'b> {
             *self.ncn_admin.key,
             true,
         ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.restaking_config.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.snapshot.key,
+            false,
+        ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.vote_counter.key,
+            false,
+        ));
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -294,11 +432,14 @@ impl<'a, 'b> AdminSetParametersCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(3 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(6 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.config.clone());
         account_infos.push(self.ncn.clone());
         account_infos.push(self.ncn_admin.clone());
+        account_infos.push(self.restaking_config.clone());
+        account_infos.push(self.snapshot.clone());
+        account_infos.push(self.vote_counter.clone());
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -318,6 +459,9 @@ impl<'a, 'b> AdminSetParametersCpi<'a, 'b> {
 ///   0. `[writable]` config
 ///   1. `[]` ncn
 ///   2. `[signer]` ncn_admin
+///   3. `[]` restaking_config
+///   4. `[]` snapshot
+///   5. `[]` vote_counter
 #[derive(Clone, Debug)]
 pub struct AdminSetParametersCpiBuilder<'a, 'b> {
     instruction: Box<AdminSetParametersCpiBuilderInstruction<'a, 'b>>,
@@ -330,11 +474,21 @@ impl<'a, 'b> AdminSetParametersCpiBuilder<'a, 'b> {
             config: None,
             ncn: None,
             ncn_admin: None,
+            restaking_config: None,
+            snapshot: None,
+            vote_counter: None,
             starting_valid_epoch: None,
             epochs_before_stall: None,
             epochs_after_consensus_before_close: None,
             valid_slots_after_consensus: None,
             minimum_stake: None,
+            enforce_signer_order: None,
+            minimum_operators: None,
+            reject_duplicate_aggregates: None,
+            allow_multiple_votes_per_epoch: None,
+            quorum_bps: None,
+            max_snapshot_age_slots: None,
+            allow_ed25519_votes: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -360,6 +514,30 @@ impl<'a, 'b> AdminSetParametersCpiBuilder<'a, 'b> {
         self.instruction.ncn_admin = Some(ncn_admin);
         self
     }
+    #[inline(always)]
+    pub fn restaking_config(
+        &mut self,
+        restaking_config: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.restaking_config = Some(restaking_config);
+        self
+    }
+    #[inline(always)]
+    pub fn snapshot(
+        &mut self,
+        snapshot: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.snapshot = Some(snapshot);
+        self
+    }
+    #[inline(always)]
+    pub fn vote_counter(
+        &mut self,
+        vote_counter: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.vote_counter = Some(vote_counter);
+        self
+    }
     /// `[optional argument]`
     #[inline(always)]
     pub fn starting_valid_epoch(&mut self, starting_valid_epoch: u64) -> &mut Self {
@@ -394,6 +572,51 @@ impl<'a, 'b> AdminSetParametersCpiBuilder<'a, 'b> {
         self.instruction.minimum_stake = Some(minimum_stake);
         self
     }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn enforce_signer_order(&mut self, enforce_signer_order: bool) -> &mut Self {
+        self.instruction.enforce_signer_order = Some(enforce_signer_order);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn minimum_operators(&mut self, minimum_operators: u64) -> &mut Self {
+        self.instruction.minimum_operators = Some(minimum_operators);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn reject_duplicate_aggregates(&mut self, reject_duplicate_aggregates: bool) -> &mut Self {
+        self.instruction.reject_duplicate_aggregates = Some(reject_duplicate_aggregates);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn allow_multiple_votes_per_epoch(
+        &mut self,
+        allow_multiple_votes_per_epoch: bool,
+    ) -> &mut Self {
+        self.instruction.allow_multiple_votes_per_epoch = Some(allow_multiple_votes_per_epoch);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn quorum_bps(&mut self, quorum_bps: u16) -> &mut Self {
+        self.instruction.quorum_bps = Some(quorum_bps);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn max_snapshot_age_slots(&mut self, max_snapshot_age_slots: u64) -> &mut Self {
+        self.instruction.max_snapshot_age_slots = Some(max_snapshot_age_slots);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn allow_ed25519_votes(&mut self, allow_ed25519_votes: bool) -> &mut Self {
+        self.instruction.allow_ed25519_votes = Some(allow_ed25519_votes);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -444,6 +667,13 @@ impl<'a, 'b> AdminSetParametersCpiBuilder<'a, 'b> {
                 .clone(),
             valid_slots_after_consensus: self.instruction.valid_slots_after_consensus.clone(),
             minimum_stake: self.instruction.minimum_stake.clone(),
+            enforce_signer_order: self.instruction.enforce_signer_order.clone(),
+            minimum_operators: self.instruction.minimum_operators.clone(),
+            reject_duplicate_aggregates: self.instruction.reject_duplicate_aggregates.clone(),
+            allow_multiple_votes_per_epoch: self.instruction.allow_multiple_votes_per_epoch.clone(),
+            quorum_bps: self.instruction.quorum_bps.clone(),
+            max_snapshot_age_slots: self.instruction.max_snapshot_age_slots.clone(),
+            allow_ed25519_votes: self.instruction.allow_ed25519_votes.clone(),
         };
         let instruction = AdminSetParametersCpi {
             __program: self.instruction.__program,
@@ -453,6 +683,18 @@ impl<'a, 'b> AdminSetParametersCpiBuilder<'a, 'b> {
             ncn: self.instruction.ncn.expect("ncn is not set"),
 
             ncn_admin: self.instruction.ncn_admin.expect("ncn_admin is not set"),
+
+            restaking_config: self
+                .instruction
+                .restaking_config
+                .expect("restaking_config is not set"),
+
+            snapshot: self.instruction.snapshot.expect("snapshot is not set"),
+
+            vote_counter: self
+                .instruction
+                .vote_counter
+                .expect("vote_counter is not set"),
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -468,11 +710,21 @@ struct AdminSetParametersCpiBuilderInstruction<'a, 'b> {
     config: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     ncn: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     ncn_admin: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    restaking_config: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    snapshot: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    vote_counter: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     starting_valid_epoch: Option<u64>,
     epochs_before_stall: Option<u64>,
     epochs_after_consensus_before_close: Option<u64>,
     valid_slots_after_consensus: Option<u64>,
     minimum_stake: Option<u128>,
+    enforce_signer_order: Option<bool>,
+    minimum_operators: Option<u64>,
+    reject_duplicate_aggregates: Option<bool>,
+    allow_multiple_votes_per_epoch: Option<bool>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
+    allow_ed25519_votes: Option<bool>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
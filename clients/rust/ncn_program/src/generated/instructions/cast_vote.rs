@@ -90,6 +90,7 @@ pub struct CastVoteInstructionArgs {
     pub aggregated_signature: [u8; 32],
     pub aggregated_g2: [u8; 64],
     pub operators_signature_bitmap: Vec<u8>,
+    pub quorum_bps: Option<u16>,
 }
 
 /// Instruction builder for `CastVote`.
@@ -111,6 +112,7 @@ pub struct CastVoteBuilder {
     aggregated_signature: Option<[u8; 32]>,
     aggregated_g2: Option<[u8; 64]>,
     operators_signature_bitmap: Option<Vec<u8>>,
+    quorum_bps: Option<u16>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -161,6 +163,12 @@ impl CastVoteBuilder {
         self.operators_signature_bitmap = Some(operators_signature_bitmap);
         self
     }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn quorum_bps(&mut self, quorum_bps: u16) -> &mut Self {
+        self.quorum_bps = Some(quorum_bps);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -201,6 +209,7 @@ impl CastVoteBuilder {
                 .operators_signature_bitmap
                 .clone()
                 .expect("operators_signature_bitmap is not set"),
+            quorum_bps: self.quorum_bps.clone(),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -369,6 +378,7 @@ impl<'a, 'b> CastVoteCpiBuilder<'a, 'b> {
             aggregated_signature: None,
             aggregated_g2: None,
             operators_signature_bitmap: None,
+            quorum_bps: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -425,6 +435,12 @@ impl<'a, 'b> CastVoteCpiBuilder<'a, 'b> {
         self.instruction.operators_signature_bitmap = Some(operators_signature_bitmap);
         self
     }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn quorum_bps(&mut self, quorum_bps: u16) -> &mut Self {
+        self.instruction.quorum_bps = Some(quorum_bps);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -482,6 +498,7 @@ impl<'a, 'b> CastVoteCpiBuilder<'a, 'b> {
                 .operators_signature_bitmap
                 .clone()
                 .expect("operators_signature_bitmap is not set"),
+            quorum_bps: self.instruction.quorum_bps.clone(),
         };
         let instruction = CastVoteCpi {
             __program: self.instruction.__program,
@@ -521,6 +538,7 @@ struct CastVoteCpiBuilderInstruction<'a, 'b> {
     aggregated_signature: Option<[u8; 32]>,
     aggregated_g2: Option<[u8; 64]>,
     operators_signature_bitmap: Option<Vec<u8>>,
+    quorum_bps: Option<u16>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
@@ -23,6 +23,8 @@ pub struct InitializeConfig {
     pub account_payer: solana_program::pubkey::Pubkey,
 
     pub system_program: solana_program::pubkey::Pubkey,
+
+    pub restaking_config: solana_program::pubkey::Pubkey,
 }
 
 impl InitializeConfig {
@@ -38,7 +40,7 @@ impl InitializeConfig {
         args: InitializeConfigInstructionArgs,
         remaining_accounts: &[solana_program::instruction::AccountMeta],
     ) -> solana_program::instruction::Instruction {
-        let mut accounts = Vec::with_capacity(7 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             self.config,
             false,
@@ -66,6 +68,10 @@ impl InitializeConfig {
             self.system_program,
             false,
         ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            self.restaking_config,
+            false,
+        ));
         accounts.extend_from_slice(remaining_accounts);
         let mut data = InitializeConfigInstructionData::new().try_to_vec().unwrap();
         let mut args = args.try_to_vec().unwrap();
@@ -104,6 +110,10 @@ pub struct InitializeConfigInstructionArgs {
     pub valid_slots_after_consensus: u64,
     pub minimum_stake: u128,
     pub ncn_fee_bps: u16,
+    pub curve_id: Option<u8>,
+    pub minimum_operators: Option<u64>,
+    pub quorum_bps: Option<u16>,
+    pub max_snapshot_age_slots: Option<u64>,
 }
 
 /// Instruction builder for `InitializeConfig`.
@@ -117,6 +127,7 @@ pub struct InitializeConfigInstructionArgs {
 ///   4. `[]` tie_breaker_admin
 ///   5. `[writable]` account_payer
 ///   6. `[optional]` system_program (default to `11111111111111111111111111111111`)
+///   7. `[]` restaking_config
 #[derive(Clone, Debug, Default)]
 pub struct InitializeConfigBuilder {
     config: Option<solana_program::pubkey::Pubkey>,
@@ -126,11 +137,16 @@ pub struct InitializeConfigBuilder {
     tie_breaker_admin: Option<solana_program::pubkey::Pubkey>,
     account_payer: Option<solana_program::pubkey::Pubkey>,
     system_program: Option<solana_program::pubkey::Pubkey>,
+    restaking_config: Option<solana_program::pubkey::Pubkey>,
     epochs_before_stall: Option<u64>,
     epochs_after_consensus_before_close: Option<u64>,
     valid_slots_after_consensus: Option<u64>,
     minimum_stake: Option<u128>,
     ncn_fee_bps: Option<u16>,
+    curve_id: Option<u8>,
+    minimum_operators: Option<u64>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
     __remaining_accounts: Vec<solana_program::instruction::AccountMeta>,
 }
 
@@ -178,6 +194,14 @@ impl InitializeConfigBuilder {
         self
     }
     #[inline(always)]
+    pub fn restaking_config(
+        &mut self,
+        restaking_config: solana_program::pubkey::Pubkey,
+    ) -> &mut Self {
+        self.restaking_config = Some(restaking_config);
+        self
+    }
+    #[inline(always)]
     pub fn epochs_before_stall(&mut self, epochs_before_stall: u64) -> &mut Self {
         self.epochs_before_stall = Some(epochs_before_stall);
         self
@@ -205,6 +229,30 @@ impl InitializeConfigBuilder {
         self.ncn_fee_bps = Some(ncn_fee_bps);
         self
     }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn curve_id(&mut self, curve_id: u8) -> &mut Self {
+        self.curve_id = Some(curve_id);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn minimum_operators(&mut self, minimum_operators: u64) -> &mut Self {
+        self.minimum_operators = Some(minimum_operators);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn quorum_bps(&mut self, quorum_bps: u16) -> &mut Self {
+        self.quorum_bps = Some(quorum_bps);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn max_snapshot_age_slots(&mut self, max_snapshot_age_slots: u64) -> &mut Self {
+        self.max_snapshot_age_slots = Some(max_snapshot_age_slots);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -237,6 +285,7 @@ impl InitializeConfigBuilder {
             system_program: self
                 .system_program
                 .unwrap_or(solana_program::pubkey!("11111111111111111111111111111111")),
+            restaking_config: self.restaking_config.expect("restaking_config is not set"),
         };
         let args = InitializeConfigInstructionArgs {
             epochs_before_stall: self
@@ -256,6 +305,10 @@ impl InitializeConfigBuilder {
                 .clone()
                 .expect("minimum_stake is not set"),
             ncn_fee_bps: self.ncn_fee_bps.clone().expect("ncn_fee_bps is not set"),
+            curve_id: self.curve_id.clone(),
+            minimum_operators: self.minimum_operators.clone(),
+            quorum_bps: self.quorum_bps.clone(),
+            max_snapshot_age_slots: self.max_snapshot_age_slots.clone(),
         };
 
         accounts.instruction_with_remaining_accounts(args, &self.__remaining_accounts)
@@ -277,6 +330,8 @@ pub struct InitializeConfigCpiAccounts<'a, 'b> {
     pub account_payer: &'b solana_program::account_info::AccountInfo<'a>,
 
     pub system_program: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub restaking_config: &'b solana_program::account_info::AccountInfo<'a>,
 }
 
 /// `initialize_config` CPI instruction.
@@ -297,6 +352,8 @@ pub struct InitializeConfigCpi<'a, 'b> {
     pub account_payer: &'b solana_program::account_info::AccountInfo<'a>,
 
     pub system_program: &'b solana_program::account_info::AccountInfo<'a>,
+
+    pub restaking_config: &'b solana_program::account_info::AccountInfo<'a>,
     /// The arguments for the instruction.
     pub __args: InitializeConfigInstructionArgs,
 }
@@ -316,6 +373,7 @@ impl<'a, 'b> InitializeConfigCpi<'a, 'b> {
             tie_breaker_admin: accounts.tie_breaker_admin,
             account_payer: accounts.account_payer,
             system_program: accounts.system_program,
+            restaking_config: accounts.restaking_config,
             __args: args,
         }
     }
@@ -352,7 +410,7 @@ impl<'a, 'b> InitializeConfigCpi<'a, 'b> {
             bool,
         )],
     ) -> solana_program::entrypoint::ProgramResult {
-        let mut accounts = Vec::with_capacity(7 + remaining_accounts.len());
+        let mut accounts = Vec::with_capacity(8 + remaining_accounts.len());
         accounts.push(solana_program::instruction::AccountMeta::new(
             *self.config.key,
             false,
@@ -381,6 +439,10 @@ impl<'a, 'b> InitializeConfigCpi<'a, 'b> {
             *self.system_program.key,
             false,
         ));
+        accounts.push(solana_program::instruction::AccountMeta::new_readonly(
+            *self.restaking_config.key,
+            false,
+        ));
         remaining_accounts.iter().for_each(|remaining_account| {
             accounts.push(solana_program::instruction::AccountMeta {
                 pubkey: *remaining_account.0.key,
@@ -397,7 +459,7 @@ impl<'a, 'b> InitializeConfigCpi<'a, 'b> {
             accounts,
             data,
         };
-        let mut account_infos = Vec::with_capacity(7 + 1 + remaining_accounts.len());
+        let mut account_infos = Vec::with_capacity(8 + 1 + remaining_accounts.len());
         account_infos.push(self.__program.clone());
         account_infos.push(self.config.clone());
         account_infos.push(self.ncn.clone());
@@ -406,6 +468,7 @@ impl<'a, 'b> InitializeConfigCpi<'a, 'b> {
         account_infos.push(self.tie_breaker_admin.clone());
         account_infos.push(self.account_payer.clone());
         account_infos.push(self.system_program.clone());
+        account_infos.push(self.restaking_config.clone());
         remaining_accounts
             .iter()
             .for_each(|remaining_account| account_infos.push(remaining_account.0.clone()));
@@ -429,6 +492,7 @@ impl<'a, 'b> InitializeConfigCpi<'a, 'b> {
 ///   4. `[]` tie_breaker_admin
 ///   5. `[writable]` account_payer
 ///   6. `[]` system_program
+///   7. `[]` restaking_config
 #[derive(Clone, Debug)]
 pub struct InitializeConfigCpiBuilder<'a, 'b> {
     instruction: Box<InitializeConfigCpiBuilderInstruction<'a, 'b>>,
@@ -445,11 +509,16 @@ impl<'a, 'b> InitializeConfigCpiBuilder<'a, 'b> {
             tie_breaker_admin: None,
             account_payer: None,
             system_program: None,
+            restaking_config: None,
             epochs_before_stall: None,
             epochs_after_consensus_before_close: None,
             valid_slots_after_consensus: None,
             minimum_stake: None,
             ncn_fee_bps: None,
+            curve_id: None,
+            minimum_operators: None,
+            quorum_bps: None,
+            max_snapshot_age_slots: None,
             __remaining_accounts: Vec::new(),
         });
         Self { instruction }
@@ -508,6 +577,14 @@ impl<'a, 'b> InitializeConfigCpiBuilder<'a, 'b> {
         self
     }
     #[inline(always)]
+    pub fn restaking_config(
+        &mut self,
+        restaking_config: &'b solana_program::account_info::AccountInfo<'a>,
+    ) -> &mut Self {
+        self.instruction.restaking_config = Some(restaking_config);
+        self
+    }
+    #[inline(always)]
     pub fn epochs_before_stall(&mut self, epochs_before_stall: u64) -> &mut Self {
         self.instruction.epochs_before_stall = Some(epochs_before_stall);
         self
@@ -536,6 +613,30 @@ impl<'a, 'b> InitializeConfigCpiBuilder<'a, 'b> {
         self.instruction.ncn_fee_bps = Some(ncn_fee_bps);
         self
     }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn curve_id(&mut self, curve_id: u8) -> &mut Self {
+        self.instruction.curve_id = Some(curve_id);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn minimum_operators(&mut self, minimum_operators: u64) -> &mut Self {
+        self.instruction.minimum_operators = Some(minimum_operators);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn quorum_bps(&mut self, quorum_bps: u16) -> &mut Self {
+        self.instruction.quorum_bps = Some(quorum_bps);
+        self
+    }
+    /// `[optional argument]`
+    #[inline(always)]
+    pub fn max_snapshot_age_slots(&mut self, max_snapshot_age_slots: u64) -> &mut Self {
+        self.instruction.max_snapshot_age_slots = Some(max_snapshot_age_slots);
+        self
+    }
     /// Add an additional account to the instruction.
     #[inline(always)]
     pub fn add_remaining_account(
@@ -603,6 +704,10 @@ impl<'a, 'b> InitializeConfigCpiBuilder<'a, 'b> {
                 .ncn_fee_bps
                 .clone()
                 .expect("ncn_fee_bps is not set"),
+            curve_id: self.instruction.curve_id.clone(),
+            minimum_operators: self.instruction.minimum_operators.clone(),
+            quorum_bps: self.instruction.quorum_bps.clone(),
+            max_snapshot_age_slots: self.instruction.max_snapshot_age_slots.clone(),
         };
         let instruction = InitializeConfigCpi {
             __program: self.instruction.__program,
@@ -632,6 +737,11 @@ impl<'a, 'b> InitializeConfigCpiBuilder<'a, 'b> {
                 .instruction
                 .system_program
                 .expect("system_program is not set"),
+
+            restaking_config: self
+                .instruction
+                .restaking_config
+                .expect("restaking_config is not set"),
             __args: args,
         };
         instruction.invoke_signed_with_remaining_accounts(
@@ -651,11 +761,16 @@ struct InitializeConfigCpiBuilderInstruction<'a, 'b> {
     tie_breaker_admin: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     account_payer: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     system_program: Option<&'b solana_program::account_info::AccountInfo<'a>>,
+    restaking_config: Option<&'b solana_program::account_info::AccountInfo<'a>>,
     epochs_before_stall: Option<u64>,
     epochs_after_consensus_before_close: Option<u64>,
     valid_slots_after_consensus: Option<u64>,
     minimum_stake: Option<u128>,
     ncn_fee_bps: Option<u16>,
+    curve_id: Option<u8>,
+    minimum_operators: Option<u64>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
     /// Additional instruction accounts `(AccountInfo, is_writable, is_signer)`.
     __remaining_accounts: Vec<(
         &'b solana_program::account_info::AccountInfo<'a>,
@@ -6,31 +6,59 @@
 //!
 
 pub(crate) mod r#admin_register_st_mint;
+pub(crate) mod r#admin_register_st_mint_with_weight;
+pub(crate) mod r#admin_repair_config;
+pub(crate) mod r#admin_set_fee_wallet;
 pub(crate) mod r#admin_set_new_admin;
 pub(crate) mod r#admin_set_parameters;
+pub(crate) mod r#admin_update_snapshot_minimum_stake;
+pub(crate) mod r#batch_initialize_operator_snapshots;
+pub(crate) mod r#cast_ed25519_vote;
 pub(crate) mod r#cast_vote;
+pub(crate) mod r#cast_vote_batch;
+pub(crate) mod r#close_snapshot;
+pub(crate) mod r#deregister_operator;
 pub(crate) mod r#initialize_config;
 pub(crate) mod r#initialize_snapshot;
 pub(crate) mod r#initialize_vault_registry;
 pub(crate) mod r#initialize_vote_counter;
+pub(crate) mod r#prove_non_signer;
 pub(crate) mod r#realloc_snapshot;
+pub(crate) mod r#refresh_operator_key;
 pub(crate) mod r#register_operator;
 pub(crate) mod r#register_vault;
+pub(crate) mod r#rollover_snapshot;
+pub(crate) mod r#set_operator_active;
 pub(crate) mod r#snapshot_vault_operator_delegation;
+pub(crate) mod r#snapshot_vault_operator_delegation_batch;
 pub(crate) mod r#update_operator_b_n128_keys;
 pub(crate) mod r#update_operator_ip_port;
 
 pub use self::r#admin_register_st_mint::*;
+pub use self::r#admin_register_st_mint_with_weight::*;
+pub use self::r#admin_repair_config::*;
+pub use self::r#admin_set_fee_wallet::*;
 pub use self::r#admin_set_new_admin::*;
 pub use self::r#admin_set_parameters::*;
+pub use self::r#admin_update_snapshot_minimum_stake::*;
+pub use self::r#batch_initialize_operator_snapshots::*;
+pub use self::r#cast_ed25519_vote::*;
 pub use self::r#cast_vote::*;
+pub use self::r#cast_vote_batch::*;
+pub use self::r#close_snapshot::*;
+pub use self::r#deregister_operator::*;
 pub use self::r#initialize_config::*;
 pub use self::r#initialize_snapshot::*;
 pub use self::r#initialize_vault_registry::*;
 pub use self::r#initialize_vote_counter::*;
+pub use self::r#prove_non_signer::*;
 pub use self::r#realloc_snapshot::*;
+pub use self::r#refresh_operator_key::*;
 pub use self::r#register_operator::*;
 pub use self::r#register_vault::*;
+pub use self::r#rollover_snapshot::*;
+pub use self::r#set_operator_active::*;
 pub use self::r#snapshot_vault_operator_delegation::*;
+pub use self::r#snapshot_vault_operator_delegation_batch::*;
 pub use self::r#update_operator_b_n128_keys::*;
 pub use self::r#update_operator_ip_port::*;
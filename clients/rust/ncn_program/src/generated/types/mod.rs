@@ -5,21 +5,27 @@
 //! <https://github.com/kinobi-so/kinobi>
 //!
 
+pub(crate) mod r#cast_vote_batch_entry;
 pub(crate) mod r#config_admin_role;
+pub(crate) mod r#consensus_record;
 pub(crate) mod r#fee;
 pub(crate) mod r#fee_config;
 pub(crate) mod r#fees;
 pub(crate) mod r#operator_snapshot;
+pub(crate) mod r#operator_snapshot_entry;
 pub(crate) mod r#st_mint_entry;
 pub(crate) mod r#stake_weights;
 pub(crate) mod r#vault_entry;
 pub(crate) mod r#vault_operator_stake_weight;
 
+pub use self::r#cast_vote_batch_entry::*;
 pub use self::r#config_admin_role::*;
+pub use self::r#consensus_record::*;
 pub use self::r#fee::*;
 pub use self::r#fee_config::*;
 pub use self::r#fees::*;
 pub use self::r#operator_snapshot::*;
+pub use self::r#operator_snapshot_entry::*;
 pub use self::r#st_mint_entry::*;
 pub use self::r#stake_weights::*;
 pub use self::r#vault_entry::*;
@@ -28,4 +28,8 @@ pub struct OperatorSnapshot {
     pub has_minimum_stake_next_epoch: bool,
     pub stake_weight: StakeWeights,
     pub next_epoch_stake_weight: StakeWeights,
+    #[cfg_attr(feature = "serde", serde(with = "serde_with::As::<serde_with::Bytes>"))]
+    pub g2_pubkey: [u8; 64],
+    pub missed_last_vote: bool,
+    pub vaults_delegated: [Pubkey; 1],
 }
@@ -0,0 +1,100 @@
+use num::CheckedAdd;
+
+use crate::{error::NCNProgramError, g1_point::G1Point};
+
+#[cfg(not(target_os = "solana"))]
+use crate::g2_point::G2Point;
+
+/// Aggregates the individual G1 signatures of several signers into the single signature
+/// `CastVote` expects. Fails when `sigs` is empty.
+pub fn aggregate_signatures(sigs: &[G1Point]) -> Result<G1Point, NCNProgramError> {
+    let (first, rest) = sigs
+        .split_first()
+        .ok_or(NCNProgramError::InvalidInputLength)?;
+
+    rest.iter().try_fold(*first, |acc, sig| {
+        acc.checked_add(sig)
+            .ok_or(NCNProgramError::AltBN128AddError)
+    })
+}
+
+/// Aggregates the G2 pubkeys of several signers into the aggregated G2 pubkey `CastVote`
+/// expects. Fails when `pks` is empty.
+#[cfg(not(target_os = "solana"))]
+pub fn aggregate_g2_pubkeys(pks: &[G2Point]) -> Result<G2Point, NCNProgramError> {
+    let (first, rest) = pks
+        .split_first()
+        .ok_or(NCNProgramError::InvalidInputLength)?;
+
+    rest.iter().try_fold(*first, |acc, pk| {
+        acc.checked_add(pk)
+            .ok_or(NCNProgramError::G2PointAdditionError)
+    })
+}
+
+#[cfg(all(test, not(target_os = "solana")))]
+mod tests {
+    use crate::{privkey::PrivKey, schemes::Sha256Normalized};
+
+    use super::*;
+
+    fn scalar(value: u8) -> PrivKey {
+        let mut bytes = [0u8; 32];
+        bytes[31] = value;
+        PrivKey(bytes)
+    }
+
+    #[test]
+    fn test_aggregate_matches_combined_signature() {
+        let message = [5u8; 32];
+        let privkeys = [scalar(3), scalar(4), scalar(5)];
+
+        let sigs: Vec<G1Point> = privkeys
+            .iter()
+            .map(|privkey| privkey.sign::<Sha256Normalized, _>(message).unwrap())
+            .collect();
+        let pks: Vec<G2Point> = privkeys
+            .iter()
+            .map(|privkey| G2Point::try_from(privkey).unwrap())
+            .collect();
+
+        let aggregated_signature = aggregate_signatures(&sigs).unwrap();
+        let aggregated_g2 = aggregate_g2_pubkeys(&pks).unwrap();
+
+        // 3 + 4 + 5 signing alone gives the same signature and G2 pubkey
+        let combined = scalar(12);
+        assert_eq!(
+            aggregated_signature.0,
+            combined.sign::<Sha256Normalized, _>(message).unwrap().0
+        );
+        assert_eq!(aggregated_g2.0, G2Point::try_from(&combined).unwrap().0);
+
+        let apk1 = G1Point::try_from(combined).unwrap();
+        aggregated_g2
+            .verify_aggregated_signature::<Sha256Normalized, _, G1Point>(
+                aggregated_signature,
+                message,
+                apk1,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_aggregate_single_and_empty() {
+        let privkey = PrivKey::from_random();
+        let sig = privkey.sign::<Sha256Normalized, _>([1u8; 32]).unwrap();
+        let pk = G2Point::try_from(&privkey).unwrap();
+
+        assert_eq!(aggregate_signatures(&[sig]).unwrap().0, sig.0);
+        assert_eq!(aggregate_g2_pubkeys(&[pk]).unwrap().0, pk.0);
+
+        assert_eq!(
+            aggregate_signatures(&[]).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+        assert_eq!(
+            aggregate_g2_pubkeys(&[]).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+    }
+}
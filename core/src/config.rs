@@ -3,13 +3,17 @@ use std::mem::size_of;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use bytemuck::{Pod, Zeroable};
-use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use jito_bytemuck::{
+    types::{PodBool, PodU16, PodU64},
+    AccountDeserialize, Discriminator,
+};
 use shank::ShankAccount;
 use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::{
-    discriminators::Discriminators, fees::FeeConfig, loaders::check_load,
-    stake_weight::StakeWeights,
+    constants::MAX_QUORUM_BPS, discriminators::Discriminators, error::NCNProgramError,
+    fees::FeeConfig, g1_point::CurveId, loaders::check_load, stake_weight::StakeWeights,
+    utils::get_epoch, vote_counter::vote_signing_payload,
 };
 
 #[derive(Debug, BorshSerialize, BorshDeserialize)]
@@ -38,6 +42,28 @@ pub struct Config {
     pub bump: u8,
     /// Minimum stake weight required to vote
     pub minimum_stake: StakeWeights,
+    /// Whether cast votes must supply the signers' `NcnOperatorState` accounts in the order of the
+    /// bits set in the signature bitmap, i.e. ascending operator index order
+    pub enforce_signer_order: PodBool,
+    /// The curve operators' BLS keys and signatures live on, see `CurveId`
+    pub curve_id: u8,
+    /// Votes are rejected until at least this many operators are registered and have the minimum
+    /// stake, 0 disables the guard
+    pub minimum_operators: PodU64,
+    /// Whether cast votes reject an aggregate signature identical to the last accepted one
+    pub reject_duplicate_aggregates: PodBool,
+    /// Whether cast votes may be accepted more than once in the same epoch
+    pub allow_multiple_votes_per_epoch: PodBool,
+    /// NCN epoch length in slots, copied from the restaking config at initialization and repair
+    pub epoch_length: PodU64,
+    /// Minimum share, in bps, of the eligible stake weight the signers of a cast vote must hold,
+    /// 0 disables the check
+    pub quorum_bps: PodU16,
+    /// Maximum slots between the last snapshot update and a cast vote, 0 disables the check
+    pub max_snapshot_age_slots: PodU64,
+    /// Whether operators may vote with their ed25519 voter key through `CastEd25519Vote`
+    pub allow_ed25519_votes: PodBool,
+    pub reserved: [u8; 99], // Reserved for future use, must be zeroed
 }
 
 impl Discriminator for Config {
@@ -47,6 +73,9 @@ impl Discriminator for Config {
 impl Config {
     const CONFIG_SEED: &'static [u8] = b"config";
     pub const SIZE: usize = 8 + size_of::<Self>();
+    /// Size of the fields from `minimum_operators` on, `reserved` included. New fields are carved
+    /// out of `reserved` so `Config::SIZE` stays the same.
+    pub const TAIL_SIZE: usize = 128;
 
     /// Creates a new Config instance with the specified parameters
     ///
@@ -80,6 +109,16 @@ impl Config {
             fee_config: *fee_config,
             bump,
             minimum_stake: *minimum_stake,
+            enforce_signer_order: PodBool::from(false),
+            curve_id: CurveId::Bn254 as u8,
+            minimum_operators: PodU64::from(0),
+            reject_duplicate_aggregates: PodBool::from(false),
+            allow_multiple_votes_per_epoch: PodBool::from(false),
+            epoch_length: PodU64::from(0),
+            quorum_bps: PodU16::from(0),
+            max_snapshot_age_slots: PodU64::from(0),
+            allow_ed25519_votes: PodBool::from(false),
+            reserved: [0; 99],
         }
     }
 
@@ -141,6 +180,91 @@ impl Config {
     pub fn minimum_stake(&self) -> &StakeWeights {
         &self.minimum_stake
     }
+
+    pub fn enforce_signer_order(&self) -> bool {
+        self.enforce_signer_order.into()
+    }
+
+    pub fn curve(&self) -> Result<CurveId, NCNProgramError> {
+        CurveId::try_from(self.curve_id)
+    }
+
+    pub fn minimum_operators(&self) -> u64 {
+        self.minimum_operators.into()
+    }
+
+    pub fn reject_duplicate_aggregates(&self) -> bool {
+        self.reject_duplicate_aggregates.into()
+    }
+
+    pub fn allow_multiple_votes_per_epoch(&self) -> bool {
+        self.allow_multiple_votes_per_epoch.into()
+    }
+
+    pub fn epoch_length(&self) -> u64 {
+        self.epoch_length.into()
+    }
+
+    /// The NCN epoch of `current_slot`, from the cached epoch length instead of the restaking
+    /// config
+    pub fn current_epoch(&self, current_slot: u64) -> Result<u64, NCNProgramError> {
+        get_epoch(current_slot, self.epoch_length())
+    }
+
+    pub fn quorum_bps(&self) -> u16 {
+        self.quorum_bps.into()
+    }
+
+    /// Sets the stake quorum cast votes are held to, failing above `MAX_QUORUM_BPS`
+    pub fn set_quorum_bps(&mut self, quorum_bps: u16) -> Result<(), NCNProgramError> {
+        if quorum_bps > MAX_QUORUM_BPS {
+            return Err(NCNProgramError::InvalidQuorumBps);
+        }
+
+        self.quorum_bps = PodU16::from(quorum_bps);
+        Ok(())
+    }
+
+    /// The stake quorum a cast vote must meet: the stricter of the configured one and the
+    /// `requested` one, `None` when neither applies
+    pub fn effective_quorum_bps(&self, requested: Option<u16>) -> Option<u16> {
+        match self.quorum_bps() {
+            0 => requested,
+            quorum_bps => Some(requested.map_or(quorum_bps, |requested| requested.max(quorum_bps))),
+        }
+    }
+
+    pub fn max_snapshot_age_slots(&self) -> u64 {
+        self.max_snapshot_age_slots.into()
+    }
+
+    /// Fails when more than `max_snapshot_age_slots` slots passed between `last_snapshot_slot`
+    /// and `current_slot`
+    pub fn check_snapshot_age(
+        &self,
+        last_snapshot_slot: u64,
+        current_slot: u64,
+    ) -> Result<(), NCNProgramError> {
+        let max_snapshot_age_slots = self.max_snapshot_age_slots();
+        if max_snapshot_age_slots > 0
+            && current_slot.saturating_sub(last_snapshot_slot) > max_snapshot_age_slots
+        {
+            return Err(NCNProgramError::SnapshotTooStale);
+        }
+
+        Ok(())
+    }
+
+    pub fn allow_ed25519_votes(&self) -> bool {
+        self.allow_ed25519_votes.into()
+    }
+
+    /// The 32 byte message operators sign to vote on the vote counter value `vote_counter` in
+    /// NCN epoch `epoch`. This is the message `CastVote`, `CastVoteBatch` and `CastEd25519Vote`
+    /// verify signatures against, so clients should build it with this function
+    pub fn consensus_message(ncn: &Pubkey, epoch: u64, vote_counter: u64) -> [u8; 32] {
+        vote_signing_payload(ncn, epoch, vote_counter, &[])
+    }
 }
 
 #[rustfmt::skip]
@@ -154,6 +278,15 @@ impl fmt::Display for Config {
         writeln!(f, "  Starting Valid Epochs:        {}", self.starting_valid_epoch())?;
         writeln!(f, "  Close Epoch:                  {}", self.epochs_after_consensus_before_close())?;
         writeln!(f, "  Minimum Stake Weight:         {:?}", self.minimum_stake())?;
+        writeln!(f, "  Enforce Signer Order:         {}", self.enforce_signer_order())?;
+        writeln!(f, "  Curve ID:                     {}", self.curve_id)?;
+        writeln!(f, "  Minimum Operators:            {}", self.minimum_operators())?;
+        writeln!(f, "  Reject Duplicate Aggregates:  {}", self.reject_duplicate_aggregates())?;
+        writeln!(f, "  Allow Multiple Votes/Epoch:   {}", self.allow_multiple_votes_per_epoch())?;
+        writeln!(f, "  Epoch Length:                 {}", self.epoch_length())?;
+        writeln!(f, "  Quorum Bps:                   {}", self.quorum_bps())?;
+        writeln!(f, "  Max Snapshot Age Slots:       {}", self.max_snapshot_age_slots())?;
+        writeln!(f, "  Allow Ed25519 Votes:          {}", self.allow_ed25519_votes())?;
 
         Ok(())
     }
@@ -175,9 +308,139 @@ mod tests {
             + size_of::<PodU64>() // starting_valid_epoch
             + size_of::<FeeConfig>() // fee_config
             + 1 // bump
-            + size_of::<StakeWeights>(); // minimum_stake
+            + size_of::<StakeWeights>() // minimum_stake
+            + size_of::<PodBool>() // enforce_signer_order
+            + 1 // curve_id
+            + Config::TAIL_SIZE;
 
+        let expected_tail = size_of::<PodU64>() // minimum_operators
+            + size_of::<PodBool>() // reject_duplicate_aggregates
+            + size_of::<PodBool>() // allow_multiple_votes_per_epoch
+            + size_of::<PodU64>() // epoch_length
+            + size_of::<PodU16>() // quorum_bps
+            + size_of::<PodU64>() // max_snapshot_age_slots
+            + size_of::<PodBool>() // allow_ed25519_votes
+            + 99; // reserved
+
+        assert_eq!(expected_tail, Config::TAIL_SIZE);
         assert_eq!(size_of::<Config>(), expected_total);
         assert_eq!(size_of::<Config>() + 8, Config::SIZE);
     }
+
+    #[test]
+    fn test_curve() {
+        let mut config = Config::new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            0,
+            10000,
+            3,
+            10,
+            &FeeConfig::new(&Pubkey::new_unique(), 400, 0).unwrap(),
+            &StakeWeights::new(100),
+            255,
+        );
+        assert_eq!(config.curve(), Ok(CurveId::Bn254));
+
+        config.curve_id = 1;
+        assert_eq!(config.curve(), Err(NCNProgramError::UnsupportedCurve));
+    }
+
+    #[test]
+    fn test_current_epoch() {
+        let mut config = Config::new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            0,
+            10000,
+            3,
+            10,
+            &FeeConfig::new(&Pubkey::new_unique(), 400, 0).unwrap(),
+            &StakeWeights::new(100),
+            255,
+        );
+        assert_eq!(
+            config.current_epoch(0),
+            Err(NCNProgramError::DenominatorIsZero)
+        );
+
+        config.epoch_length = PodU64::from(432_000);
+        assert_eq!(config.epoch_length(), 432_000);
+        assert_eq!(config.current_epoch(0), Ok(0));
+        assert_eq!(config.current_epoch(431_999), Ok(0));
+        assert_eq!(config.current_epoch(432_000), Ok(1));
+        assert_eq!(config.current_epoch(864_000), Ok(2));
+        assert_eq!(config.current_epoch(u64::MAX), Ok(u64::MAX / 432_000));
+    }
+
+    #[test]
+    fn test_quorum_bps() {
+        let mut config = Config::new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            0,
+            10000,
+            3,
+            10,
+            &FeeConfig::new(&Pubkey::new_unique(), 400, 0).unwrap(),
+            &StakeWeights::new(100),
+            255,
+        );
+        assert_eq!(config.quorum_bps(), 0);
+        assert_eq!(config.effective_quorum_bps(None), None);
+        assert_eq!(config.effective_quorum_bps(Some(5000)), Some(5000));
+
+        config.set_quorum_bps(6667).unwrap();
+        assert_eq!(config.effective_quorum_bps(None), Some(6667));
+        assert_eq!(config.effective_quorum_bps(Some(5000)), Some(6667));
+        assert_eq!(config.effective_quorum_bps(Some(8000)), Some(8000));
+
+        config.set_quorum_bps(10_000).unwrap();
+        assert_eq!(config.quorum_bps(), 10_000);
+        assert_eq!(
+            config.set_quorum_bps(10_001),
+            Err(NCNProgramError::InvalidQuorumBps)
+        );
+        assert_eq!(config.quorum_bps(), 10_000);
+    }
+
+    #[test]
+    fn test_check_snapshot_age() {
+        let mut config = Config::new(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            0,
+            10000,
+            3,
+            10,
+            &FeeConfig::new(&Pubkey::new_unique(), 400, 0).unwrap(),
+            &StakeWeights::new(100),
+            255,
+        );
+        assert_eq!(config.max_snapshot_age_slots(), 0);
+        assert_eq!(config.check_snapshot_age(0, u64::MAX), Ok(()));
+
+        config.max_snapshot_age_slots = PodU64::from(100);
+        assert_eq!(config.check_snapshot_age(1_000, 1_100), Ok(()));
+        assert_eq!(
+            config.check_snapshot_age(1_000, 1_101),
+            Err(NCNProgramError::SnapshotTooStale)
+        );
+        // A snapshot updated after the current slot is never stale
+        assert_eq!(config.check_snapshot_age(1_200, 1_100), Ok(()));
+    }
+
+    #[test]
+    fn test_consensus_message() {
+        let ncn = Pubkey::new_unique();
+
+        let message = Config::consensus_message(&ncn, 5, 7);
+        assert_eq!(message, vote_signing_payload(&ncn, 5, 7, &[]));
+        assert_ne!(message, Config::consensus_message(&ncn, 6, 7));
+        assert_ne!(message, Config::consensus_message(&ncn, 5, 8));
+        assert_ne!(
+            message,
+            Config::consensus_message(&Pubkey::new_unique(), 5, 7)
+        );
+    }
 }
@@ -5,9 +5,26 @@ use spl_math::precise_number::PreciseNumber;
 use crate::error::NCNProgramError;
 
 pub const MAX_FEE_BPS: u64 = 10_000;
+pub const MAX_QUORUM_BPS: u16 = 10_000;
 pub const MAX_ST_MINTS: usize = 1;
+// The account layouts of `Snapshot` and `VaultRegistry` are sized by these limits, so accounts
+// created by a `small-limits` build can't be read by a regular build and vice versa. The feature
+// only exists to keep tests fast; on-chain builds must always use the full limits.
+// `MAX_VAULTS` is already at its minimum of 1 and is the same for both builds.
 pub const MAX_VAULTS: usize = 1;
+#[cfg(not(feature = "small-limits"))]
 pub const MAX_OPERATORS: usize = 256;
+#[cfg(feature = "small-limits")]
+pub const MAX_OPERATORS: usize = 16;
+/// Number of per-epoch consensus records kept by the vote counter
+pub const MAX_CONSENSUS_RECORDS: usize = 8;
+pub const MAX_SIGNER_BITMAP_BYTES: usize = MAX_OPERATORS.div_ceil(8);
+/// Most operators a `SnapshotVaultOperatorDelegationBatch` takes, bounded by the transaction size
+/// since every operator adds three accounts
+pub const MAX_SNAPSHOT_BATCH_OPERATORS: usize = 6;
+/// Compute units kept in reserve by `SnapshotVaultOperatorDelegationBatch`, it stops before the
+/// next operator once fewer remain
+pub const SNAPSHOT_BATCH_MIN_REMAINING_COMPUTE_UNITS: u64 = 20_000;
 pub const MIN_EPOCHS_BEFORE_STALL: u64 = 1;
 pub const MAX_EPOCHS_BEFORE_STALL: u64 = 50;
 pub const MIN_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE: u64 = 10;
@@ -34,6 +51,8 @@ pub const DEFAULT_CONSENSUS_REACHED_SLOT: u64 = u64::MAX;
 pub const MAX_REALLOC_BYTES: u64 = MAX_PERMITTED_DATA_INCREASE as u64;
 
 pub const WEIGHT: u128 = 100;
+/// Scale of the st_mint weights, a weight of `WEIGHT_PRECISION` counts the stake 1:1
+pub const WEIGHT_PRECISION: u128 = 1_000_000_000;
 pub static MODULUS: UBig = unsafe {
     UBig::from_static_words(&[
         0x3c208c16d87cfd47,
@@ -236,6 +236,66 @@ pub enum NCNProgramError {
     NoOperatorsRegistered,
     #[error("Invalid operator count")]
     InvalidOperatorCount,
+    #[error("Invalid snapshot backup")]
+    InvalidSnapshotBackup,
+    #[error("Unsupported snapshot backup version")]
+    UnsupportedSnapshotBackupVersion,
+    #[error("Operator NCN link is not active")]
+    OperatorLinkInactive,
+    #[error("Signer accounts are not in ascending operator index order")]
+    SignerOrderViolation,
+    #[error("Operator signature bitmap exceeds the maximum size")]
+    BitmapTooLarge,
+    #[error("Signers stake weight is below the requested quorum")]
+    SignerStakeQuorumNotMet,
+    #[error("Unsupported curve")]
+    UnsupportedCurve,
+    #[error("Invalid G2 Pubkey")]
+    InvalidG2Pubkey,
+    #[error("Snapshot already rolled over this epoch")]
+    SnapshotAlreadyRolledOver,
+    #[error("Not enough operators registered for consensus")]
+    NotEnoughOperatorsRegistered,
+    #[error("Not enough operators with the minimum stake for consensus")]
+    InsufficientOperators,
+    #[error("Operator signed the message")]
+    OperatorSignedMessage,
+    #[error("Aggregate signature was already submitted")]
+    DuplicateAggregateSubmission,
+    #[error("Vote message mismatch")]
+    VoteMessageMismatch,
+    #[error("Vault operator delegation was not updated this epoch")]
+    VaultOperatorDelegationNotUpdated,
+    #[error("Duplicate operator index")]
+    DuplicateOperatorIndex,
+    #[error("No signers in bitmap")]
+    NoSignersInBitmap,
+    #[error("Snapshot version mismatch")]
+    SnapshotVersionMismatch,
+    #[error("G1 pubkey already registered by another operator")]
+    DuplicateOperatorG1Pubkey,
+    #[error("Parameter change not allowed while a vote is pending")]
+    ParameterChangeNotAllowed,
+    #[error("A vote was already cast this epoch")]
+    AlreadyVotedThisEpoch,
+    #[error("Quorum bps exceeds 10000")]
+    InvalidQuorumBps,
+    #[error("G1 pubkey is not part of the total aggregated pubkey")]
+    G1PubkeyNotInAggregate,
+    #[error("Invalid operator snapshot bytes")]
+    InvalidOperatorSnapshotBytes,
+    #[error("Snapshot too stale")]
+    SnapshotTooStale,
+    #[error("G2 aggregate mismatch")]
+    G2AggregateMismatch,
+    #[error("Ed25519 votes are disabled")]
+    Ed25519VotesDisabled,
+    #[error("Invalid ed25519 instruction")]
+    InvalidEd25519Instruction,
+    #[error("Operator already cast an ed25519 vote this epoch")]
+    Ed25519AlreadyVoted,
+    #[error("NCN operator index does not match the operator's NCN operator state")]
+    NcnOperatorIndexMismatch,
 }
 
 impl<T> DecodeError<T> for NCNProgramError {
@@ -0,0 +1,65 @@
+//! # Events
+//!
+//! Events are logged with `sol_log_data` as two fields: the event name, then the borsh encoded
+//! event. Clients find them in the `Program data:` lines of the transaction logs.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+/// Logged once the last operator of the NCN registers in the snapshot
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotFinalizedEvent {
+    /// The NCN of the snapshot
+    pub ncn: Pubkey,
+    /// Slot the snapshot was finalized in
+    pub slot_finalized: u64,
+    /// Number of operators registered in the snapshot
+    pub operators_registered: u64,
+}
+
+impl SnapshotFinalizedEvent {
+    pub const NAME: &'static [u8] = b"SnapshotFinalizedEvent";
+
+    pub fn emit(&self) {
+        let data = self
+            .try_to_vec()
+            .expect("Serializing an event into memory cannot fail");
+        sol_log_data(&[Self::NAME, &data]);
+    }
+
+    /// Decodes the event from the fields of a `sol_log_data` log, `None` for any other log
+    pub fn from_log_data(fields: &[&[u8]]) -> Option<Self> {
+        match fields {
+            [name, data] if *name == Self::NAME => Self::try_from_slice(data).ok(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_finalized_event_from_log_data() {
+        let event = SnapshotFinalizedEvent {
+            ncn: Pubkey::new_unique(),
+            slot_finalized: 1_234,
+            operators_registered: 3,
+        };
+        let data = event.try_to_vec().unwrap();
+
+        assert_eq!(
+            SnapshotFinalizedEvent::from_log_data(&[SnapshotFinalizedEvent::NAME, &data]),
+            Some(event)
+        );
+        assert_eq!(
+            SnapshotFinalizedEvent::from_log_data(&[b"OtherEvent", &data]),
+            None
+        );
+        assert_eq!(
+            SnapshotFinalizedEvent::from_log_data(&[SnapshotFinalizedEvent::NAME]),
+            None
+        );
+    }
+}
@@ -167,6 +167,26 @@ impl FeeConfig {
         current_fees.precise_ncn_fee_bps()
     }
 
+    /// Lamports owed to the NCN fee wallet on `amount` at the current epoch's NCN fee
+    pub fn calculate_ncn_fee(
+        &self,
+        amount: u64,
+        current_epoch: u64,
+    ) -> Result<u64, NCNProgramError> {
+        let current_fees = self.current_fees(current_epoch);
+        current_fees.calculate_ncn_fee(amount)
+    }
+
+    /// Lamports owed to the protocol fee wallet on `amount` at the current epoch's protocol fee
+    pub fn calculate_protocol_fee(
+        &self,
+        amount: u64,
+        current_epoch: u64,
+    ) -> Result<u64, NCNProgramError> {
+        let current_fees = self.current_fees(current_epoch);
+        current_fees.calculate_protocol_fee(amount)
+    }
+
     /// Sets the NCN fee percentage for the next epoch
     pub fn set_ncn_fee_bps(
         &mut self,
@@ -376,6 +396,30 @@ impl Fees {
         PreciseNumber::new(total_fee_bps.into()).ok_or(NCNProgramError::NewPreciseNumberError)
     }
 
+    // ------ FEE AMOUNTS -----------------
+
+    /// Calculates the NCN fee owed on `amount`, rounded down
+    pub fn calculate_ncn_fee(&self, amount: u64) -> Result<u64, NCNProgramError> {
+        Self::calculate_fee(amount, self.ncn_fee_bps()?)
+    }
+
+    /// Calculates the Protocol fee owed on `amount`, rounded down
+    pub fn calculate_protocol_fee(&self, amount: u64) -> Result<u64, NCNProgramError> {
+        Self::calculate_fee(amount, self.protocol_fee_bps()?)
+    }
+
+    /// `amount * fee_bps / MAX_FEE_BPS`, rounded down so the fees never exceed what is owed. The
+    /// product is computed in u128, so only a fee above 100% could fail to fit back in a u64
+    fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64, NCNProgramError> {
+        let fee = (amount as u128)
+            .checked_mul(fee_bps as u128)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?
+            .checked_div(MAX_FEE_BPS as u128)
+            .ok_or(NCNProgramError::DenominatorIsZero)?;
+
+        u64::try_from(fee).map_err(|_| NCNProgramError::ArithmeticOverflow)
+    }
+
     // ------ SETTERS -----------------
 
     /// Sets the activation epoch for these fees
@@ -788,4 +832,78 @@ mod tests {
 
         assert!(precise_total.eq(&expected));
     }
+
+    /// Tests NCN fee amounts round down at 1 bps
+    #[test]
+    fn test_calculate_ncn_fee_one_bps() {
+        const EPOCH: u64 = 10;
+
+        let fee_config = FeeConfig::new(&Pubkey::new_unique(), 1, EPOCH).unwrap();
+
+        assert_eq!(fee_config.calculate_ncn_fee(0, EPOCH).unwrap(), 0);
+        assert_eq!(fee_config.calculate_ncn_fee(9_999, EPOCH).unwrap(), 0);
+        assert_eq!(fee_config.calculate_ncn_fee(10_000, EPOCH).unwrap(), 1);
+        assert_eq!(fee_config.calculate_ncn_fee(19_999, EPOCH).unwrap(), 1);
+        assert_eq!(
+            fee_config.calculate_ncn_fee(u64::MAX, EPOCH).unwrap(),
+            u64::MAX / 10_000
+        );
+    }
+
+    /// Tests a 0 bps NCN fee is never owed anything
+    #[test]
+    fn test_calculate_ncn_fee_zero_bps() {
+        const EPOCH: u64 = 10;
+
+        let fee_config = FeeConfig::new(&Pubkey::new_unique(), 0, EPOCH).unwrap();
+
+        assert_eq!(fee_config.calculate_ncn_fee(10_000, EPOCH).unwrap(), 0);
+        assert_eq!(fee_config.calculate_ncn_fee(u64::MAX, EPOCH).unwrap(), 0);
+    }
+
+    /// Tests a 10000 bps NCN fee is owed the whole amount, even at u64::MAX
+    #[test]
+    fn test_calculate_ncn_fee_max_bps() {
+        const EPOCH: u64 = 10;
+
+        let fee_config = FeeConfig::new(&Pubkey::new_unique(), MAX_FEE_BPS as u16, EPOCH).unwrap();
+
+        assert_eq!(fee_config.calculate_ncn_fee(1, EPOCH).unwrap(), 1);
+        assert_eq!(fee_config.calculate_ncn_fee(12_345, EPOCH).unwrap(), 12_345);
+        assert_eq!(
+            fee_config.calculate_ncn_fee(u64::MAX, EPOCH).unwrap(),
+            u64::MAX
+        );
+    }
+
+    /// Tests Protocol fee amounts follow the same rounding as NCN fees
+    #[test]
+    fn test_calculate_protocol_fee() {
+        const EPOCH: u64 = 10;
+
+        let fee_config = FeeConfig::new(&Pubkey::new_unique(), 0, EPOCH).unwrap();
+
+        // 400 bps
+        assert_eq!(fee_config.calculate_protocol_fee(24, EPOCH).unwrap(), 0);
+        assert_eq!(fee_config.calculate_protocol_fee(25, EPOCH).unwrap(), 1);
+        assert_eq!(
+            fee_config.calculate_protocol_fee(10_000, EPOCH).unwrap(),
+            400
+        );
+        assert_eq!(
+            fee_config.calculate_protocol_fee(u64::MAX, EPOCH).unwrap(),
+            ((u64::MAX as u128 * 400) / 10_000) as u64
+        );
+
+        // The fee amounts follow the fees active in the epoch
+        let mut fee_config = FeeConfig::new(&Pubkey::new_unique(), 100, EPOCH).unwrap();
+        fee_config
+            .update_fee_config(Some(200), None, EPOCH)
+            .unwrap();
+        assert_eq!(fee_config.calculate_ncn_fee(10_000, EPOCH).unwrap(), 100);
+        assert_eq!(
+            fee_config.calculate_ncn_fee(10_000, EPOCH + 1).unwrap(),
+            200
+        );
+    }
 }
@@ -18,6 +18,7 @@ use crate::constants::BN128_ADDITION_SUCESS_RESULT;
 use crate::{
     constants::{G1_GENERATOR, G2_MINUS_ONE, MODULUS},
     error::NCNProgramError,
+    utils::decode_hex,
 };
 use crate::{g2_point::G2Point, privkey::PrivKey, schemes::BLSSignature};
 
@@ -31,6 +32,26 @@ pub struct G1Point(pub [u8; 64]);
 #[derive(Clone, Default, Debug, Copy)]
 pub struct G1CompressedPoint(pub [u8; 32]);
 
+/// Pairing-friendly curve an NCN's BLS keys live on, stored as `Config::curve_id`.
+/// Only BN254 is implemented, other ids are rejected with `UnsupportedCurve`.
+#[derive(Clone, Default, Debug, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CurveId {
+    #[default]
+    Bn254 = 0,
+}
+
+impl TryFrom<u8> for CurveId {
+    type Error = NCNProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bn254),
+            _ => Err(NCNProgramError::UnsupportedCurve),
+        }
+    }
+}
+
 // =============================================================================
 // BASIC CONSTRUCTORS AND CONVERSIONS
 // =============================================================================
@@ -157,6 +178,28 @@ impl TryFrom<&G1CompressedPoint> for G1Point {
     }
 }
 
+// =============================================================================
+// CURVE DISPATCH
+// =============================================================================
+
+impl CurveId {
+    /// Decompresses a G1 point on this curve
+    pub fn g1_decompress(&self, point: &G1CompressedPoint) -> Result<G1Point, NCNProgramError> {
+        match self {
+            Self::Bn254 => G1Point::try_from(point),
+        }
+    }
+
+    /// Adds two G1 points on this curve
+    pub fn g1_add(&self, lhs: &G1Point, rhs: &G1Point) -> Result<G1Point, NCNProgramError> {
+        match self {
+            Self::Bn254 => lhs
+                .checked_add(rhs)
+                .ok_or(NCNProgramError::AltBN128AddError),
+        }
+    }
+}
+
 // =============================================================================
 // TRAIT IMPLEMENTATIONS
 // =============================================================================
@@ -236,6 +279,11 @@ impl G1Point {
         ))
     }
 
+    /// Whether this is the point at infinity, which alt_bn128 encodes as all zeroes
+    pub fn is_identity(&self) -> bool {
+        self.0 == [0u8; 64]
+    }
+
     /// Returns the negation of the point: (x, -y mod p)
     pub fn negate(&self) -> Self {
         // x: first 32 bytes, y: last 32 bytes
@@ -344,3 +392,121 @@ impl G1CompressedPoint {
         G1CompressedPoint::try_from(private_key).expect("Invalid private key for G1")
     }
 }
+
+// =============================================================================
+// HEX AND SERDE
+// =============================================================================
+
+impl G1CompressedPoint {
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, NCNProgramError> {
+        Self::try_from(decode_hex(hex)?)
+    }
+}
+
+impl G1Point {
+    /// Hex of the compressed point
+    pub fn to_hex(&self) -> Result<String, NCNProgramError> {
+        Ok(G1CompressedPoint::try_from(*self)?.to_hex())
+    }
+
+    /// Parses the hex of a compressed point, failing if it is not on the curve
+    pub fn from_hex(hex: &str) -> Result<Self, NCNProgramError> {
+        G1Point::try_from(&G1CompressedPoint::from_hex(hex)?)
+    }
+}
+
+/// Serialized as the hex of the compressed point, e.g. for keys stored in JSON config
+#[cfg(feature = "serde")]
+impl serde::Serialize for G1Point {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex().map_err(serde::ser::Error::custom)?)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for G1Point {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = <String as serde::Deserialize>::deserialize(deserializer)?;
+        G1Point::from_hex(&hex).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_g1_point_is_identity() {
+        assert!(G1Point([0u8; 64]).is_identity());
+
+        let point = G1Point::from_random();
+        assert!(!point.is_identity());
+        assert!((point + point.negate()).is_identity());
+    }
+
+    #[test]
+    fn test_g1_point_hex_round_trip() {
+        let point = G1Point::from_random();
+        let hex = point.to_hex().unwrap();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(G1Point::from_hex(&hex).unwrap().0, point.0);
+
+        let compressed = G1CompressedPoint::try_from(point).unwrap();
+        assert_eq!(compressed.to_hex(), hex);
+        assert_eq!(G1CompressedPoint::from_hex(&hex).unwrap().0, compressed.0);
+
+        // The identity point
+        let identity = G1Point::default();
+        let hex = identity.to_hex().unwrap();
+        assert_eq!(hex, "00".repeat(32));
+        assert_eq!(G1Point::from_hex(&hex).unwrap().0, identity.0);
+    }
+
+    #[test]
+    fn test_g1_point_from_hex_rejects_malformed_length() {
+        let hex = G1Point::from_random().to_hex().unwrap();
+
+        assert_eq!(
+            G1Point::from_hex(&hex[..62]).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+        assert_eq!(
+            G1Point::from_hex(&hex[..63]).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+        assert_eq!(
+            G1Point::from_hex(&format!("{}00", hex)).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+        assert_eq!(
+            G1Point::from_hex(&"zz".repeat(32)).unwrap_err(),
+            NCNProgramError::SerializationError
+        );
+    }
+
+    #[test]
+    fn test_g1_point_compression_round_trip() {
+        for _ in 0..256 {
+            // Negating covers both y parities, which compression stores in the flag bits
+            let random = G1Point::from_random();
+            for point in [random, random.negate()] {
+                let compressed = G1CompressedPoint::try_from(point).unwrap();
+                assert_eq!(G1Point::try_from(&compressed).unwrap().0, point.0);
+            }
+
+            let compressed = G1CompressedPoint::from_random();
+            let point = G1Point::try_from(&compressed).unwrap();
+            assert_eq!(G1CompressedPoint::try_from(point).unwrap().0, compressed.0);
+        }
+
+        // The identity point
+        let identity = G1Point::default();
+        let compressed = G1CompressedPoint::try_from(identity).unwrap();
+        assert_eq!(compressed.0, [0; 32]);
+        assert_eq!(G1Point::try_from(&compressed).unwrap().0, identity.0);
+    }
+}
@@ -5,9 +5,8 @@ pub struct G2CompressedPoint(pub [u8; 64]);
 
 #[cfg(not(target_os = "solana"))]
 use ark_bn254::Fr;
-#[cfg(not(target_os = "solana"))]
-use ark_ec::AffineRepr;
-#[cfg(not(target_os = "solana"))]
+use ark_bn254::G2Affine;
+use ark_ec::{AffineRepr, CurveGroup};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 #[cfg(not(target_os = "solana"))]
 use num::CheckedAdd;
@@ -21,10 +20,10 @@ use solana_program::msg;
 use crate::{
     constants::{BN128_ADDITION_SUCESS_RESULT, G1_GENERATOR, G2_MINUS_ONE},
     error::NCNProgramError,
-    g1_point::{G1CompressedPoint, G1Point},
+    g1_point::{CurveId, G1CompressedPoint, G1Point},
     privkey::PrivKey,
     schemes::{BLSSignature, HashToCurve, Sha256Normalized},
-    utils::compute_alpha,
+    utils::{compute_alpha, decode_hex},
 };
 
 impl G2Point {
@@ -102,6 +101,23 @@ impl G2Point {
         }
     }
 
+    /// Verifies an operator's proof of possession: `signature` must be a BLS signature over the
+    /// compressed G1 pubkey bytes, hashed with the normalized sha256 scheme, under this G2 key.
+    ///
+    /// A failed pairing means the operator doesn't hold the G2 private key and is reported as
+    /// `InvalidG2Pubkey`.
+    pub fn verify_proof_of_possession(
+        self,
+        signature: G1Point,
+        g1_pubkey: [u8; 32],
+    ) -> Result<(), NCNProgramError> {
+        self.verify_signature::<Sha256Normalized, _, _>(signature, g1_pubkey)
+            .map_err(|e| match e {
+                NCNProgramError::BLSVerificationError => NCNProgramError::InvalidG2Pubkey,
+                e => e,
+            })
+    }
+
     pub fn verify_aggregated_signature<H: HashToCurve, T: AsRef<[u8]>, S: BLSSignature>(
         self,
         aggregated_signature: G1Point,
@@ -145,6 +161,78 @@ impl G2Point {
     }
 }
 
+impl G2Point {
+    /// Sums `points` in software, since the runtime has no syscall to add G2 points. The points
+    /// are expected to come from `alt_bn128_g2_decompress`, which checks they are on the curve.
+    pub fn sum(points: &[G2Point]) -> Result<G2Point, NCNProgramError> {
+        let sum = points
+            .iter()
+            .try_fold(G2Affine::zero().into_group(), |sum, point| {
+                Ok::<_, NCNProgramError>(sum + point.to_affine()?)
+            })?
+            .into_affine();
+
+        G2Point::from_affine(&sum)
+    }
+
+    // The runtime lays a G2 point out as big endian `x.c1 || x.c0 || y.c1 || y.c0` and arkworks
+    // as little endian `x.c0 || x.c1 || y.c0 || y.c1`, so each coordinate is reversed
+    fn to_affine(self) -> Result<G2Affine, NCNProgramError> {
+        if self.0 == [0u8; 128] {
+            return Ok(G2Affine::zero());
+        }
+
+        let mut bytes = self.0;
+        bytes[..64].reverse();
+        bytes[64..].reverse();
+
+        G2Affine::deserialize_uncompressed_unchecked(&bytes[..])
+            .map_err(|_| NCNProgramError::G2PointDecompressionError)
+    }
+
+    fn from_affine(point: &G2Affine) -> Result<G2Point, NCNProgramError> {
+        if point.is_zero() {
+            return Ok(G2Point([0u8; 128]));
+        }
+
+        let mut bytes = [0u8; 128];
+        point
+            .serialize_uncompressed(&mut &mut bytes[..])
+            .map_err(|_| NCNProgramError::SerializationError)?;
+        bytes[..64].reverse();
+        bytes[64..].reverse();
+
+        Ok(G2Point(bytes))
+    }
+}
+
+impl CurveId {
+    /// Decompresses a G2 point on this curve
+    pub fn g2_decompress(&self, point: G2CompressedPoint) -> Result<G2Point, NCNProgramError> {
+        match self {
+            Self::Bn254 => G2Point::try_from(point),
+        }
+    }
+
+    /// Verifies an aggregated signature against the signers' aggregated G1 and G2 pubkeys on
+    /// this curve
+    pub fn verify_aggregated_signature<H: HashToCurve, T: AsRef<[u8]>>(
+        &self,
+        apk2: G2Point,
+        aggregated_signature: G1Point,
+        message: T,
+        apk1: G1Point,
+    ) -> Result<(), NCNProgramError> {
+        match self {
+            Self::Bn254 => apk2.verify_aggregated_signature::<H, T, G1Point>(
+                aggregated_signature,
+                message,
+                apk1,
+            ),
+        }
+    }
+}
+
 #[cfg(not(target_os = "solana"))]
 impl core::ops::Add for G2Point {
     type Output = G2Point;
@@ -338,3 +426,146 @@ impl TryFrom<Vec<u8>> for G2CompressedPoint {
         Ok(G2CompressedPoint(array))
     }
 }
+
+// =============================================================================
+// UTILITY METHODS
+// =============================================================================
+
+#[cfg(not(target_os = "solana"))]
+impl G2Point {
+    pub fn from_random() -> G2Point {
+        let private_key = PrivKey::from_random();
+        G2Point::try_from(&private_key).expect("Invalid private key for G2")
+    }
+}
+
+#[cfg(not(target_os = "solana"))]
+impl G2CompressedPoint {
+    pub fn from_random() -> G2CompressedPoint {
+        let private_key = PrivKey::from_random();
+        G2CompressedPoint::try_from(&private_key).expect("Invalid private key for G2")
+    }
+}
+
+// =============================================================================
+// HEX AND SERDE
+// =============================================================================
+
+impl G2CompressedPoint {
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, NCNProgramError> {
+        Self::try_from(decode_hex(hex)?)
+    }
+}
+
+impl G2Point {
+    /// Hex of the compressed point
+    pub fn to_hex(&self) -> Result<String, NCNProgramError> {
+        Ok(G2CompressedPoint::try_from(self)?.to_hex())
+    }
+
+    /// Parses the hex of a compressed point, failing if it is not on the curve
+    pub fn from_hex(hex: &str) -> Result<Self, NCNProgramError> {
+        G2Point::try_from(G2CompressedPoint::from_hex(hex)?)
+    }
+}
+
+/// Serialized as the hex of the compressed point, e.g. for keys stored in JSON config
+#[cfg(feature = "serde")]
+impl serde::Serialize for G2Point {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex().map_err(serde::ser::Error::custom)?)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for G2Point {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = <String as serde::Deserialize>::deserialize(deserializer)?;
+        G2Point::from_hex(&hex).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_g2_point_hex_round_trip() {
+        let point = G2Point::try_from(&PrivKey::from_random()).unwrap();
+        let hex = point.to_hex().unwrap();
+        assert_eq!(hex.len(), 128);
+        assert_eq!(G2Point::from_hex(&hex).unwrap().0, point.0);
+
+        let compressed = G2CompressedPoint::try_from(&point).unwrap();
+        assert_eq!(compressed.to_hex(), hex);
+        assert_eq!(G2CompressedPoint::from_hex(&hex).unwrap().0, compressed.0);
+
+        // The identity point
+        let identity = G2Point([0; 128]);
+        let hex = identity.to_hex().unwrap();
+        assert_eq!(hex, "00".repeat(64));
+        assert_eq!(G2Point::from_hex(&hex).unwrap().0, identity.0);
+    }
+
+    #[test]
+    fn test_g2_point_from_hex_rejects_malformed_length() {
+        let hex = G2Point::try_from(&PrivKey::from_random())
+            .unwrap()
+            .to_hex()
+            .unwrap();
+
+        assert_eq!(
+            G2Point::from_hex(&hex[..126]).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+        assert_eq!(
+            G2Point::from_hex(&hex[..127]).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+        assert_eq!(
+            G2CompressedPoint::from_hex(&format!("{}00", hex)).unwrap_err(),
+            NCNProgramError::InvalidInputLength
+        );
+    }
+
+    #[test]
+    fn test_g2_point_compression_round_trip() {
+        for _ in 0..32 {
+            let point = G2Point::from_random();
+            let compressed = G2CompressedPoint::try_from(&point).unwrap();
+            assert_eq!(G2Point::try_from(compressed).unwrap().0, point.0);
+
+            let compressed = G2CompressedPoint::from_random();
+            let point = G2Point::try_from(compressed).unwrap();
+            assert_eq!(G2CompressedPoint::try_from(&point).unwrap().0, compressed.0);
+        }
+
+        // The identity point
+        let identity = G2Point([0; 128]);
+        let compressed = G2CompressedPoint::try_from(&identity).unwrap();
+        assert_eq!(compressed.0, [0; 64]);
+        assert_eq!(G2Point::try_from(compressed).unwrap().0, identity.0);
+    }
+
+    #[test]
+    fn test_g2_point_sum() {
+        let points: Vec<G2Point> = (0..3).map(|_| G2Point::from_random()).collect();
+
+        assert_eq!(
+            G2Point::sum(&points).unwrap().0,
+            (points[0] + points[1] + points[2]).0
+        );
+        assert_eq!(G2Point::sum(&points[..1]).unwrap().0, points[0].0);
+
+        // The identity point is the empty sum and adds nothing
+        assert_eq!(G2Point::sum(&[]).unwrap().0, [0; 128]);
+        assert_eq!(
+            G2Point::sum(&[points[0], G2Point([0; 128])]).unwrap().0,
+            points[0].0
+        );
+    }
+}
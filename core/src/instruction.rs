@@ -1,8 +1,27 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankInstruction;
+use solana_program::pubkey::Pubkey;
 
 use crate::config::ConfigAdminRole;
 
+/// A single aggregated vote inside a `CastVoteBatch`
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct CastVoteBatchEntry {
+    pub aggregated_signature: [u8; 32],
+    pub aggregated_g2: [u8; 64],
+    pub operators_signature_bitmap: Vec<u8>,
+}
+
+/// A single operator inserted by `BatchInitializeOperatorSnapshots`
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct OperatorSnapshotEntry {
+    pub operator: Pubkey,
+    pub g1_pubkey: [u8; 32],
+    pub g2_pubkey: [u8; 64],
+    pub signature: [u8; 64],
+    pub ncn_operator_index: u64,
+}
+
 /// Represents all instructions supported by the NCN Program
 /// Each instruction specifies the accounts it requires and any parameters
 /// The instruction variants are organized into logical sections:
@@ -26,6 +45,7 @@ pub enum NCNProgramInstruction {
     #[account(4, name = "tie_breaker_admin")]
     #[account(5, writable, name = "account_payer")]
     #[account(6, name = "system_program")]
+    #[account(7, name = "restaking_config")]
     InitializeConfig {
         /// Number of epochs before voting is considered stalled
         epochs_before_stall: u64,
@@ -37,6 +57,16 @@ pub enum NCNProgramInstruction {
         minimum_stake: u128,
         /// NCN fee basis points (bps) for the NCN program
         ncn_fee_bps: u16,
+        /// Curve the operators' BLS keys live on, defaults to BN254
+        curve_id: Option<u8>,
+        /// Operators with the minimum stake required before votes are accepted, defaults to 0
+        minimum_operators: Option<u64>,
+        /// Minimum share, in bps, of the eligible stake weight the signers of a cast vote must
+        /// hold, defaults to 0 which disables the check
+        quorum_bps: Option<u16>,
+        /// Maximum slots between the last snapshot update and a cast vote, defaults to 0 which
+        /// disables the check
+        max_snapshot_age_slots: Option<u64>,
     },
 
     /// Initializes the vault registry account to track validator vaults
@@ -147,12 +177,16 @@ pub enum NCNProgramInstruction {
     #[account(7, name = "ncn_operator_state")]
     #[account(8, name = "vault_operator_delegation")]
     #[account(9, writable, name = "snapshot")]
+    #[account(10, name = "vault_registry")]
     SnapshotVaultOperatorDelegation{},
 
     // ---------------------------------------------------- //
     //                         VOTE                         //
     // ---------------------------------------------------- //
     /// Cast a vote
+    ///
+    /// Remaining accounts: one `NcnOperatorState` per signer, in ascending operator index order
+    /// when the config enforces signer order
     #[account(0, name = "config")]
     #[account(1, name = "ncn")]
     #[account(2, name = "snapshot")]
@@ -162,6 +196,8 @@ pub enum NCNProgramInstruction {
         aggregated_signature: [u8; 32],
         aggregated_g2: [u8; 64],
         operators_signature_bitmap: Vec<u8>,
+        /// Minimum share, in bps, of the eligible stake weight the signers must hold
+        quorum_bps: Option<u16>,
     },
 
 
@@ -172,12 +208,22 @@ pub enum NCNProgramInstruction {
     #[account(0, writable, name = "config")]
     #[account(1, name = "ncn")]
     #[account(2, signer, name = "ncn_admin")]
+    #[account(3, name = "restaking_config")]
+    #[account(4, name = "snapshot")]
+    #[account(5, name = "vote_counter")]
     AdminSetParameters {
         starting_valid_epoch: Option<u64>,
         epochs_before_stall: Option<u64>,
         epochs_after_consensus_before_close: Option<u64>,
         valid_slots_after_consensus: Option<u64>,
         minimum_stake: Option<u128>,
+        enforce_signer_order: Option<bool>,
+        minimum_operators: Option<u64>,
+        reject_duplicate_aggregates: Option<bool>,
+        allow_multiple_votes_per_epoch: Option<bool>,
+        quorum_bps: Option<u16>,
+        max_snapshot_age_slots: Option<u64>,
+        allow_ed25519_votes: Option<bool>,
     },
 
 
@@ -197,4 +243,373 @@ pub enum NCNProgramInstruction {
     #[account(3, writable, name = "vault_registry")]
     #[account(4, signer, writable, name = "admin")]
     AdminRegisterStMint{ },
+
+    /// Re-validates and re-writes a partially initialized config without changing admin roles, growing a config written with an earlier layout
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "ncn_fee_wallet")]
+    #[account(3, signer, name = "ncn_admin")]
+    #[account(4, name = "restaking_config")]
+    #[account(5, writable, name = "account_payer")]
+    #[account(6, name = "system_program")]
+    AdminRepairConfig {
+        /// Number of epochs before voting is considered stalled
+        epochs_before_stall: u64,
+        /// Number of epochs after consensus before accounts can be closed
+        epochs_after_consensus_before_close: u64,
+        /// Number of slots after consensus where voting is still valid
+        valid_slots_after_consensus: u64,
+        /// Minimum stake for a validator to be considered valid
+        minimum_stake: u128,
+        /// NCN fee basis points (bps) for the NCN program
+        ncn_fee_bps: u16,
+    },
+
+    /// Closes the snapshot account once the close window has passed, returning its rent to the account payer
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, writable, name = "snapshot")]
+    #[account(3, writable, name = "account_payer")]
+    #[account(4, name = "restaking_config")]
+    CloseSnapshot {},
+
+    /// Removes an operator from the snapshot, subtracting its G1 pubkey from the total aggregate and closing its ncn operator account
+    #[account(0, name = "config")]
+    #[account(1, writable, name = "ncn_operator_account")]
+    #[account(2, name = "ncn")]
+    #[account(3, name = "operator")]
+    #[account(4, signer, name = "operator_admin")]
+    #[account(5, writable, name = "snapshot")]
+    #[account(6, writable, name = "account_payer")]
+    DeregisterOperator {},
+
+    /// Copies an operator's keys from its ncn operator account into the snapshot, updating the total aggregated G1 pubkey
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn_operator_account")]
+    #[account(2, name = "ncn")]
+    #[account(3, name = "operator")]
+    #[account(4, writable, name = "snapshot")]
+    RefreshOperatorKey {},
+
+    /// Promotes every active operator's next epoch stake weight to its current stake weight once per epoch
+    #[account(0, name = "config")]
+    #[account(1, name = "restaking_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, writable, name = "snapshot")]
+    RolloverSnapshot {},
+
+    /// Proves an operator did not sign the message of the last successful vote and flags its
+    /// operator snapshot as having missed the vote
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "operator")]
+    #[account(3, writable, name = "snapshot")]
+    #[account(4, name = "vote_counter")]
+    ProveNonSigner {
+        aggregated_signature: [u8; 32],
+        aggregated_g2: [u8; 64],
+        operators_signature_bitmap: Vec<u8>,
+    },
+
+    /// Snapshots the delegations between a vault and several operators, stopping early when the
+    /// compute budget runs low
+    ///
+    /// Remaining accounts: the `operator`, `ncn_operator_state` and `vault_operator_delegation` of
+    /// every operator, in `operator_indices` order
+    #[account(0, name = "config")]
+    #[account(1, name = "restaking_config")]
+    #[account(2, name = "ncn")]
+    #[account(3, name = "vault")]
+    #[account(4, name = "vault_ncn_ticket")]
+    #[account(5, name = "ncn_vault_ticket")]
+    #[account(6, writable, name = "snapshot")]
+    #[account(7, name = "vault_registry")]
+    SnapshotVaultOperatorDelegationBatch {
+        operator_indices: Vec<u64>,
+    },
+
+    /// Updates the minimum stake of the current snapshot and recomputes every operator's minimum stake flags
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, writable, name = "snapshot")]
+    #[account(3, signer, name = "ncn_admin")]
+    AdminUpdateSnapshotMinimumStake {
+        minimum_stake: u128,
+    },
+
+    /// Inserts several operators into the snapshot at once to bootstrap a large NCN, adding all
+    /// of their G1 pubkeys to the total aggregated G1 pubkey. Every operator is checked like in
+    /// `RegisterOperator` and gets its ncn operator account.
+    /// Remaining accounts: the `operator`, `ncn_operator_state` and writable `ncn_operator_account`
+    /// of every operator, in `operators` order
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, writable, name = "snapshot")]
+    #[account(3, signer, name = "ncn_admin")]
+    #[account(4, name = "restaking_config")]
+    #[account(5, writable, name = "account_payer")]
+    #[account(6, name = "system_program")]
+    BatchInitializeOperatorSnapshots {
+        operators: Vec<OperatorSnapshotEntry>,
+    },
+
+    /// Marks a registered operator active or inactive without deregistering it, leaving the
+    /// total aggregated G1 pubkey untouched
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "operator")]
+    #[account(3, writable, name = "snapshot")]
+    #[account(4, signer, name = "ncn_admin")]
+    SetOperatorActive {
+        active: bool,
+    },
+
+    /// Rotates the wallet receiving the NCN fees
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, signer, name = "ncn_admin")]
+    AdminSetFeeWallet {
+        new_wallet: Pubkey,
+    },
+
+    /// Registers a new staked token mint in the vault registry with an explicit weight
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "st_mint")]
+    #[account(3, writable, name = "vault_registry")]
+    #[account(4, signer, writable, name = "admin")]
+    AdminRegisterStMintWithWeight {
+        /// Weight of the stake in this mint, scaled by `WEIGHT_PRECISION`
+        weight: u128,
+    },
+
+    /// Cast an ed25519 fallback vote with the operator's voter key, tallied separately from the
+    /// BLS aggregates
+    ///
+    /// The previous instruction of the transaction must be an ed25519 program instruction
+    /// verifying the voter's signature of the vote message
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "operator")]
+    #[account(3, name = "snapshot")]
+    #[account(4, name = "restaking_config")]
+    #[account(5, writable, name = "vote_counter")]
+    #[account(6, name = "instructions_sysvar")]
+    CastEd25519Vote {},
+
+    /// Cast several aggregated votes against the same snapshot, reverting if any fails
+    ///
+    /// Remaining accounts: the signers' `NcnOperatorState` accounts, grouped per vote in batch order
+    #[account(0, name = "config")]
+    #[account(1, name = "ncn")]
+    #[account(2, name = "snapshot")]
+    #[account(3, name = "restaking_config")]
+    #[account(4, writable, name = "vote_counter")]
+    CastVoteBatch {
+        votes: Vec<CastVoteBatchEntry>,
+    },
+}
+
+impl NCNProgramInstruction {
+    /// Every instruction name paired with its discriminator, in discriminator order
+    const VARIANTS: [(&'static str, u8); 28] = [
+        ("InitializeConfig", 0),
+        ("InitializeVaultRegistry", 1),
+        ("RegisterVault", 2),
+        ("RegisterOperator", 3),
+        ("UpdateOperatorBN128Keys", 4),
+        ("UpdateOperatorIpPort", 5),
+        ("InitializeVoteCounter", 6),
+        ("InitializeSnapshot", 7),
+        ("ReallocSnapshot", 8),
+        ("SnapshotVaultOperatorDelegation", 9),
+        ("CastVote", 10),
+        ("AdminSetParameters", 11),
+        ("AdminSetNewAdmin", 12),
+        ("AdminRegisterStMint", 13),
+        ("AdminRepairConfig", 14),
+        ("CloseSnapshot", 15),
+        ("DeregisterOperator", 16),
+        ("RefreshOperatorKey", 17),
+        ("RolloverSnapshot", 18),
+        ("ProveNonSigner", 19),
+        ("SnapshotVaultOperatorDelegationBatch", 20),
+        ("AdminUpdateSnapshotMinimumStake", 21),
+        ("BatchInitializeOperatorSnapshots", 22),
+        ("SetOperatorActive", 23),
+        ("AdminSetFeeWallet", 24),
+        ("AdminRegisterStMintWithWeight", 25),
+        ("CastEd25519Vote", 26),
+        ("CastVoteBatch", 27),
+    ];
+
+    /// The first byte of the serialized instruction data, identifying the instruction
+    pub const fn discriminator(&self) -> u8 {
+        match self {
+            Self::InitializeConfig { .. } => 0,
+            Self::InitializeVaultRegistry => 1,
+            Self::RegisterVault => 2,
+            Self::RegisterOperator { .. } => 3,
+            Self::UpdateOperatorBN128Keys { .. } => 4,
+            Self::UpdateOperatorIpPort { .. } => 5,
+            Self::InitializeVoteCounter => 6,
+            Self::InitializeSnapshot {} => 7,
+            Self::ReallocSnapshot {} => 8,
+            Self::SnapshotVaultOperatorDelegation {} => 9,
+            Self::CastVote { .. } => 10,
+            Self::AdminSetParameters { .. } => 11,
+            Self::AdminSetNewAdmin { .. } => 12,
+            Self::AdminRegisterStMint {} => 13,
+            Self::AdminRepairConfig { .. } => 14,
+            Self::CloseSnapshot {} => 15,
+            Self::DeregisterOperator {} => 16,
+            Self::RefreshOperatorKey {} => 17,
+            Self::RolloverSnapshot {} => 18,
+            Self::ProveNonSigner { .. } => 19,
+            Self::SnapshotVaultOperatorDelegationBatch { .. } => 20,
+            Self::AdminUpdateSnapshotMinimumStake { .. } => 21,
+            Self::BatchInitializeOperatorSnapshots { .. } => 22,
+            Self::SetOperatorActive { .. } => 23,
+            Self::AdminSetFeeWallet { .. } => 24,
+            Self::AdminRegisterStMintWithWeight { .. } => 25,
+            Self::CastEd25519Vote {} => 26,
+            Self::CastVoteBatch { .. } => 27,
+        }
+    }
+
+    /// Names and discriminators of all supported instructions, for SDK generation
+    pub const fn all_variants() -> &'static [(&'static str, u8)] {
+        &Self::VARIANTS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use borsh::BorshSerialize;
+
+    use super::*;
+
+    fn sample_instructions() -> Vec<NCNProgramInstruction> {
+        vec![
+            NCNProgramInstruction::InitializeConfig {
+                epochs_before_stall: 3,
+                epochs_after_consensus_before_close: 10,
+                valid_slots_after_consensus: 10000,
+                minimum_stake: 100,
+                ncn_fee_bps: 400,
+                curve_id: None,
+                minimum_operators: None,
+                quorum_bps: None,
+                max_snapshot_age_slots: None,
+            },
+            NCNProgramInstruction::InitializeVaultRegistry,
+            NCNProgramInstruction::RegisterVault,
+            NCNProgramInstruction::RegisterOperator {
+                g1_pubkey: [0; 32],
+                g2_pubkey: [0; 64],
+                signature: [0; 64],
+            },
+            NCNProgramInstruction::UpdateOperatorBN128Keys {
+                g1_pubkey: [0; 32],
+                g2_pubkey: [0; 64],
+                signature: [0; 64],
+            },
+            NCNProgramInstruction::UpdateOperatorIpPort {
+                ip_address: [127, 0, 0, 1],
+                port: 8080,
+            },
+            NCNProgramInstruction::InitializeVoteCounter,
+            NCNProgramInstruction::InitializeSnapshot {},
+            NCNProgramInstruction::ReallocSnapshot {},
+            NCNProgramInstruction::SnapshotVaultOperatorDelegation {},
+            NCNProgramInstruction::CastVote {
+                aggregated_signature: [0; 32],
+                aggregated_g2: [0; 64],
+                operators_signature_bitmap: vec![0xff],
+                quorum_bps: None,
+            },
+            NCNProgramInstruction::AdminSetParameters {
+                starting_valid_epoch: None,
+                epochs_before_stall: None,
+                epochs_after_consensus_before_close: None,
+                valid_slots_after_consensus: None,
+                minimum_stake: None,
+                enforce_signer_order: None,
+                minimum_operators: None,
+                reject_duplicate_aggregates: None,
+                allow_multiple_votes_per_epoch: None,
+                quorum_bps: None,
+                max_snapshot_age_slots: None,
+                allow_ed25519_votes: None,
+            },
+            NCNProgramInstruction::AdminSetNewAdmin {
+                role: ConfigAdminRole::TieBreakerAdmin,
+            },
+            NCNProgramInstruction::AdminRegisterStMint {},
+            NCNProgramInstruction::AdminRepairConfig {
+                epochs_before_stall: 3,
+                epochs_after_consensus_before_close: 10,
+                valid_slots_after_consensus: 10000,
+                minimum_stake: 100,
+                ncn_fee_bps: 400,
+            },
+            NCNProgramInstruction::CloseSnapshot {},
+            NCNProgramInstruction::DeregisterOperator {},
+            NCNProgramInstruction::RefreshOperatorKey {},
+            NCNProgramInstruction::RolloverSnapshot {},
+            NCNProgramInstruction::ProveNonSigner {
+                aggregated_signature: [0; 32],
+                aggregated_g2: [0; 64],
+                operators_signature_bitmap: vec![0xfe],
+            },
+            NCNProgramInstruction::SnapshotVaultOperatorDelegationBatch {
+                operator_indices: vec![0, 1],
+            },
+            NCNProgramInstruction::AdminUpdateSnapshotMinimumStake { minimum_stake: 100 },
+            NCNProgramInstruction::BatchInitializeOperatorSnapshots {
+                operators: vec![OperatorSnapshotEntry {
+                    operator: Pubkey::new_unique(),
+                    g1_pubkey: [0; 32],
+                    g2_pubkey: [0; 64],
+                    signature: [0; 64],
+                    ncn_operator_index: 0,
+                }],
+            },
+            NCNProgramInstruction::SetOperatorActive { active: false },
+            NCNProgramInstruction::AdminSetFeeWallet {
+                new_wallet: Pubkey::new_unique(),
+            },
+            NCNProgramInstruction::AdminRegisterStMintWithWeight {
+                weight: 2_000_000_000,
+            },
+            NCNProgramInstruction::CastEd25519Vote {},
+            NCNProgramInstruction::CastVoteBatch { votes: vec![] },
+        ]
+    }
+
+    #[test]
+    fn test_discriminators_are_stable_and_unique() {
+        let instructions = sample_instructions();
+        let variants = NCNProgramInstruction::all_variants();
+        assert_eq!(instructions.len(), variants.len());
+
+        let mut seen = HashSet::new();
+        for (instruction, (name, discriminator)) in instructions.iter().zip(variants) {
+            assert!(
+                seen.insert(*discriminator),
+                "duplicate discriminator {}",
+                discriminator
+            );
+            assert_eq!(instruction.discriminator(), *discriminator, "{}", name);
+
+            // The discriminator must match what is actually sent on the wire
+            let data = instruction.try_to_vec().unwrap();
+            assert_eq!(data[0], *discriminator, "{}", name);
+
+            // Names follow the Debug representation of the variant
+            assert!(format!("{:?}", instruction).starts_with(name), "{}", name);
+        }
+    }
 }
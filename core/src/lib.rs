@@ -1,16 +1,19 @@
 #![allow(unexpected_cfgs)]
 
 pub mod account_payer;
+pub mod aggregation;
 pub mod config;
 pub mod constants;
 pub mod discriminators;
 pub mod error;
+pub mod events;
 pub mod fees;
 pub mod g1_point;
 pub mod g2_point;
 pub mod instruction;
 pub mod loaders;
 pub mod ncn_operator_account;
+pub mod operator_merkle;
 pub mod privkey;
 pub mod schemes;
 pub mod snapshot;
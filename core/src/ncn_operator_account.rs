@@ -154,6 +154,8 @@ impl NCNOperatorAccount {
         &self.g2_pubkey
     }
 
+    /// The operator's slot in the snapshot and its bit in vote signer bitmaps, assigned on
+    /// registration
     pub fn ncn_operator_index(&self) -> u64 {
         self.ncn_operator_index.into()
     }
@@ -0,0 +1,331 @@
+use num::CheckedAdd;
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    constants::{G1_COMPRESSED_POINT_SIZE, G1_POINT_SIZE},
+    error::NCNProgramError,
+    g1_point::{G1CompressedPoint, G1Point},
+    g2_point::G2Point,
+    schemes::HashToCurve,
+    snapshot::{OperatorSnapshot, Snapshot},
+};
+
+/// Domain separators so a leaf can never be passed off as an inner node
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// An operator as committed to by the operator Merkle root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorLeaf {
+    pub operator: Pubkey,
+    pub g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+    pub stake_weight: u128,
+}
+
+impl OperatorLeaf {
+    pub fn hash(&self) -> [u8; 32] {
+        solana_nostd_sha256::hashv(&[
+            LEAF_PREFIX,
+            self.operator.as_ref(),
+            &self.g1_pubkey,
+            &self.stake_weight.to_le_bytes(),
+        ])
+    }
+}
+
+impl From<&OperatorSnapshot> for OperatorLeaf {
+    fn from(operator_snapshot: &OperatorSnapshot) -> Self {
+        Self {
+            operator: *operator_snapshot.operator(),
+            g1_pubkey: operator_snapshot.g1_pubkey(),
+            stake_weight: operator_snapshot.stake_weight().stake_weight(),
+        }
+    }
+}
+
+/// A signer's individual signature together with the proof that the signer is in the operator
+/// Merkle root
+#[derive(Debug, Clone)]
+pub struct ProvenSignature {
+    pub leaf: OperatorLeaf,
+    /// The signer's index among the leaves, which is its snapshot index
+    pub index: u64,
+    /// Sibling hashes from the leaf up to the root
+    pub proof: Vec<[u8; 32]>,
+    pub signature: G1Point,
+}
+
+fn hash_nodes(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    solana_nostd_sha256::hashv(&[NODE_PREFIX, left, right])
+}
+
+/// Every level of the tree over `leaves`, leaves first. A level with an odd number of nodes pairs
+/// its last node with itself.
+fn tree_levels(leaves: &[OperatorLeaf]) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves.iter().map(OperatorLeaf::hash).collect::<Vec<_>>()];
+
+    while levels.last().is_some_and(|level| level.len() > 1) {
+        let next_level = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| hash_nodes(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+        levels.push(next_level);
+    }
+
+    levels
+}
+
+/// The registered operators of `snapshot` as Merkle leaves, in snapshot index order
+pub fn operator_leaves(snapshot: &Snapshot) -> Vec<OperatorLeaf> {
+    snapshot
+        .registered_operator_snapshots()
+        .map(|(_, operator_snapshot)| OperatorLeaf::from(operator_snapshot))
+        .collect()
+}
+
+/// The Merkle root over `leaves`, `None` when there are no leaves
+pub fn operator_merkle_root(leaves: &[OperatorLeaf]) -> Option<[u8; 32]> {
+    if leaves.is_empty() {
+        return None;
+    }
+
+    tree_levels(leaves).last().map(|level| level[0])
+}
+
+/// The membership proof of the leaf at `index`, `None` when `index` is out of range
+pub fn operator_merkle_proof(leaves: &[OperatorLeaf], index: usize) -> Option<Vec<[u8; 32]>> {
+    if index >= leaves.len() {
+        return None;
+    }
+
+    let levels = tree_levels(leaves);
+    let mut position = index;
+    let mut proof = Vec::with_capacity(levels.len() - 1);
+
+    for level in &levels[..levels.len() - 1] {
+        let sibling = position ^ 1;
+        proof.push(*level.get(sibling).unwrap_or(&level[position]));
+        position /= 2;
+    }
+
+    Some(proof)
+}
+
+/// Whether `proof` shows `leaf` sits at `index` under `root`
+pub fn verify_operator_proof(
+    root: &[u8; 32],
+    leaf: &OperatorLeaf,
+    index: u64,
+    proof: &[[u8; 32]],
+) -> bool {
+    let mut position = index;
+    let mut hash = leaf.hash();
+
+    for sibling in proof {
+        hash = if position % 2 == 0 {
+            hash_nodes(&hash, sibling)
+        } else {
+            hash_nodes(sibling, &hash)
+        };
+        position /= 2;
+    }
+
+    position == 0 && hash == *root
+}
+
+/// Verifies a vote statelessly from the operator Merkle root instead of the snapshot account.
+///
+/// Every signer must prove its membership under `root`, appear once and the non-signers must
+/// stay within a third of `total_operators`, as in `CastVote`. The individual signatures and the
+/// signers' G1 pubkeys are aggregated and checked against `aggregated_g2` with one pairing.
+///
+/// Returns the signers' total stake weight.
+pub fn verify_vote_with_proofs<H: HashToCurve>(
+    root: &[u8; 32],
+    total_operators: u64,
+    signers: &[ProvenSignature],
+    aggregated_g2: G2Point,
+    message: &[u8; 32],
+) -> Result<u128, NCNProgramError> {
+    if signers.is_empty() || signers.len() as u64 > total_operators {
+        return Err(NCNProgramError::InvalidInputLength);
+    }
+
+    let mut apk1 = G1Point([0u8; G1_POINT_SIZE]);
+    let mut aggregated_signature = G1Point([0u8; G1_POINT_SIZE]);
+    let mut signer_stake_weight: u128 = 0;
+
+    for (i, signer) in signers.iter().enumerate() {
+        if signer.index >= total_operators
+            || !verify_operator_proof(root, &signer.leaf, signer.index, &signer.proof)
+        {
+            return Err(NCNProgramError::InvalidMerkleProof);
+        }
+
+        if signers[..i].iter().any(|other| other.index == signer.index) {
+            return Err(NCNProgramError::DuplicateVoteCast);
+        }
+
+        let g1_pubkey = G1Point::try_from(&G1CompressedPoint(signer.leaf.g1_pubkey))?;
+        apk1 = apk1
+            .checked_add(&g1_pubkey)
+            .ok_or(NCNProgramError::AltBN128AddError)?;
+        aggregated_signature = aggregated_signature
+            .checked_add(&signer.signature)
+            .ok_or(NCNProgramError::AltBN128AddError)?;
+        signer_stake_weight = signer_stake_weight
+            .checked_add(signer.leaf.stake_weight)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?;
+    }
+
+    let non_signers_count = total_operators - signers.len() as u64;
+    if non_signers_count > total_operators / 3 {
+        return Err(NCNProgramError::QuorumNotMet);
+    }
+
+    aggregated_g2.verify_aggregated_signature::<H, &[u8; 32], G1Point>(
+        aggregated_signature,
+        message,
+        apk1,
+    )?;
+
+    Ok(signer_stake_weight)
+}
+
+#[cfg(all(test, not(target_os = "solana")))]
+mod tests {
+    use crate::{privkey::PrivKey, schemes::Sha256Normalized};
+
+    use super::*;
+
+    fn operators(count: usize) -> (Vec<PrivKey>, Vec<OperatorLeaf>) {
+        let privkeys: Vec<PrivKey> = (0..count).map(|_| PrivKey::from_random()).collect();
+        let leaves = privkeys
+            .iter()
+            .enumerate()
+            .map(|(i, privkey)| OperatorLeaf {
+                operator: Pubkey::new_unique(),
+                g1_pubkey: G1CompressedPoint::try_from(*privkey).unwrap().0,
+                stake_weight: 100 * (i as u128 + 1),
+            })
+            .collect();
+
+        (privkeys, leaves)
+    }
+
+    fn proven_signature(
+        privkeys: &[PrivKey],
+        leaves: &[OperatorLeaf],
+        index: usize,
+        message: &[u8; 32],
+    ) -> ProvenSignature {
+        ProvenSignature {
+            leaf: leaves[index],
+            index: index as u64,
+            proof: operator_merkle_proof(leaves, index).unwrap(),
+            signature: privkeys[index]
+                .sign::<Sha256Normalized, &[u8; 32]>(message)
+                .unwrap(),
+        }
+    }
+
+    fn aggregated_g2(privkeys: &[PrivKey], indices: &[usize]) -> G2Point {
+        indices
+            .iter()
+            .map(|&i| G2Point::try_from(&privkeys[i]).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_operator_merkle_proofs() {
+        for count in [1, 2, 3, 5, 8] {
+            let (_, leaves) = operators(count);
+            let root = operator_merkle_root(&leaves).unwrap();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = operator_merkle_proof(&leaves, index).unwrap();
+                assert!(verify_operator_proof(&root, leaf, index as u64, &proof));
+
+                // The same leaf does not verify at another index
+                let other_index = (index as u64 + 1) % count as u64;
+                if other_index != index as u64 {
+                    assert!(!verify_operator_proof(&root, leaf, other_index, &proof));
+                }
+            }
+
+            assert!(operator_merkle_proof(&leaves, count).is_none());
+        }
+
+        assert!(operator_merkle_root(&[]).is_none());
+    }
+
+    #[test]
+    fn test_verify_vote_with_proofs() {
+        let (privkeys, leaves) = operators(3);
+        let root = operator_merkle_root(&leaves).unwrap();
+        let message = [3u8; 32];
+
+        let signers = vec![
+            proven_signature(&privkeys, &leaves, 0, &message),
+            proven_signature(&privkeys, &leaves, 2, &message),
+        ];
+
+        let signer_stake_weight = verify_vote_with_proofs::<Sha256Normalized>(
+            &root,
+            3,
+            &signers,
+            aggregated_g2(&privkeys, &[0, 2]),
+            &message,
+        )
+        .unwrap();
+        assert_eq!(signer_stake_weight, 100 + 300);
+    }
+
+    #[test]
+    fn test_verify_vote_with_proofs_fails() {
+        let (privkeys, leaves) = operators(3);
+        let root = operator_merkle_root(&leaves).unwrap();
+        let message = [3u8; 32];
+        let apk2 = aggregated_g2(&privkeys, &[0, 2]);
+        let signers = vec![
+            proven_signature(&privkeys, &leaves, 0, &message),
+            proven_signature(&privkeys, &leaves, 2, &message),
+        ];
+
+        // A leaf claiming more stake than was committed to
+        let mut inflated = signers.clone();
+        inflated[1].leaf.stake_weight += 1;
+        assert_eq!(
+            verify_vote_with_proofs::<Sha256Normalized>(&root, 3, &inflated, apk2, &message),
+            Err(NCNProgramError::InvalidMerkleProof)
+        );
+
+        // The same signer counted twice
+        let duplicate = vec![signers[0].clone(), signers[0].clone()];
+        assert_eq!(
+            verify_vote_with_proofs::<Sha256Normalized>(&root, 3, &duplicate, apk2, &message),
+            Err(NCNProgramError::DuplicateVoteCast)
+        );
+
+        // One signer out of three misses the quorum
+        assert_eq!(
+            verify_vote_with_proofs::<Sha256Normalized>(
+                &root,
+                3,
+                &signers[..1],
+                aggregated_g2(&privkeys, &[0]),
+                &message
+            ),
+            Err(NCNProgramError::QuorumNotMet)
+        );
+
+        // Signatures over another message
+        assert_eq!(
+            verify_vote_with_proofs::<Sha256Normalized>(&root, 3, &signers, apk2, &[4u8; 32]),
+            Err(NCNProgramError::BLSVerificationError)
+        );
+    }
+}
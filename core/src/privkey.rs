@@ -1,9 +1,13 @@
 #[cfg(not(target_os = "solana"))]
-use rand::RngCore;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 
 use solana_bn254::prelude::alt_bn128_multiplication;
 
-use crate::{error::NCNProgramError, g1_point::G1Point, schemes::HashToCurve};
+use crate::{
+    error::NCNProgramError,
+    g1_point::G1Point,
+    schemes::{HashToCurve, Sha256Normalized, Sha256NormalizedScheme},
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct PrivKey(pub [u8; 32]);
@@ -11,12 +15,23 @@ pub struct PrivKey(pub [u8; 32]);
 impl PrivKey {
     #[cfg(not(target_os = "solana"))]
     pub fn from_random() -> PrivKey {
+        Self::from_rng(&mut rand::thread_rng())
+    }
+
+    /// Deterministically derives a private key from `seed`, for reproducible keys in tests
+    #[cfg(not(target_os = "solana"))]
+    pub fn from_seed(seed: u64) -> PrivKey {
+        Self::from_rng(&mut StdRng::seed_from_u64(seed))
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    fn from_rng<R: RngCore>(rng: &mut R) -> PrivKey {
         use crate::constants::MODULUS;
 
         loop {
             let mut bytes = [0u8; 32];
 
-            rand::thread_rng().fill_bytes(&mut bytes);
+            rng.fill_bytes(&mut bytes);
 
             let num = dashu::integer::UBig::from_be_bytes(&bytes);
 
@@ -41,6 +56,16 @@ impl PrivKey {
 
         Ok(G1Point(g1_sol_uncompressed))
     }
+
+    /// Signs `message` under the domain separation tag of `scheme`, e.g. a vote under
+    /// `Sha256NormalizedScheme::for_ncn`
+    pub fn sign_with_scheme<T: AsRef<[u8]>>(
+        &self,
+        scheme: &Sha256NormalizedScheme,
+        message: T,
+    ) -> Result<G1Point, NCNProgramError> {
+        self.sign::<Sha256Normalized, &[u8]>(&scheme.domain_separated_message(message.as_ref())?)
+    }
 }
 
 #[cfg(all(test, not(target_os = "solana")))]
@@ -0,0 +1,235 @@
+//! # Ed25519 fallback votes
+//!
+//! Operators can vote with the ed25519 key of their jito `Operator` voter instead of their BN254
+//! key. The signature is not verified by this program: the transaction must carry an instruction
+//! of the native ed25519 program right before `CastEd25519Vote`, which fails the whole
+//! transaction on an invalid signature. The program only checks that this instruction signs the
+//! expected message with the expected key.
+//!
+//! ed25519 keys can't be folded into the snapshot's aggregated G1 pubkey, so this is not a
+//! `HashToCurve` scheme and ed25519 votes are tallied separately from the BLS aggregates.
+
+use solana_program::{ed25519_program, instruction::Instruction, pubkey::Pubkey};
+
+use crate::error::NCNProgramError;
+
+pub const ED25519_PUBKEY_SIZE: usize = 32;
+pub const ED25519_SIGNATURE_SIZE: usize = 64;
+
+/// `num_signatures` and a padding byte
+const SIGNATURE_OFFSETS_START: usize = 2;
+/// Seven little endian u16 offsets per signature
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const DATA_START: usize = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE;
+const PUBKEY_OFFSET: usize = DATA_START;
+const SIGNATURE_OFFSET: usize = PUBKEY_OFFSET + ED25519_PUBKEY_SIZE;
+const MESSAGE_OFFSET: usize = SIGNATURE_OFFSET + ED25519_SIGNATURE_SIZE;
+
+/// Instruction index meaning "the ed25519 instruction itself"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Where the ed25519 program reads the signature, pubkey and message of one signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, NCNProgramError> {
+        let read_u16 = |i: usize| -> Result<u16, NCNProgramError> {
+            bytes
+                .get(i * 2..i * 2 + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+                .ok_or(NCNProgramError::InvalidEd25519Instruction)
+        };
+
+        Ok(Self {
+            signature_offset: read_u16(0)?,
+            signature_instruction_index: read_u16(1)?,
+            public_key_offset: read_u16(2)?,
+            public_key_instruction_index: read_u16(3)?,
+            message_data_offset: read_u16(4)?,
+            message_data_size: read_u16(5)?,
+            message_instruction_index: read_u16(6)?,
+        })
+    }
+
+    fn to_bytes(self) -> [u8; SIGNATURE_OFFSETS_SIZE] {
+        let mut bytes = [0u8; SIGNATURE_OFFSETS_SIZE];
+        for (i, value) in [
+            self.signature_offset,
+            self.signature_instruction_index,
+            self.public_key_offset,
+            self.public_key_instruction_index,
+            self.message_data_offset,
+            self.message_data_size,
+            self.message_instruction_index,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            bytes[i * 2..i * 2 + 2].copy_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Whether every offset points into the ed25519 instruction itself, so its data alone
+    /// determines what was verified
+    fn is_self_contained(&self) -> bool {
+        self.signature_instruction_index == CURRENT_INSTRUCTION
+            && self.public_key_instruction_index == CURRENT_INSTRUCTION
+            && self.message_instruction_index == CURRENT_INSTRUCTION
+    }
+}
+
+/// Builds an ed25519 program instruction verifying `signature` of `message` by `pubkey`, with
+/// the same layout as the one built by the Solana SDK
+pub fn new_ed25519_instruction(
+    pubkey: &Pubkey,
+    signature: &[u8; ED25519_SIGNATURE_SIZE],
+    message: &[u8],
+) -> Instruction {
+    let offsets = Ed25519SignatureOffsets {
+        signature_offset: SIGNATURE_OFFSET as u16,
+        signature_instruction_index: CURRENT_INSTRUCTION,
+        public_key_offset: PUBKEY_OFFSET as u16,
+        public_key_instruction_index: CURRENT_INSTRUCTION,
+        message_data_offset: MESSAGE_OFFSET as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: CURRENT_INSTRUCTION,
+    };
+
+    let mut data = Vec::with_capacity(MESSAGE_OFFSET + message.len());
+    data.extend_from_slice(&[1, 0]);
+    data.extend_from_slice(&offsets.to_bytes());
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Checks that the data of an ed25519 program instruction verifies exactly one signature, by
+/// `pubkey` over `message`, read from the instruction itself. Fails with
+/// `InvalidEd25519Instruction` otherwise
+pub fn check_ed25519_instruction_data(
+    data: &[u8],
+    pubkey: &Pubkey,
+    message: &[u8],
+) -> Result<(), NCNProgramError> {
+    if data.first() != Some(&1) {
+        return Err(NCNProgramError::InvalidEd25519Instruction);
+    }
+
+    let offsets = Ed25519SignatureOffsets::from_bytes(
+        data.get(SIGNATURE_OFFSETS_START..DATA_START)
+            .ok_or(NCNProgramError::InvalidEd25519Instruction)?,
+    )?;
+    if !offsets.is_self_contained() {
+        return Err(NCNProgramError::InvalidEd25519Instruction);
+    }
+
+    let read = |offset: u16, size: usize| {
+        let start = offset as usize;
+        data.get(start..start + size)
+            .ok_or(NCNProgramError::InvalidEd25519Instruction)
+    };
+
+    if read(offsets.public_key_offset, ED25519_PUBKEY_SIZE)? != pubkey.as_ref()
+        || read(
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )? != message
+    {
+        return Err(NCNProgramError::InvalidEd25519Instruction);
+    }
+
+    // The signature must be in bounds, the ed25519 program checks it against the pubkey
+    read(offsets.signature_offset, ED25519_SIGNATURE_SIZE)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_ed25519_instruction_layout() {
+        let pubkey = Pubkey::new_unique();
+        let signature = [7u8; ED25519_SIGNATURE_SIZE];
+        let message = [9u8; 32];
+
+        let ix = new_ed25519_instruction(&pubkey, &signature, &message);
+
+        assert_eq!(ix.program_id, ed25519_program::id());
+        assert!(ix.accounts.is_empty());
+        assert_eq!(ix.data.len(), MESSAGE_OFFSET + message.len());
+        assert_eq!(&ix.data[..2], &[1, 0]);
+        assert_eq!(&ix.data[PUBKEY_OFFSET..SIGNATURE_OFFSET], pubkey.as_ref());
+        assert_eq!(&ix.data[SIGNATURE_OFFSET..MESSAGE_OFFSET], &signature);
+        assert_eq!(&ix.data[MESSAGE_OFFSET..], &message);
+    }
+
+    #[test]
+    fn test_check_ed25519_instruction_data() {
+        let pubkey = Pubkey::new_unique();
+        let message = [9u8; 32];
+        let ix = new_ed25519_instruction(&pubkey, &[7u8; ED25519_SIGNATURE_SIZE], &message);
+
+        assert_eq!(
+            check_ed25519_instruction_data(&ix.data, &pubkey, &message),
+            Ok(())
+        );
+        assert_eq!(
+            check_ed25519_instruction_data(&ix.data, &Pubkey::new_unique(), &message),
+            Err(NCNProgramError::InvalidEd25519Instruction)
+        );
+        assert_eq!(
+            check_ed25519_instruction_data(&ix.data, &pubkey, &[8u8; 32]),
+            Err(NCNProgramError::InvalidEd25519Instruction)
+        );
+        assert_eq!(
+            check_ed25519_instruction_data(&ix.data[..MESSAGE_OFFSET], &pubkey, &message),
+            Err(NCNProgramError::InvalidEd25519Instruction)
+        );
+    }
+
+    #[test]
+    fn test_check_ed25519_instruction_data_rejects_other_layouts() {
+        let pubkey = Pubkey::new_unique();
+        let message = [9u8; 32];
+        let ix = new_ed25519_instruction(&pubkey, &[7u8; ED25519_SIGNATURE_SIZE], &message);
+
+        // Two signatures
+        let mut data = ix.data.clone();
+        data[0] = 2;
+        assert_eq!(
+            check_ed25519_instruction_data(&data, &pubkey, &message),
+            Err(NCNProgramError::InvalidEd25519Instruction)
+        );
+
+        // Pubkey read from another instruction of the transaction
+        let mut data = ix.data.clone();
+        data[8..10].copy_from_slice(&0u16.to_le_bytes());
+        assert_eq!(
+            check_ed25519_instruction_data(&data, &pubkey, &message),
+            Err(NCNProgramError::InvalidEd25519Instruction)
+        );
+
+        assert_eq!(
+            check_ed25519_instruction_data(&[], &pubkey, &message),
+            Err(NCNProgramError::InvalidEd25519Instruction)
+        );
+    }
+}
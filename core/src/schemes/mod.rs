@@ -6,3 +6,5 @@ pub use sha256::*;
 
 pub mod sha256_normalized;
 pub use sha256_normalized::*;
+
+pub mod ed25519;
@@ -1,7 +1,8 @@
 use dashu::integer::UBig;
 use solana_bn254::compression::prelude::alt_bn128_g1_decompress;
+use solana_program::pubkey::Pubkey;
 
-use crate::{constants::MODULUS, error::NCNProgramError, g1_point::G1Point};
+use crate::{constants::MODULUS, error::NCNProgramError, g1_point::G1Point, g2_point::G2Point};
 
 use super::HashToCurve;
 
@@ -46,3 +47,147 @@ impl HashToCurve for Sha256Normalized {
             .ok_or(NCNProgramError::HashToCurveError)
     }
 }
+
+/// `Sha256Normalized` under a domain separation tag (DST), so a signature made in one domain,
+/// such as one NCN, never verifies in another. The tag and its length are prepended to the
+/// message before it is hashed to the curve. The default empty tag leaves messages unchanged,
+/// which keeps signatures made with plain `Sha256Normalized` valid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sha256NormalizedScheme<'a> {
+    dst: &'a [u8],
+}
+
+impl<'a> Sha256NormalizedScheme<'a> {
+    pub const fn with_dst(dst: &'a [u8]) -> Self {
+        Self { dst }
+    }
+
+    /// The scheme separated by the NCN pubkey, so operators shared between NCNs cannot have
+    /// their signatures reused across them
+    pub fn for_ncn(ncn: &'a Pubkey) -> Self {
+        Self::with_dst(ncn.as_ref())
+    }
+
+    pub const fn dst(&self) -> &'a [u8] {
+        self.dst
+    }
+
+    /// The bytes actually hashed to the curve for `message`, to sign or verify with
+    /// `Sha256Normalized`
+    pub fn domain_separated_message(&self, message: &[u8]) -> Result<Vec<u8>, NCNProgramError> {
+        if self.dst.is_empty() {
+            return Ok(message.to_vec());
+        }
+
+        let dst_len =
+            u8::try_from(self.dst.len()).map_err(|_| NCNProgramError::InvalidInputLength)?;
+
+        Ok([&[dst_len], self.dst, message].concat())
+    }
+
+    pub fn try_hash_to_curve<T: AsRef<[u8]>>(
+        &self,
+        message: T,
+    ) -> Result<G1Point, NCNProgramError> {
+        Sha256Normalized::try_hash_to_curve(self.domain_separated_message(message.as_ref())?)
+    }
+
+    /// `HashToCurve::verify_aggregate` under this scheme's tag: `Ok(false)` when the pairing
+    /// check fails, so clients can pre-flight a vote
+    pub fn verify_aggregate(
+        &self,
+        apk1: &G1Point,
+        apk2: &G2Point,
+        message: &[u8; 32],
+        signature: &G1Point,
+    ) -> Result<bool, NCNProgramError> {
+        let message = self.domain_separated_message(message)?;
+
+        match apk2.verify_aggregated_signature::<Sha256Normalized, &[u8], G1Point>(
+            *signature, &message, *apk1,
+        ) {
+            Ok(()) => Ok(true),
+            Err(NCNProgramError::BLSVerificationError) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(all(test, not(target_os = "solana")))]
+mod test {
+    use crate::{g2_point::G2Point, privkey::PrivKey};
+
+    use super::*;
+
+    #[test]
+    fn test_default_dst_matches_sha256_normalized() {
+        let message = [7u8; 32];
+
+        assert_eq!(
+            Sha256NormalizedScheme::default()
+                .try_hash_to_curve(message)
+                .unwrap()
+                .0,
+            Sha256Normalized::try_hash_to_curve(message).unwrap().0
+        );
+    }
+
+    #[test]
+    fn test_signature_fails_under_another_dst() {
+        let privkey = PrivKey::from_random();
+        let apk1 = G1Point::try_from(privkey).unwrap();
+        let apk2 = G2Point::try_from(&privkey).unwrap();
+        let message = [7u8; 32];
+
+        let ncn = Pubkey::new_unique();
+        let other_ncn = Pubkey::new_unique();
+        let scheme = Sha256NormalizedScheme::for_ncn(&ncn);
+        let other_scheme = Sha256NormalizedScheme::for_ncn(&other_ncn);
+
+        let separated_message = scheme.domain_separated_message(&message).unwrap();
+        let signature = privkey
+            .sign::<Sha256Normalized, &[u8]>(&separated_message)
+            .unwrap();
+
+        // Valid in its own domain
+        assert!(apk2
+            .verify_aggregated_signature::<Sha256Normalized, &[u8], G1Point>(
+                signature,
+                &separated_message,
+                apk1,
+            )
+            .is_ok());
+
+        // Invalid under another NCN's tag and under the default tag
+        for other_message in [
+            other_scheme.domain_separated_message(&message).unwrap(),
+            Sha256NormalizedScheme::default()
+                .domain_separated_message(&message)
+                .unwrap(),
+        ] {
+            assert_eq!(
+                apk2.verify_aggregated_signature::<Sha256Normalized, &[u8], G1Point>(
+                    signature,
+                    &other_message,
+                    apk1,
+                ),
+                Err(NCNProgramError::BLSVerificationError)
+            );
+        }
+
+        // Signing and pre-flighting under the scheme agree with the above
+        let signature = privkey.sign_with_scheme(&scheme, message).unwrap();
+        assert!(scheme
+            .verify_aggregate(&apk1, &apk2, &message, &signature)
+            .unwrap());
+        assert!(!other_scheme
+            .verify_aggregate(&apk1, &apk2, &message, &signature)
+            .unwrap());
+
+        // Tags longer than their length prefix can express are rejected
+        assert_eq!(
+            Sha256NormalizedScheme::with_dst(&[0; 256]).domain_separated_message(&message),
+            Err(NCNProgramError::InvalidInputLength)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{error::NCNProgramError, g1_point::G1Point};
+use crate::{error::NCNProgramError, g1_point::G1Point, g2_point::G2Point};
 
 pub trait HashToCurve {
     /// # Try Hash To Curve
@@ -12,9 +12,105 @@ pub trait HashToCurve {
     /// - Hash scalar normalization
     /// - Domain separation
     fn try_hash_to_curve<T: AsRef<[u8]>>(message: T) -> Result<G1Point, NCNProgramError>;
+
+    /// # Verify Aggregate
+    ///
+    /// Checks an aggregated signature against the aggregated G1 and G2 public keys of its signers
+    /// using this scheme's hash-to-curve, the same pairing check `CastVote` performs on-chain.
+    ///
+    /// Returns `Ok(false)` when the pairing check fails, so clients can pre-flight a vote before
+    /// submitting it. Errors are reserved for inputs that cannot be hashed or paired at all.
+    fn verify_aggregate(
+        &self,
+        apk1: &G1Point,
+        apk2: &G2Point,
+        message: &[u8; 32],
+        signature: &G1Point,
+    ) -> Result<bool, NCNProgramError>
+    where
+        Self: Sized,
+    {
+        match apk2
+            .verify_aggregated_signature::<Self, &[u8; 32], G1Point>(*signature, message, *apk1)
+        {
+            Ok(()) => Ok(true),
+            Err(NCNProgramError::BLSVerificationError) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 // Trait to represent any type that can be used as a BLS signature
 pub trait BLSSignature {
     fn to_bytes(&self) -> Result<[u8; 64], NCNProgramError>;
 }
+
+#[cfg(all(test, not(target_os = "solana")))]
+mod test {
+    use crate::{
+        g1_point::G1Point,
+        g2_point::G2Point,
+        privkey::PrivKey,
+        schemes::{Sha256, Sha256Normalized},
+    };
+
+    use super::HashToCurve;
+
+    /// Aggregated G1 pubkey, G2 pubkey and signature of three random signers over `message`
+    fn aggregate<H: HashToCurve>(message: &[u8; 32]) -> (G1Point, G2Point, G1Point) {
+        let privkeys: Vec<PrivKey> = (0..3).map(|_| PrivKey::from_random()).collect();
+
+        let apk1 = privkeys
+            .iter()
+            .map(|privkey| G1Point::try_from(*privkey).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+        let apk2 = privkeys
+            .iter()
+            .map(|privkey| G2Point::try_from(privkey).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+        let signature = privkeys
+            .iter()
+            .map(|privkey| privkey.sign::<H, &[u8; 32]>(message).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+
+        (apk1, apk2, signature)
+    }
+
+    #[test]
+    fn verify_aggregate_sha256_normalized() {
+        let message = [7u8; 32];
+        let (apk1, apk2, signature) = aggregate::<Sha256Normalized>(&message);
+
+        assert!(Sha256Normalized
+            .verify_aggregate(&apk1, &apk2, &message, &signature)
+            .unwrap());
+
+        // A signature carrying an extra signer the public keys don't account for must not verify
+        let tampered_signature = signature
+            + PrivKey::from_random()
+                .sign::<Sha256Normalized, _>(message)
+                .unwrap();
+        assert!(!Sha256Normalized
+            .verify_aggregate(&apk1, &apk2, &message, &tampered_signature)
+            .unwrap());
+    }
+
+    #[test]
+    fn verify_aggregate_sha256() {
+        let message = [7u8; 32];
+        let (apk1, apk2, signature) = aggregate::<Sha256>(&message);
+
+        assert!(Sha256
+            .verify_aggregate(&apk1, &apk2, &message, &signature)
+            .unwrap());
+
+        let tampered_signature =
+            signature + PrivKey::from_random().sign::<Sha256, _>(message).unwrap();
+        assert!(!Sha256
+            .verify_aggregate(&apk1, &apk2, &message, &tampered_signature)
+            .unwrap());
+    }
+}
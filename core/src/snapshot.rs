@@ -1,24 +1,32 @@
 use core::fmt;
 use std::mem::size_of;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use bytemuck::{Pod, Zeroable};
 use jito_bytemuck::{
     types::{PodBool, PodU64},
     AccountDeserialize, Discriminator,
 };
 use jito_vault_core::vault_operator_delegation::VaultOperatorDelegation;
+use num::CheckedAdd;
 use shank::{ShankAccount, ShankType};
 use solana_bn254::compression::prelude::alt_bn128_g1_decompress;
 use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
 use spl_math::precise_number::PreciseNumber;
 
 use crate::{
-    constants::{G1_COMPRESSED_POINT_SIZE, MAX_OPERATORS, MAX_VAULTS},
+    constants::{
+        G1_COMPRESSED_POINT_SIZE, G1_POINT_SIZE, G2_COMPRESSED_POINT_SIZE, MAX_FEE_BPS,
+        MAX_OPERATORS, MAX_SIGNER_BITMAP_BYTES, MAX_VAULTS, WEIGHT_PRECISION,
+    },
     discriminators::Discriminators,
     error::NCNProgramError,
     g1_point::{G1CompressedPoint, G1Point},
+    g2_point::{G2CompressedPoint, G2Point},
     loaders::check_load,
+    operator_merkle::{operator_leaves, operator_merkle_root},
     stake_weight::StakeWeights,
+    utils::{get_epoch, is_bit_set},
 };
 
 // PDA'd ["snapshot", NCN]
@@ -29,6 +37,8 @@ pub struct Snapshot {
     ncn: Pubkey,
     /// Bump seed for the PDA
     bump: u8,
+    /// Layout version the snapshot was initialized with, see `Snapshot::VERSION`
+    version: u8,
     /// Slot Snapshot was created
     slot_created: PodU64,
     /// Keeps track of the number of completed operator registration through `snapshot_vault_operator_delegation` and `initialize_operator_snapshot`
@@ -38,11 +48,21 @@ pub struct Snapshot {
     /// total Operators G1 Pubkey aggregated stake weights
     total_aggregated_g1_pubkey: [u8; 32],
     /// Array of operator snapshots
-    operator_snapshots: [OperatorSnapshot; 256],
+    operator_snapshots: [OperatorSnapshot; MAX_OPERATORS],
     /// Minimum stake weight required to vote
     minimum_stake: StakeWeights,
 
     last_snapshot_slot: PodU64, // Track the last slot when the snapshot was taken
+
+    /// Last NCN epoch the next epoch stake weights were rolled over in, `u64::MAX` if never
+    last_rollover_epoch: PodU64,
+
+    /// Slot every operator of the NCN was registered in, `u64::MAX` until then
+    slot_finalized: PodU64,
+
+    /// Bitmap of the operator slots freed by `deregister_operator`, a slot's bit is cleared again
+    /// once an operator registers in it
+    deregistered_operators: [u8; MAX_SIGNER_BITMAP_BYTES],
 }
 
 impl Discriminator for Snapshot {
@@ -52,6 +72,10 @@ impl Discriminator for Snapshot {
 impl Snapshot {
     const SNAPSHOT_SEED: &'static [u8] = b"snapshot";
     pub const SIZE: usize = 8 + size_of::<Self>();
+    /// Layout version written at initialization. Bump it whenever the layout of `Snapshot` or
+    /// `OperatorSnapshot` changes. Version 2 added the operators' `vaults_delegated`, version 3
+    /// `slot_finalized`, version 4 `deregistered_operators`
+    pub const VERSION: u8 = 4;
 
     pub fn new(ncn: &Pubkey, bump: u8, current_slot: u64, minimum_stake: StakeWeights) -> Self {
         Self {
@@ -59,11 +83,15 @@ impl Snapshot {
             slot_created: PodU64::from(current_slot),
             last_snapshot_slot: PodU64::from(0),
             bump,
+            version: Self::VERSION,
             operators_registered: PodU64::from(0),
             operators_can_vote_count: PodU64::from(0),
             total_aggregated_g1_pubkey: [0; G1_COMPRESSED_POINT_SIZE],
-            operator_snapshots: [OperatorSnapshot::default(); 256],
+            operator_snapshots: [OperatorSnapshot::default(); MAX_OPERATORS],
             minimum_stake,
+            last_rollover_epoch: PodU64::from(u64::MAX),
+            slot_finalized: PodU64::from(u64::MAX),
+            deregistered_operators: [0; MAX_SIGNER_BITMAP_BYTES],
         }
     }
 
@@ -80,12 +108,16 @@ impl Snapshot {
         self.slot_created = PodU64::from(current_slot);
         self.last_snapshot_slot = PodU64::from(0);
         self.bump = bump;
+        self.version = Self::VERSION;
         self.operators_registered = PodU64::from(0);
         self.operators_can_vote_count = PodU64::from(0);
         self.total_aggregated_g1_pubkey = [0; G1_COMPRESSED_POINT_SIZE];
         let default_operator_snapshot = OperatorSnapshot::default();
-        self.operator_snapshots = [default_operator_snapshot; 256];
+        self.operator_snapshots = [default_operator_snapshot; MAX_OPERATORS];
         self.minimum_stake = minimum_stake;
+        self.last_rollover_epoch = PodU64::from(u64::MAX);
+        self.slot_finalized = PodU64::from(u64::MAX);
+        self.deregistered_operators = [0; MAX_SIGNER_BITMAP_BYTES];
     }
 
     pub fn seeds(ncn: &Pubkey) -> Vec<Vec<u8>> {
@@ -116,7 +148,31 @@ impl Snapshot {
             &expected_pda,
             Some(Self::DISCRIMINATOR),
             expect_writable,
-        )
+        )?;
+
+        let account_data = account.data.borrow();
+        let snapshot = Self::try_from_slice_unchecked(&account_data)?;
+        snapshot.check_version()?;
+
+        Ok(())
+    }
+
+    pub const fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Fails with `SnapshotVersionMismatch` when the snapshot was written with a layout this
+    /// program does not know
+    pub fn check_version(&self) -> Result<(), NCNProgramError> {
+        if self.version != Self::VERSION {
+            msg!(
+                "Snapshot version {} does not match the program's version {}",
+                self.version,
+                Self::VERSION
+            );
+            return Err(NCNProgramError::SnapshotVersionMismatch);
+        }
+        Ok(())
     }
 
     pub fn load_to_close(
@@ -127,10 +183,41 @@ impl Snapshot {
         Self::load(program_id, account_to_close, ncn, true)
     }
 
+    pub const fn ncn(&self) -> &Pubkey {
+        &self.ncn
+    }
+
     pub fn operators_registered(&self) -> u64 {
         self.operators_registered.into()
     }
 
+    /// Number of operator slots a signer bitmap covers, one past the highest registered
+    /// operator's slot. A deregistered operator frees its slot without moving the operators after
+    /// it, so this exceeds `operators_registered` once an operator below the highest one left.
+    pub fn operator_slot_count(&self) -> u64 {
+        self.operator_snapshots
+            .iter()
+            .rposition(|snapshot| snapshot.ncn_operator_index() != u64::MAX)
+            .map_or(0, |slot| slot as u64 + 1)
+    }
+
+    /// The registered operator snapshots paired with their slot, which is also their bit in a
+    /// signer bitmap. Slots freed by deregistered operators are skipped.
+    pub fn registered_operator_snapshots(
+        &self,
+    ) -> impl Iterator<Item = (usize, &OperatorSnapshot)> + '_ {
+        self.operator_snapshots
+            .iter()
+            .enumerate()
+            .filter(|(_, snapshot)| snapshot.ncn_operator_index() != u64::MAX)
+    }
+
+    /// Whether exactly one operator is registered, in which case the total aggregated G1 pubkey
+    /// is that operator's own key and any vote it signs alone meets the quorum
+    pub fn is_single_operator(&self) -> bool {
+        self.operators_registered() == 1
+    }
+
     pub fn operators_can_vote_count(&self) -> u64 {
         self.operators_can_vote_count.into()
     }
@@ -139,24 +226,117 @@ impl Snapshot {
         self.total_aggregated_g1_pubkey
     }
 
+    pub fn slot_created(&self) -> u64 {
+        self.slot_created.into()
+    }
+
     pub fn last_snapshot_slot(&self) -> u64 {
         self.last_snapshot_slot.into()
     }
 
+    pub fn last_rollover_epoch(&self) -> u64 {
+        self.last_rollover_epoch.into()
+    }
+
+    pub fn slot_finalized(&self) -> u64 {
+        self.slot_finalized.into()
+    }
+
+    /// Whether every operator of the NCN was registered, see `record_finalization`
+    pub fn finalized(&self) -> bool {
+        self.slot_finalized() != u64::MAX
+    }
+
+    /// Number of operator slots freed by `deregister_operator` and not registered again since
+    pub fn operators_deregistered(&self) -> u64 {
+        self.deregistered_operators
+            .iter()
+            .map(|byte| u64::from(byte.count_ones()))
+            .sum()
+    }
+
+    /// Whether every one of the NCN's `ncn_operator_count` operators was either registered or
+    /// deregistered. The NCN's operator count never decreases, so deregistered operators still
+    /// count toward it.
+    pub fn has_every_operator(&self, ncn_operator_count: u64) -> bool {
+        self.operators_registered()
+            .saturating_add(self.operators_deregistered())
+            >= ncn_operator_count
+    }
+
+    /// Records `current_slot` as the finalized slot when the snapshot is not finalized yet and
+    /// it has every operator of the NCN, see `has_every_operator`. Returns whether this call
+    /// finalized the snapshot, so the caller emits `SnapshotFinalizedEvent` exactly once.
+    pub fn record_finalization(&mut self, ncn_operator_count: u64, current_slot: u64) -> bool {
+        if self.finalized() || !self.has_every_operator(ncn_operator_count) {
+            return false;
+        }
+
+        self.slot_finalized = PodU64::from(current_slot);
+        true
+    }
+
     pub fn minimum_stake(&self) -> &StakeWeights {
         &self.minimum_stake
     }
 
+    /// Whether consensus has stalled: at least `epochs_before_stall` epochs have passed since
+    /// the last snapshot was taken. Errors when `epoch_length` is zero.
+    pub fn is_stalled(
+        &self,
+        current_slot: u64,
+        epoch_length: u64,
+        epochs_before_stall: u64,
+    ) -> Result<bool, NCNProgramError> {
+        let current_epoch = get_epoch(current_slot, epoch_length)?;
+        let snapshot_epoch = get_epoch(self.last_snapshot_slot(), epoch_length)?;
+
+        let epochs_since_snapshot = current_epoch.saturating_sub(snapshot_epoch);
+
+        Ok(epochs_since_snapshot >= epochs_before_stall)
+    }
+
+    /// Counts a newly registered operator, and towards `operators_can_vote_count` when it can
+    /// vote. Neither count changes if either would overflow.
     fn increment_operator_registration(
         &mut self,
         current_slot: u64,
+        can_vote: bool,
     ) -> Result<(), NCNProgramError> {
-        self.operators_registered = PodU64::from(
-            self.operators_registered()
-                .checked_add(1)
-                .ok_or(NCNProgramError::ArithmeticOverflow)?,
-        );
+        let operators_registered = self
+            .operators_registered()
+            .checked_add(1)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?;
+        let operators_can_vote_count = self
+            .operators_can_vote_count()
+            .checked_add(u64::from(can_vote))
+            .ok_or(NCNProgramError::ArithmeticOverflow)?;
+
+        self.operators_registered = PodU64::from(operators_registered);
+        self.operators_can_vote_count = PodU64::from(operators_can_vote_count);
+        self.last_snapshot_slot = PodU64::from(current_slot);
 
+        Ok(())
+    }
+
+    /// Uncounts a removed operator, and from `operators_can_vote_count` when it could vote.
+    /// Neither count changes if either would underflow.
+    fn decrement_operator_registration(
+        &mut self,
+        current_slot: u64,
+        could_vote: bool,
+    ) -> Result<(), NCNProgramError> {
+        let operators_registered = self
+            .operators_registered()
+            .checked_sub(1)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?;
+        let operators_can_vote_count = self
+            .operators_can_vote_count()
+            .checked_sub(u64::from(could_vote))
+            .ok_or(NCNProgramError::ArithmeticOverflow)?;
+
+        self.operators_registered = PodU64::from(operators_registered);
+        self.operators_can_vote_count = PodU64::from(operators_can_vote_count);
         self.last_snapshot_slot = PodU64::from(current_slot);
 
         Ok(())
@@ -181,21 +361,181 @@ impl Snapshot {
         Ok(())
     }
 
-    /// Subtracts a G1 pubkey from the total aggregated pubkey
+    /// Subtracts a G1 pubkey from the total aggregated pubkey. Fails with
+    /// `G1PubkeyNotInAggregate` unless a registered operator snapshot holds the key, so a key
+    /// that was never added cannot corrupt the total.
     pub fn subtract_g1_pubkey_from_total_agg(
         &mut self,
         g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE],
     ) -> Result<(), NCNProgramError> {
-        alt_bn128_g1_decompress(g1_pubkey).map_err(|_| NCNProgramError::InvalidG1Pubkey)?;
+        if !self.is_g1_pubkey_in_total_agg(g1_pubkey) {
+            return Err(NCNProgramError::G1PubkeyNotInAggregate);
+        }
+
         let total_aggregated_g1_pubkey_point =
             G1Point::try_from(&G1CompressedPoint(self.total_aggregated_g1_pubkey))?;
-        let pk_point = G1Point::try_from(&G1CompressedPoint(*g1_pubkey))?;
-        let new_point = total_aggregated_g1_pubkey_point + pk_point.negate();
+        let new_point = Self::subtract_g1_pubkey(total_aggregated_g1_pubkey_point, g1_pubkey)?;
         let compressed = G1CompressedPoint::try_from(new_point)?;
         self.total_aggregated_g1_pubkey = compressed.0;
         Ok(())
     }
 
+    /// Whether a registered operator snapshot holds `g1_pubkey`, i.e. whether it was added to
+    /// the total aggregated pubkey
+    pub fn is_g1_pubkey_in_total_agg(&self, g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE]) -> bool {
+        self.operator_snapshots.iter().any(|operator_snapshot| {
+            operator_snapshot.ncn_operator_index() != u64::MAX
+                && operator_snapshot.g1_pubkey() == *g1_pubkey
+        })
+    }
+
+    /// Subtracts a compressed G1 pubkey from an aggregated point
+    fn subtract_g1_pubkey(
+        aggregated_point: G1Point,
+        g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE],
+    ) -> Result<G1Point, NCNProgramError> {
+        alt_bn128_g1_decompress(g1_pubkey).map_err(|_| NCNProgramError::InvalidG1Pubkey)?;
+        let pk_point = G1Point::try_from(&G1CompressedPoint(*g1_pubkey))?;
+        Ok(aggregated_point + pk_point.negate())
+    }
+
+    /// Moves the total aggregated pubkey from `old_g1_pubkey` to `new_g1_pubkey` in one step,
+    /// without rebuilding it from every operator. The new total is fully computed before it is
+    /// written, so if either pubkey is invalid the total aggregated pubkey is left unchanged.
+    pub fn replace_operator_g1_pubkey(
+        &mut self,
+        old_g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE],
+        new_g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE],
+    ) -> Result<(), NCNProgramError> {
+        alt_bn128_g1_decompress(new_g1_pubkey).map_err(|_| NCNProgramError::InvalidG1Pubkey)?;
+        let new_point = G1Point::try_from(&G1CompressedPoint(*new_g1_pubkey))?;
+
+        let total_aggregated_g1_pubkey_point =
+            G1Point::try_from(&G1CompressedPoint(self.total_aggregated_g1_pubkey))?;
+        let new_total_point =
+            Self::subtract_g1_pubkey(total_aggregated_g1_pubkey_point, old_g1_pubkey)?
+                .checked_add(&new_point)
+                .ok_or(NCNProgramError::AltBN128AddError)?;
+        let compressed = G1CompressedPoint::try_from(new_total_point)?;
+
+        self.total_aggregated_g1_pubkey = compressed.0;
+        Ok(())
+    }
+
+    /// Aggregates the G1 pubkeys of the signers by adding up every registered operator whose
+    /// bit is set in `signer_bitmap`. Cheaper than subtraction when few operators signed.
+    /// Returns the point at infinity when nobody signed.
+    pub fn aggregate_signers_by_addition(
+        &self,
+        signer_bitmap: &[u8],
+    ) -> Result<G1Point, NCNProgramError> {
+        self.aggregate_g1_pubkeys(|i, _| is_bit_set(signer_bitmap, i))
+    }
+
+    /// Aggregates the G1 pubkeys of the signers in `signer_bitmap` that also have the minimum
+    /// stake, the set of operators that counts towards weighted consensus. Returns the point at
+    /// infinity when no eligible operator signed.
+    pub fn aggregate_eligible_signers_g1(
+        &self,
+        signer_bitmap: &[u8],
+    ) -> Result<G1Point, NCNProgramError> {
+        self.aggregate_g1_pubkeys(|i, operator_snapshot| {
+            operator_snapshot.has_minimum_stake() && is_bit_set(signer_bitmap, i)
+        })
+    }
+
+    /// Sums the stored G2 pubkeys of the registered operators whose bit is set in
+    /// `signer_bitmap`, the aggregated G2 pubkey a vote by those signers must carry. Fails with
+    /// `InvalidG2Pubkey` when a signer has no G2 pubkey stored.
+    pub fn aggregate_signers_g2(&self, signer_bitmap: &[u8]) -> Result<G2Point, NCNProgramError> {
+        let mut signer_g2_points = Vec::new();
+
+        for (i, operator_snapshot) in self.registered_operator_snapshots() {
+            if !is_bit_set(signer_bitmap, i) {
+                continue;
+            }
+
+            if !operator_snapshot.have_valid_bn128_g2_pubkey() {
+                msg!(
+                    "The operator {} has no G2 pubkey stored",
+                    operator_snapshot.operator()
+                );
+                return Err(NCNProgramError::InvalidG2Pubkey);
+            }
+
+            signer_g2_points.push(G2Point::try_from(G2CompressedPoint(
+                operator_snapshot.g2_pubkey(),
+            ))?);
+        }
+
+        G2Point::sum(&signer_g2_points)
+    }
+
+    fn aggregate_g1_pubkeys(
+        &self,
+        include: impl Fn(usize, &OperatorSnapshot) -> bool,
+    ) -> Result<G1Point, NCNProgramError> {
+        let mut aggregated_point = G1Point([0u8; G1_POINT_SIZE]);
+
+        for (i, operator_snapshot) in self.registered_operator_snapshots() {
+            if !include(i, operator_snapshot) {
+                continue;
+            }
+
+            let pk_point = G1Point::try_from(&G1CompressedPoint(operator_snapshot.g1_pubkey()))
+                .map_err(|_| NCNProgramError::G1PointDecompressionError)?;
+            aggregated_point = aggregated_point
+                .checked_add(&pk_point)
+                .ok_or(NCNProgramError::AltBN128AddError)?;
+        }
+
+        Ok(aggregated_point)
+    }
+
+    /// The decompressed `total_aggregated_g1_pubkey`, which is the signers' aggregated G1 pubkey
+    /// when every registered operator signed
+    pub fn total_aggregated_g1_point(&self) -> Result<G1Point, NCNProgramError> {
+        G1Point::try_from(&G1CompressedPoint(self.total_aggregated_g1_pubkey))
+            .map_err(|_| NCNProgramError::G1PointDecompressionError)
+    }
+
+    /// Aggregates the G1 pubkeys of the signers by starting from the precomputed
+    /// `total_aggregated_g1_pubkey` and subtracting every registered operator whose bit is set
+    /// in `non_signer_bitmap`. Cheaper than addition when most operators signed.
+    pub fn aggregate_signers_by_subtraction(
+        &self,
+        non_signer_bitmap: &[u8],
+    ) -> Result<G1Point, NCNProgramError> {
+        let mut aggregated_point = self.total_aggregated_g1_point()?;
+
+        for (i, operator_snapshot) in self.registered_operator_snapshots() {
+            if !is_bit_set(non_signer_bitmap, i) {
+                continue;
+            }
+
+            aggregated_point =
+                Self::subtract_g1_pubkey(aggregated_point, &operator_snapshot.g1_pubkey())?;
+        }
+
+        Ok(aggregated_point)
+    }
+
+    /// Checks that `signer_aggregate` plus the aggregate of the registered operators whose bit is
+    /// unset in the signer `bitmap` adds up to the stored total aggregated pubkey. A `false`
+    /// result means the bitmap does not describe the signers `signer_aggregate` was built from.
+    pub fn verify_bitmap_partition(
+        &self,
+        signer_aggregate: &G1Point,
+        bitmap: &[u8],
+    ) -> Result<bool, NCNProgramError> {
+        let non_signer_aggregate = self.aggregate_g1_pubkeys(|i, _| !is_bit_set(bitmap, i))?;
+        let sum = signer_aggregate
+            .checked_add(&non_signer_aggregate)
+            .ok_or(NCNProgramError::AltBN128AddError)?;
+
+        Ok(sum.0 == self.total_aggregated_g1_point()?.0)
+    }
+
     pub fn register_operator_g1_pubkey(
         &mut self,
         operator_g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE],
@@ -229,16 +569,13 @@ impl Snapshot {
                     .map(|operator_snapshot| operator_snapshot.g1_pubkey());
 
                 if let Some(old_pubkey) = old_g1_pubkey {
-                    // Subtract the old pubkey from the total aggregated pubkey
-                    self.subtract_g1_pubkey_from_total_agg(&old_pubkey)?;
+                    // Move the total aggregated pubkey from the old pubkey to the new one
+                    self.replace_operator_g1_pubkey(&old_pubkey, operator_g1_pubkey)?;
 
                     // Now update the operator's pubkey
                     if let Some(operator_snapshot) = self.find_mut_operator_snapshot(operator) {
                         operator_snapshot.update_g1_pubkey(&operator_g1_pubkey);
                     }
-
-                    // Add the new pubkey to the total aggregated pubkey
-                    self.add_g1_pubkey_to_total_agg(operator_g1_pubkey)?;
                 } else {
                     msg!("Operator snapshot not found for operator: {}", operator);
                 }
@@ -256,25 +593,22 @@ impl Snapshot {
         &self.operator_snapshots
     }
 
-    /// Get an operator snapshot by operator index
+    /// Get an operator snapshot by operator index, `None` when no operator is registered there
     pub fn get_operator_snapshot(&self, ncn_operator_index: u64) -> Option<&OperatorSnapshot> {
-        if ncn_operator_index >= self.operators_registered() {
-            return None;
-        }
-        let snapshot = &self.operator_snapshots[ncn_operator_index as usize];
-        Some(snapshot)
+        self.operator_snapshots
+            .get(usize::try_from(ncn_operator_index).ok()?)
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX)
     }
 
-    /// Get a mutable operator snapshot by operator index
+    /// Get a mutable operator snapshot by operator index, `None` when no operator is registered
+    /// there
     pub fn get_mut_operator_snapshot(
         &mut self,
         ncn_operator_index: u64,
     ) -> Option<&mut OperatorSnapshot> {
-        if ncn_operator_index >= self.operators_registered() {
-            return None;
-        }
-        let snapshot = &mut self.operator_snapshots[ncn_operator_index as usize];
-        Some(snapshot)
+        self.operator_snapshots
+            .get_mut(usize::try_from(ncn_operator_index).ok()?)
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX)
     }
 
     /// Find an operator snapshot by operator pubkey
@@ -294,7 +628,30 @@ impl Snapshot {
         })
     }
 
-    /// Add a new operator snapshot to the array
+    /// Flags `operator` as having missed a vote, given the signer bitmap of that vote. Fails when
+    /// the operator is not registered or its bit is set, i.e. it did sign.
+    pub fn record_missed_vote(
+        &mut self,
+        operator: &Pubkey,
+        signers_bitmap: &[u8],
+    ) -> Result<(), NCNProgramError> {
+        let operator_snapshot = self
+            .find_mut_operator_snapshot(operator)
+            .ok_or(NCNProgramError::OperatorIsNotInSnapshot)?;
+
+        if is_bit_set(
+            signers_bitmap,
+            operator_snapshot.ncn_operator_index() as usize,
+        ) {
+            return Err(NCNProgramError::OperatorSignedMessage);
+        }
+
+        operator_snapshot.set_missed_last_vote(true);
+        Ok(())
+    }
+
+    /// Add a new operator snapshot to the array. Fails when another registered operator already
+    /// uses its G1 pubkey, since the total aggregated G1 pubkey could no longer tell them apart.
     pub fn add_operator_snapshot(
         &mut self,
         operator_snapshot: OperatorSnapshot,
@@ -310,1614 +667,4952 @@ impl Snapshot {
             return Err(NCNProgramError::DuplicateVaultOperatorDelegation);
         }
 
-        self.operator_snapshots[operator_index as usize] = operator_snapshot;
+        let g1_pubkey = operator_snapshot.g1_pubkey();
+        if self
+            .operator_snapshots
+            .iter()
+            .any(|other| other.ncn_operator_index() != u64::MAX && other.g1_pubkey() == g1_pubkey)
+        {
+            return Err(NCNProgramError::DuplicateOperatorG1Pubkey);
+        }
 
-        self.increment_operator_registration(slot)?;
+        self.increment_operator_registration(slot, operator_snapshot.can_vote())?;
+        self.operator_snapshots[operator_index as usize] = operator_snapshot;
+        self.deregistered_operators[operator_index as usize / 8] &= !(1 << (operator_index % 8));
         Ok(())
     }
 
-    /// Get all active operator snapshots
-    pub fn get_active_operator_snapshots(&self) -> Vec<&OperatorSnapshot> {
-        self.operator_snapshots
-            .iter()
-            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX && snapshot.is_active())
-            .collect()
-    }
-
-    /// Update an operator snapshot in the array
-    pub fn update_operator_snapshot(
+    /// Inserts several operator snapshots at once, adding each G1 pubkey to the total aggregated
+    /// G1 pubkey. Fails before inserting anything when two of them share an NCN operator index.
+    pub fn add_operator_snapshots(
         &mut self,
-        operator_index: usize,
-        operator_snapshot: &OperatorSnapshot,
-    ) {
-        self.operator_snapshots[operator_index] = *operator_snapshot;
-    }
-}
-
-// Operator snapshot entry within Snapshot
-#[derive(Debug, Clone, Copy, Zeroable, Pod, ShankType)]
-#[repr(C)]
-pub struct OperatorSnapshot {
-    operator: Pubkey,
-
-    g1_pubkey: [u8; 32], // G1 compressed pubkey
-
-    slot_created: PodU64,
-    last_snapshot_slot: PodU64,
-
-    is_active: PodBool,
+        operator_snapshots: &[OperatorSnapshot],
+        slot: u64,
+    ) -> Result<(), NCNProgramError> {
+        for (i, operator_snapshot) in operator_snapshots.iter().enumerate() {
+            if operator_snapshots[..i]
+                .iter()
+                .any(|other| other.ncn_operator_index() == operator_snapshot.ncn_operator_index())
+            {
+                return Err(NCNProgramError::DuplicateOperatorIndex);
+            }
+        }
 
-    ncn_operator_index: PodU64,
+        for operator_snapshot in operator_snapshots {
+            self.add_operator_snapshot(*operator_snapshot, slot)?;
+            self.register_operator_g1_pubkey(&operator_snapshot.g1_pubkey())?;
+        }
 
-    operator_index: PodU64,
+        Ok(())
+    }
 
-    has_minimum_stake: PodBool,
-    has_minimum_stake_next_epoch: PodBool,
+    /// Replaces a registered operator's keys with the ones from its ncn operator account, moving
+    /// the total aggregated pubkey from the old G1 pubkey to the new one
+    pub fn refresh_operator_keys(
+        &mut self,
+        operator: &Pubkey,
+        g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE],
+        g2_pubkey: &[u8; G2_COMPRESSED_POINT_SIZE],
+    ) -> Result<(), NCNProgramError> {
+        let old_g1_pubkey = self
+            .find_operator_snapshot(operator)
+            .map(|operator_snapshot| operator_snapshot.g1_pubkey())
+            .ok_or(NCNProgramError::OperatorIsNotInSnapshot)?;
 
-    stake_weight: StakeWeights,
-    next_epoch_stake_weight: StakeWeights,
-}
+        if old_g1_pubkey != *g1_pubkey {
+            self.replace_operator_g1_pubkey(&old_g1_pubkey, g1_pubkey)?;
+        }
 
-impl Default for OperatorSnapshot {
-    fn default() -> Self {
-        Self {
-            operator: Pubkey::default(),
-            g1_pubkey: [0; G1_COMPRESSED_POINT_SIZE],
-            slot_created: PodU64::from(0),
-            last_snapshot_slot: PodU64::from(0),
-            is_active: PodBool::from(false),
-            ncn_operator_index: PodU64::from(u64::MAX),
-            operator_index: PodU64::from(u64::MAX),
-            has_minimum_stake: PodBool::from(false),
-            has_minimum_stake_next_epoch: PodBool::from(false),
-            stake_weight: StakeWeights::default(),
-            next_epoch_stake_weight: StakeWeights::default(),
+        if let Some(operator_snapshot) = self.find_mut_operator_snapshot(operator) {
+            operator_snapshot.update_g1_pubkey(g1_pubkey);
+            operator_snapshot.update_g2_pubkey(g2_pubkey);
         }
-    }
-}
 
-impl OperatorSnapshot {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        operator: &Pubkey,
-        current_slot: u64,
-        is_active: bool,
-        ncn_operator_index: u64,
-        operator_index: u64,
-        g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
-    ) -> Result<Self, NCNProgramError> {
-        Ok(Self {
-            operator: *operator,
-            slot_created: PodU64::from(current_slot),
-            last_snapshot_slot: PodU64::from(0),
-            is_active: PodBool::from(is_active),
-            ncn_operator_index: PodU64::from(ncn_operator_index),
-            operator_index: PodU64::from(operator_index),
-            g1_pubkey,
-            has_minimum_stake: PodBool::from(false),
-            has_minimum_stake_next_epoch: PodBool::from(false),
-            stake_weight: StakeWeights::default(),
-            next_epoch_stake_weight: StakeWeights::default(),
-        })
+        Ok(())
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn initialize(
+    /// Removes a registered operator: subtracts its G1 pubkey from the total aggregated pubkey,
+    /// frees its slot in the operator snapshots, marks the slot in `deregistered_operators` and
+    /// decrements the registered operator count. The other operators keep their slot, and so
+    /// their bit in signer bitmaps, and a finalized snapshot stays finalized.
+    pub fn deregister_operator(
         &mut self,
         operator: &Pubkey,
-        current_slot: u64,
-        is_active: bool,
-        ncn_operator_index: u64,
-        operator_index: u64,
-        g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
-        vault_operator_delegation_count: u64,
+        slot: u64,
     ) -> Result<(), NCNProgramError> {
-        if vault_operator_delegation_count > MAX_VAULTS as u64 {
-            return Err(NCNProgramError::TooManyVaultOperatorDelegations);
+        let (g1_pubkey, could_vote, ncn_operator_index) = self
+            .find_operator_snapshot(operator)
+            .map(|operator_snapshot| {
+                (
+                    operator_snapshot.g1_pubkey(),
+                    operator_snapshot.can_vote(),
+                    operator_snapshot.ncn_operator_index(),
+                )
+            })
+            .ok_or(NCNProgramError::OperatorIsNotInSnapshot)?;
+
+        self.subtract_g1_pubkey_from_total_agg(&g1_pubkey)?;
+
+        if let Some(operator_snapshot) = self.find_mut_operator_snapshot(operator) {
+            *operator_snapshot = OperatorSnapshot::default();
         }
 
-        // Initializes field by field to avoid overflowing stack
-        self.operator = *operator;
-        self.slot_created = PodU64::from(current_slot);
-        self.last_snapshot_slot = PodU64::from(0);
-        self.is_active = PodBool::from(is_active);
-        self.ncn_operator_index = PodU64::from(ncn_operator_index);
-        self.operator_index = PodU64::from(operator_index);
-        self.g1_pubkey = g1_pubkey;
-        self.has_minimum_stake = PodBool::from(false);
-        self.has_minimum_stake_next_epoch = PodBool::from(false);
-        self.stake_weight = StakeWeights::default();
+        self.decrement_operator_registration(slot, could_vote)?;
+        self.deregistered_operators[ncn_operator_index as usize / 8] |=
+            1 << (ncn_operator_index % 8);
 
         Ok(())
     }
 
-    pub fn ncn_operator_index(&self) -> u64 {
-        self.ncn_operator_index.into()
-    }
-
-    pub fn is_active(&self) -> bool {
-        self.is_active.into()
-    }
+    /// Marks a registered operator active or inactive without deregistering it, moving it in or
+    /// out of `operators_can_vote_count`. Its G1 pubkey stays in the total aggregated pubkey.
+    pub fn set_operator_active(
+        &mut self,
+        operator: &Pubkey,
+        active: bool,
+    ) -> Result<(), NCNProgramError> {
+        let operator_snapshot = self
+            .find_operator_snapshot(operator)
+            .ok_or(NCNProgramError::OperatorIsNotInSnapshot)?;
 
-    pub fn g1_pubkey(&self) -> [u8; G1_COMPRESSED_POINT_SIZE] {
-        self.g1_pubkey
+        if operator_snapshot.is_active() == active {
+            return Ok(());
+        }
+
+        let operators_can_vote_count = if !operator_snapshot.has_minimum_stake() {
+            self.operators_can_vote_count()
+        } else if active {
+            self.operators_can_vote_count()
+                .checked_add(1)
+                .ok_or(NCNProgramError::ArithmeticOverflow)?
+        } else {
+            self.operators_can_vote_count()
+                .checked_sub(1)
+                .ok_or(NCNProgramError::ArithmeticOverflow)?
+        };
+
+        if let Some(operator_snapshot) = self.find_mut_operator_snapshot(operator) {
+            operator_snapshot.is_active = PodBool::from(active);
+        }
+        self.operators_can_vote_count = PodU64::from(operators_can_vote_count);
+
+        Ok(())
     }
 
-    pub fn slot_created(&self) -> u64 {
-        self.slot_created.into()
+    /// Promotes every active operator's next epoch stake weight to its current stake weight and
+    /// re-checks it against the minimum stake. The delegated vaults are cleared, so the first
+    /// vault snapshotted afterwards replaces the promoted stake weight. Can only run once per NCN
+    /// epoch.
+    pub fn rollover(&mut self, ncn_epoch: u64, slot: u64) -> Result<(), NCNProgramError> {
+        let last_rollover_epoch = self.last_rollover_epoch();
+        if last_rollover_epoch != u64::MAX && ncn_epoch <= last_rollover_epoch {
+            msg!(
+                "Snapshot was already rolled over in epoch {}",
+                last_rollover_epoch
+            );
+            return Err(NCNProgramError::SnapshotAlreadyRolledOver);
+        }
+
+        let minimum_stake = self.minimum_stake;
+        for operator_snapshot in self
+            .operator_snapshots
+            .iter_mut()
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX && snapshot.is_active())
+        {
+            let next_epoch_stake_weight = operator_snapshot.next_epoch_stake_weight;
+            operator_snapshot.set_stake_weight(&next_epoch_stake_weight);
+            operator_snapshot.set_has_minimum_stake_this_epoch(
+                next_epoch_stake_weight.stake_weight() >= minimum_stake.stake_weight(),
+            );
+            operator_snapshot.clear_vaults();
+            operator_snapshot.last_snapshot_slot = PodU64::from(slot);
+        }
+
+        self.refresh_operators_can_vote_count();
+        self.last_rollover_epoch = PodU64::from(ncn_epoch);
+        self.last_snapshot_slot = PodU64::from(slot);
+
+        Ok(())
     }
 
-    pub fn last_snapshot_slot(&self) -> u64 {
-        self.last_snapshot_slot.into()
+    /// Prepares the snapshot account for another epoch without reinitializing it.
+    ///
+    /// The NCN, bump, layout version, creation slot and last rollover epoch always persist,
+    /// `minimum_stake` replaces the previous minimum stake, `operators_can_vote_count` drops to 0
+    /// and `last_snapshot_slot` moves to `current_slot`.
+    ///
+    /// With `preserve_operators` every operator slot keeps its operator, keys and active status,
+    /// so `operators_registered`, the total aggregated G1 pubkey and the finalized slot persist,
+    /// but its stake weights, minimum stake flags, missed vote flag and delegated vaults are
+    /// cleared until it is snapshotted again. Otherwise every operator slot and the total
+    /// aggregated G1 pubkey are cleared, `operators_registered` drops to 0 and the snapshot is no
+    /// longer finalized, as after `initialize`.
+    pub fn reset_for_new_epoch(
+        &mut self,
+        current_slot: u64,
+        minimum_stake: StakeWeights,
+        preserve_operators: bool,
+    ) {
+        if preserve_operators {
+            for operator_snapshot in self
+                .operator_snapshots
+                .iter_mut()
+                .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX)
+            {
+                operator_snapshot.set_stake_weight(&StakeWeights::default());
+                operator_snapshot.set_next_epoch_stake_weight(&StakeWeights::default());
+                operator_snapshot.set_has_minimum_stake_this_epoch(false);
+                operator_snapshot.set_has_minimum_stake_next_epoch(false);
+                operator_snapshot.set_missed_last_vote(false);
+                operator_snapshot.clear_vaults();
+            }
+        } else {
+            let default_operator_snapshot = OperatorSnapshot::default();
+            self.operator_snapshots = [default_operator_snapshot; MAX_OPERATORS];
+            self.operators_registered = PodU64::from(0);
+            self.total_aggregated_g1_pubkey = [0; G1_COMPRESSED_POINT_SIZE];
+            self.slot_finalized = PodU64::from(u64::MAX);
+            self.deregistered_operators = [0; MAX_SIGNER_BITMAP_BYTES];
+        }
+
+        self.operators_can_vote_count = PodU64::from(0);
+        self.last_snapshot_slot = PodU64::from(current_slot);
+        self.minimum_stake = minimum_stake;
     }
 
-    pub fn have_valid_bn128_g1_pubkey(&self) -> bool {
-        self.g1_pubkey != [0u8; 32]
+    /// Replaces the minimum stake captured when the snapshot was created and recomputes the
+    /// minimum stake flags of every registered operator against it, without a re-snapshot
+    pub fn set_minimum_stake(&mut self, minimum_stake: StakeWeights) {
+        self.minimum_stake = minimum_stake;
+
+        for operator_snapshot in self
+            .operator_snapshots
+            .iter_mut()
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX)
+        {
+            operator_snapshot.set_has_minimum_stake_this_epoch(
+                operator_snapshot.stake_weight().stake_weight() >= minimum_stake.stake_weight(),
+            );
+            operator_snapshot.set_has_minimum_stake_next_epoch(
+                operator_snapshot.next_epoch_stake_weight().stake_weight()
+                    >= minimum_stake.stake_weight(),
+            );
+        }
+
+        self.refresh_operators_can_vote_count();
     }
 
-    pub const fn operator(&self) -> &Pubkey {
-        &self.operator
+    /// Get all active operator snapshots
+    pub fn get_active_operator_snapshots(&self) -> Vec<&OperatorSnapshot> {
+        self.operator_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX && snapshot.is_active())
+            .collect()
     }
 
-    pub fn has_minimum_stake(&self) -> bool {
-        self.has_minimum_stake.into()
+    /// The active operator snapshots ranked by stake weight, highest first. Ties are broken by
+    /// ascending NCN operator index so the order is deterministic.
+    pub fn operators_by_stake_weight(&self) -> Vec<&OperatorSnapshot> {
+        let mut operators = self.get_active_operator_snapshots();
+        operators.sort_by(|a, b| {
+            b.stake_weight()
+                .stake_weight()
+                .cmp(&a.stake_weight().stake_weight())
+                .then_with(|| a.ncn_operator_index().cmp(&b.ncn_operator_index()))
+        });
+        operators
     }
 
-    pub fn has_minimum_stake_now(
+    /// Get the active operator snapshots that have the minimum stake `current_epoch -
+    /// snapshot_epoch` epochs after the snapshot was taken, i.e. the operators eligible to vote
+    pub fn get_eligible_voters(
         &self,
         current_epoch: u64,
         snapshot_epoch: u64,
-    ) -> Result<bool, NCNProgramError> {
-        let epoch_diff = current_epoch - snapshot_epoch;
-        match epoch_diff {
-            0 => Ok(self.has_minimum_stake.into()),
-            1 => Ok(self.has_minimum_stake_next_epoch.into()),
-            _ => {
-                msg!("Operator snapshot is outdated: {}", self.operator());
-                Err(NCNProgramError::OperatorSnapshotOutdated)
+    ) -> Result<Vec<&OperatorSnapshot>, NCNProgramError> {
+        let mut eligible_voters = Vec::new();
+        for operator_snapshot in self.get_active_operator_snapshots() {
+            if operator_snapshot.has_minimum_stake_now(current_epoch, snapshot_epoch)? {
+                eligible_voters.push(operator_snapshot);
             }
         }
+
+        Ok(eligible_voters)
     }
 
-    pub fn has_minimum_stake_next_epoch(&self) -> bool {
-        self.has_minimum_stake_next_epoch.into()
+    /// Number of registered operators that are active, the operators the 1/3 non-signer rule of a
+    /// vote is measured against
+    pub fn active_operators_count(&self) -> u64 {
+        self.registered_operator_snapshots()
+            .filter(|(_, snapshot)| snapshot.is_active())
+            .count() as u64
     }
 
-    pub fn stake_weight(&self) -> &StakeWeights {
-        &self.stake_weight
+    /// Number of G1 point additions needed to rebuild the total aggregated pubkey from the
+    /// active operators' keys, so keepers can estimate the compute cost beforehand
+    pub fn rebuild_aggregate_op_count(&self) -> u64 {
+        self.active_operators_count().saturating_sub(1)
     }
 
-    pub fn next_epoch_stake_weight(&self) -> &StakeWeights {
-        &self.next_epoch_stake_weight
+    /// Counts the registered operators per stake weight bucket. The range between the lowest
+    /// and highest stake weight is split into `buckets` equal-width bins.
+    pub fn stake_weight_histogram(&self, buckets: u32) -> Vec<u64> {
+        let mut histogram = vec![0u64; buckets as usize];
+        if buckets == 0 {
+            return histogram;
+        }
+
+        let stake_weights: Vec<u128> = self
+            .operator_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX)
+            .map(|snapshot| snapshot.stake_weight().stake_weight())
+            .collect();
+
+        let (Some(&min), Some(&max)) = (stake_weights.iter().min(), stake_weights.iter().max())
+        else {
+            return histogram;
+        };
+
+        // Rounded up so that the highest stake weight still lands in the last bucket
+        let bucket_width = (max - min) / buckets as u128 + 1;
+        for stake_weight in stake_weights {
+            let bucket = ((stake_weight - min) / bucket_width).min(buckets as u128 - 1);
+            histogram[bucket as usize] += 1;
+        }
+
+        histogram
     }
 
-    pub fn set_has_minimum_stake_this_epoch(&mut self, has_minimum_stake: bool) {
-        self.has_minimum_stake = PodBool::from(has_minimum_stake);
+    /// Sums the stake weight of the active registered operators that have the minimum stake and
+    /// whose bit is set in `bitmap`, the signers' side of `total_eligible_stake_weight`
+    pub fn signer_stake_weight_total(&self, bitmap: &[u8]) -> Result<u128, NCNProgramError> {
+        self.eligible_stake_weight_sum(|i| {
+            self.operator_snapshots[i].is_active() && is_bit_set(bitmap, i)
+        })
     }
 
-    pub fn set_has_minimum_stake_next_epoch(&mut self, has_minimum_stake: bool) {
-        self.has_minimum_stake_next_epoch = PodBool::from(has_minimum_stake);
+    /// The active registered operators whose bit is unset in the signer `bitmap` of a cast
+    /// vote. Bits past the registered operators are ignored and missing bytes count as unset.
+    pub fn non_signers(&self, bitmap: &[u8]) -> Vec<&OperatorSnapshot> {
+        self.registered_operator_snapshots()
+            .filter(|(i, operator_snapshot)| {
+                operator_snapshot.is_active() && !is_bit_set(bitmap, *i)
+            })
+            .map(|(_, operator_snapshot)| operator_snapshot)
+            .collect()
     }
 
-    pub fn set_stake_weight(&mut self, stake_weight_so_far: &StakeWeights) {
-        self.stake_weight = *stake_weight_so_far;
+    /// Sums the stake weight of every active registered operator that has the minimum stake,
+    /// the denominator of quorum and reporting
+    pub fn total_eligible_stake_weight(&self) -> Result<u128, NCNProgramError> {
+        self.eligible_stake_weight_sum(|i| self.operator_snapshots[i].is_active())
     }
 
-    pub fn set_next_epoch_stake_weight(&mut self, next_epoch_stake_weight: &StakeWeights) {
-        self.next_epoch_stake_weight = *next_epoch_stake_weight;
+    /// `total_eligible_stake_weight` clamped at `u128::MAX` instead of failing on overflow.
+    /// For display only: a clamped total is not the real total, so quorum and fee math must use
+    /// `total_eligible_stake_weight`
+    pub fn total_eligible_stake_weight_saturating(&self) -> u128 {
+        self.eligible_stake_weights(|i| self.operator_snapshots[i].is_active())
+            .fold(0, u128::saturating_add)
     }
 
-    pub fn snapshot_vault_operator_delegation(
-        &mut self,
-        current_slot: u64,
-        stake_weights: &StakeWeights,
-        next_epoch_stake_weights: &StakeWeights,
-        minimum_stake: &StakeWeights,
-    ) -> Result<(), NCNProgramError> {
-        self.set_stake_weight(stake_weights);
-        self.set_next_epoch_stake_weight(next_epoch_stake_weights);
+    fn eligible_stake_weight_sum(
+        &self,
+        include: impl Fn(usize) -> bool,
+    ) -> Result<u128, NCNProgramError> {
+        self.eligible_stake_weights(include)
+            .try_fold(0u128, |total, stake_weight| total.checked_add(stake_weight))
+            .ok_or(NCNProgramError::ArithmeticOverflow)
+    }
 
-        self.set_has_minimum_stake_this_epoch(
-            self.stake_weight().stake_weight() >= minimum_stake.stake_weight(),
-        );
+    /// Stake weights of the registered operators that have the minimum stake and pass `include`
+    fn eligible_stake_weights(
+        &self,
+        include: impl Fn(usize) -> bool,
+    ) -> impl Iterator<Item = u128> + '_ {
+        self.registered_operator_snapshots()
+            .filter(move |(i, operator_snapshot)| {
+                operator_snapshot.has_minimum_stake() && include(*i)
+            })
+            .map(|(_, operator_snapshot)| operator_snapshot.stake_weight().stake_weight())
+    }
 
-        self.set_has_minimum_stake_next_epoch(
-            self.next_epoch_stake_weight().stake_weight() >= minimum_stake.stake_weight(),
-        );
+    /// Combined stake weight of the given operators as basis points of the total stake weight
+    /// of all registered operators. Operators listed more than once are counted once.
+    pub fn coalition_power_bps(&self, operators: &[Pubkey]) -> Result<u16, NCNProgramError> {
+        let total_stake_weight = self
+            .registered_operator_snapshots()
+            .try_fold(0u128, |total, (_, operator_snapshot)| {
+                total.checked_add(operator_snapshot.stake_weight().stake_weight())
+            })
+            .ok_or(NCNProgramError::ArithmeticOverflow)?;
+
+        if total_stake_weight == 0 {
+            return Err(NCNProgramError::DenominatorIsZero);
+        }
 
-        self.last_snapshot_slot = PodU64::from(current_slot);
-        Ok(())
+        let mut coalition_stake_weight: u128 = 0;
+        for (i, operator) in operators.iter().enumerate() {
+            if operators[..i].contains(operator) {
+                continue;
+            }
+
+            let operator_snapshot = self
+                .find_operator_snapshot(operator)
+                .ok_or(NCNProgramError::OperatorIsNotInSnapshot)?;
+
+            coalition_stake_weight = coalition_stake_weight
+                .checked_add(operator_snapshot.stake_weight().stake_weight())
+                .ok_or(NCNProgramError::ArithmeticOverflow)?;
+        }
+
+        let power_bps = coalition_stake_weight
+            .checked_mul(MAX_FEE_BPS as u128)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?
+            .checked_div(total_stake_weight)
+            .ok_or(NCNProgramError::DenominatorIsZero)?;
+
+        u16::try_from(power_bps).map_err(|_| NCNProgramError::ArithmeticOverflow)
     }
 
-    pub fn calculate_stake_weights(
-        vault_operator_delegation: &VaultOperatorDelegation,
-    ) -> Result<(u128, u128), ProgramError> {
-        let total_security = vault_operator_delegation
-            .delegation_state
-            .total_security()?;
+    /// Stake weight of `operator` as basis points of `total_eligible_stake_weight`, rounded
+    /// down. Inactive operators and operators below the minimum stake have a share of zero.
+    pub fn operator_stake_share_bps(&self, operator: &Pubkey) -> Result<u16, NCNProgramError> {
+        let operator_snapshot = self
+            .find_operator_snapshot(operator)
+            .ok_or(NCNProgramError::OperatorIsNotInSnapshot)?;
 
-        let cooling_down_amount = vault_operator_delegation
-            .delegation_state
-            .cooling_down_amount();
+        if !operator_snapshot.is_active() || !operator_snapshot.has_minimum_stake() {
+            return Ok(0);
+        }
 
-        let precise_total_security = PreciseNumber::new(total_security as u128)
+        let precise_stake_weight =
+            PreciseNumber::new(operator_snapshot.stake_weight().stake_weight())
+                .ok_or(NCNProgramError::NewPreciseNumberError)?;
+        let precise_total_stake_weight = PreciseNumber::new(self.total_eligible_stake_weight()?)
             .ok_or(NCNProgramError::NewPreciseNumberError)?;
-        let precies_cooling_down_amount = PreciseNumber::new(cooling_down_amount as u128)
+        let precise_max_bps = PreciseNumber::new(MAX_FEE_BPS as u128)
             .ok_or(NCNProgramError::NewPreciseNumberError)?;
-        let precise_next_epoch_securites = precise_total_security
-            .checked_sub(&precies_cooling_down_amount)
-            .ok_or(NCNProgramError::ArithmeticUnderflowError)?;
 
-        let total_stake_weight = precise_total_security
-            .to_imprecise()
-            .ok_or(NCNProgramError::CastToImpreciseNumberError)?;
-        let next_epoch_stake_weight = precise_next_epoch_securites
+        let share_bps = precise_stake_weight
+            .checked_mul(&precise_max_bps)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?
+            .checked_div(&precise_total_stake_weight)
+            .ok_or(NCNProgramError::DenominatorIsZero)?
+            .floor()
+            .ok_or(NCNProgramError::ArithmeticFloorError)?
             .to_imprecise()
             .ok_or(NCNProgramError::CastToImpreciseNumberError)?;
 
-        Ok((total_stake_weight, next_epoch_stake_weight))
+        u16::try_from(share_bps).map_err(|_| NCNProgramError::ArithmeticOverflow)
     }
 
-    pub fn update_g1_pubkey(&mut self, g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE]) {
-        self.g1_pubkey = *g1_pubkey;
+    /// Whether `root` is the operator Merkle root of the registered operators, e.g. a root
+    /// mirrored to another chain that should be reconciled with this snapshot
+    pub fn matches_merkle_root(&self, root: &[u8; 32]) -> bool {
+        operator_merkle_root(&operator_leaves(self)).is_some_and(|own_root| own_root == *root)
     }
-}
-
-#[derive(Debug, Clone, Copy, Zeroable, Pod, ShankType)]
-#[repr(C)]
-pub struct VaultOperatorStakeWeight {
-    vault: Pubkey,
-    vault_index: PodU64,
-    stake_weight: StakeWeights,
-}
 
-impl Default for VaultOperatorStakeWeight {
-    fn default() -> Self {
-        Self {
-            vault: Pubkey::default(),
-            vault_index: PodU64::from(u64::MAX),
-            stake_weight: StakeWeights::default(),
+    /// SHA-256 hash of the snapshot content, so keepers can confirm they produced the same
+    /// snapshot. Covers the registered operators sorted by pubkey with their G1 pubkeys and stake
+    /// weights, followed by the total aggregated G1 pubkey. Slots, indices and flags are left out,
+    /// so the operator insertion order does not matter
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut operator_snapshots: Vec<&OperatorSnapshot> = self
+            .registered_operator_snapshots()
+            .map(|(_, operator_snapshot)| operator_snapshot)
+            .collect();
+        operator_snapshots.sort_by_key(|operator_snapshot| *operator_snapshot.operator());
+
+        let mut content = Vec::with_capacity(
+            operator_snapshots.len() * (32 + G1_COMPRESSED_POINT_SIZE + 16)
+                + G1_COMPRESSED_POINT_SIZE,
+        );
+        for operator_snapshot in operator_snapshots {
+            content.extend_from_slice(operator_snapshot.operator().as_ref());
+            content.extend_from_slice(&operator_snapshot.g1_pubkey());
+            content.extend_from_slice(
+                &operator_snapshot
+                    .stake_weight()
+                    .stake_weight()
+                    .to_le_bytes(),
+            );
         }
-    }
-}
+        content.extend_from_slice(&self.total_aggregated_g1_pubkey);
 
-impl VaultOperatorStakeWeight {
-    pub fn new(vault: &Pubkey, vault_index: u64, stake_weight: &StakeWeights) -> Self {
-        Self {
-            vault: *vault,
-            vault_index: PodU64::from(vault_index),
-            stake_weight: *stake_weight,
-        }
+        solana_nostd_sha256::hashv(&[&content])
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.vault_index() == u64::MAX
+    /// Update an operator snapshot in the array. Panics when `operator_index` is out of the
+    /// array, see `try_update_operator_snapshot`
+    pub fn update_operator_snapshot(
+        &mut self,
+        operator_index: usize,
+        operator_snapshot: &OperatorSnapshot,
+    ) {
+        self.operator_snapshots[operator_index] = *operator_snapshot;
+        self.refresh_operators_can_vote_count();
     }
 
-    pub fn vault_index(&self) -> u64 {
-        self.vault_index.into()
+    /// Update the snapshot of a registered operator, failing with
+    /// `TooManyVaultOperatorDelegations` when no operator is registered at `operator_index`
+    pub fn try_update_operator_snapshot(
+        &mut self,
+        operator_index: usize,
+        operator_snapshot: &OperatorSnapshot,
+    ) -> Result<(), NCNProgramError> {
+        if self.get_operator_snapshot(operator_index as u64).is_none() {
+            return Err(NCNProgramError::TooManyVaultOperatorDelegations);
+        }
+
+        self.update_operator_snapshot(operator_index, operator_snapshot);
+        Ok(())
     }
 
-    pub const fn stake_weights(&self) -> &StakeWeights {
-        &self.stake_weight
+    /// Recounts the registered operators that are active and have the minimum stake this epoch
+    fn refresh_operators_can_vote_count(&mut self) {
+        let operators_can_vote_count = self
+            .operator_snapshots
+            .iter()
+            .filter(|snapshot| snapshot.ncn_operator_index() != u64::MAX && snapshot.can_vote())
+            .count() as u64;
+
+        self.operators_can_vote_count = PodU64::from(operators_can_vote_count);
     }
 
-    pub const fn vault(&self) -> &Pubkey {
-        &self.vault
+    /// Serializes the snapshot into a version-tagged, borsh-encoded backup.
+    /// Only registered operator snapshots are included.
+    pub fn to_backup_bytes(&self) -> Vec<u8> {
+        let backup = SnapshotBackup {
+            version: SnapshotBackup::VERSION,
+            ncn: self.ncn.to_bytes(),
+            bump: self.bump,
+            slot_created: self.slot_created.into(),
+            operators_registered: self.operators_registered(),
+            operators_can_vote_count: self.operators_can_vote_count(),
+            total_aggregated_g1_pubkey: self.total_aggregated_g1_pubkey,
+            minimum_stake: self.minimum_stake.stake_weight(),
+            last_snapshot_slot: self.last_snapshot_slot(),
+            last_rollover_epoch: self.last_rollover_epoch(),
+            slot_finalized: self.slot_finalized(),
+            deregistered_operators: self.deregistered_operators,
+            operator_snapshots: self
+                .operator_snapshots
+                .iter()
+                .filter(|operator_snapshot| operator_snapshot.ncn_operator_index() != u64::MAX)
+                .map(OperatorSnapshotBackup::from)
+                .collect(),
+        };
+
+        backup
+            .try_to_vec()
+            .expect("Serializing a snapshot backup into memory cannot fail")
     }
-}
 
-#[rustfmt::skip]
-impl fmt::Display for Snapshot {
-   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-       writeln!(f, "\n\n----------- Snapshot -------------")?;
-       writeln!(f, "  NCN:                          {}", self.ncn)?;
-       writeln!(f, "  Bump:                         {}", self.bump)?;
-       writeln!(f, "  Operators Registered:         {}", self.operators_registered())?;
-       writeln!(f, "  Operators can vote:           {}", self.operators_can_vote_count())?;
-       writeln!(f, "  Last Snapshot Slot:           {}", self.last_snapshot_slot())?;
-       writeln!(f, "  Total Agg G1 Pubkey:          {:?}", self.total_aggregated_g1_pubkey())?;
-       writeln!(f, "  Minimum Stake Weight:         {:?}", self.minimum_stake())?;
-       writeln!(f, "  operators snapshots:")?;
-       for operator_snapshot in self.operator_snapshots.iter() {
-        if operator_snapshot.ncn_operator_index() != u64::MAX {
-           writeln!(f, "{}", operator_snapshot)?;
-        }
-       }
+    /// Restores a snapshot from bytes produced by `to_backup_bytes`
+    pub fn from_backup_bytes(bytes: &[u8]) -> Result<Box<Self>, NCNProgramError> {
+        let version = *bytes
+            .first()
+            .ok_or(NCNProgramError::InvalidSnapshotBackup)?;
+        if version != SnapshotBackup::VERSION {
+            msg!("Unsupported snapshot backup version: {}", version);
+            return Err(NCNProgramError::UnsupportedSnapshotBackupVersion);
+        }
 
-       writeln!(f, "\n")?;
-       Ok(())
-   }
-}
+        let backup = SnapshotBackup::try_from_slice(bytes)
+            .map_err(|_| NCNProgramError::InvalidSnapshotBackup)?;
 
-#[rustfmt::skip]
-impl fmt::Display for OperatorSnapshot {
-   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-       writeln!(f, "\n\n----------- Operator Snapshot -------------")?;
-       writeln!(f, "  Operator:                     {}", self.operator)?;
-       writeln!(f, "  Is Active:                    {}", self.is_active())?;
-       writeln!(f, "  NCN Operator Index:           {}", self.ncn_operator_index())?;
-       writeln!(f, "  Slot Last Snapshoted:         {}", self.last_snapshot_slot())?;
-       writeln!(f, "  G1 Pubkey:                    {:?}", self.g1_pubkey())?;
-       writeln!(f, "  Has Minimum Stake Weight:     {}", self.has_minimum_stake())?;
-       writeln!(f, "  Has Minimum next epoch:       {}", self.has_minimum_stake_next_epoch())?;
-       writeln!(f, "  Stake Weight:                 {:?}", self.stake_weight())?;
-       writeln!(f, "  Next Epoch Stake Weight:      {:?}", self.next_epoch_stake_weight())?;
+        if backup.operator_snapshots.len() as u64 != backup.operators_registered {
+            return Err(NCNProgramError::InvalidSnapshotBackup);
+        }
 
-       writeln!(f, "\n")?;
-       Ok(())
-   }
+        let mut snapshot = Box::new(Self::new(
+            &Pubkey::new_from_array(backup.ncn),
+            backup.bump,
+            backup.slot_created,
+            StakeWeights::new(backup.minimum_stake),
+        ));
+        snapshot.operators_registered = PodU64::from(backup.operators_registered);
+        snapshot.operators_can_vote_count = PodU64::from(backup.operators_can_vote_count);
+        snapshot.total_aggregated_g1_pubkey = backup.total_aggregated_g1_pubkey;
+        snapshot.last_snapshot_slot = PodU64::from(backup.last_snapshot_slot);
+        snapshot.last_rollover_epoch = PodU64::from(backup.last_rollover_epoch);
+        snapshot.slot_finalized = PodU64::from(backup.slot_finalized);
+        snapshot.deregistered_operators = backup.deregistered_operators;
+
+        for operator_backup in backup.operator_snapshots.iter() {
+            let index = operator_backup.ncn_operator_index;
+            if index >= MAX_OPERATORS as u64
+                || snapshot.operator_snapshots[index as usize].ncn_operator_index() != u64::MAX
+            {
+                return Err(NCNProgramError::InvalidSnapshotBackup);
+            }
+            snapshot.operator_snapshots[index as usize] = OperatorSnapshot::from(operator_backup);
+        }
+
+        Ok(snapshot)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use solana_program::msg;
+/// Portable, borsh-encoded representation of a `Snapshot` used for off-chain backups
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+struct SnapshotBackup {
+    /// Must stay the first field so the version can be read before decoding the rest
+    version: u8,
+    ncn: [u8; 32],
+    bump: u8,
+    slot_created: u64,
+    operators_registered: u64,
+    operators_can_vote_count: u64,
+    total_aggregated_g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+    minimum_stake: u128,
+    last_snapshot_slot: u64,
+    last_rollover_epoch: u64,
+    slot_finalized: u64,
+    deregistered_operators: [u8; MAX_SIGNER_BITMAP_BYTES],
+    operator_snapshots: Vec<OperatorSnapshotBackup>,
+}
 
-    use super::*;
+impl SnapshotBackup {
+    /// Version 2 added the operators' G2 pubkeys, version 3 the last rollover epoch, version 4
+    /// the finalized slot, version 5 the deregistered operators, version 6 the operators' missed
+    /// vote flag, version 7 the operators' delegated vaults
+    const VERSION: u8 = 7;
+}
 
-    #[test]
-    fn test_operator_snapshot_size() {
-        use std::mem::size_of;
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+struct OperatorSnapshotBackup {
+    operator: [u8; 32],
+    g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+    slot_created: u64,
+    last_snapshot_slot: u64,
+    is_active: bool,
+    ncn_operator_index: u64,
+    operator_index: u64,
+    has_minimum_stake: bool,
+    has_minimum_stake_next_epoch: bool,
+    stake_weight: u128,
+    next_epoch_stake_weight: u128,
+    g2_pubkey: [u8; G2_COMPRESSED_POINT_SIZE],
+    missed_last_vote: bool,
+    vaults_delegated: [[u8; 32]; MAX_VAULTS],
+}
 
-        let expected_total = size_of::<Pubkey>() // operator
-            + size_of::<[u8; G1_COMPRESSED_POINT_SIZE]>() // g1_pubkey
-            + size_of::<PodU64>() // slot_created
-            + size_of::<PodU64>() // slot_last_snapshoted
-            + size_of::<PodBool>() // is_active
-            + size_of::<PodU64>() // ncn_operator_index
-            + size_of::<PodU64>() // operator_index
-            + size_of::<PodBool>() // has_minimum_stake
-            + size_of::<PodBool>() // has_minimum_stake_next_epoch
-            + size_of::<StakeWeights>() // stake_weight
-            + size_of::<StakeWeights>(); // next_epoch_stake_weight
+impl From<&OperatorSnapshot> for OperatorSnapshotBackup {
+    fn from(operator_snapshot: &OperatorSnapshot) -> Self {
+        Self {
+            operator: operator_snapshot.operator.to_bytes(),
+            g1_pubkey: operator_snapshot.g1_pubkey,
+            slot_created: operator_snapshot.slot_created(),
+            last_snapshot_slot: operator_snapshot.last_snapshot_slot(),
+            is_active: operator_snapshot.is_active(),
+            ncn_operator_index: operator_snapshot.ncn_operator_index(),
+            operator_index: operator_snapshot.operator_index.into(),
+            has_minimum_stake: operator_snapshot.has_minimum_stake(),
+            has_minimum_stake_next_epoch: operator_snapshot.has_minimum_stake_next_epoch(),
+            stake_weight: operator_snapshot.stake_weight().stake_weight(),
+            next_epoch_stake_weight: operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            g2_pubkey: operator_snapshot.g2_pubkey,
+            missed_last_vote: operator_snapshot.missed_last_vote(),
+            vaults_delegated: operator_snapshot
+                .vaults_delegated
+                .map(|vault| vault.to_bytes()),
+        }
+    }
+}
 
-        assert_eq!(size_of::<OperatorSnapshot>(), expected_total);
+impl From<&OperatorSnapshotBackup> for OperatorSnapshot {
+    fn from(backup: &OperatorSnapshotBackup) -> Self {
+        Self {
+            operator: Pubkey::new_from_array(backup.operator),
+            g1_pubkey: backup.g1_pubkey,
+            slot_created: PodU64::from(backup.slot_created),
+            last_snapshot_slot: PodU64::from(backup.last_snapshot_slot),
+            is_active: PodBool::from(backup.is_active),
+            ncn_operator_index: PodU64::from(backup.ncn_operator_index),
+            operator_index: PodU64::from(backup.operator_index),
+            has_minimum_stake: PodBool::from(backup.has_minimum_stake),
+            has_minimum_stake_next_epoch: PodBool::from(backup.has_minimum_stake_next_epoch),
+            stake_weight: StakeWeights::new(backup.stake_weight),
+            next_epoch_stake_weight: StakeWeights::new(backup.next_epoch_stake_weight),
+            g2_pubkey: backup.g2_pubkey,
+            missed_last_vote: PodBool::from(backup.missed_last_vote),
+            vaults_delegated: backup.vaults_delegated.map(Pubkey::new_from_array),
+        }
     }
+}
 
-    #[test]
-    fn test_snapshot_size() {
-        use std::mem::size_of;
+/// Compact, borsh-encoded representation of a single `OperatorSnapshot` for off-chain gossip
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+struct OperatorSnapshotWire {
+    /// Must stay the first field so the version can be read before decoding the rest
+    version: u8,
+    operator_snapshot: OperatorSnapshotBackup,
+}
 
-        msg!("Snapshot size: {:?}", size_of::<Snapshot>());
+impl OperatorSnapshotWire {
+    /// Version 2 moved the missed vote flag into the operator snapshot backup, version 3 added
+    /// the delegated vaults
+    const VERSION: u8 = 3;
+    /// Length of the SHA-256 prefix appended to the encoded entry
+    const CHECKSUM_LEN: usize = 4;
+
+    fn checksum(payload: &[u8]) -> [u8; Self::CHECKSUM_LEN] {
+        let hash = solana_nostd_sha256::hashv(&[payload]);
+        let mut checksum = [0; Self::CHECKSUM_LEN];
+        checksum.copy_from_slice(&hash[..Self::CHECKSUM_LEN]);
+        checksum
+    }
+}
 
-        let expected_total = size_of::<Pubkey>() // ncn
-            + size_of::<u8>() // bump
-            + size_of::<PodU64>() // slot_created
-            + size_of::<PodU64>() // last_snapshot_slot
-            + size_of::<PodU64>() // operators_registered
+// Operator snapshot entry within Snapshot
+//
+// Migration note: `g2_pubkey`, then `missed_last_vote` and `vaults_delegated` were appended to this entry, which
+// changes the layout and size of the `Snapshot` account. Snapshots created with the previous layout can't be loaded or reallocated in
+// place; close them with `CloseSnapshot` and initialize a new snapshot, then re-register operators.
+#[derive(Debug, Clone, Copy, Zeroable, Pod, ShankType)]
+#[repr(C)]
+pub struct OperatorSnapshot {
+    operator: Pubkey,
+
+    g1_pubkey: [u8; 32], // G1 compressed pubkey
+
+    slot_created: PodU64,
+    last_snapshot_slot: PodU64,
+
+    is_active: PodBool,
+
+    ncn_operator_index: PodU64,
+
+    operator_index: PodU64,
+
+    has_minimum_stake: PodBool,
+    has_minimum_stake_next_epoch: PodBool,
+
+    stake_weight: StakeWeights,
+    next_epoch_stake_weight: StakeWeights,
+
+    g2_pubkey: [u8; 64], // G2 compressed pubkey
+
+    missed_last_vote: PodBool,
+
+    /// Vaults whose delegation was snapshotted in the NCN epoch of `last_snapshot_slot`,
+    /// `Pubkey::default()` for unused entries
+    vaults_delegated: [Pubkey; MAX_VAULTS],
+}
+
+impl Default for OperatorSnapshot {
+    fn default() -> Self {
+        Self {
+            operator: Pubkey::default(),
+            g1_pubkey: [0; G1_COMPRESSED_POINT_SIZE],
+            slot_created: PodU64::from(0),
+            last_snapshot_slot: PodU64::from(0),
+            is_active: PodBool::from(false),
+            ncn_operator_index: PodU64::from(u64::MAX),
+            operator_index: PodU64::from(u64::MAX),
+            has_minimum_stake: PodBool::from(false),
+            has_minimum_stake_next_epoch: PodBool::from(false),
+            stake_weight: StakeWeights::default(),
+            next_epoch_stake_weight: StakeWeights::default(),
+            g2_pubkey: [0; G2_COMPRESSED_POINT_SIZE],
+            missed_last_vote: PodBool::from(false),
+            vaults_delegated: [Pubkey::default(); MAX_VAULTS],
+        }
+    }
+}
+
+impl OperatorSnapshot {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        operator: &Pubkey,
+        current_slot: u64,
+        is_active: bool,
+        ncn_operator_index: u64,
+        operator_index: u64,
+        g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+        g2_pubkey: [u8; G2_COMPRESSED_POINT_SIZE],
+    ) -> Result<Self, NCNProgramError> {
+        Ok(Self {
+            operator: *operator,
+            slot_created: PodU64::from(current_slot),
+            last_snapshot_slot: PodU64::from(0),
+            is_active: PodBool::from(is_active),
+            ncn_operator_index: PodU64::from(ncn_operator_index),
+            operator_index: PodU64::from(operator_index),
+            g1_pubkey,
+            has_minimum_stake: PodBool::from(false),
+            has_minimum_stake_next_epoch: PodBool::from(false),
+            stake_weight: StakeWeights::default(),
+            next_epoch_stake_weight: StakeWeights::default(),
+            g2_pubkey,
+            missed_last_vote: PodBool::from(false),
+            vaults_delegated: [Pubkey::default(); MAX_VAULTS],
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        operator: &Pubkey,
+        current_slot: u64,
+        is_active: bool,
+        ncn_operator_index: u64,
+        operator_index: u64,
+        g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+        g2_pubkey: [u8; G2_COMPRESSED_POINT_SIZE],
+        vault_operator_delegation_count: u64,
+    ) -> Result<(), NCNProgramError> {
+        if vault_operator_delegation_count > MAX_VAULTS as u64 {
+            return Err(NCNProgramError::TooManyVaultOperatorDelegations);
+        }
+
+        // Initializes field by field to avoid overflowing stack
+        self.operator = *operator;
+        self.slot_created = PodU64::from(current_slot);
+        self.last_snapshot_slot = PodU64::from(0);
+        self.is_active = PodBool::from(is_active);
+        self.ncn_operator_index = PodU64::from(ncn_operator_index);
+        self.operator_index = PodU64::from(operator_index);
+        self.g1_pubkey = g1_pubkey;
+        self.g2_pubkey = g2_pubkey;
+        self.has_minimum_stake = PodBool::from(false);
+        self.has_minimum_stake_next_epoch = PodBool::from(false);
+        self.stake_weight = StakeWeights::default();
+        self.missed_last_vote = PodBool::from(false);
+        self.vaults_delegated = [Pubkey::default(); MAX_VAULTS];
+
+        Ok(())
+    }
+
+    pub fn ncn_operator_index(&self) -> u64 {
+        self.ncn_operator_index.into()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active.into()
+    }
+
+    pub fn g1_pubkey(&self) -> [u8; G1_COMPRESSED_POINT_SIZE] {
+        self.g1_pubkey
+    }
+
+    pub fn g2_pubkey(&self) -> [u8; G2_COMPRESSED_POINT_SIZE] {
+        self.g2_pubkey
+    }
+
+    pub fn slot_created(&self) -> u64 {
+        self.slot_created.into()
+    }
+
+    pub fn last_snapshot_slot(&self) -> u64 {
+        self.last_snapshot_slot.into()
+    }
+
+    /// Serializes the entry into a version-tagged, borsh-encoded payload followed by a checksum,
+    /// without the rest of the snapshot
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let wire = OperatorSnapshotWire {
+            version: OperatorSnapshotWire::VERSION,
+            operator_snapshot: OperatorSnapshotBackup::from(self),
+        };
+
+        let mut bytes = wire
+            .try_to_vec()
+            .expect("Serializing an operator snapshot into memory cannot fail");
+        let checksum = OperatorSnapshotWire::checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        bytes
+    }
+
+    /// Restores an entry from bytes produced by `to_bytes`. Fails when the checksum does not
+    /// match, so a corrupted or tampered entry is never accepted
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, NCNProgramError> {
+        if bytes.len() <= OperatorSnapshotWire::CHECKSUM_LEN {
+            return Err(NCNProgramError::InvalidOperatorSnapshotBytes);
+        }
+
+        let (payload, checksum) = bytes.split_at(bytes.len() - OperatorSnapshotWire::CHECKSUM_LEN);
+        if OperatorSnapshotWire::checksum(payload) != checksum {
+            msg!("Operator snapshot checksum mismatch");
+            return Err(NCNProgramError::InvalidOperatorSnapshotBytes);
+        }
+
+        if payload[0] != OperatorSnapshotWire::VERSION {
+            msg!("Unsupported operator snapshot version: {}", payload[0]);
+            return Err(NCNProgramError::InvalidOperatorSnapshotBytes);
+        }
+
+        let wire = OperatorSnapshotWire::try_from_slice(payload)
+            .map_err(|_| NCNProgramError::InvalidOperatorSnapshotBytes)?;
+
+        Ok(Self::from(&wire.operator_snapshot))
+    }
+
+    pub fn have_valid_bn128_g1_pubkey(&self) -> bool {
+        self.g1_pubkey != [0u8; 32]
+    }
+
+    pub fn have_valid_bn128_g2_pubkey(&self) -> bool {
+        self.g2_pubkey != [0u8; 64]
+    }
+
+    pub const fn operator(&self) -> &Pubkey {
+        &self.operator
+    }
+
+    pub fn has_minimum_stake(&self) -> bool {
+        self.has_minimum_stake.into()
+    }
+
+    /// Whether the operator counts towards `operators_can_vote_count`: active with the minimum
+    /// stake this epoch
+    pub fn can_vote(&self) -> bool {
+        self.is_active() && self.has_minimum_stake()
+    }
+
+    pub fn has_minimum_stake_now(
+        &self,
+        current_epoch: u64,
+        snapshot_epoch: u64,
+    ) -> Result<bool, NCNProgramError> {
+        let epoch_diff = current_epoch - snapshot_epoch;
+        match epoch_diff {
+            0 => Ok(self.has_minimum_stake.into()),
+            1 => Ok(self.has_minimum_stake_next_epoch.into()),
+            _ => {
+                msg!("Operator snapshot is outdated: {}", self.operator());
+                Err(NCNProgramError::OperatorSnapshotOutdated)
+            }
+        }
+    }
+
+    pub fn has_minimum_stake_next_epoch(&self) -> bool {
+        self.has_minimum_stake_next_epoch.into()
+    }
+
+    /// Whether `ProveNonSigner` proved the operator missed a vote that reached consensus
+    pub fn missed_last_vote(&self) -> bool {
+        self.missed_last_vote.into()
+    }
+
+    pub fn stake_weight(&self) -> &StakeWeights {
+        &self.stake_weight
+    }
+
+    pub fn next_epoch_stake_weight(&self) -> &StakeWeights {
+        &self.next_epoch_stake_weight
+    }
+
+    pub fn set_has_minimum_stake_this_epoch(&mut self, has_minimum_stake: bool) {
+        self.has_minimum_stake = PodBool::from(has_minimum_stake);
+    }
+
+    pub fn set_has_minimum_stake_next_epoch(&mut self, has_minimum_stake: bool) {
+        self.has_minimum_stake_next_epoch = PodBool::from(has_minimum_stake);
+    }
+
+    pub fn set_missed_last_vote(&mut self, missed_last_vote: bool) {
+        self.missed_last_vote = PodBool::from(missed_last_vote);
+    }
+
+    /// The vaults whose delegation was snapshotted in the NCN epoch of `last_snapshot_slot`
+    pub fn vaults_delegated(&self) -> impl Iterator<Item = &Pubkey> {
+        self.vaults_delegated
+            .iter()
+            .filter(|vault| **vault != Pubkey::default())
+    }
+
+    pub fn contains_vault(&self, vault: &Pubkey) -> bool {
+        self.vaults_delegated().any(|delegated| delegated == vault)
+    }
+
+    /// Records that the delegation of `vault` was snapshotted. Fails with
+    /// `DuplicateVaultOperatorDelegation` when it already was, so its stake can't be counted
+    /// twice, and with `TooManyVaultOperatorDelegations` when every entry is used.
+    pub fn insert_vault(&mut self, vault: &Pubkey) -> Result<(), NCNProgramError> {
+        if self.contains_vault(vault) {
+            return Err(NCNProgramError::DuplicateVaultOperatorDelegation);
+        }
+
+        let entry = self
+            .vaults_delegated
+            .iter_mut()
+            .find(|entry| **entry == Pubkey::default())
+            .ok_or(NCNProgramError::TooManyVaultOperatorDelegations)?;
+        *entry = *vault;
+
+        Ok(())
+    }
+
+    pub fn clear_vaults(&mut self) {
+        self.vaults_delegated = [Pubkey::default(); MAX_VAULTS];
+    }
+
+    pub fn set_stake_weight(&mut self, stake_weight_so_far: &StakeWeights) {
+        self.stake_weight = *stake_weight_so_far;
+    }
+
+    pub fn set_next_epoch_stake_weight(&mut self, next_epoch_stake_weight: &StakeWeights) {
+        self.next_epoch_stake_weight = *next_epoch_stake_weight;
+    }
+
+    pub fn snapshot_vault_operator_delegation(
+        &mut self,
+        current_slot: u64,
+        stake_weights: &StakeWeights,
+        next_epoch_stake_weights: &StakeWeights,
+        minimum_stake: &StakeWeights,
+    ) -> Result<(), NCNProgramError> {
+        self.set_stake_weight(stake_weights);
+        self.set_next_epoch_stake_weight(next_epoch_stake_weights);
+
+        self.set_has_minimum_stake_this_epoch(
+            self.stake_weight().stake_weight() >= minimum_stake.stake_weight(),
+        );
+
+        self.set_has_minimum_stake_next_epoch(
+            self.next_epoch_stake_weight().stake_weight() >= minimum_stake.stake_weight(),
+        );
+
+        self.last_snapshot_slot = PodU64::from(current_slot);
+        Ok(())
+    }
+
+    /// Records the delegation of `vault` to this operator. The vaults snapshotted in an earlier
+    /// NCN epoch are forgotten first, then the stake weights of the first vault of the epoch
+    /// replace the previous ones and those of later vaults are added to them. Fails with
+    /// `DuplicateVaultOperatorDelegation` when `vault` was already snapshotted this epoch.
+    pub fn snapshot_vault_delegation(
+        &mut self,
+        vault: &Pubkey,
+        current_slot: u64,
+        ncn_epoch_length: u64,
+        stake_weights: &StakeWeights,
+        next_epoch_stake_weights: &StakeWeights,
+        minimum_stake: &StakeWeights,
+    ) -> Result<(), NCNProgramError> {
+        if get_epoch(self.last_snapshot_slot(), ncn_epoch_length)?
+            < get_epoch(current_slot, ncn_epoch_length)?
+        {
+            self.clear_vaults();
+        }
+
+        let mut stake_weights = *stake_weights;
+        let mut next_epoch_stake_weights = *next_epoch_stake_weights;
+        if self.vaults_delegated().next().is_some() {
+            stake_weights.increment(self.stake_weight())?;
+            next_epoch_stake_weights.increment(self.next_epoch_stake_weight())?;
+        }
+
+        self.insert_vault(vault)?;
+        self.snapshot_vault_operator_delegation(
+            current_slot,
+            &stake_weights,
+            &next_epoch_stake_weights,
+            minimum_stake,
+        )
+    }
+
+    /// Stake weights of a delegation for this epoch and the next one, with the stake scaled by
+    /// the `st_mint_weight` of the vault's mint
+    pub fn calculate_stake_weights(
+        vault_operator_delegation: &VaultOperatorDelegation,
+        st_mint_weight: u128,
+    ) -> Result<(u128, u128), ProgramError> {
+        let total_security = vault_operator_delegation
+            .delegation_state
+            .total_security()?;
+
+        let cooling_down_amount = vault_operator_delegation
+            .delegation_state
+            .cooling_down_amount();
+
+        let precise_total_security = PreciseNumber::new(total_security as u128)
+            .ok_or(NCNProgramError::NewPreciseNumberError)?;
+        let precies_cooling_down_amount = PreciseNumber::new(cooling_down_amount as u128)
+            .ok_or(NCNProgramError::NewPreciseNumberError)?;
+        let precise_next_epoch_securites = precise_total_security
+            .checked_sub(&precies_cooling_down_amount)
+            .ok_or(NCNProgramError::ArithmeticUnderflowError)?;
+
+        let total_stake_weight =
+            Self::apply_st_mint_weight(&precise_total_security, st_mint_weight)?;
+        let next_epoch_stake_weight =
+            Self::apply_st_mint_weight(&precise_next_epoch_securites, st_mint_weight)?;
+
+        Ok((total_stake_weight, next_epoch_stake_weight))
+    }
+
+    /// Scales `amount` by `st_mint_weight / WEIGHT_PRECISION`
+    pub fn apply_st_mint_weight(
+        amount: &PreciseNumber,
+        st_mint_weight: u128,
+    ) -> Result<u128, NCNProgramError> {
+        if st_mint_weight == 0 {
+            return Err(NCNProgramError::WeightNotSet);
+        }
+
+        let precise_weight =
+            PreciseNumber::new(st_mint_weight).ok_or(NCNProgramError::NewPreciseNumberError)?;
+        let precise_weight_precision =
+            PreciseNumber::new(WEIGHT_PRECISION).ok_or(NCNProgramError::NewPreciseNumberError)?;
+
+        amount
+            .checked_mul(&precise_weight)
+            .ok_or(NCNProgramError::ArithmeticOverflow)?
+            .checked_div(&precise_weight_precision)
+            .ok_or(NCNProgramError::DenominatorIsZero)?
+            .to_imprecise()
+            .ok_or(NCNProgramError::CastToImpreciseNumberError)
+    }
+
+    pub fn update_g1_pubkey(&mut self, g1_pubkey: &[u8; G1_COMPRESSED_POINT_SIZE]) {
+        self.g1_pubkey = *g1_pubkey;
+    }
+
+    pub fn update_g2_pubkey(&mut self, g2_pubkey: &[u8; G2_COMPRESSED_POINT_SIZE]) {
+        self.g2_pubkey = *g2_pubkey;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Zeroable, Pod, ShankType)]
+#[repr(C)]
+pub struct VaultOperatorStakeWeight {
+    vault: Pubkey,
+    vault_index: PodU64,
+    stake_weight: StakeWeights,
+}
+
+impl Default for VaultOperatorStakeWeight {
+    fn default() -> Self {
+        Self {
+            vault: Pubkey::default(),
+            vault_index: PodU64::from(u64::MAX),
+            stake_weight: StakeWeights::default(),
+        }
+    }
+}
+
+impl VaultOperatorStakeWeight {
+    pub fn new(vault: &Pubkey, vault_index: u64, stake_weight: &StakeWeights) -> Self {
+        Self {
+            vault: *vault,
+            vault_index: PodU64::from(vault_index),
+            stake_weight: *stake_weight,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vault_index() == u64::MAX
+    }
+
+    pub fn vault_index(&self) -> u64 {
+        self.vault_index.into()
+    }
+
+    pub const fn stake_weights(&self) -> &StakeWeights {
+        &self.stake_weight
+    }
+
+    pub const fn vault(&self) -> &Pubkey {
+        &self.vault
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n\n----------- Snapshot -------------")?;
+        writeln!(f, "  NCN:                          {}", self.ncn)?;
+        writeln!(f, "  Bump:                         {}", self.bump)?;
+        writeln!(f, "  Version:                      {}", self.version)?;
+        writeln!(
+            f,
+            "  Operators Registered:         {}",
+            self.operators_registered()
+        )?;
+        writeln!(
+            f,
+            "  Operators can vote:           {}",
+            self.operators_can_vote_count()
+        )?;
+        writeln!(
+            f,
+            "  Last Snapshot Slot:           {}",
+            self.last_snapshot_slot()
+        )?;
+        writeln!(
+            f,
+            "  Slot Finalized:               {}",
+            self.slot_finalized()
+        )?;
+        writeln!(
+            f,
+            "  Operators Deregistered:       {}",
+            self.operators_deregistered()
+        )?;
+        writeln!(
+            f,
+            "  Total Agg G1 Pubkey:          {:?}",
+            self.total_aggregated_g1_pubkey()
+        )?;
+        writeln!(
+            f,
+            "  Minimum Stake Weight:         {:?}",
+            self.minimum_stake()
+        )?;
+        writeln!(f, "  operators snapshots:")?;
+        for operator_snapshot in self.operator_snapshots.iter() {
+            if operator_snapshot.ncn_operator_index() != u64::MAX {
+                writeln!(f, "{}", operator_snapshot)?;
+            }
+        }
+
+        writeln!(f, "\n")?;
+        Ok(())
+    }
+}
+
+#[rustfmt::skip]
+impl fmt::Display for OperatorSnapshot {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+       writeln!(f, "\n\n----------- Operator Snapshot -------------")?;
+       writeln!(f, "  Operator:                     {}", self.operator)?;
+       writeln!(f, "  Is Active:                    {}", self.is_active())?;
+       writeln!(f, "  NCN Operator Index:           {}", self.ncn_operator_index())?;
+       writeln!(f, "  Slot Last Snapshoted:         {}", self.last_snapshot_slot())?;
+       writeln!(f, "  G1 Pubkey:                    {:?}", self.g1_pubkey())?;
+       writeln!(f, "  G2 Pubkey:                    {:?}", self.g2_pubkey())?;
+       writeln!(f, "  Has Minimum Stake Weight:     {}", self.has_minimum_stake())?;
+       writeln!(f, "  Has Minimum next epoch:       {}", self.has_minimum_stake_next_epoch())?;
+       writeln!(f, "  Stake Weight:                 {:?}", self.stake_weight())?;
+       writeln!(f, "  Next Epoch Stake Weight:      {:?}", self.next_epoch_stake_weight())?;
+       writeln!(f, "  Missed Last Vote:             {}", self.missed_last_vote())?;
+       writeln!(f, "  Vaults Delegated:             {}", self.vaults_delegated().count())?;
+
+       writeln!(f, "\n")?;
+       Ok(())
+   }
+}
+
+/// Serializes the registered operators only, with the aggregated G1 pubkey as compressed hex
+#[cfg(feature = "serde")]
+impl serde::Serialize for Snapshot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let operators: Vec<&OperatorSnapshot> = self
+            .operator_snapshots
+            .iter()
+            .filter(|operator_snapshot| operator_snapshot.ncn_operator_index() != u64::MAX)
+            .collect();
+
+        let mut state = serializer.serialize_struct("Snapshot", 11)?;
+        state.serialize_field("ncn", &self.ncn.to_string())?;
+        state.serialize_field("version", &self.version)?;
+        state.serialize_field("slot_created", &self.slot_created())?;
+        state.serialize_field("last_snapshot_slot", &self.last_snapshot_slot())?;
+        state.serialize_field("slot_finalized", &self.slot_finalized())?;
+        state.serialize_field("operators_registered", &self.operators_registered())?;
+        state.serialize_field("operators_can_vote_count", &self.operators_can_vote_count())?;
+        state.serialize_field("operators_deregistered", &self.operators_deregistered())?;
+        state.serialize_field(
+            "total_aggregated_g1_pubkey",
+            &G1CompressedPoint(self.total_aggregated_g1_pubkey).to_hex(),
+        )?;
+        state.serialize_field("minimum_stake", &self.minimum_stake)?;
+        state.serialize_field("operators", &operators)?;
+        state.end()
+    }
+}
+
+/// Serializes the operator's pubkeys as compressed hex
+#[cfg(feature = "serde")]
+impl serde::Serialize for OperatorSnapshot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let vaults_delegated: Vec<String> = self
+            .vaults_delegated()
+            .map(|vault| vault.to_string())
+            .collect();
+
+        let mut state = serializer.serialize_struct("OperatorSnapshot", 14)?;
+        state.serialize_field("operator", &self.operator.to_string())?;
+        state.serialize_field("ncn_operator_index", &self.ncn_operator_index())?;
+        state.serialize_field("operator_index", &u64::from(self.operator_index))?;
+        state.serialize_field("is_active", &self.is_active())?;
+        state.serialize_field("slot_created", &self.slot_created())?;
+        state.serialize_field("last_snapshot_slot", &self.last_snapshot_slot())?;
+        state.serialize_field("g1_pubkey", &G1CompressedPoint(self.g1_pubkey).to_hex())?;
+        state.serialize_field(
+            "g2_pubkey",
+            &crate::g2_point::G2CompressedPoint(self.g2_pubkey).to_hex(),
+        )?;
+        state.serialize_field("has_minimum_stake", &self.has_minimum_stake())?;
+        state.serialize_field(
+            "has_minimum_stake_next_epoch",
+            &self.has_minimum_stake_next_epoch(),
+        )?;
+        state.serialize_field("stake_weight", &self.stake_weight)?;
+        state.serialize_field("next_epoch_stake_weight", &self.next_epoch_stake_weight)?;
+        state.serialize_field("missed_last_vote", &self.missed_last_vote())?;
+        state.serialize_field("vaults_delegated", &vaults_delegated)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::msg;
+
+    use super::*;
+    use crate::privkey::PrivKey;
+
+    #[test]
+    fn test_operator_snapshot_size() {
+        use std::mem::size_of;
+
+        let expected_total = size_of::<Pubkey>() // operator
+            + size_of::<[u8; G1_COMPRESSED_POINT_SIZE]>() // g1_pubkey
+            + size_of::<PodU64>() // slot_created
+            + size_of::<PodU64>() // slot_last_snapshoted
+            + size_of::<PodBool>() // is_active
+            + size_of::<PodU64>() // ncn_operator_index
+            + size_of::<PodU64>() // operator_index
+            + size_of::<PodBool>() // has_minimum_stake
+            + size_of::<PodBool>() // has_minimum_stake_next_epoch
+            + size_of::<StakeWeights>() // stake_weight
+            + size_of::<StakeWeights>() // next_epoch_stake_weight
+            + size_of::<[u8; G2_COMPRESSED_POINT_SIZE]>() // g2_pubkey
+            + size_of::<PodBool>() // missed_last_vote
+            + size_of::<[Pubkey; MAX_VAULTS]>(); // vaults_delegated
+
+        assert_eq!(size_of::<OperatorSnapshot>(), expected_total);
+    }
+
+    #[test]
+    fn test_snapshot_size() {
+        use std::mem::size_of;
+
+        msg!("Snapshot size: {:?}", size_of::<Snapshot>());
+
+        let expected_total = size_of::<Pubkey>() // ncn
+            + size_of::<u8>() // bump
+            + size_of::<u8>() // version
+            + size_of::<PodU64>() // slot_created
+            + size_of::<PodU64>() // last_snapshot_slot
+            + size_of::<PodU64>() // operators_registered
             + size_of::<PodU64>() // operators_can_vote_count
             + size_of::<[u8; G1_COMPRESSED_POINT_SIZE]>() // total_aggregated_g1_pubkey
-            + size_of::<[OperatorSnapshot; 256]>() // operator_snapshots
-            + size_of::<StakeWeights>(); // minimum_stake
+            + size_of::<[OperatorSnapshot; MAX_OPERATORS]>() // operator_snapshots
+            + size_of::<StakeWeights>() // minimum_stake
+            + size_of::<PodU64>() // last_rollover_epoch
+            + size_of::<PodU64>() // slot_finalized
+            + size_of::<[u8; MAX_SIGNER_BITMAP_BYTES]>(); // deregistered_operators
+
+        assert_eq!(size_of::<Snapshot>(), expected_total);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_version() {
+        let program_id = Pubkey::new_unique();
+        let ncn = Pubkey::new_unique();
+        let (address, bump, _) = Snapshot::find_program_address(&program_id, &ncn);
+
+        let mut data = vec![0u8; Snapshot::SIZE];
+        data[0] = Snapshot::DISCRIMINATOR;
+        Snapshot::try_from_slice_unchecked_mut(&mut data)
+            .unwrap()
+            .initialize(&ncn, bump, 0, StakeWeights::new(1));
+
+        let mut lamports = 0;
+        let account = AccountInfo::new(
+            &address,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert!(Snapshot::load(&program_id, &account, &ncn, true).is_ok());
+
+        {
+            let mut account_data = account.data.borrow_mut();
+            let snapshot = Snapshot::try_from_slice_unchecked_mut(&mut account_data).unwrap();
+            assert_eq!(snapshot.version(), Snapshot::VERSION);
+            snapshot.version = Snapshot::VERSION + 1;
+        }
+
+        assert_eq!(
+            Snapshot::load(&program_id, &account, &ncn, true).unwrap_err(),
+            ProgramError::from(NCNProgramError::SnapshotVersionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_vault_operator_stake_weight_is_empty() {
+        // Test default (should be empty)
+        let default_weight = VaultOperatorStakeWeight::default();
+        assert!(default_weight.is_empty());
+
+        // Test non-empty case
+        let non_empty_weight =
+            VaultOperatorStakeWeight::new(&Pubkey::new_unique(), 1, &StakeWeights::default());
+        assert!(!non_empty_weight.is_empty());
+    }
+
+    #[test]
+    fn test_operator_snapshot_initialize_active_inactive() {
+        let current_slot = 100;
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Create two operator snapshots - one for active and one for inactive
+        let mut active_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            current_slot,
+            true,
+            0,
+            0,
+            g1_pubkey,
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        let mut inactive_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            current_slot,
+            false,
+            0,
+            0,
+            g1_pubkey,
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Initialize active snapshot
+        active_snapshot
+            .initialize(
+                &Pubkey::new_unique(),
+                current_slot,
+                true, // is_active
+                0,
+                0,
+                g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+                1,
+            )
+            .unwrap();
+
+        // Initialize inactive snapshot
+        inactive_snapshot
+            .initialize(
+                &Pubkey::new_unique(),
+                current_slot,
+                false, // not active
+                0,
+                0,
+                g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+                1,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_operator_snapshots_aggregates_every_g1_pubkey() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        let operator_count = MAX_OPERATORS.min(32);
+        let g1_points: Vec<G1Point> = (0..operator_count)
+            .map(|_| G1Point::try_from(PrivKey::from_random()).unwrap())
+            .collect();
+        let operator_snapshots: Vec<OperatorSnapshot> = g1_points
+            .iter()
+            .enumerate()
+            .map(|(i, g1_point)| {
+                OperatorSnapshot::new(
+                    &Pubkey::new_unique(),
+                    100,
+                    true,
+                    i as u64,
+                    u64::MAX,
+                    G1CompressedPoint::try_from(*g1_point).unwrap().0,
+                    [0; G2_COMPRESSED_POINT_SIZE],
+                )
+                .unwrap()
+            })
+            .collect();
+
+        // Two operators at the same index are rejected before anything is inserted
+        let mut duplicated = operator_snapshots[..2].to_vec();
+        duplicated[1] = operator_snapshots[0];
+        assert_eq!(
+            snapshot.add_operator_snapshots(&duplicated, 100),
+            Err(NCNProgramError::DuplicateOperatorIndex)
+        );
+        assert_eq!(snapshot.operators_registered(), 0);
+
+        snapshot
+            .add_operator_snapshots(&operator_snapshots, 100)
+            .unwrap();
+
+        assert_eq!(snapshot.operators_registered(), operator_count as u64);
+        let expected_total = g1_points.into_iter().reduce(|acc, x| acc + x).unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            G1CompressedPoint::try_from(expected_total).unwrap().0
+        );
+
+        // Indices already in the snapshot are still rejected
+        assert_eq!(
+            snapshot.add_operator_snapshots(&operator_snapshots[..1], 100),
+            Err(NCNProgramError::DuplicateVaultOperatorDelegation)
+        );
+    }
+
+    #[test]
+    fn test_add_g1_pubkey_to_total_aggregated_and_subtract() {
+        // Create a snapshot
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+        // Initial aggregated is zero
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            [0u8; G1_COMPRESSED_POINT_SIZE]
+        );
+
+        // Add first pubkey
+        let pk1 = G1CompressedPoint::from_random().0;
+        snapshot.add_g1_pubkey_to_total_agg(&pk1).unwrap();
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), pk1);
+
+        // Add second pubkey
+        let pk2 = G1CompressedPoint::from_random().0;
+        snapshot.add_g1_pubkey_to_total_agg(&pk2).unwrap();
+        let after_add = snapshot.total_aggregated_g1_pubkey();
+        assert_ne!(after_add, pk1);
+        assert_ne!(after_add, pk2);
+
+        // Only keys held by a registered operator snapshot can be subtracted
+        for (index, g1_pubkey) in [pk1, pk2].iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                index as u64,
+                index as u64,
+                *g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        // Subtract second pubkey
+        snapshot.subtract_g1_pubkey_from_total_agg(&pk2).unwrap();
+        let after_subtract = snapshot.total_aggregated_g1_pubkey();
+        assert_eq!(after_subtract, pk1);
+
+        // Subtract first pubkey, should give zero point (identity)
+        snapshot.subtract_g1_pubkey_from_total_agg(&pk1).unwrap();
+        let after_zero = snapshot.total_aggregated_g1_pubkey();
+        assert_eq!(after_zero, G1CompressedPoint::default().0);
+    }
+
+    #[test]
+    fn test_subtract_g1_pubkey_not_in_aggregate() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,
+            true,
+            0,
+            0,
+            g1_pubkey,
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 100)
+            .unwrap();
+        snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+
+        // An unrelated key is rejected and the total is left untouched
+        let unrelated_g1_pubkey = G1CompressedPoint::from_random().0;
+        assert!(!snapshot.is_g1_pubkey_in_total_agg(&unrelated_g1_pubkey));
+        assert_eq!(
+            snapshot.subtract_g1_pubkey_from_total_agg(&unrelated_g1_pubkey),
+            Err(NCNProgramError::G1PubkeyNotInAggregate)
+        );
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), g1_pubkey);
+
+        snapshot
+            .subtract_g1_pubkey_from_total_agg(&g1_pubkey)
+            .unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            G1CompressedPoint::default().0
+        );
+    }
+
+    #[test]
+    fn test_replace_operator_g1_pubkey() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        let pk1 = G1CompressedPoint::from_random().0;
+        let pk2 = G1CompressedPoint::from_random().0;
+        let pk3 = G1CompressedPoint::from_random().0;
+        let rotated_pk2 = G1CompressedPoint::from_random().0;
+        for pk in [pk1, pk2, pk3] {
+            snapshot.add_g1_pubkey_to_total_agg(&pk).unwrap();
+        }
+
+        // The same total built from scratch with the rotated key
+        let mut expected = Snapshot::new(&Pubkey::new_unique(), 1, 100, StakeWeights::new(1));
+        for pk in [pk1, rotated_pk2, pk3] {
+            expected.add_g1_pubkey_to_total_agg(&pk).unwrap();
+        }
+
+        snapshot
+            .replace_operator_g1_pubkey(&pk2, &rotated_pk2)
+            .unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            expected.total_aggregated_g1_pubkey()
+        );
+    }
+
+    #[test]
+    fn test_replace_operator_g1_pubkey_invalid_keeps_total() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        let pk1 = G1CompressedPoint::from_random().0;
+        let pk2 = G1CompressedPoint::from_random().0;
+        snapshot.add_g1_pubkey_to_total_agg(&pk1).unwrap();
+        snapshot.add_g1_pubkey_to_total_agg(&pk2).unwrap();
+        let total = snapshot.total_aggregated_g1_pubkey();
+
+        let mut invalid_pubkey = [0u8; G1_COMPRESSED_POINT_SIZE];
+        invalid_pubkey[0] = 1;
+
+        // An invalid new key
+        assert_eq!(
+            snapshot.replace_operator_g1_pubkey(&pk2, &invalid_pubkey),
+            Err(NCNProgramError::InvalidG1Pubkey)
+        );
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), total);
+
+        // An invalid old key
+        assert_eq!(
+            snapshot.replace_operator_g1_pubkey(&invalid_pubkey, &pk2),
+            Err(NCNProgramError::InvalidG1Pubkey)
+        );
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), total);
+    }
+
+    #[test]
+    fn test_deregister_operator() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let g1_pubkeys: Vec<[u8; G1_COMPRESSED_POINT_SIZE]> =
+            (0..3).map(|_| G1CompressedPoint::from_random().0).collect();
+
+        for (index, (operator, g1_pubkey)) in operators.iter().zip(&g1_pubkeys).enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                true,
+                index as u64,
+                index as u64,
+                *g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+            snapshot.register_operator_g1_pubkey(g1_pubkey).unwrap();
+        }
+
+        snapshot.deregister_operator(&operators[1], 200).unwrap();
+
+        assert_eq!(snapshot.operators_registered(), 2);
+        assert_eq!(snapshot.last_snapshot_slot(), 200);
+        assert!(snapshot.find_operator_snapshot(&operators[1]).is_none());
+        assert_eq!(
+            snapshot.operator_snapshots()[1].ncn_operator_index(),
+            u64::MAX
+        );
+
+        // The aggregate is now the sum of the remaining operators' keys
+        let remaining = G1Point::try_from(&G1CompressedPoint(g1_pubkeys[0])).unwrap()
+            + G1Point::try_from(&G1CompressedPoint(g1_pubkeys[2])).unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            G1CompressedPoint::try_from(remaining).unwrap().0
+        );
+
+        // The remaining operators keep their slot, so signer bitmaps still span 3 bits
+        assert_eq!(snapshot.operator_slot_count(), 3);
+        assert!(snapshot.get_operator_snapshot(1).is_none());
+        assert_eq!(
+            snapshot
+                .registered_operator_snapshots()
+                .map(|(slot, _)| slot)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        let signer_bitmap = [0b101];
+        assert_eq!(
+            snapshot
+                .aggregate_signers_by_addition(&signer_bitmap)
+                .unwrap()
+                .0,
+            remaining.0
+        );
+        assert_eq!(
+            snapshot.aggregate_signers_by_subtraction(&[0]).unwrap().0,
+            remaining.0
+        );
+        assert!(snapshot.non_signers(&signer_bitmap).is_empty());
+
+        // An operator can't be deregistered twice
+        assert_eq!(
+            snapshot.deregister_operator(&operators[1], 200),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
+        );
+        assert_eq!(
+            snapshot.deregister_operator(&Pubkey::new_unique(), 200),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
+        );
+        assert_eq!(snapshot.operators_registered(), 2);
+    }
+
+    #[test]
+    fn test_register_operator_g1_pubkey() {
+        // Create a snapshot with default aggregated G1 pubkey (all zeros) using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Verify initial state - total_aggregated_g1_pubkey should be all zeros
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            [0u8; G1_COMPRESSED_POINT_SIZE]
+        );
+
+        // Generate a random G1 pubkey
+        let operator_g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Register the operator's G1 pubkey
+        let result = snapshot.register_operator_g1_pubkey(&operator_g1_pubkey);
+        assert!(result.is_ok());
+
+        // Verify the aggregated G1 pubkey is no longer all zeros
+        assert_ne!(
+            snapshot.total_aggregated_g1_pubkey(),
+            [0u8; G1_COMPRESSED_POINT_SIZE]
+        );
+
+        // The aggregated pubkey should now equal the first operator's pubkey
+        // since we started with zero point (identity element for addition)
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), operator_g1_pubkey);
+    }
+
+    #[test]
+    fn test_register_multiple_operator_g1_pubkeys() {
+        // Create a snapshot using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Generate multiple random G1 pubkeys
+        let operator1_g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator2_g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator3_g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Register first operator's G1 pubkey
+        snapshot
+            .register_operator_g1_pubkey(&operator1_g1_pubkey)
+            .unwrap();
+        let after_first = snapshot.total_aggregated_g1_pubkey();
+
+        // Register second operator's G1 pubkey
+        snapshot
+            .register_operator_g1_pubkey(&operator2_g1_pubkey)
+            .unwrap();
+        let after_second = snapshot.total_aggregated_g1_pubkey();
+
+        // Register third operator's G1 pubkey
+        snapshot
+            .register_operator_g1_pubkey(&operator3_g1_pubkey)
+            .unwrap();
+        let after_third = snapshot.total_aggregated_g1_pubkey();
+
+        // Verify that each registration changes the aggregated pubkey
+        assert_ne!(after_first, [0u8; G1_COMPRESSED_POINT_SIZE]);
+        assert_ne!(after_second, after_first);
+        assert_ne!(after_third, after_second);
+
+        // Manually compute the expected aggregated result to verify correctness
+        let point1 = G1Point::try_from(&G1CompressedPoint(operator1_g1_pubkey)).unwrap();
+        let point2 = G1Point::try_from(&G1CompressedPoint(operator2_g1_pubkey)).unwrap();
+        let point3 = G1Point::try_from(&G1CompressedPoint(operator3_g1_pubkey)).unwrap();
+
+        let expected_aggregate = point1 + point2 + point3;
+        let expected_compressed = G1CompressedPoint::try_from(expected_aggregate).unwrap();
+
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), expected_compressed.0);
+    }
+
+    #[test]
+    fn test_operator_snapshot_g1_pubkey_storage() {
+        // Generate a random G1 pubkey
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Create an operator snapshot with the G1 pubkey
+        let operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Verify the G1 pubkey is stored correctly
+        assert_eq!(operator_snapshot.g1_pubkey, g1_pubkey);
+    }
+
+    #[test]
+    fn test_operator_snapshot_initialize_g1_pubkey() {
+        // Generate two different G1 pubkeys
+        let original_g1_pubkey = G1CompressedPoint::from_random().0;
+        let new_g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Create an operator snapshot with the original G1 pubkey
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,                // current_slot
+            true,               // is_active
+            0,                  // ncn_operator_index
+            0,                  // operator_index
+            original_g1_pubkey, // original g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Initialize with a new G1 pubkey
+        operator_snapshot
+            .initialize(
+                &Pubkey::new_unique(),
+                100,           // current_slot
+                true,          // is_active
+                0,             // ncn_operator_index
+                0,             // operator_index
+                new_g1_pubkey, // new g1_pubkey
+                [0; G2_COMPRESSED_POINT_SIZE],
+                1,
+            )
+            .unwrap();
+
+        // Verify the G1 pubkey was updated during initialization
+        assert_eq!(operator_snapshot.g1_pubkey, new_g1_pubkey);
+        assert_ne!(operator_snapshot.g1_pubkey, original_g1_pubkey);
+    }
+
+    #[test]
+    fn test_snapshot_aggregation_order_independence() {
+        // Test that G1 pubkey aggregation is order-independent (commutative)
+        let operator1_g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator2_g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Create two snapshots using heap allocation to avoid stack overflow
+        let mut snapshot1 = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        // Register operators in different orders
+        // Snapshot 1: operator1 first, then operator2
+        snapshot1
+            .register_operator_g1_pubkey(&operator1_g1_pubkey)
+            .unwrap();
+        snapshot1
+            .register_operator_g1_pubkey(&operator2_g1_pubkey)
+            .unwrap();
+
+        let mut snapshot2 = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+        // Snapshot 2: operator2 first, then operator1
+        snapshot2
+            .register_operator_g1_pubkey(&operator2_g1_pubkey)
+            .unwrap();
+        snapshot2
+            .register_operator_g1_pubkey(&operator1_g1_pubkey)
+            .unwrap();
+
+        // Both should result in the same aggregated G1 pubkey
+        assert_eq!(
+            snapshot1.total_aggregated_g1_pubkey(),
+            snapshot2.total_aggregated_g1_pubkey()
+        );
+    }
+
+    #[test]
+    fn test_snapshot_g1_pubkey_getter() {
+        // Create a snapshot using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Initially should be all zeros
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            [0u8; G1_COMPRESSED_POINT_SIZE]
+        );
+
+        // Register an operator G1 pubkey
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+
+        // Verify getter returns the updated value
+        assert_eq!(snapshot.total_aggregated_g1_pubkey(), g1_pubkey);
+    }
+
+    #[test]
+    fn test_snapshot_add_operator_snapshot() {
+        // Create a snapshot using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Create an operator snapshot
+        let operator_pubkey = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = OperatorSnapshot::new(
+            &operator_pubkey,
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Add the operator snapshot to the snapshot
+        let result = snapshot.add_operator_snapshot(operator_snapshot, 100);
+        assert!(result.is_ok());
+
+        {
+            // Verify the operator snapshot was added using its index
+            let retrieved_snapshot = snapshot.get_operator_snapshot(0);
+            let test = snapshot.operator_snapshots[0];
+            assert!(retrieved_snapshot.is_some());
+            assert_eq!(retrieved_snapshot.unwrap().operator(), &operator_pubkey);
+        }
+
+        {
+            // Verify the operator snapshot was added using its id
+            let retrieved_snapshot = snapshot.find_operator_snapshot(&operator_pubkey);
+            assert!(retrieved_snapshot.is_some());
+            assert_eq!(retrieved_snapshot.unwrap().operator(), &operator_pubkey);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_find_operator_snapshot() {
+        // Create a snapshot using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Create operator snapshots
+        let operator1_pubkey = Pubkey::new_unique();
+        let operator2_pubkey = Pubkey::new_unique();
+        let g1_pubkey_1 = G1CompressedPoint::from_random().0;
+        let g1_pubkey_2 = G1CompressedPoint::from_random().0;
+
+        let operator1_snapshot = OperatorSnapshot::new(
+            &operator1_pubkey,
+            100,         // current_slot
+            true,        // is_active
+            0,           // ncn_operator_index (index 0)
+            0,           // operator_index
+            g1_pubkey_1, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        let operator2_snapshot = OperatorSnapshot::new(
+            &operator2_pubkey,
+            100,         // current_slot
+            true,        // is_active
+            1,           // ncn_operator_index (index 1)
+            1,           // operator_index
+            g1_pubkey_2, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Add both operator snapshots
+        snapshot
+            .add_operator_snapshot(operator1_snapshot, 100)
+            .unwrap();
+        snapshot
+            .add_operator_snapshot(operator2_snapshot, 110)
+            .unwrap();
+
+        // Find operator snapshots by pubkey
+        let found1 = snapshot.find_operator_snapshot(&operator1_pubkey);
+        let found2 = snapshot.find_operator_snapshot(&operator2_pubkey);
+        let not_found = snapshot.find_operator_snapshot(&Pubkey::new_unique());
+
+        assert!(found1.is_some());
+        assert_eq!(found1.unwrap().operator(), &operator1_pubkey);
+
+        assert!(found2.is_some());
+        assert_eq!(found2.unwrap().operator(), &operator2_pubkey);
+
+        assert!(not_found.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_add_operator_snapshot_duplicate() {
+        // Create a snapshot using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Create two operator snapshots with the same index
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator1_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        let operator2_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index (same as operator1)
+            1,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Add first operator snapshot - should succeed
+        let result1 = snapshot.add_operator_snapshot(operator1_snapshot, 100);
+        assert!(result1.is_ok());
+
+        // Try to add second operator snapshot with same index - should fail
+        let result2 = snapshot.add_operator_snapshot(operator2_snapshot, 100);
+        assert!(result2.is_err());
+        assert_eq!(
+            result2.unwrap_err(),
+            NCNProgramError::DuplicateVaultOperatorDelegation
+        );
+    }
+
+    #[test]
+    fn test_snapshot_add_operator_snapshot_duplicate_g1_pubkey() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = |index: u64| {
+            OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,   // current_slot
+                true,  // is_active
+                index, // ncn_operator_index
+                index, // operator_index
+                g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap()
+        };
+
+        snapshot
+            .add_operator_snapshot(operator_snapshot(0), 100)
+            .unwrap();
+
+        // A second operator with the same G1 pubkey is rejected
+        assert_eq!(
+            snapshot.add_operator_snapshot(operator_snapshot(1), 100),
+            Err(NCNProgramError::DuplicateOperatorG1Pubkey)
+        );
+        assert_eq!(snapshot.operators_registered(), 1);
+
+        // Once the first operator is deregistered the G1 pubkey is free again
+        snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+        let first_operator = *snapshot.get_operator_snapshot(0).unwrap().operator();
+        snapshot.deregister_operator(&first_operator, 110).unwrap();
+        snapshot
+            .add_operator_snapshot(operator_snapshot(1), 120)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_get_active_operator_snapshots() {
+        // Create a snapshot using heap allocation
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Create active operator snapshot
+        let active_operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,                                // current_slot
+            true,                               // is_active = true
+            0,                                  // ncn_operator_index
+            0,                                  // operator_index
+            G1CompressedPoint::from_random().0, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Create inactive operator snapshot
+        let inactive_operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,                                // current_slot
+            false,                              // is_active = false
+            1,                                  // ncn_operator_index
+            1,                                  // operator_index
+            G1CompressedPoint::from_random().0, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        // Add both operator snapshots
+        snapshot
+            .add_operator_snapshot(active_operator_snapshot, 100)
+            .unwrap();
+        snapshot
+            .add_operator_snapshot(inactive_operator_snapshot, 100)
+            .unwrap();
+
+        // Get active operator snapshots
+        let active_snapshots = snapshot.get_active_operator_snapshots();
+
+        // Should only return the active one
+        assert_eq!(active_snapshots.len(), 1);
+        assert!(active_snapshots[0].is_active());
+        assert_eq!(active_snapshots[0].ncn_operator_index(), 0);
+    }
+
+    #[test]
+    fn test_is_single_operator() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            100,
+            StakeWeights::new(100),
+        ));
+        assert!(!snapshot.is_single_operator());
+
+        for index in 0..2 {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                index,
+                index,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+
+            assert_eq!(snapshot.is_single_operator(), index == 0);
+        }
+    }
+
+    #[test]
+    fn test_record_finalization() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+        assert!(!snapshot.finalized());
+        assert_eq!(snapshot.slot_finalized(), u64::MAX);
+
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let register_operator = |snapshot: &mut Snapshot, index: usize, slot: u64| {
+            let g1_pubkey = G1CompressedPoint::from_random().0;
+            let operator_snapshot = OperatorSnapshot::new(
+                &operators[index],
+                slot,
+                true,
+                index as u64,
+                index as u64,
+                g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, slot)
+                .unwrap();
+            snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+        };
+
+        // Only the registration of the last operator finalizes the snapshot
+        for index in 0..3 {
+            let slot = 200 + index as u64;
+            register_operator(&mut snapshot, index, slot);
+            assert_eq!(snapshot.record_finalization(3, slot), index == 2);
+        }
+        assert!(snapshot.finalized());
+        assert_eq!(snapshot.slot_finalized(), 202);
+
+        // Finalizes once
+        assert!(!snapshot.record_finalization(3, 300));
+        assert_eq!(snapshot.slot_finalized(), 202);
+
+        // A deregistered operator still counts toward the NCN's operators, so the snapshot
+        // stays finalized, and registering it again does not finalize it twice
+        snapshot.deregister_operator(&operators[1], 310).unwrap();
+        assert_eq!(snapshot.operators_deregistered(), 1);
+        assert!(snapshot.has_every_operator(3));
+        assert!(snapshot.finalized());
+        register_operator(&mut snapshot, 1, 320);
+        assert_eq!(snapshot.operators_deregistered(), 0);
+        assert!(!snapshot.record_finalization(3, 320));
+        assert_eq!(snapshot.slot_finalized(), 202);
+
+        // Preserving the operators keeps the finalization, a full reset clears it
+        snapshot.deregister_operator(&operators[2], 330).unwrap();
+        snapshot.reset_for_new_epoch(400, StakeWeights::new(1), true);
+        assert_eq!(snapshot.slot_finalized(), 202);
+        assert_eq!(snapshot.operators_deregistered(), 1);
+        snapshot.reset_for_new_epoch(500, StakeWeights::new(1), false);
+        assert!(!snapshot.finalized());
+        assert_eq!(snapshot.operators_deregistered(), 0);
+
+        // An operator deregistered before the last registration counts toward finalization
+        register_operator(&mut snapshot, 0, 600);
+        register_operator(&mut snapshot, 1, 601);
+        snapshot.deregister_operator(&operators[0], 602).unwrap();
+        assert!(!snapshot.record_finalization(3, 602));
+        register_operator(&mut snapshot, 2, 603);
+        assert!(snapshot.record_finalization(3, 603));
+        assert_eq!(snapshot.slot_finalized(), 603);
+    }
+
+    #[test]
+    fn test_reset_for_new_epoch() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                      // bump
+            100,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        ));
+
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let g1_pubkeys: Vec<[u8; G1_COMPRESSED_POINT_SIZE]> =
+            (0..3).map(|_| G1CompressedPoint::from_random().0).collect();
+
+        // Registers every operator with `stake_weight` as of `slot`
+        let register_operators = |snapshot: &mut Snapshot, stake_weight: u128, slot: u64| {
+            for (index, operator) in operators.iter().enumerate() {
+                let mut operator_snapshot = OperatorSnapshot::new(
+                    operator,
+                    slot,
+                    true,
+                    index as u64,
+                    index as u64,
+                    g1_pubkeys[index],
+                    [0; G2_COMPRESSED_POINT_SIZE],
+                )
+                .unwrap();
+                operator_snapshot
+                    .snapshot_vault_operator_delegation(
+                        slot,
+                        &StakeWeights::new(stake_weight),
+                        &StakeWeights::new(stake_weight),
+                        snapshot.minimum_stake(),
+                    )
+                    .unwrap();
+                snapshot
+                    .add_operator_snapshot(operator_snapshot, slot)
+                    .unwrap();
+                snapshot
+                    .register_operator_g1_pubkey(&g1_pubkeys[index])
+                    .unwrap();
+            }
+        };
+
+        // First epoch
+        register_operators(&mut snapshot, 150, 100);
+        assert_eq!(snapshot.operators_registered(), 3);
+        assert_eq!(snapshot.operators_can_vote_count(), 3);
+        let total_aggregated_g1_pubkey = snapshot.total_aggregated_g1_pubkey();
+
+        // A full reset frees every slot, so the same operators register again
+        snapshot.reset_for_new_epoch(200, StakeWeights::new(200), false);
+        assert_eq!(snapshot.operators_registered(), 0);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
+        assert_eq!(snapshot.last_snapshot_slot(), 200);
+        assert_eq!(snapshot.slot_created(), 100);
+        assert_eq!(snapshot.minimum_stake().stake_weight(), 200);
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            [0; G1_COMPRESSED_POINT_SIZE]
+        );
+        assert!(snapshot.find_operator_snapshot(&operators[0]).is_none());
+
+        // Second epoch, below the raised minimum stake
+        register_operators(&mut snapshot, 150, 200);
+        assert_eq!(snapshot.operators_registered(), 3);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            total_aggregated_g1_pubkey
+        );
+
+        // Preserving the operators keeps their slots and keys but clears their stake
+        snapshot
+            .find_mut_operator_snapshot(&operators[1])
+            .unwrap()
+            .set_missed_last_vote(true);
+        snapshot.reset_for_new_epoch(300, StakeWeights::new(100), true);
+        assert_eq!(snapshot.operators_registered(), 3);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
+        assert_eq!(snapshot.last_snapshot_slot(), 300);
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            total_aggregated_g1_pubkey
+        );
+        for (operator, g1_pubkey) in operators.iter().zip(&g1_pubkeys) {
+            let operator_snapshot = snapshot.find_operator_snapshot(operator).unwrap();
+            assert_eq!(operator_snapshot.g1_pubkey(), *g1_pubkey);
+            assert!(operator_snapshot.is_active());
+            assert_eq!(operator_snapshot.stake_weight().stake_weight(), 0);
+            assert_eq!(
+                operator_snapshot.next_epoch_stake_weight().stake_weight(),
+                0
+            );
+            assert!(!operator_snapshot.has_minimum_stake());
+            assert!(!operator_snapshot.has_minimum_stake_next_epoch());
+            assert!(!operator_snapshot.missed_last_vote());
+        }
+    }
+
+    #[test]
+    fn test_rollover() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                      // bump
+            100,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        ));
+        assert_eq!(snapshot.last_rollover_epoch(), u64::MAX);
+
+        // (is_active, stake_weight, next_epoch_stake_weight)
+        let operators = [
+            (true, 50, 150),  // crosses above the minimum
+            (true, 150, 50),  // crosses below the minimum
+            (true, 150, 100), // stays at the minimum
+            (false, 50, 150), // inactive, left untouched
+        ];
+        for (index, (is_active, stake_weight, next_epoch_stake_weight)) in
+            operators.into_iter().enumerate()
+        {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,          // current_slot
+                is_active,    // is_active
+                index as u64, // ncn_operator_index
+                index as u64, // operator_index
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(next_epoch_stake_weight),
+                    snapshot.minimum_stake(),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        snapshot.rollover(5, 200).unwrap();
+
+        assert_eq!(snapshot.last_rollover_epoch(), 5);
+        assert_eq!(snapshot.last_snapshot_slot(), 200);
+
+        let operator_snapshots = snapshot.operator_snapshots();
+        assert_eq!(operator_snapshots[0].stake_weight().stake_weight(), 150);
+        assert!(operator_snapshots[0].has_minimum_stake());
+        assert_eq!(operator_snapshots[0].last_snapshot_slot(), 200);
+
+        assert_eq!(operator_snapshots[1].stake_weight().stake_weight(), 50);
+        assert!(!operator_snapshots[1].has_minimum_stake());
+
+        assert_eq!(operator_snapshots[2].stake_weight().stake_weight(), 100);
+        assert!(operator_snapshots[2].has_minimum_stake());
+
+        assert_eq!(operator_snapshots[3].stake_weight().stake_weight(), 50);
+        assert!(!operator_snapshots[3].has_minimum_stake());
+        assert_eq!(operator_snapshots[3].last_snapshot_slot(), 100);
+
+        // Only one rollover per epoch
+        assert_eq!(
+            snapshot.rollover(5, 300),
+            Err(NCNProgramError::SnapshotAlreadyRolledOver)
+        );
+        assert_eq!(snapshot.last_snapshot_slot(), 200);
+
+        snapshot.rollover(6, 300).unwrap();
+        assert_eq!(snapshot.last_rollover_epoch(), 6);
+    }
+
+    #[test]
+    fn test_record_missed_vote() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for (index, operator) in operators.iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                true,
+                index as u64,
+                index as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        // Operators 0 and 2 signed
+        let signers_bitmap = [0b101];
+
+        // Present in the bitmap, nothing to prove
+        assert_eq!(
+            snapshot.record_missed_vote(&operators[0], &signers_bitmap),
+            Err(NCNProgramError::OperatorSignedMessage)
+        );
+        assert!(!snapshot
+            .find_operator_snapshot(&operators[0])
+            .unwrap()
+            .missed_last_vote());
+
+        // Absent from the bitmap
+        snapshot
+            .record_missed_vote(&operators[1], &signers_bitmap)
+            .unwrap();
+        assert!(snapshot
+            .find_operator_snapshot(&operators[1])
+            .unwrap()
+            .missed_last_vote());
+        assert!(!snapshot
+            .find_operator_snapshot(&operators[2])
+            .unwrap()
+            .missed_last_vote());
+
+        assert_eq!(
+            snapshot.record_missed_vote(&Pubkey::new_unique(), &signers_bitmap),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
+        );
+    }
+
+    #[test]
+    fn test_operators_can_vote_count() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                      // bump
+            100,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        ));
+
+        let operator_snapshot = |operator: &Pubkey,
+                                 index: u64,
+                                 g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+                                 stake_weight: u128,
+                                 next_epoch_stake_weight: u128| {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,   // current_slot
+                true,  // is_active
+                index, // ncn_operator_index
+                index, // operator_index
+                g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(next_epoch_stake_weight),
+                    &StakeWeights::new(100),
+                )
+                .unwrap();
+            operator_snapshot
+        };
+
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let g1_pubkeys: Vec<[u8; G1_COMPRESSED_POINT_SIZE]> =
+            (0..3).map(|_| G1CompressedPoint::from_random().0).collect();
+
+        // (stake_weight, next_epoch_stake_weight)
+        let stake_weights = [(150, 150), (150, 50), (50, 50)];
+        for (index, (stake_weight, next_epoch_stake_weight)) in
+            stake_weights.into_iter().enumerate()
+        {
+            snapshot
+                .add_operator_snapshot(
+                    operator_snapshot(
+                        &operators[index],
+                        index as u64,
+                        g1_pubkeys[index],
+                        stake_weight,
+                        next_epoch_stake_weight,
+                    ),
+                    100,
+                )
+                .unwrap();
+            snapshot
+                .register_operator_g1_pubkey(&g1_pubkeys[index])
+                .unwrap();
+        }
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+
+        // The second operator drops below the minimum stake
+        snapshot.rollover(5, 200).unwrap();
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+
+        // The third operator reaches it
+        snapshot.update_operator_snapshot(
+            2,
+            &operator_snapshot(&operators[2], 2, g1_pubkeys[2], 150, 150),
+        );
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+
+        snapshot.deregister_operator(&operators[0], 300).unwrap();
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+    }
+
+    #[test]
+    fn test_operators_by_stake_weight() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Operators 0 and 2 tie, the lower NCN operator index ranks first
+        let stake_weights = [200u128, 500, 200];
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for (index, (operator, stake_weight)) in operators.iter().zip(stake_weights).enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                true,
+                index as u64,
+                index as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(1),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        let ranked: Vec<(Pubkey, u128)> = snapshot
+            .operators_by_stake_weight()
+            .iter()
+            .map(|operator_snapshot| {
+                (
+                    *operator_snapshot.operator(),
+                    operator_snapshot.stake_weight().stake_weight(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (operators[1], 500),
+                (operators[0], 200),
+                (operators[2], 200)
+            ]
+        );
+
+        // Inactive operators are left out
+        snapshot.set_operator_active(&operators[1], false).unwrap();
+        assert_eq!(snapshot.operators_by_stake_weight().len(), 2);
+    }
+
+    #[test]
+    fn test_non_signers() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        let operators: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+        for (index, operator) in operators.iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                true,
+                index as u64,
+                index as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+        snapshot.set_operator_active(&operators[4], false).unwrap();
+
+        let non_signers = |bitmap: &[u8]| -> Vec<Pubkey> {
+            snapshot
+                .non_signers(bitmap)
+                .iter()
+                .map(|operator_snapshot| *operator_snapshot.operator())
+                .collect()
+        };
+
+        // Operators 0, 2, 3 and 8 signed, the inactive operator 4 is never listed
+        assert_eq!(
+            non_signers(&[0b0000_1101, 0b0000_0001]),
+            vec![
+                operators[1],
+                operators[5],
+                operators[6],
+                operators[7],
+                operators[9]
+            ]
+        );
+
+        // Trailing bits past the 10 registered operators do not hide anyone
+        assert_eq!(non_signers(&[0b1110_1111, 0b1111_1110]), vec![operators[8]]);
+        assert!(non_signers(&[0xFF, 0xFF]).is_empty());
+
+        // A short bitmap leaves the operators it does not cover unsigned
+        assert_eq!(non_signers(&[0xFF]), vec![operators[8], operators[9]]);
+        assert_eq!(non_signers(&[]).len(), 9);
+    }
+
+    #[test]
+    fn test_set_operator_active() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                      // bump
+            100,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        ));
+
+        let operators: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        for (index, operator) in operators.iter().enumerate() {
+            let g1_pubkey = G1CompressedPoint::from_random().0;
+            let mut operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,          // current_slot
+                true,         // is_active
+                index as u64, // ncn_operator_index
+                index as u64, // operator_index
+                g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(150),
+                    &StakeWeights::new(150),
+                    &StakeWeights::new(100),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+            snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+        }
+        let total_aggregated_g1_pubkey = snapshot.total_aggregated_g1_pubkey();
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+
+        snapshot.set_operator_active(&operators[0], false).unwrap();
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+        assert_eq!(snapshot.operators_registered(), 2);
+        assert_eq!(snapshot.get_active_operator_snapshots().len(), 1);
+        assert_eq!(
+            snapshot.get_eligible_voters(5, 5).unwrap()[0].operator(),
+            &operators[1]
+        );
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            total_aggregated_g1_pubkey
+        );
+
+        // Setting the same state again changes nothing
+        snapshot.set_operator_active(&operators[0], false).unwrap();
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+
+        // A rollover does not count the inactive operator back in
+        snapshot.rollover(5, 200).unwrap();
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+
+        snapshot.set_operator_active(&operators[0], true).unwrap();
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+        assert_eq!(snapshot.get_eligible_voters(5, 5).unwrap().len(), 2);
+
+        assert_eq!(
+            snapshot.set_operator_active(&Pubkey::new_unique(), false),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
+        );
+    }
+
+    #[test]
+    fn test_get_eligible_voters() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                      // bump
+            100,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        ));
+
+        let operators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+        // (stake_weight, next_epoch_stake_weight)
+        let stake_weights = [(150, 150), (150, 50), (50, 150)];
+        for (index, (stake_weight, next_epoch_stake_weight)) in
+            stake_weights.into_iter().enumerate()
+        {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &operators[index],
+                100,          // current_slot
+                true,         // is_active
+                index as u64, // ncn_operator_index
+                index as u64, // operator_index
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(next_epoch_stake_weight),
+                    &StakeWeights::new(100),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        let eligible_operators = |eligible_voters: Vec<&OperatorSnapshot>| {
+            eligible_voters
+                .iter()
+                .map(|operator_snapshot| *operator_snapshot.operator())
+                .collect::<Vec<_>>()
+        };
+
+        // Same epoch as the snapshot
+        assert_eq!(
+            eligible_operators(snapshot.get_eligible_voters(5, 5).unwrap()),
+            vec![operators[0], operators[1]]
+        );
+
+        // One epoch after the snapshot
+        assert_eq!(
+            eligible_operators(snapshot.get_eligible_voters(6, 5).unwrap()),
+            vec![operators[0], operators[2]]
+        );
+
+        // Two epochs after the snapshot
+        assert_eq!(
+            snapshot.get_eligible_voters(7, 5).unwrap_err(),
+            NCNProgramError::OperatorSnapshotOutdated
+        );
+    }
+
+    #[test]
+    fn test_stake_weight_histogram() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // No operators yet, every bucket is empty
+        assert_eq!(snapshot.stake_weight_histogram(3), vec![0, 0, 0]);
+
+        for (index, stake_weight) in [0u128, 10, 20, 30, 40, 50, 60, 70, 80, 90]
+            .into_iter()
+            .enumerate()
+        {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,          // current_slot
+                true,         // is_active
+                index as u64, // ncn_operator_index
+                index as u64, // operator_index
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(stake_weight));
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        assert_eq!(snapshot.stake_weight_histogram(5), vec![2, 2, 2, 2, 2]);
+        assert_eq!(snapshot.stake_weight_histogram(2), vec![5, 5]);
+        assert_eq!(snapshot.stake_weight_histogram(1), vec![10]);
+        assert_eq!(snapshot.stake_weight_histogram(0), Vec::<u64>::new());
+
+        // A single heavy operator skews the distribution into the first bucket
+        let mut operator_snapshot = *snapshot.get_operator_snapshot(9).unwrap();
+        operator_snapshot.set_stake_weight(&StakeWeights::new(1_000));
+        snapshot.update_operator_snapshot(9, &operator_snapshot);
+
+        assert_eq!(snapshot.stake_weight_histogram(4), vec![9, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_try_update_operator_snapshot_out_of_range() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            100,
+            StakeWeights::new(10),
+        ));
+        let operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,
+            true,
+            0,
+            0,
+            G1CompressedPoint::from_random().0,
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 100)
+            .unwrap();
+
+        let mut updated = operator_snapshot;
+        updated.set_stake_weight(&StakeWeights::new(50));
+        snapshot.try_update_operator_snapshot(0, &updated).unwrap();
+        assert_eq!(
+            snapshot
+                .get_operator_snapshot(0)
+                .unwrap()
+                .stake_weight()
+                .stake_weight(),
+            50
+        );
+
+        // Past the registered operators, and past the array itself
+        for operator_index in [1, MAX_OPERATORS, usize::MAX] {
+            assert_eq!(
+                snapshot
+                    .try_update_operator_snapshot(operator_index, &updated)
+                    .unwrap_err(),
+                NCNProgramError::TooManyVaultOperatorDelegations
+            );
+        }
+        assert!(snapshot.get_operator_snapshot(1).is_none());
+    }
+
+    #[test]
+    fn test_rebuild_aggregate_op_count() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Nothing to add up without operators
+        assert_eq!(snapshot.rebuild_aggregate_op_count(), 0);
+
+        for index in 0..5 {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,   // current_slot
+                true,  // is_active
+                index, // ncn_operator_index
+                index, // operator_index
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        // Inactive operators are not part of the rebuild
+        let inactive_operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,   // current_slot
+            false, // is_active
+            5,     // ncn_operator_index
+            5,     // operator_index
+            G1CompressedPoint::from_random().0,
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+        snapshot
+            .add_operator_snapshot(inactive_operator_snapshot, 100)
+            .unwrap();
+
+        assert_eq!(snapshot.operators_registered(), 6);
+        assert_eq!(snapshot.active_operators_count(), 5);
+        assert_eq!(snapshot.rebuild_aggregate_op_count(), 4);
+    }
+
+    #[test]
+    fn test_snapshot_initialize() {
+        let ncn = Pubkey::new_unique();
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        snapshot.initialize(
+            &ncn,
+            2,                      // bump
+            200,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        );
+
+        assert_eq!(snapshot.ncn, ncn);
+        assert_eq!(snapshot.bump, 2);
+        assert_eq!(u64::from(snapshot.slot_created), 200u64);
+        assert_eq!(snapshot.minimum_stake().stake_weight(), 100);
+    }
+
+    #[test]
+    fn test_snapshot_seeds_and_pda() {
+        let ncn = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+
+        let seeds = Snapshot::seeds(&ncn);
+        assert_eq!(seeds.len(), 2);
+        assert_eq!(seeds[0], b"snapshot");
+        assert_eq!(seeds[1], ncn.to_bytes().to_vec());
+
+        let (pda, bump, returned_seeds) = Snapshot::find_program_address(&program_id, &ncn);
+        assert_eq!(returned_seeds, seeds);
+        assert!(bump > 0);
+        assert!(pda != Pubkey::default());
+    }
+
+    #[test]
+    fn test_snapshot_getters() {
+        let ncn = Pubkey::new_unique();
+        let snapshot = Snapshot::new(
+            &ncn,
+            3,                      // bump
+            500,                    // current_slot
+            StakeWeights::new(200), // minimum_stake
+        );
+
+        assert_eq!(snapshot.operators_registered(), 0);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
+        assert_eq!(snapshot.last_snapshot_slot(), 0);
+        assert_eq!(snapshot.minimum_stake().stake_weight(), 200);
+    }
+
+    #[test]
+    fn test_snapshot_increment_operator_registration() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
 
-        assert_eq!(size_of::<Snapshot>(), expected_total);
+        // An operator with the minimum stake can vote
+        let result = snapshot.increment_operator_registration(150, true);
+        assert!(result.is_ok());
+        assert_eq!(snapshot.operators_registered(), 1);
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+
+        // An operator without it is registered but can't vote
+        let result = snapshot.increment_operator_registration(200, false);
+        assert!(result.is_ok());
+        assert_eq!(snapshot.operators_registered(), 2);
+        assert_eq!(snapshot.operators_can_vote_count(), 1);
+        assert_eq!(snapshot.last_snapshot_slot(), 200);
+
+        snapshot.decrement_operator_registration(250, true).unwrap();
+        assert_eq!(snapshot.operators_registered(), 1);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
+        assert_eq!(snapshot.last_snapshot_slot(), 250);
     }
 
     #[test]
-    fn test_vault_operator_stake_weight_is_empty() {
-        // Test default (should be empty)
-        let default_weight = VaultOperatorStakeWeight::default();
-        assert!(default_weight.is_empty());
+    fn test_snapshot_get_operator_snapshot() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        // Test getting non-existent snapshot
+        assert!(snapshot.get_operator_snapshot(0).is_none());
+        assert!(snapshot.get_operator_snapshot(1).is_none());
+
+        // Add an operator snapshot
+        let operator_pubkey = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = OperatorSnapshot::new(
+            &operator_pubkey,
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 100)
+            .unwrap();
+
+        // Test getting existing snapshot
+        let retrieved = snapshot.get_operator_snapshot(0);
+        assert!(retrieved.is_some());
+        assert_eq!(retrieved.unwrap().operator(), &operator_pubkey);
+
+        // Test getting out of bounds
+        assert!(snapshot.get_operator_snapshot(2).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_get_mut_operator_snapshot() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        // Add an operator snapshot
+        let operator_pubkey = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = OperatorSnapshot::new(
+            &operator_pubkey,
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 100)
+            .unwrap();
+
+        // Test getting mutable reference
+        let mut_snapshot = snapshot.get_mut_operator_snapshot(0);
+        assert!(mut_snapshot.is_some());
+
+        // Modify the snapshot
+        if let Some(snapshot) = mut_snapshot {
+            snapshot.set_has_minimum_stake_this_epoch(true);
+            assert!(snapshot.has_minimum_stake());
+        }
+
+        // Verify the change persisted
+        let retrieved = snapshot.get_operator_snapshot(0);
+        assert!(retrieved.unwrap().has_minimum_stake());
+    }
+
+    #[test]
+    fn test_snapshot_find_mut_operator_snapshot() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        let operator_pubkey = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = OperatorSnapshot::new(
+            &operator_pubkey,
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 100)
+            .unwrap();
+
+        // Test finding by pubkey
+        let mut_found = snapshot.find_mut_operator_snapshot(&operator_pubkey);
+        assert!(mut_found.is_some());
+
+        // Test finding non-existent
+        let not_found = snapshot.find_mut_operator_snapshot(&Pubkey::new_unique());
+        assert!(not_found.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_add_operator_snapshot_index_overflow() {
+        let mut snapshot = Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        );
+
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,                  // current_slot
+            true,                 // is_active
+            MAX_OPERATORS as u64, // ncn_operator_index (too large)
+            0,                    // operator_index
+            g1_pubkey,            // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        let result = snapshot.add_operator_snapshot(operator_snapshot, 100);
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            NCNProgramError::TooManyVaultOperatorDelegations
+        );
+    }
+
+    #[test]
+    fn test_operator_snapshot_new() {
+        let operator = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+
+        let snapshot = OperatorSnapshot::new(
+            &operator,
+            100,       // current_slot
+            true,      // is_active
+            5,         // ncn_operator_index
+            10,        // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+
+        assert_eq!(snapshot.operator(), &operator);
+        assert_eq!(u64::from(snapshot.slot_created), 100u64);
+        assert_eq!(u64::from(snapshot.last_snapshot_slot), 0u64);
+        assert!(snapshot.is_active());
+        assert_eq!(snapshot.ncn_operator_index(), 5);
+        assert_eq!(u64::from(snapshot.operator_index), 10u64);
+        assert_eq!(snapshot.g1_pubkey(), g1_pubkey);
+        assert!(!snapshot.has_minimum_stake());
+        assert_eq!(snapshot.stake_weight().stake_weight(), 0);
+    }
+
+    #[test]
+    fn test_operator_snapshot_initialize_validation() {
+        let mut snapshot = OperatorSnapshot::default();
+
+        // Test with too many vault operator delegations
+        let result = snapshot.initialize(
+            &Pubkey::new_unique(),
+            100,                                // current_slot
+            true,                               // is_active
+            0,                                  // ncn_operator_index
+            0,                                  // operator_index
+            G1CompressedPoint::from_random().0, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+            MAX_VAULTS as u64 + 1, // too many delegations
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            NCNProgramError::TooManyVaultOperatorDelegations
+        );
+    }
+
+    #[test]
+    fn test_operator_snapshot_initialize_inactive() {
+        let mut snapshot = OperatorSnapshot::default();
+
+        let operator = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+
+        // Use a smaller vault_operator_delegation_count that fits with MAX_VAULTS=1
+        snapshot
+            .initialize(
+                &operator,
+                100,       // current_slot
+                false,     // is_active = false
+                0,         // ncn_operator_index
+                0,         // operator_index
+                g1_pubkey, // g1_pubkey
+                [0; G2_COMPRESSED_POINT_SIZE],
+                1, // vault_operator_delegation_count (reduced from 5 to 1)
+            )
+            .unwrap();
+
+        assert_eq!(snapshot.operator(), &operator);
+        assert!(!snapshot.is_active());
+        assert_eq!(u64::from(snapshot.last_snapshot_slot), 0u64); // Should be finalized immediately
+    }
+
+    #[test]
+    fn test_operator_snapshot_have_valid_bn128_g1_pubkey() {
+        let mut snapshot = OperatorSnapshot::default();
+
+        // Test with invalid pubkey (all zeros)
+        assert!(!snapshot.have_valid_bn128_g1_pubkey());
+
+        // Test with valid pubkey
+        let valid_g1_pubkey = G1CompressedPoint::from_random().0;
+        snapshot.g1_pubkey = valid_g1_pubkey;
+        assert!(snapshot.have_valid_bn128_g1_pubkey());
+    }
+
+    #[test]
+    fn test_operator_snapshot_have_valid_bn128_g2_pubkey() {
+        let mut snapshot = OperatorSnapshot::default();
+
+        // Test with invalid pubkey (all zeros)
+        assert!(!snapshot.have_valid_bn128_g2_pubkey());
+
+        // Test with valid pubkey
+        let valid_g2_pubkey = G2CompressedPoint::try_from(&PrivKey::from_random())
+            .unwrap()
+            .0;
+        snapshot.update_g2_pubkey(&valid_g2_pubkey);
+        assert!(snapshot.have_valid_bn128_g2_pubkey());
+        assert_eq!(snapshot.g2_pubkey(), valid_g2_pubkey);
+    }
+
+    #[test]
+    fn test_apply_st_mint_weight() {
+        let amount = PreciseNumber::new(1_000).unwrap();
+
+        // The default weight counts the stake 1:1, other weights scale it
+        assert_eq!(
+            OperatorSnapshot::apply_st_mint_weight(&amount, WEIGHT_PRECISION).unwrap(),
+            1_000
+        );
+        assert_eq!(
+            OperatorSnapshot::apply_st_mint_weight(&amount, 3 * WEIGHT_PRECISION).unwrap(),
+            3_000
+        );
+        assert_eq!(
+            OperatorSnapshot::apply_st_mint_weight(&amount, WEIGHT_PRECISION / 4).unwrap(),
+            250
+        );
 
-        // Test non-empty case
-        let non_empty_weight =
-            VaultOperatorStakeWeight::new(&Pubkey::new_unique(), 1, &StakeWeights::default());
-        assert!(!non_empty_weight.is_empty());
+        assert_eq!(
+            OperatorSnapshot::apply_st_mint_weight(&amount, 0),
+            Err(NCNProgramError::WeightNotSet)
+        );
     }
 
     #[test]
-    fn test_operator_snapshot_initialize_active_inactive() {
-        let current_slot = 100;
-        let g1_pubkey = G1CompressedPoint::from_random().0;
+    fn test_insert_vault() {
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,
+            true,
+            0,
+            0,
+            [0; G1_COMPRESSED_POINT_SIZE],
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+        let vault = Pubkey::new_unique();
 
-        // Create two operator snapshots - one for active and one for inactive
-        let mut active_snapshot =
-            OperatorSnapshot::new(&Pubkey::new_unique(), current_slot, true, 0, 0, g1_pubkey)
-                .unwrap();
+        assert!(!operator_snapshot.contains_vault(&vault));
+        assert!(!operator_snapshot.contains_vault(&Pubkey::default()));
 
-        let mut inactive_snapshot =
-            OperatorSnapshot::new(&Pubkey::new_unique(), current_slot, false, 0, 0, g1_pubkey)
+        operator_snapshot.insert_vault(&vault).unwrap();
+        assert!(operator_snapshot.contains_vault(&vault));
+        assert_eq!(operator_snapshot.vaults_delegated().count(), 1);
+
+        assert_eq!(
+            operator_snapshot.insert_vault(&vault),
+            Err(NCNProgramError::DuplicateVaultOperatorDelegation)
+        );
+
+        for _ in 1..MAX_VAULTS {
+            operator_snapshot
+                .insert_vault(&Pubkey::new_unique())
                 .unwrap();
+        }
+        assert_eq!(
+            operator_snapshot.insert_vault(&Pubkey::new_unique()),
+            Err(NCNProgramError::TooManyVaultOperatorDelegations)
+        );
 
-        // Initialize active snapshot
-        active_snapshot
-            .initialize(
-                &Pubkey::new_unique(),
-                current_slot,
-                true, // is_active
-                0,
-                0,
-                g1_pubkey,
-                1,
+        operator_snapshot.clear_vaults();
+        assert!(!operator_snapshot.contains_vault(&vault));
+        assert_eq!(operator_snapshot.vaults_delegated().count(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_vault_delegation() {
+        const EPOCH_LENGTH: u64 = 100;
+
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            0,
+            true,
+            0,
+            0,
+            [0; G1_COMPRESSED_POINT_SIZE],
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+        let vault = Pubkey::new_unique();
+        let minimum_stake = StakeWeights::new(100);
+
+        operator_snapshot
+            .snapshot_vault_delegation(
+                &vault,
+                150,
+                EPOCH_LENGTH,
+                &StakeWeights::new(200),
+                &StakeWeights::new(100),
+                &minimum_stake,
             )
             .unwrap();
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 200);
+        assert!(operator_snapshot.contains_vault(&vault));
 
-        // Initialize inactive snapshot
-        inactive_snapshot
-            .initialize(
-                &Pubkey::new_unique(),
-                current_slot,
-                false, // not active
-                0,
-                0,
-                g1_pubkey,
-                1,
+        // The same vault can't be counted twice in the same epoch
+        assert_eq!(
+            operator_snapshot.snapshot_vault_delegation(
+                &vault,
+                160,
+                EPOCH_LENGTH,
+                &StakeWeights::new(200),
+                &StakeWeights::new(100),
+                &minimum_stake,
+            ),
+            Err(NCNProgramError::DuplicateVaultOperatorDelegation)
+        );
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 200);
+        assert_eq!(operator_snapshot.last_snapshot_slot(), 150);
+
+        // In the next epoch the vault's delegation replaces the previous stake weights
+        operator_snapshot
+            .snapshot_vault_delegation(
+                &vault,
+                250,
+                EPOCH_LENGTH,
+                &StakeWeights::new(50),
+                &StakeWeights::new(50),
+                &minimum_stake,
             )
             .unwrap();
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 50);
+        assert!(!operator_snapshot.has_minimum_stake());
+        assert_eq!(operator_snapshot.vaults_delegated().count(), 1);
     }
 
     #[test]
-    fn test_add_g1_pubkey_to_total_aggregated_and_subtract() {
-        // Create a snapshot
-        let mut snapshot = Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        );
-        // Initial aggregated is zero
-        assert_eq!(
-            snapshot.total_aggregated_g1_pubkey(),
-            [0u8; G1_COMPRESSED_POINT_SIZE]
-        );
+    fn test_operator_snapshot_stake_weight_operations() {
+        let mut snapshot = OperatorSnapshot::default();
 
-        // Add first pubkey
-        let pk1 = G1CompressedPoint::from_random().0;
-        snapshot.add_g1_pubkey_to_total_agg(&pk1).unwrap();
-        assert_eq!(snapshot.total_aggregated_g1_pubkey(), pk1);
+        // Test initial state
+        assert_eq!(snapshot.stake_weight().stake_weight(), 0);
+        assert!(!snapshot.has_minimum_stake());
 
-        // Add second pubkey
-        let pk2 = G1CompressedPoint::from_random().0;
-        snapshot.add_g1_pubkey_to_total_agg(&pk2).unwrap();
-        let after_add = snapshot.total_aggregated_g1_pubkey();
-        assert_ne!(after_add, pk1);
-        assert_ne!(after_add, pk2);
+        // Test setting stake weight
+        let stake_weight = StakeWeights::new(100);
+        snapshot.set_stake_weight(&stake_weight);
+        assert_eq!(snapshot.stake_weight().stake_weight(), 100);
 
-        // Subtract second pubkey
-        snapshot.subtract_g1_pubkey_from_total_agg(&pk2).unwrap();
-        let after_subtract = snapshot.total_aggregated_g1_pubkey();
-        assert_eq!(after_subtract, pk1);
+        // Test incrementing stake weight
+        let increment = StakeWeights::new(50);
+        let _ = snapshot.stake_weight.increment(&increment);
+        assert_eq!(snapshot.stake_weight().stake_weight(), 150);
 
-        // Subtract first pubkey, should give zero point (identity)
-        snapshot.subtract_g1_pubkey_from_total_agg(&pk1).unwrap();
-        let after_zero = snapshot.total_aggregated_g1_pubkey();
-        assert_eq!(after_zero, G1CompressedPoint::default().0);
+        // Test setting minimum stake weight flag
+        snapshot.set_has_minimum_stake_this_epoch(true);
+        assert!(snapshot.has_minimum_stake());
     }
 
     #[test]
-    fn test_register_operator_g1_pubkey() {
-        // Create a snapshot with default aggregated G1 pubkey (all zeros) using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        ));
-
-        // Verify initial state - total_aggregated_g1_pubkey should be all zeros
-        assert_eq!(
-            snapshot.total_aggregated_g1_pubkey(),
-            [0u8; G1_COMPRESSED_POINT_SIZE]
-        );
+    fn test_vault_operator_stake_weight_new() {
+        let vault = Pubkey::new_unique();
+        let stake_weight = StakeWeights::new(500);
 
-        // Generate a random G1 pubkey
-        let operator_g1_pubkey = G1CompressedPoint::from_random().0;
+        let vault_stake_weight = VaultOperatorStakeWeight::new(&vault, 10, &stake_weight);
 
-        // Register the operator's G1 pubkey
-        let result = snapshot.register_operator_g1_pubkey(&operator_g1_pubkey);
-        assert!(result.is_ok());
+        assert_eq!(vault_stake_weight.vault(), &vault);
+        assert_eq!(vault_stake_weight.vault_index(), 10);
+        assert_eq!(vault_stake_weight.stake_weights().stake_weight(), 500);
+        assert!(!vault_stake_weight.is_empty());
+    }
 
-        // Verify the aggregated G1 pubkey is no longer all zeros
-        assert_ne!(
-            snapshot.total_aggregated_g1_pubkey(),
-            [0u8; G1_COMPRESSED_POINT_SIZE]
-        );
+    #[test]
+    fn test_vault_operator_stake_weight_default() {
+        let default_weight = VaultOperatorStakeWeight::default();
 
-        // The aggregated pubkey should now equal the first operator's pubkey
-        // since we started with zero point (identity element for addition)
-        assert_eq!(snapshot.total_aggregated_g1_pubkey(), operator_g1_pubkey);
+        assert_eq!(default_weight.vault(), &Pubkey::default());
+        assert_eq!(default_weight.vault_index(), u64::MAX);
+        assert_eq!(default_weight.stake_weights().stake_weight(), 0);
+        assert!(default_weight.is_empty());
     }
 
     #[test]
-    fn test_register_multiple_operator_g1_pubkeys() {
-        // Create a snapshot using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
+    fn test_snapshot_register_operator_g1_pubkey_invalid_pubkey() {
+        let mut snapshot = Snapshot::new(
             &Pubkey::new_unique(),
             1,                    // bump
             100,                  // current_slot
             StakeWeights::new(1), // minimum_stake
-        ));
-
-        // Generate multiple random G1 pubkeys
-        let operator1_g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator2_g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator3_g1_pubkey = G1CompressedPoint::from_random().0;
-
-        // Register first operator's G1 pubkey
-        snapshot
-            .register_operator_g1_pubkey(&operator1_g1_pubkey)
-            .unwrap();
-        let after_first = snapshot.total_aggregated_g1_pubkey();
-
-        // Register second operator's G1 pubkey
-        snapshot
-            .register_operator_g1_pubkey(&operator2_g1_pubkey)
-            .unwrap();
-        let after_second = snapshot.total_aggregated_g1_pubkey();
-
-        // Register third operator's G1 pubkey
-        snapshot
-            .register_operator_g1_pubkey(&operator3_g1_pubkey)
-            .unwrap();
-        let after_third = snapshot.total_aggregated_g1_pubkey();
-
-        // Verify that each registration changes the aggregated pubkey
-        assert_ne!(after_first, [0u8; G1_COMPRESSED_POINT_SIZE]);
-        assert_ne!(after_second, after_first);
-        assert_ne!(after_third, after_second);
-
-        // Manually compute the expected aggregated result to verify correctness
-        let point1 = G1Point::try_from(&G1CompressedPoint(operator1_g1_pubkey)).unwrap();
-        let point2 = G1Point::try_from(&G1CompressedPoint(operator2_g1_pubkey)).unwrap();
-        let point3 = G1Point::try_from(&G1CompressedPoint(operator3_g1_pubkey)).unwrap();
+        );
 
-        let expected_aggregate = point1 + point2 + point3;
-        let expected_compressed = G1CompressedPoint::try_from(expected_aggregate).unwrap();
+        // Test with invalid G1 pubkey (all zeros except one byte)
+        let mut invalid_pubkey = [0u8; G1_COMPRESSED_POINT_SIZE];
+        invalid_pubkey[0] = 1; // Make it invalid G1 point
 
-        assert_eq!(snapshot.total_aggregated_g1_pubkey(), expected_compressed.0);
+        let result = snapshot.register_operator_g1_pubkey(&invalid_pubkey);
+        assert!(result.is_err()); // Should fail with invalid G1 point
     }
 
     #[test]
-    fn test_operator_snapshot_g1_pubkey_storage() {
-        // Generate a random G1 pubkey
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-
-        // Create an operator snapshot with the G1 pubkey
-        let operator_snapshot = OperatorSnapshot::new(
+    fn test_snapshot_edge_cases() {
+        // Test with maximum values
+        let snapshot = Snapshot::new(
             &Pubkey::new_unique(),
-            100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+            255,                          // bump
+            u64::MAX,                     // current_slot
+            StakeWeights::new(u128::MAX), // minimum_stake
+        );
 
-        // Verify the G1 pubkey is stored correctly
-        assert_eq!(operator_snapshot.g1_pubkey, g1_pubkey);
+        assert_eq!(snapshot.minimum_stake().stake_weight(), u128::MAX);
     }
 
     #[test]
-    fn test_operator_snapshot_initialize_g1_pubkey() {
-        // Generate two different G1 pubkeys
-        let original_g1_pubkey = G1CompressedPoint::from_random().0;
-        let new_g1_pubkey = G1CompressedPoint::from_random().0;
+    fn test_operator_snapshot_edge_cases() {
+        let operator = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
 
-        // Create an operator snapshot with the original G1 pubkey
-        let mut operator_snapshot = OperatorSnapshot::new(
-            &Pubkey::new_unique(),
-            100,                // current_slot
-            true,               // is_active
-            0,                  // ncn_operator_index
-            0,                  // operator_index
-            original_g1_pubkey, // original g1_pubkey
+        let snapshot = OperatorSnapshot::new(
+            &operator,
+            u64::MAX,                 // current_slot
+            true,                     // is_active
+            MAX_OPERATORS as u64 - 1, // ncn_operator_index
+            u64::MAX,                 // operator_index
+            g1_pubkey,                // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
         )
         .unwrap();
 
-        // Initialize with a new G1 pubkey
-        operator_snapshot
-            .initialize(
-                &Pubkey::new_unique(),
-                100,           // current_slot
-                true,          // is_active
-                0,             // ncn_operator_index
-                0,             // operator_index
-                new_g1_pubkey, // new g1_pubkey
-                1,
-            )
-            .unwrap();
-
-        // Verify the G1 pubkey was updated during initialization
-        assert_eq!(operator_snapshot.g1_pubkey, new_g1_pubkey);
-        assert_ne!(operator_snapshot.g1_pubkey, original_g1_pubkey);
+        assert_eq!(u64::from(snapshot.slot_created), u64::MAX);
+        assert_eq!(snapshot.ncn_operator_index(), MAX_OPERATORS as u64 - 1);
+        assert_eq!(u64::from(snapshot.operator_index), u64::MAX);
     }
 
     #[test]
-    fn test_snapshot_aggregation_order_independence() {
-        // Test that G1 pubkey aggregation is order-independent (commutative)
-        let operator1_g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator2_g1_pubkey = G1CompressedPoint::from_random().0;
-
-        // Create two snapshots using heap allocation to avoid stack overflow
-        let mut snapshot1 = Snapshot::new(
+    fn test_snapshot_arithmetic_overflow_protection() {
+        let mut snapshot = Snapshot::new(
             &Pubkey::new_unique(),
             1,                    // bump
             100,                  // current_slot
             StakeWeights::new(1), // minimum_stake
         );
 
-        // Register operators in different orders
-        // Snapshot 1: operator1 first, then operator2
-        snapshot1
-            .register_operator_g1_pubkey(&operator1_g1_pubkey)
-            .unwrap();
-        snapshot1
-            .register_operator_g1_pubkey(&operator2_g1_pubkey)
-            .unwrap();
+        // Set to maximum values to test overflow
+        snapshot.operators_registered = PodU64::from(u64::MAX);
 
-        let mut snapshot2 = Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        );
-        // Snapshot 2: operator2 first, then operator1
-        snapshot2
-            .register_operator_g1_pubkey(&operator2_g1_pubkey)
-            .unwrap();
-        snapshot2
-            .register_operator_g1_pubkey(&operator1_g1_pubkey)
-            .unwrap();
+        let result = snapshot.increment_operator_registration(200, false);
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), NCNProgramError::ArithmeticOverflow);
+
+        // An overflowing vote count leaves the registered count untouched
+        snapshot.operators_registered = PodU64::from(0);
+        snapshot.operators_can_vote_count = PodU64::from(u64::MAX);
 
-        // Both should result in the same aggregated G1 pubkey
         assert_eq!(
-            snapshot1.total_aggregated_g1_pubkey(),
-            snapshot2.total_aggregated_g1_pubkey()
+            snapshot.increment_operator_registration(200, true),
+            Err(NCNProgramError::ArithmeticOverflow)
         );
+        assert_eq!(snapshot.operators_registered(), 0);
+        assert_eq!(snapshot.last_snapshot_slot(), 0);
     }
 
     #[test]
-    fn test_snapshot_g1_pubkey_getter() {
-        // Create a snapshot using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        ));
+    fn test_snapshot_vault_operator_delegation_single_use() {
+        // Create an active operator snapshot
+        let operator = Pubkey::new_unique();
+        let g1_pubkey = G1CompressedPoint::from_random().0;
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &operator,
+            100,       // current_slot
+            true,      // is_active
+            0,         // ncn_operator_index
+            0,         // operator_index
+            g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
 
-        // Initially should be all zeros
+        // Verify initial state
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 0);
         assert_eq!(
-            snapshot.total_aggregated_g1_pubkey(),
-            [0u8; G1_COMPRESSED_POINT_SIZE]
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            0
         );
+        assert!(!operator_snapshot.has_minimum_stake());
+        assert!(!operator_snapshot.has_minimum_stake_next_epoch());
+        assert_eq!(operator_snapshot.last_snapshot_slot(), 0);
 
-        // Register an operator G1 pubkey
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+        // Create stake weights for testing
+        let current_stake_weights = StakeWeights::new(1000);
+        let next_epoch_stake_weights = StakeWeights::new(1200);
+        let minimum_stake = StakeWeights::new(500);
 
-        // Verify getter returns the updated value
-        assert_eq!(snapshot.total_aggregated_g1_pubkey(), g1_pubkey);
+        // Call snapshot_vault_operator_delegation once
+        let current_slot = 150;
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            current_slot,
+            &current_stake_weights,
+            &next_epoch_stake_weights,
+            &minimum_stake,
+        );
+
+        // Verify the call succeeded
+        assert!(result.is_ok());
+
+        // Verify the state was updated correctly
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1000);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            1200
+        );
+        assert!(operator_snapshot.has_minimum_stake()); // 1000 >= 500
+        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 1200 >= 500
+        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot);
     }
 
     #[test]
-    fn test_snapshot_add_operator_snapshot() {
-        // Create a snapshot using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        ));
-
-        // Create an operator snapshot
-        let operator_pubkey = Pubkey::new_unique();
+    fn test_snapshot_vault_operator_delegation_multiple_updates() {
+        // Create an active operator snapshot
+        let operator = Pubkey::new_unique();
         let g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator_snapshot = OperatorSnapshot::new(
-            &operator_pubkey,
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &operator,
             100,       // current_slot
             true,      // is_active
             0,         // ncn_operator_index
             0,         // operator_index
             g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
         )
         .unwrap();
 
-        // Add the operator snapshot to the snapshot
-        let result = snapshot.add_operator_snapshot(operator_snapshot, 100);
-        assert!(result.is_ok());
+        let minimum_stake = StakeWeights::new(500);
 
-        {
-            // Verify the operator snapshot was added using its index
-            let retrieved_snapshot = snapshot.get_operator_snapshot(0);
-            let test = snapshot.operator_snapshots[0];
-            assert!(retrieved_snapshot.is_some());
-            assert_eq!(retrieved_snapshot.unwrap().operator(), &operator_pubkey);
-        }
+        // First snapshot - initial stake weights
+        let current_stake_weights_1 = StakeWeights::new(1000);
+        let next_epoch_stake_weights_1 = StakeWeights::new(1200);
+        let current_slot_1 = 150;
 
-        {
-            // Verify the operator snapshot was added using its id
-            let retrieved_snapshot = snapshot.find_operator_snapshot(&operator_pubkey);
-            assert!(retrieved_snapshot.is_some());
-            assert_eq!(retrieved_snapshot.unwrap().operator(), &operator_pubkey);
-        }
-    }
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            current_slot_1,
+            &current_stake_weights_1,
+            &next_epoch_stake_weights_1,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
 
-    #[test]
-    fn test_snapshot_find_operator_snapshot() {
-        // Create a snapshot using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        ));
+        // Verify first snapshot state
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1000);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            1200
+        );
+        assert!(operator_snapshot.has_minimum_stake());
+        assert!(operator_snapshot.has_minimum_stake_next_epoch());
+        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_1);
 
-        // Create operator snapshots
-        let operator1_pubkey = Pubkey::new_unique();
-        let operator2_pubkey = Pubkey::new_unique();
-        let g1_pubkey_1 = G1CompressedPoint::from_random().0;
-        let g1_pubkey_2 = G1CompressedPoint::from_random().0;
+        // Second snapshot - updated stake weights (increased)
+        let current_stake_weights_2 = StakeWeights::new(1500);
+        let next_epoch_stake_weights_2 = StakeWeights::new(1800);
+        let current_slot_2 = 200;
 
-        let operator1_snapshot = OperatorSnapshot::new(
-            &operator1_pubkey,
-            100,         // current_slot
-            true,        // is_active
-            0,           // ncn_operator_index (index 0)
-            0,           // operator_index
-            g1_pubkey_1, // g1_pubkey
-        )
-        .unwrap();
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            current_slot_2,
+            &current_stake_weights_2,
+            &next_epoch_stake_weights_2,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
 
-        let operator2_snapshot = OperatorSnapshot::new(
-            &operator2_pubkey,
-            100,         // current_slot
-            true,        // is_active
-            1,           // ncn_operator_index (index 1)
-            1,           // operator_index
-            g1_pubkey_2, // g1_pubkey
-        )
-        .unwrap();
+        // Verify second snapshot state (should be updated)
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1500);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            1800
+        );
+        assert!(operator_snapshot.has_minimum_stake()); // 1500 >= 500
+        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 1800 >= 500
+        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_2);
 
-        // Add both operator snapshots
-        snapshot
-            .add_operator_snapshot(operator1_snapshot, 100)
-            .unwrap();
-        snapshot
-            .add_operator_snapshot(operator2_snapshot, 110)
-            .unwrap();
+        // Third snapshot - decreased stake weights
+        let current_stake_weights_3 = StakeWeights::new(800);
+        let next_epoch_stake_weights_3 = StakeWeights::new(900);
+        let current_slot_3 = 250;
 
-        // Find operator snapshots by pubkey
-        let found1 = snapshot.find_operator_snapshot(&operator1_pubkey);
-        let found2 = snapshot.find_operator_snapshot(&operator2_pubkey);
-        let not_found = snapshot.find_operator_snapshot(&Pubkey::new_unique());
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            current_slot_3,
+            &current_stake_weights_3,
+            &next_epoch_stake_weights_3,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
 
-        assert!(found1.is_some());
-        assert_eq!(found1.unwrap().operator(), &operator1_pubkey);
+        // Verify third snapshot state (should be updated again)
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 800);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            900
+        );
+        assert!(operator_snapshot.has_minimum_stake()); // 800 >= 500
+        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 900 >= 500
+        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_3);
 
-        assert!(found2.is_some());
-        assert_eq!(found2.unwrap().operator(), &operator2_pubkey);
+        // Fourth snapshot - stake weights below minimum
+        let current_stake_weights_4 = StakeWeights::new(300);
+        let next_epoch_stake_weights_4 = StakeWeights::new(400);
+        let current_slot_4 = 300;
+
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            current_slot_4,
+            &current_stake_weights_4,
+            &next_epoch_stake_weights_4,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
 
-        assert!(not_found.is_none());
+        // Verify fourth snapshot state
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 300);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            400
+        );
+        assert!(!operator_snapshot.has_minimum_stake()); // 300 < 500
+        assert!(!operator_snapshot.has_minimum_stake_next_epoch()); // 400 < 500
+        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_4);
     }
 
     #[test]
-    fn test_snapshot_add_operator_snapshot_duplicate() {
-        // Create a snapshot using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        ));
-
-        // Create two operator snapshots with the same index
+    fn test_snapshot_vault_operator_delegation_inactive_operator() {
+        // Create an inactive operator snapshot
+        let operator = Pubkey::new_unique();
         let g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator1_snapshot = OperatorSnapshot::new(
-            &Pubkey::new_unique(),
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &operator,
             100,       // current_slot
-            true,      // is_active
+            false,     // is_active = false
             0,         // ncn_operator_index
             0,         // operator_index
             g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
         )
         .unwrap();
 
-        let operator2_snapshot = OperatorSnapshot::new(
-            &Pubkey::new_unique(),
-            100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index (same as operator1)
-            1,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+        // Verify operator is inactive
+        assert!(!operator_snapshot.is_active());
 
-        // Add first operator snapshot - should succeed
-        let result1 = snapshot.add_operator_snapshot(operator1_snapshot, 100);
-        assert!(result1.is_ok());
+        // Try to call snapshot_vault_operator_delegation on inactive operator
+        let current_stake_weights = StakeWeights::new(1000);
+        let next_epoch_stake_weights = StakeWeights::new(1200);
+        let minimum_stake = StakeWeights::new(500);
 
-        // Try to add second operator snapshot with same index - should fail
-        let result2 = snapshot.add_operator_snapshot(operator2_snapshot, 100);
-        assert!(result2.is_err());
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            150, // current_slot
+            &current_stake_weights,
+            &next_epoch_stake_weights,
+            &minimum_stake,
+        );
+
+        // Should fail with OperatorSnapshotIsNotActive error
+        assert!(result.is_err());
         assert_eq!(
-            result2.unwrap_err(),
-            NCNProgramError::DuplicateVaultOperatorDelegation
+            result.unwrap_err(),
+            NCNProgramError::OperatorSnapshotIsNotActive
         );
     }
 
     #[test]
-    fn test_snapshot_get_active_operator_snapshots() {
-        // Create a snapshot using heap allocation
-        let mut snapshot = Box::new(Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        ));
-
+    fn test_snapshot_vault_operator_delegation_edge_cases() {
+        // Create an active operator snapshot
+        let operator = Pubkey::new_unique();
         let g1_pubkey = G1CompressedPoint::from_random().0;
-
-        // Create active operator snapshot
-        let active_operator_snapshot = OperatorSnapshot::new(
-            &Pubkey::new_unique(),
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &operator,
             100,       // current_slot
-            true,      // is_active = true
+            true,      // is_active
             0,         // ncn_operator_index
             0,         // operator_index
             g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
         )
         .unwrap();
 
-        // Create inactive operator snapshot
-        let inactive_operator_snapshot = OperatorSnapshot::new(
-            &Pubkey::new_unique(),
-            100,       // current_slot
-            false,     // is_active = false
-            1,         // ncn_operator_index
-            1,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
-
-        // Add both operator snapshots
-        snapshot
-            .add_operator_snapshot(active_operator_snapshot, 100)
-            .unwrap();
-        snapshot
-            .add_operator_snapshot(inactive_operator_snapshot, 100)
-            .unwrap();
-
-        // Get active operator snapshots
-        let active_snapshots = snapshot.get_active_operator_snapshots();
-
-        // Should only return the active one
-        assert_eq!(active_snapshots.len(), 1);
-        assert!(active_snapshots[0].is_active());
-        assert_eq!(active_snapshots[0].ncn_operator_index(), 0);
-    }
-
-    #[test]
-    fn test_snapshot_initialize() {
-        let ncn = Pubkey::new_unique();
-        let mut snapshot = Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        );
+        let minimum_stake = StakeWeights::new(1000);
 
-        snapshot.initialize(
-            &ncn,
-            2,                      // bump
-            200,                    // current_slot
-            StakeWeights::new(100), // minimum_stake
+        // Test with zero stake weights
+        let zero_stake_weights = StakeWeights::new(0);
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            150,
+            &zero_stake_weights,
+            &zero_stake_weights,
+            &minimum_stake,
         );
-
-        assert_eq!(snapshot.ncn, ncn);
-        assert_eq!(snapshot.bump, 2);
-        assert_eq!(u64::from(snapshot.slot_created), 200u64);
-        assert_eq!(snapshot.minimum_stake().stake_weight(), 100);
-    }
-
-    #[test]
-    fn test_snapshot_seeds_and_pda() {
-        let ncn = Pubkey::new_unique();
-        let program_id = Pubkey::new_unique();
-
-        let seeds = Snapshot::seeds(&ncn);
-        assert_eq!(seeds.len(), 2);
-        assert_eq!(seeds[0], b"snapshot");
-        assert_eq!(seeds[1], ncn.to_bytes().to_vec());
-
-        let (pda, bump, returned_seeds) = Snapshot::find_program_address(&program_id, &ncn);
-        assert_eq!(returned_seeds, seeds);
-        assert!(bump > 0);
-        assert!(pda != Pubkey::default());
-    }
-
-    #[test]
-    fn test_snapshot_getters() {
-        let ncn = Pubkey::new_unique();
-        let snapshot = Snapshot::new(
-            &ncn,
-            3,                      // bump
-            500,                    // current_slot
-            StakeWeights::new(200), // minimum_stake
+        assert!(result.is_ok());
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 0);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            0
         );
+        assert!(!operator_snapshot.has_minimum_stake()); // 0 < 1000
+        assert!(!operator_snapshot.has_minimum_stake_next_epoch()); // 0 < 1000
 
-        assert_eq!(snapshot.operators_registered(), 0);
-        assert_eq!(snapshot.operators_can_vote_count(), 0);
-        assert_eq!(snapshot.last_snapshot_slot(), 0);
-        assert_eq!(snapshot.minimum_stake().stake_weight(), 200);
-    }
-
-    #[test]
-    fn test_snapshot_increment_operator_registration() {
-        let mut snapshot = Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
+        // Test with exactly minimum stake weight
+        let exact_minimum = StakeWeights::new(1000);
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            200,
+            &exact_minimum,
+            &exact_minimum,
+            &minimum_stake,
         );
-
-        // First increment - should succeed
-        let result = snapshot.increment_operator_registration(150);
         assert!(result.is_ok());
-        assert_eq!(snapshot.operators_registered(), 1);
-        assert_eq!(snapshot.operators_can_vote_count(), 0); // operators_can_vote_count is not updated by this method
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1000);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            1000
+        );
+        assert!(operator_snapshot.has_minimum_stake()); // 1000 >= 1000
+        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 1000 >= 1000
 
-        // Second increment - should finalize
-        let result = snapshot.increment_operator_registration(200);
+        // Test with maximum stake weights
+        let max_stake_weights = StakeWeights::new(u128::MAX);
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            250,
+            &max_stake_weights,
+            &max_stake_weights,
+            &minimum_stake,
+        );
         assert!(result.is_ok());
-        assert_eq!(snapshot.operators_registered(), 2);
-        assert_eq!(snapshot.operators_can_vote_count(), 0); // Still 0 - not updated by registration
-        assert_eq!(snapshot.last_snapshot_slot(), 200);
-    }
-
-    #[test]
-    fn test_snapshot_get_operator_snapshot() {
-        let mut snapshot = Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
+        assert_eq!(operator_snapshot.stake_weight().stake_weight(), u128::MAX);
+        assert_eq!(
+            operator_snapshot.next_epoch_stake_weight().stake_weight(),
+            u128::MAX
         );
-
-        // Test getting non-existent snapshot
-        assert!(snapshot.get_operator_snapshot(0).is_none());
-        assert!(snapshot.get_operator_snapshot(1).is_none());
-
-        // Add an operator snapshot
-        let operator_pubkey = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator_snapshot = OperatorSnapshot::new(
-            &operator_pubkey,
-            100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
-
-        snapshot
-            .add_operator_snapshot(operator_snapshot, 100)
-            .unwrap();
-
-        // Test getting existing snapshot
-        let retrieved = snapshot.get_operator_snapshot(0);
-        assert!(retrieved.is_some());
-        assert_eq!(retrieved.unwrap().operator(), &operator_pubkey);
-
-        // Test getting out of bounds
-        assert!(snapshot.get_operator_snapshot(2).is_none());
+        assert!(operator_snapshot.has_minimum_stake()); // u128::MAX >= 1000
+        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // u128::MAX >= 1000
     }
 
     #[test]
-    fn test_snapshot_get_mut_operator_snapshot() {
-        let mut snapshot = Snapshot::new(
-            &Pubkey::new_unique(),
-            1,                    // bump
-            100,                  // current_slot
-            StakeWeights::new(1), // minimum_stake
-        );
-
-        // Add an operator snapshot
-        let operator_pubkey = Pubkey::new_unique();
+    fn test_snapshot_vault_operator_delegation_slot_tracking() {
+        // Create an active operator snapshot
+        let operator = Pubkey::new_unique();
         let g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator_snapshot = OperatorSnapshot::new(
-            &operator_pubkey,
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &operator,
             100,       // current_slot
             true,      // is_active
             0,         // ncn_operator_index
             0,         // operator_index
             g1_pubkey, // g1_pubkey
+            [0; G2_COMPRESSED_POINT_SIZE],
         )
         .unwrap();
 
-        snapshot
-            .add_operator_snapshot(operator_snapshot, 100)
-            .unwrap();
+        let stake_weights = StakeWeights::new(1000);
+        let minimum_stake = StakeWeights::new(500);
 
-        // Test getting mutable reference
-        let mut_snapshot = snapshot.get_mut_operator_snapshot(0);
-        assert!(mut_snapshot.is_some());
+        // Initial state
+        assert_eq!(operator_snapshot.last_snapshot_slot(), 0);
 
-        // Modify the snapshot
-        if let Some(snapshot) = mut_snapshot {
-            snapshot.set_has_minimum_stake_this_epoch(true);
-            assert!(snapshot.has_minimum_stake());
-        }
+        // First snapshot
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            150,
+            &stake_weights,
+            &stake_weights,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
+        assert_eq!(operator_snapshot.last_snapshot_slot(), 150);
 
-        // Verify the change persisted
-        let retrieved = snapshot.get_operator_snapshot(0);
-        assert!(retrieved.unwrap().has_minimum_stake());
+        // Second snapshot with later slot
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            200,
+            &stake_weights,
+            &stake_weights,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
+        assert_eq!(operator_snapshot.last_snapshot_slot(), 200);
+
+        // Third snapshot with earlier slot (should still update)
+        let result = operator_snapshot.snapshot_vault_operator_delegation(
+            175,
+            &stake_weights,
+            &stake_weights,
+            &minimum_stake,
+        );
+        assert!(result.is_ok());
+        assert_eq!(operator_snapshot.last_snapshot_slot(), 175);
     }
 
     #[test]
-    fn test_snapshot_find_mut_operator_snapshot() {
-        let mut snapshot = Snapshot::new(
+    fn test_snapshot_backup_bytes_round_trip() {
+        let mut snapshot = Box::new(Snapshot::new(
             &Pubkey::new_unique(),
             1,                    // bump
             100,                  // current_slot
             StakeWeights::new(1), // minimum_stake
-        );
-
-        let operator_pubkey = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator_snapshot = OperatorSnapshot::new(
-            &operator_pubkey,
-            100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+        ));
 
-        snapshot
-            .add_operator_snapshot(operator_snapshot, 100)
+        for i in 0..3u64 {
+            let g1_pubkey = G1CompressedPoint::from_random().0;
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100 + i,   // current_slot
+                i != 2,    // is_active
+                i,         // ncn_operator_index
+                i + 10,    // operator_index
+                g1_pubkey, // g1_pubkey
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
             .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    150 + i,
+                    &StakeWeights::new(1000 * (i as u128 + 1)),
+                    &StakeWeights::new(500),
+                    snapshot.minimum_stake(),
+                )
+                .unwrap();
+            operator_snapshot.set_missed_last_vote(i == 1);
+            operator_snapshot
+                .insert_vault(&Pubkey::new_unique())
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 200 + i)
+                .unwrap();
+            snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+        }
+        assert!(snapshot.record_finalization(3, 250));
+        // An operator slot freed before the backup was taken
+        snapshot.deregistered_operators[0] |= 1 << 5;
 
-        // Test finding by pubkey
-        let mut_found = snapshot.find_mut_operator_snapshot(&operator_pubkey);
-        assert!(mut_found.is_some());
+        let bytes = snapshot.to_backup_bytes();
+        let restored = Snapshot::from_backup_bytes(&bytes).unwrap();
 
-        // Test finding non-existent
-        let not_found = snapshot.find_mut_operator_snapshot(&Pubkey::new_unique());
-        assert!(not_found.is_none());
+        assert_eq!(restored.ncn, snapshot.ncn);
+        assert_eq!(restored.bump, snapshot.bump);
+        assert_eq!(
+            u64::from(restored.slot_created),
+            u64::from(snapshot.slot_created)
+        );
+        assert_eq!(restored.operators_registered(), 3);
+        assert_eq!(
+            restored.operators_can_vote_count(),
+            snapshot.operators_can_vote_count()
+        );
+        assert_eq!(restored.last_snapshot_slot(), snapshot.last_snapshot_slot());
+        assert_eq!(restored.slot_finalized(), 250);
+        assert_eq!(
+            restored.deregistered_operators,
+            snapshot.deregistered_operators
+        );
+        assert_eq!(restored.operators_deregistered(), 1);
+        assert_eq!(
+            restored.total_aggregated_g1_pubkey(),
+            snapshot.total_aggregated_g1_pubkey()
+        );
+        assert_eq!(
+            restored.minimum_stake().stake_weight(),
+            snapshot.minimum_stake().stake_weight()
+        );
+        assert_eq!(
+            bytemuck::bytes_of(&restored.operator_snapshots),
+            bytemuck::bytes_of(&snapshot.operator_snapshots)
+        );
+        assert!(restored
+            .get_operator_snapshot(1)
+            .unwrap()
+            .missed_last_vote());
+        assert!(!restored
+            .get_operator_snapshot(0)
+            .unwrap()
+            .missed_last_vote());
+
+        // The delegated vaults survive, so a restored snapshot still rejects double counting
+        for (_, operator_snapshot) in snapshot.registered_operator_snapshots() {
+            let vault = *operator_snapshot.vaults_delegated().next().unwrap();
+            let mut restored_operator_snapshot = *restored
+                .get_operator_snapshot(operator_snapshot.ncn_operator_index())
+                .unwrap();
+            assert!(restored_operator_snapshot.contains_vault(&vault));
+            assert_eq!(
+                restored_operator_snapshot.insert_vault(&vault),
+                Err(NCNProgramError::DuplicateVaultOperatorDelegation)
+            );
+        }
     }
 
     #[test]
-    fn test_snapshot_add_operator_snapshot_index_overflow() {
-        let mut snapshot = Snapshot::new(
+    fn test_snapshot_backup_bytes_rejects_bad_input() {
+        let snapshot = Box::new(Snapshot::new(
             &Pubkey::new_unique(),
             1,                    // bump
             100,                  // current_slot
             StakeWeights::new(1), // minimum_stake
-        );
+        ));
 
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let operator_snapshot = OperatorSnapshot::new(
-            &Pubkey::new_unique(),
-            100,                  // current_slot
-            true,                 // is_active
-            MAX_OPERATORS as u64, // ncn_operator_index (too large)
-            0,                    // operator_index
-            g1_pubkey,            // g1_pubkey
-        )
-        .unwrap();
+        let mut bytes = snapshot.to_backup_bytes();
 
-        let result = snapshot.add_operator_snapshot(operator_snapshot, 100);
-        assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err(),
-            NCNProgramError::TooManyVaultOperatorDelegations
+            Snapshot::from_backup_bytes(&[]).unwrap_err(),
+            NCNProgramError::InvalidSnapshotBackup
         );
-    }
-
-    #[test]
-    fn test_operator_snapshot_new() {
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-
-        let snapshot = OperatorSnapshot::new(
-            &operator, 100,       // current_slot
-            true,      // is_active
-            5,         // ncn_operator_index
-            10,        // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
-
-        assert_eq!(snapshot.operator(), &operator);
-        assert_eq!(u64::from(snapshot.slot_created), 100u64);
-        assert_eq!(u64::from(snapshot.last_snapshot_slot), 0u64);
-        assert!(snapshot.is_active());
-        assert_eq!(snapshot.ncn_operator_index(), 5);
-        assert_eq!(u64::from(snapshot.operator_index), 10u64);
-        assert_eq!(snapshot.g1_pubkey(), g1_pubkey);
-        assert!(!snapshot.has_minimum_stake());
-        assert_eq!(snapshot.stake_weight().stake_weight(), 0);
-    }
-
-    #[test]
-    fn test_operator_snapshot_initialize_validation() {
-        let mut snapshot = OperatorSnapshot::default();
-
-        // Test with too many vault operator delegations
-        let result = snapshot.initialize(
-            &Pubkey::new_unique(),
-            100,                                // current_slot
-            true,                               // is_active
-            0,                                  // ncn_operator_index
-            0,                                  // operator_index
-            G1CompressedPoint::from_random().0, // g1_pubkey
-            MAX_VAULTS as u64 + 1,              // too many delegations
+        assert_eq!(
+            Snapshot::from_backup_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            NCNProgramError::InvalidSnapshotBackup
         );
 
-        assert!(result.is_err());
+        bytes[0] = SnapshotBackup::VERSION + 1;
         assert_eq!(
-            result.unwrap_err(),
-            NCNProgramError::TooManyVaultOperatorDelegations
+            Snapshot::from_backup_bytes(&bytes).unwrap_err(),
+            NCNProgramError::UnsupportedSnapshotBackupVersion
         );
     }
 
-    #[test]
-    fn test_operator_snapshot_initialize_inactive() {
-        let mut snapshot = OperatorSnapshot::default();
-
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-
-        // Use a smaller vault_operator_delegation_count that fits with MAX_VAULTS=1
-        snapshot
-            .initialize(
-                &operator, 100,       // current_slot
-                false,     // is_active = false
-                0,         // ncn_operator_index
-                0,         // operator_index
-                g1_pubkey, // g1_pubkey
-                1,         // vault_operator_delegation_count (reduced from 5 to 1)
+    fn wire_test_operator_snapshot() -> OperatorSnapshot {
+        let mut operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            100,  // current_slot
+            true, // is_active
+            3,    // ncn_operator_index
+            7,    // operator_index
+            G1CompressedPoint::from_random().0,
+            G2CompressedPoint::try_from(&PrivKey::from_random())
+                .unwrap()
+                .0,
+        )
+        .unwrap();
+        operator_snapshot
+            .snapshot_vault_operator_delegation(
+                150,
+                &StakeWeights::new(2_000),
+                &StakeWeights::new(1_500),
+                &StakeWeights::new(1_000),
             )
             .unwrap();
-
-        assert_eq!(snapshot.operator(), &operator);
-        assert!(!snapshot.is_active());
-        assert_eq!(u64::from(snapshot.last_snapshot_slot), 0u64); // Should be finalized immediately
+        operator_snapshot.set_missed_last_vote(true);
+        operator_snapshot
+            .insert_vault(&Pubkey::new_unique())
+            .unwrap();
+        operator_snapshot
     }
 
     #[test]
-    fn test_operator_snapshot_have_valid_bn128_g1_pubkey() {
-        let mut snapshot = OperatorSnapshot::default();
-
-        // Test with invalid pubkey (all zeros)
-        assert!(!snapshot.have_valid_bn128_g1_pubkey());
+    fn test_operator_snapshot_bytes_round_trip() {
+        let operator_snapshot = wire_test_operator_snapshot();
 
-        // Test with valid pubkey
-        let valid_g1_pubkey = G1CompressedPoint::from_random().0;
-        snapshot.g1_pubkey = valid_g1_pubkey;
-        assert!(snapshot.have_valid_bn128_g1_pubkey());
+        let bytes = operator_snapshot.to_bytes();
+        let restored = OperatorSnapshot::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            bytemuck::bytes_of(&restored),
+            bytemuck::bytes_of(&operator_snapshot)
+        );
+        assert!(restored.missed_last_vote());
+        assert_eq!(
+            restored.vaults_delegated().collect::<Vec<_>>(),
+            operator_snapshot.vaults_delegated().collect::<Vec<_>>()
+        );
     }
 
     #[test]
-    fn test_operator_snapshot_stake_weight_operations() {
-        let mut snapshot = OperatorSnapshot::default();
-
-        // Test initial state
-        assert_eq!(snapshot.stake_weight().stake_weight(), 0);
-        assert!(!snapshot.has_minimum_stake());
-
-        // Test setting stake weight
-        let stake_weight = StakeWeights::new(100);
-        snapshot.set_stake_weight(&stake_weight);
-        assert_eq!(snapshot.stake_weight().stake_weight(), 100);
-
-        // Test incrementing stake weight
-        let increment = StakeWeights::new(50);
-        let _ = snapshot.stake_weight.increment(&increment);
-        assert_eq!(snapshot.stake_weight().stake_weight(), 150);
+    fn test_operator_snapshot_bytes_rejects_mutations() {
+        let bytes = wire_test_operator_snapshot().to_bytes();
+
+        // Any single mutated byte, in the payload or the checksum, is rejected
+        for index in 0..bytes.len() {
+            let mut mutated = bytes.clone();
+            mutated[index] ^= 0x01;
+            assert_eq!(
+                OperatorSnapshot::from_bytes(&mutated).unwrap_err(),
+                NCNProgramError::InvalidOperatorSnapshotBytes
+            );
+        }
 
-        // Test setting minimum stake weight flag
-        snapshot.set_has_minimum_stake_this_epoch(true);
-        assert!(snapshot.has_minimum_stake());
+        assert_eq!(
+            OperatorSnapshot::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            NCNProgramError::InvalidOperatorSnapshotBytes
+        );
+        assert_eq!(
+            OperatorSnapshot::from_bytes(&[]).unwrap_err(),
+            NCNProgramError::InvalidOperatorSnapshotBytes
+        );
     }
 
     #[test]
-    fn test_vault_operator_stake_weight_new() {
-        let vault = Pubkey::new_unique();
-        let stake_weight = StakeWeights::new(500);
+    fn test_coalition_power_bps() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        let vault_stake_weight = VaultOperatorStakeWeight::new(&vault, 10, &stake_weight);
+        // Total stake weight of 1_000 split across three operators
+        let stake_weights = [200u128, 300, 500];
+        let operators: Vec<Pubkey> = stake_weights.iter().map(|_| Pubkey::new_unique()).collect();
+
+        for (i, (operator, stake_weight)) in operators.iter().zip(stake_weights).enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                true,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(stake_weight));
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        assert_eq!(vault_stake_weight.vault(), &vault);
-        assert_eq!(vault_stake_weight.vault_index(), 10);
-        assert_eq!(vault_stake_weight.stake_weights().stake_weight(), 500);
-        assert!(!vault_stake_weight.is_empty());
-    }
+        // 200 + 300 out of 1_000 is half the stake
+        let power = snapshot
+            .coalition_power_bps(&[operators[0], operators[1]])
+            .unwrap();
+        assert_eq!(power, 5_000);
+
+        // Repeated operators are only counted once
+        let power = snapshot
+            .coalition_power_bps(&[operators[0], operators[1], operators[0]])
+            .unwrap();
+        assert_eq!(power, 5_000);
 
-    #[test]
-    fn test_vault_operator_stake_weight_default() {
-        let default_weight = VaultOperatorStakeWeight::default();
+        assert_eq!(snapshot.coalition_power_bps(&operators).unwrap(), 10_000);
+        assert_eq!(snapshot.coalition_power_bps(&[]).unwrap(), 0);
 
-        assert_eq!(default_weight.vault(), &Pubkey::default());
-        assert_eq!(default_weight.vault_index(), u64::MAX);
-        assert_eq!(default_weight.stake_weights().stake_weight(), 0);
-        assert!(default_weight.is_empty());
+        assert_eq!(
+            snapshot.coalition_power_bps(&[Pubkey::new_unique()]),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
+        );
     }
 
     #[test]
-    fn test_snapshot_register_operator_g1_pubkey_invalid_pubkey() {
-        let mut snapshot = Snapshot::new(
+    fn test_verify_bitmap_partition() {
+        let mut snapshot = Box::new(Snapshot::new(
             &Pubkey::new_unique(),
             1,                    // bump
             100,                  // current_slot
             StakeWeights::new(1), // minimum_stake
-        );
+        ));
 
-        // Test with invalid G1 pubkey (all zeros except one byte)
-        let mut invalid_pubkey = [0u8; G1_COMPRESSED_POINT_SIZE];
-        invalid_pubkey[0] = 1; // Make it invalid G1 point
+        let operator_count = 10;
+        let g1_pubkeys: Vec<[u8; G1_COMPRESSED_POINT_SIZE]> = (0..operator_count)
+            .map(|_| G1CompressedPoint::from_random().0)
+            .collect();
 
-        let result = snapshot.register_operator_g1_pubkey(&invalid_pubkey);
-        assert!(result.is_err()); // Should fail with invalid G1 point
+        for (i, g1_pubkey) in g1_pubkeys.iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                i as u64,
+                i as u64,
+                *g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+            snapshot.register_operator_g1_pubkey(g1_pubkey).unwrap();
+        }
+
+        // Operators 0, 2, 5 and 9 signed
+        let signers = [0, 2, 5, 9];
+        let signer_aggregate = signers
+            .iter()
+            .map(|&i| G1Point::try_from(&G1CompressedPoint(g1_pubkeys[i])).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+        let non_signers: Vec<usize> = (0..operator_count)
+            .filter(|i| !signers.contains(i))
+            .collect();
+
+        let bitmap = crate::utils::create_signer_bitmap(&non_signers, operator_count);
+        assert!(snapshot
+            .verify_bitmap_partition(&signer_aggregate, &bitmap)
+            .unwrap());
+
+        // Every signer shifted one operator up
+        let shifted_non_signers: Vec<usize> = (0..operator_count)
+            .filter(|i| !signers.iter().any(|signer| signer + 1 == *i))
+            .collect();
+        let shifted_bitmap =
+            crate::utils::create_signer_bitmap(&shifted_non_signers, operator_count);
+        assert!(!snapshot
+            .verify_bitmap_partition(&signer_aggregate, &shifted_bitmap)
+            .unwrap());
+
+        // One signer missing from the bitmap
+        let mut missing_signer = bitmap.clone();
+        missing_signer[1] &= !(1 << 1);
+        assert!(!snapshot
+            .verify_bitmap_partition(&signer_aggregate, &missing_signer)
+            .unwrap());
+
+        // One non-signer counted as a signer
+        let mut extra_signer = bitmap.clone();
+        extra_signer[0] |= 1 << 1;
+        assert!(!snapshot
+            .verify_bitmap_partition(&signer_aggregate, &extra_signer)
+            .unwrap());
     }
 
     #[test]
-    fn test_snapshot_edge_cases() {
-        // Test with maximum values
-        let snapshot = Snapshot::new(
+    fn test_aggregate_signers_addition_matches_subtraction() {
+        let mut snapshot = Box::new(Snapshot::new(
             &Pubkey::new_unique(),
-            255,                          // bump
-            u64::MAX,                     // current_slot
-            StakeWeights::new(u128::MAX), // minimum_stake
-        );
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        assert_eq!(snapshot.minimum_stake().stake_weight(), u128::MAX);
-    }
+        let operator_count = 10;
+        let g1_pubkeys: Vec<[u8; G1_COMPRESSED_POINT_SIZE]> = (0..operator_count)
+            .map(|_| G1CompressedPoint::from_random().0)
+            .collect();
 
-    #[test]
-    fn test_operator_snapshot_edge_cases() {
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
+        for (i, g1_pubkey) in g1_pubkeys.iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                i as u64,
+                i as u64,
+                *g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+            snapshot.register_operator_g1_pubkey(g1_pubkey).unwrap();
+        }
 
-        let snapshot = OperatorSnapshot::new(
-            &operator,
-            u64::MAX,                 // current_slot
-            true,                     // is_active
-            MAX_OPERATORS as u64 - 1, // ncn_operator_index
-            u64::MAX,                 // operator_index
-            g1_pubkey,                // g1_pubkey
-        )
-        .unwrap();
+        for non_signers in [vec![], vec![3], vec![0, 9], vec![1, 2, 4, 5, 6, 7, 8]] {
+            let signer_bitmap = crate::utils::create_signer_bitmap(&non_signers, operator_count);
+            let non_signer_bitmap: Vec<u8> = signer_bitmap.iter().map(|byte| !byte).collect();
 
-        assert_eq!(u64::from(snapshot.slot_created), u64::MAX);
-        assert_eq!(snapshot.ncn_operator_index(), MAX_OPERATORS as u64 - 1);
-        assert_eq!(u64::from(snapshot.operator_index), u64::MAX);
+            let by_addition = snapshot
+                .aggregate_signers_by_addition(&signer_bitmap)
+                .unwrap();
+            let by_subtraction = snapshot
+                .aggregate_signers_by_subtraction(&non_signer_bitmap)
+                .unwrap();
+            assert_eq!(by_addition.0, by_subtraction.0);
+
+            let expected = g1_pubkeys
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !non_signers.contains(i))
+                .map(|(_, g1_pubkey)| G1Point::try_from(&G1CompressedPoint(*g1_pubkey)).unwrap())
+                .reduce(|acc, x| acc + x)
+                .unwrap();
+            assert_eq!(by_addition.0, expected.0);
+        }
+
+        // With every operator signing, the stored total is exactly the direct sum
+        let full_bitmap = crate::utils::create_signer_bitmap(&[], operator_count);
+        assert_eq!(
+            snapshot.total_aggregated_g1_point().unwrap().0,
+            snapshot
+                .aggregate_signers_by_addition(&full_bitmap)
+                .unwrap()
+                .0
+        );
     }
 
     #[test]
-    fn test_snapshot_arithmetic_overflow_protection() {
-        let mut snapshot = Snapshot::new(
+    fn test_aggregate_signers_g2() {
+        let mut snapshot = Box::new(Snapshot::new(
             &Pubkey::new_unique(),
             1,                    // bump
             100,                  // current_slot
             StakeWeights::new(1), // minimum_stake
-        );
-
-        // Set to maximum values to test overflow
-        snapshot.operators_registered = PodU64::from(u64::MAX);
+        ));
 
-        let result = snapshot.increment_operator_registration(200);
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), NCNProgramError::ArithmeticOverflow);
-    }
+        let operator_count = 4;
+        let g2_pubkeys: Vec<[u8; G2_COMPRESSED_POINT_SIZE]> = (0..operator_count)
+            .map(|_| G2CompressedPoint::from_random().0)
+            .collect();
 
-    #[test]
-    fn test_snapshot_vault_operator_delegation_single_use() {
-        // Create an active operator snapshot
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let mut operator_snapshot = OperatorSnapshot::new(
-            &operator, 100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+        for (i, g2_pubkey) in g2_pubkeys.iter().enumerate() {
+            let operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                *g2_pubkey,
+            )
+            .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        // Verify initial state
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 0);
+        // Operators 0, 1 and 3 signed
+        let bitmap = crate::utils::create_signer_bitmap(&[2], operator_count);
+        let expected = [0, 1, 3]
+            .iter()
+            .map(|i| G2Point::try_from(G2CompressedPoint(g2_pubkeys[*i])).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap();
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            0
+            snapshot.aggregate_signers_g2(&bitmap).unwrap().0,
+            expected.0
         );
-        assert!(!operator_snapshot.has_minimum_stake());
-        assert!(!operator_snapshot.has_minimum_stake_next_epoch());
-        assert_eq!(operator_snapshot.last_snapshot_slot(), 0);
-
-        // Create stake weights for testing
-        let current_stake_weights = StakeWeights::new(1000);
-        let next_epoch_stake_weights = StakeWeights::new(1200);
-        let minimum_stake = StakeWeights::new(500);
 
-        // Call snapshot_vault_operator_delegation once
-        let current_slot = 150;
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            current_slot,
-            &current_stake_weights,
-            &next_epoch_stake_weights,
-            &minimum_stake,
+        // A signer without a stored G2 pubkey can't be part of the aggregate
+        snapshot
+            .get_mut_operator_snapshot(1)
+            .unwrap()
+            .update_g2_pubkey(&[0; G2_COMPRESSED_POINT_SIZE]);
+        assert_eq!(
+            snapshot.aggregate_signers_g2(&bitmap).unwrap_err(),
+            NCNProgramError::InvalidG2Pubkey
         );
 
-        // Verify the call succeeded
-        assert!(result.is_ok());
-
-        // Verify the state was updated correctly
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1000);
+        // Unless it did not sign
+        let bitmap = crate::utils::create_signer_bitmap(&[1, 2], operator_count);
+        let expected = G2Point::try_from(G2CompressedPoint(g2_pubkeys[0])).unwrap()
+            + G2Point::try_from(G2CompressedPoint(g2_pubkeys[3])).unwrap();
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            1200
+            snapshot.aggregate_signers_g2(&bitmap).unwrap().0,
+            expected.0
         );
-        assert!(operator_snapshot.has_minimum_stake()); // 1000 >= 500
-        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 1200 >= 500
-        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot);
     }
 
     #[test]
-    fn test_snapshot_vault_operator_delegation_multiple_updates() {
-        // Create an active operator snapshot
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let mut operator_snapshot = OperatorSnapshot::new(
-            &operator, 100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+    fn test_aggregate_eligible_signers_g1_excludes_operators_below_minimum() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        let minimum_stake = StakeWeights::new(500);
+        // Operator 1 signs but lacks the minimum stake
+        let has_minimum_stake = [true, false, true, true];
+        let g1_pubkeys: Vec<[u8; G1_COMPRESSED_POINT_SIZE]> = (0..has_minimum_stake.len())
+            .map(|_| G1CompressedPoint::from_random().0)
+            .collect();
 
-        // First snapshot - initial stake weights
-        let current_stake_weights_1 = StakeWeights::new(1000);
-        let next_epoch_stake_weights_1 = StakeWeights::new(1200);
-        let current_slot_1 = 150;
+        for (i, g1_pubkey) in g1_pubkeys.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                i as u64,
+                i as u64,
+                *g1_pubkey,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_has_minimum_stake_this_epoch(has_minimum_stake[i]);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            current_slot_1,
-            &current_stake_weights_1,
-            &next_epoch_stake_weights_1,
-            &minimum_stake,
-        );
-        assert!(result.is_ok());
+        // Operators 0, 1 and 2 signed
+        let bitmap = crate::utils::create_signer_bitmap(&[3], has_minimum_stake.len());
 
-        // Verify first snapshot state
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1000);
-        assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            1200
-        );
-        assert!(operator_snapshot.has_minimum_stake());
-        assert!(operator_snapshot.has_minimum_stake_next_epoch());
-        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_1);
+        let aggregated = snapshot.aggregate_eligible_signers_g1(&bitmap).unwrap();
+        let expected = G1Point::try_from(&G1CompressedPoint(g1_pubkeys[0])).unwrap()
+            + G1Point::try_from(&G1CompressedPoint(g1_pubkeys[2])).unwrap();
+        assert_eq!(aggregated.0, expected.0);
 
-        // Second snapshot - updated stake weights (increased)
-        let current_stake_weights_2 = StakeWeights::new(1500);
-        let next_epoch_stake_weights_2 = StakeWeights::new(1800);
-        let current_slot_2 = 200;
+        // The plain signer aggregate still includes the ineligible signer
+        let all_signers = snapshot.aggregate_signers_by_addition(&bitmap).unwrap();
+        assert_ne!(aggregated.0, all_signers.0);
 
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            current_slot_2,
-            &current_stake_weights_2,
-            &next_epoch_stake_weights_2,
-            &minimum_stake,
-        );
-        assert!(result.is_ok());
+        // Only the ineligible operator signed
+        let bitmap = crate::utils::create_signer_bitmap(&[0, 2, 3], has_minimum_stake.len());
+        let aggregated = snapshot.aggregate_eligible_signers_g1(&bitmap).unwrap();
+        assert_eq!(aggregated.0, [0u8; G1_POINT_SIZE]);
+    }
+
+    #[test]
+    fn test_is_stalled() {
+        let epoch_length = 100;
+        let epochs_before_stall = 3;
+
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            0,                    // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // Last snapshot taken in epoch 2
+        let operator_snapshot = OperatorSnapshot::new(
+            &Pubkey::new_unique(),
+            250,
+            true,
+            0,
+            0,
+            [0; 32],
+            [0; G2_COMPRESSED_POINT_SIZE],
+        )
+        .unwrap();
+        snapshot
+            .add_operator_snapshot(operator_snapshot, 250)
+            .unwrap();
+        assert_eq!(snapshot.last_snapshot_slot(), 250);
+
+        // Same epoch and one epoch short of the threshold
+        assert!(!snapshot
+            .is_stalled(299, epoch_length, epochs_before_stall)
+            .unwrap());
+        assert!(!snapshot
+            .is_stalled(499, epoch_length, epochs_before_stall)
+            .unwrap());
+
+        // Exactly at the threshold
+        assert!(snapshot
+            .is_stalled(500, epoch_length, epochs_before_stall)
+            .unwrap());
+        assert!(snapshot
+            .is_stalled(10_000, epoch_length, epochs_before_stall)
+            .unwrap());
+
+        // A clock behind the snapshot is never stalled
+        assert!(!snapshot
+            .is_stalled(0, epoch_length, epochs_before_stall)
+            .unwrap());
 
-        // Verify second snapshot state (should be updated)
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1500);
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            1800
+            snapshot.is_stalled(500, 0, epochs_before_stall),
+            Err(NCNProgramError::DenominatorIsZero)
         );
-        assert!(operator_snapshot.has_minimum_stake()); // 1500 >= 500
-        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 1800 >= 500
-        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_2);
+    }
 
-        // Third snapshot - decreased stake weights
-        let current_stake_weights_3 = StakeWeights::new(800);
-        let next_epoch_stake_weights_3 = StakeWeights::new(900);
-        let current_slot_3 = 250;
+    #[test]
+    fn test_total_eligible_stake_weight() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            current_slot_3,
-            &current_stake_weights_3,
-            &next_epoch_stake_weights_3,
-            &minimum_stake,
-        );
-        assert!(result.is_ok());
+        // (stake weight, is active, has minimum stake)
+        let operators = [
+            (100u128, true, true),
+            (200, true, false),
+            (300, false, true),
+            (400, true, true),
+        ];
+        for (i, (stake_weight, is_active, has_minimum_stake)) in operators.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                *is_active,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(*stake_weight));
+            operator_snapshot.set_has_minimum_stake_this_epoch(*has_minimum_stake);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        // Verify third snapshot state (should be updated again)
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 800);
+        // Neither the below-minimum nor the inactive operator count
+        assert_eq!(snapshot.total_eligible_stake_weight().unwrap(), 500);
+
+        // With every operator ineligible the total is zero
+        for i in 0..operators.len() {
+            let mut operator_snapshot = *snapshot.get_operator_snapshot(i as u64).unwrap();
+            operator_snapshot.set_has_minimum_stake_this_epoch(false);
+            snapshot.update_operator_snapshot(i, &operator_snapshot);
+        }
+        assert_eq!(snapshot.total_eligible_stake_weight().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_operator_stake_share_bps() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        // (stake weight, is active, has minimum stake), 1_000 of eligible stake weight in total
+        let operators = [
+            (500u128, true, true),
+            (200, true, true),
+            (300, true, true),
+            (400, true, false),
+            (600, false, true),
+        ];
+        let operator_pubkeys: Vec<Pubkey> =
+            operators.iter().map(|_| Pubkey::new_unique()).collect();
+        for (i, (stake_weight, is_active, has_minimum_stake)) in operators.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &operator_pubkeys[i],
+                100,
+                *is_active,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(*stake_weight));
+            operator_snapshot.set_has_minimum_stake_this_epoch(*has_minimum_stake);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        // Exactly half the eligible stake
+        assert_eq!(
+            snapshot.operator_stake_share_bps(&operator_pubkeys[0]),
+            Ok(5_000)
+        );
+        assert_eq!(
+            snapshot.operator_stake_share_bps(&operator_pubkeys[1]),
+            Ok(2_000)
+        );
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            900
+            snapshot.operator_stake_share_bps(&operator_pubkeys[2]),
+            Ok(3_000)
         );
-        assert!(operator_snapshot.has_minimum_stake()); // 800 >= 500
-        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 900 >= 500
-        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_3);
-
-        // Fourth snapshot - stake weights below minimum
-        let current_stake_weights_4 = StakeWeights::new(300);
-        let next_epoch_stake_weights_4 = StakeWeights::new(400);
-        let current_slot_4 = 300;
 
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            current_slot_4,
-            &current_stake_weights_4,
-            &next_epoch_stake_weights_4,
-            &minimum_stake,
+        // Below the minimum stake or inactive operators have no share
+        assert_eq!(
+            snapshot.operator_stake_share_bps(&operator_pubkeys[3]),
+            Ok(0)
+        );
+        assert_eq!(
+            snapshot.operator_stake_share_bps(&operator_pubkeys[4]),
+            Ok(0)
         );
-        assert!(result.is_ok());
 
-        // Verify fourth snapshot state
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 300);
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            400
+            snapshot.operator_stake_share_bps(&Pubkey::new_unique()),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
         );
-        assert!(!operator_snapshot.has_minimum_stake()); // 300 < 500
-        assert!(!operator_snapshot.has_minimum_stake_next_epoch()); // 400 < 500
-        assert_eq!(operator_snapshot.last_snapshot_slot(), current_slot_4);
     }
 
     #[test]
-    fn test_snapshot_vault_operator_delegation_inactive_operator() {
-        // Create an inactive operator snapshot
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let mut operator_snapshot = OperatorSnapshot::new(
-            &operator, 100,       // current_slot
-            false,     // is_active = false
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+    fn test_operator_stake_share_bps_rounds_down() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        // Verify operator is inactive
-        assert!(!operator_snapshot.is_active());
+        // One third of the stake each, with stake weights past u64
+        let operator_pubkeys: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for (i, operator) in operator_pubkeys.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                operator,
+                100,
+                true,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(u64::MAX as u128 * 7));
+            operator_snapshot.set_has_minimum_stake_this_epoch(true);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        // Try to call snapshot_vault_operator_delegation on inactive operator
-        let current_stake_weights = StakeWeights::new(1000);
-        let next_epoch_stake_weights = StakeWeights::new(1200);
-        let minimum_stake = StakeWeights::new(500);
+        for operator in operator_pubkeys.iter() {
+            assert_eq!(snapshot.operator_stake_share_bps(operator), Ok(3_333));
+        }
+    }
 
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            150, // current_slot
-            &current_stake_weights,
-            &next_epoch_stake_weights,
-            &minimum_stake,
-        );
+    #[test]
+    fn test_total_eligible_stake_weight_overflow() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        for i in 0..2u64 {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                i,
+                i,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(u128::MAX));
+            operator_snapshot.set_has_minimum_stake_this_epoch(true);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        // Should fail with OperatorSnapshotIsNotActive error
-        assert!(result.is_err());
         assert_eq!(
-            result.unwrap_err(),
-            NCNProgramError::OperatorSnapshotIsNotActive
+            snapshot.total_eligible_stake_weight(),
+            Err(NCNProgramError::ArithmeticOverflow)
         );
     }
 
     #[test]
-    fn test_snapshot_vault_operator_delegation_edge_cases() {
-        // Create an active operator snapshot
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let mut operator_snapshot = OperatorSnapshot::new(
-            &operator, 100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+    fn test_total_eligible_stake_weight_saturating() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        let minimum_stake = StakeWeights::new(1000);
+        // (stake weight, is active)
+        let operators = [(u128::MAX / 2, true), (300, false), (u128::MAX / 2, true)];
+        for (i, (stake_weight, is_active)) in operators.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                *is_active,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(*stake_weight));
+            operator_snapshot.set_has_minimum_stake_this_epoch(true);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        // Test with zero stake weights
-        let zero_stake_weights = StakeWeights::new(0);
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            150,
-            &zero_stake_weights,
-            &zero_stake_weights,
-            &minimum_stake,
-        );
-        assert!(result.is_ok());
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 0);
+        // Without overflow both totals agree, the inactive operator does not count
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            0
-        );
-        assert!(!operator_snapshot.has_minimum_stake()); // 0 < 1000
-        assert!(!operator_snapshot.has_minimum_stake_next_epoch()); // 0 < 1000
-
-        // Test with exactly minimum stake weight
-        let exact_minimum = StakeWeights::new(1000);
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            200,
-            &exact_minimum,
-            &exact_minimum,
-            &minimum_stake,
+            snapshot.total_eligible_stake_weight_saturating(),
+            snapshot.total_eligible_stake_weight().unwrap()
         );
-        assert!(result.is_ok());
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), 1000);
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            1000
+            snapshot.total_eligible_stake_weight_saturating(),
+            u128::MAX - 1
         );
-        assert!(operator_snapshot.has_minimum_stake()); // 1000 >= 1000
-        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // 1000 >= 1000
 
-        // Test with maximum stake weights
-        let max_stake_weights = StakeWeights::new(u128::MAX);
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            250,
-            &max_stake_weights,
-            &max_stake_weights,
-            &minimum_stake,
-        );
-        assert!(result.is_ok());
-        assert_eq!(operator_snapshot.stake_weight().stake_weight(), u128::MAX);
+        // Activating the inactive operator pushes the sum past `u128::MAX`
+        let operator = *snapshot.get_operator_snapshot(1).unwrap().operator();
+        snapshot.set_operator_active(&operator, true).unwrap();
+
         assert_eq!(
-            operator_snapshot.next_epoch_stake_weight().stake_weight(),
-            u128::MAX
+            snapshot.total_eligible_stake_weight(),
+            Err(NCNProgramError::ArithmeticOverflow)
         );
-        assert!(operator_snapshot.has_minimum_stake()); // u128::MAX >= 1000
-        assert!(operator_snapshot.has_minimum_stake_next_epoch()); // u128::MAX >= 1000
+        assert_eq!(snapshot.total_eligible_stake_weight_saturating(), u128::MAX);
     }
 
     #[test]
-    fn test_snapshot_vault_operator_delegation_slot_tracking() {
-        // Create an active operator snapshot
-        let operator = Pubkey::new_unique();
-        let g1_pubkey = G1CompressedPoint::from_random().0;
-        let mut operator_snapshot = OperatorSnapshot::new(
-            &operator, 100,       // current_slot
-            true,      // is_active
-            0,         // ncn_operator_index
-            0,         // operator_index
-            g1_pubkey, // g1_pubkey
-        )
-        .unwrap();
+    fn test_signer_stake_weight_total_excludes_operators_below_minimum() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
 
-        let stake_weights = StakeWeights::new(1000);
-        let minimum_stake = StakeWeights::new(500);
+        // (stake weight, has minimum stake)
+        let operators = [(100u128, true), (200, false), (300, true), (400, true)];
+        for (i, (stake_weight, has_minimum_stake)) in operators.iter().enumerate() {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,
+                true,
+                i as u64,
+                i as u64,
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot.set_stake_weight(&StakeWeights::new(*stake_weight));
+            operator_snapshot.set_has_minimum_stake_this_epoch(*has_minimum_stake);
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
 
-        // Initial state
-        assert_eq!(operator_snapshot.last_snapshot_slot(), 0);
+        // The below-minimum operator does not count towards the eligible total
+        assert_eq!(snapshot.total_eligible_stake_weight().unwrap(), 800);
 
-        // First snapshot
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            150,
-            &stake_weights,
-            &stake_weights,
-            &minimum_stake,
+        // Operators 0, 1 and 2 signed, operator 1 lacks the minimum stake
+        let bitmap = crate::utils::create_signer_bitmap(&[3], operators.len());
+        assert_eq!(snapshot.signer_stake_weight_total(&bitmap).unwrap(), 400);
+
+        // Everyone signed
+        let bitmap = crate::utils::create_signer_bitmap(&[], operators.len());
+        assert_eq!(snapshot.signer_stake_weight_total(&bitmap).unwrap(), 800);
+
+        // Only the below-minimum operator signed
+        let bitmap = crate::utils::create_signer_bitmap(&[0, 2, 3], operators.len());
+        assert_eq!(snapshot.signer_stake_weight_total(&bitmap).unwrap(), 0);
+
+        // A paused operator leaves both sides
+        let paused = *snapshot.get_operator_snapshot(3).unwrap().operator();
+        snapshot.set_operator_active(&paused, false).unwrap();
+        assert_eq!(snapshot.total_eligible_stake_weight().unwrap(), 400);
+        let bitmap = crate::utils::create_signer_bitmap(&[], operators.len());
+        assert_eq!(snapshot.signer_stake_weight_total(&bitmap).unwrap(), 400);
+    }
+
+    #[test]
+    fn test_matches_merkle_root() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                    // bump
+            100,                  // current_slot
+            StakeWeights::new(1), // minimum_stake
+        ));
+
+        for index in 0..3 {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,   // current_slot
+                true,  // is_active
+                index, // ncn_operator_index
+                index, // operator_index
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(100),
+                    &StakeWeights::new(100),
+                    &StakeWeights::new(1),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        // The root as mirrored to another chain
+        let mirrored_root = operator_merkle_root(&operator_leaves(&snapshot)).unwrap();
+        assert!(snapshot.matches_merkle_root(&mirrored_root));
+
+        // A later change to the operator set no longer matches the mirrored root
+        let mut operator_snapshot = *snapshot.get_operator_snapshot(1).unwrap();
+        operator_snapshot
+            .snapshot_vault_operator_delegation(
+                200,
+                &StakeWeights::new(150),
+                &StakeWeights::new(150),
+                &StakeWeights::new(1),
+            )
+            .unwrap();
+        snapshot.update_operator_snapshot(1, &operator_snapshot);
+        assert!(!snapshot.matches_merkle_root(&mirrored_root));
+
+        // An empty snapshot has no root to match
+        let empty_snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,
+            100,
+            StakeWeights::new(1),
+        ));
+        assert!(!empty_snapshot.matches_merkle_root(&mirrored_root));
+    }
+
+    #[test]
+    fn test_content_hash_ignores_insertion_order() {
+        let ncn = Pubkey::new_unique();
+        let operators: Vec<(Pubkey, [u8; G1_COMPRESSED_POINT_SIZE], u128)> = (0..3)
+            .map(|i| {
+                (
+                    Pubkey::new_unique(),
+                    G1CompressedPoint::from_random().0,
+                    100 * (i + 1),
+                )
+            })
+            .collect();
+
+        let build_snapshot = |order: &[usize], slot: u64| {
+            let mut snapshot = Box::new(Snapshot::new(&ncn, 1, slot, StakeWeights::new(1)));
+            for (index, &i) in order.iter().enumerate() {
+                let (operator, g1_pubkey, stake_weight) = operators[i];
+                let mut operator_snapshot = OperatorSnapshot::new(
+                    &operator,
+                    slot,
+                    true,
+                    index as u64,
+                    index as u64,
+                    g1_pubkey,
+                    [0; G2_COMPRESSED_POINT_SIZE],
+                )
+                .unwrap();
+                operator_snapshot
+                    .snapshot_vault_operator_delegation(
+                        slot,
+                        &StakeWeights::new(stake_weight),
+                        &StakeWeights::new(stake_weight),
+                        &StakeWeights::new(1),
+                    )
+                    .unwrap();
+                snapshot
+                    .add_operator_snapshot(operator_snapshot, slot)
+                    .unwrap();
+                snapshot.register_operator_g1_pubkey(&g1_pubkey).unwrap();
+            }
+            snapshot
+        };
+
+        // Two keepers registering the same operators in different orders and slots
+        let snapshot = build_snapshot(&[0, 1, 2], 100);
+        let mut other_snapshot = build_snapshot(&[2, 0, 1], 250);
+        assert_eq!(snapshot.content_hash(), other_snapshot.content_hash());
+
+        // A different stake weight changes the hash
+        let mut operator_snapshot = *other_snapshot.get_operator_snapshot(0).unwrap();
+        operator_snapshot
+            .snapshot_vault_operator_delegation(
+                300,
+                &StakeWeights::new(301),
+                &StakeWeights::new(301),
+                &StakeWeights::new(1),
+            )
+            .unwrap();
+        other_snapshot.update_operator_snapshot(0, &operator_snapshot);
+        assert_ne!(snapshot.content_hash(), other_snapshot.content_hash());
+    }
+
+    #[test]
+    fn test_set_minimum_stake_recomputes_flags() {
+        let mut snapshot = Box::new(Snapshot::new(
+            &Pubkey::new_unique(),
+            1,                      // bump
+            100,                    // current_slot
+            StakeWeights::new(100), // minimum_stake
+        ));
+
+        // (stake_weight, next_epoch_stake_weight)
+        let stake_weights = [(150, 150), (150, 50), (50, 300)];
+        for (index, (stake_weight, next_epoch_stake_weight)) in
+            stake_weights.into_iter().enumerate()
+        {
+            let mut operator_snapshot = OperatorSnapshot::new(
+                &Pubkey::new_unique(),
+                100,          // current_slot
+                true,         // is_active
+                index as u64, // ncn_operator_index
+                index as u64, // operator_index
+                G1CompressedPoint::from_random().0,
+                [0; G2_COMPRESSED_POINT_SIZE],
+            )
+            .unwrap();
+            operator_snapshot
+                .snapshot_vault_operator_delegation(
+                    100,
+                    &StakeWeights::new(stake_weight),
+                    &StakeWeights::new(next_epoch_stake_weight),
+                    &StakeWeights::new(100),
+                )
+                .unwrap();
+            snapshot
+                .add_operator_snapshot(operator_snapshot, 100)
+                .unwrap();
+        }
+
+        let flags = |snapshot: &Snapshot| {
+            (0..3)
+                .map(|index| {
+                    let operator_snapshot = snapshot.get_operator_snapshot(index).unwrap();
+                    (
+                        operator_snapshot.has_minimum_stake(),
+                        operator_snapshot.has_minimum_stake_next_epoch(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            flags(&snapshot),
+            vec![(true, true), (true, false), (false, true)]
         );
-        assert!(result.is_ok());
-        assert_eq!(operator_snapshot.last_snapshot_slot(), 150);
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
 
-        // Second snapshot with later slot
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            200,
-            &stake_weights,
-            &stake_weights,
-            &minimum_stake,
+        // Raised above every current stake weight
+        snapshot.set_minimum_stake(StakeWeights::new(200));
+        assert_eq!(snapshot.minimum_stake().stake_weight(), 200);
+        assert_eq!(
+            flags(&snapshot),
+            vec![(false, false), (false, false), (false, true)]
         );
-        assert!(result.is_ok());
-        assert_eq!(operator_snapshot.last_snapshot_slot(), 200);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
 
-        // Third snapshot with earlier slot (should still update)
-        let result = operator_snapshot.snapshot_vault_operator_delegation(
-            175,
-            &stake_weights,
-            &stake_weights,
-            &minimum_stake,
+        // Lowered below every stake weight
+        snapshot.set_minimum_stake(StakeWeights::new(50));
+        assert_eq!(
+            flags(&snapshot),
+            vec![(true, true), (true, true), (true, true)]
         );
-        assert!(result.is_ok());
-        assert_eq!(operator_snapshot.last_snapshot_slot(), 175);
+        assert_eq!(snapshot.operators_can_vote_count(), 3);
     }
 }
@@ -19,6 +19,17 @@ impl Default for StakeWeights {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for StakeWeights {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("StakeWeights", 1)?;
+        state.serialize_field("stake_weight", &self.stake_weight())?;
+        state.end()
+    }
+}
+
 impl StakeWeights {
     pub fn new(stake_weight: u128) -> Self {
         Self {
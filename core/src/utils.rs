@@ -1,4 +1,6 @@
-use solana_program::program_error::ProgramError;
+use std::fmt::Display;
+
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
 
 use crate::constants::MODULUS;
 use crate::{constants::MAX_REALLOC_BYTES, error::NCNProgramError, snapshot::OperatorSnapshot};
@@ -23,11 +25,40 @@ pub fn assert_ncn_program_error<T>(
     assert_eq!(test_error.err().unwrap(), ncn_program_error);
 }
 
+/// Decodes a hex string, odd or otherwise malformed lengths fail with `InvalidInputLength`
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, NCNProgramError> {
+    hex::decode(hex).map_err(|e| match e {
+        hex::FromHexError::OddLength | hex::FromHexError::InvalidStringLength => {
+            NCNProgramError::InvalidInputLength
+        }
+        hex::FromHexError::InvalidHexCharacter { .. } => NCNProgramError::SerializationError,
+    })
+}
+
 pub fn get_epoch(slot: u64, ncn_epoch_length: u64) -> Result<u64, NCNProgramError> {
     slot.checked_div(ncn_epoch_length)
         .ok_or(NCNProgramError::DenominatorIsZero)
 }
 
+/// Logs a change made by an admin instruction in a fixed `key=value` format, so the history of
+/// every configuration change can be audited from the transaction logs
+pub fn log_admin_change(
+    instruction: &str,
+    admin: &Pubkey,
+    field: &str,
+    old_value: impl Display,
+    new_value: impl Display,
+) {
+    msg!(
+        "Admin change: instruction={} admin={} field={} old={} new={}",
+        instruction,
+        admin,
+        field,
+        old_value,
+        new_value
+    );
+}
+
 /// Determines if an operator is eligible to vote in the current epoch
 ///
 /// An operator can vote if:
@@ -113,3 +144,84 @@ pub fn create_signer_bitmap(non_signer_indices: &[usize], total_operators: usize
     // Return the constructed bitmap.
     bitmap
 }
+
+/// Builds the `operators_signature_bitmap` consumed by `CastVote` from the snapshot indices of the
+/// operators that signed. Bit `i % 8` of byte `i / 8` is set for signer `i`, every other bit,
+/// including the trailing bits of the last byte, is zero. Indices at or above `total_operators`
+/// are ignored.
+pub fn build_signer_bitmap(total_operators: u64, signer_indices: &[u64]) -> Vec<u8> {
+    let mut bitmap = vec![0u8; total_operators.div_ceil(8) as usize];
+
+    for &index in signer_indices
+        .iter()
+        .filter(|&&index| index < total_operators)
+    {
+        bitmap[(index / 8) as usize] |= 1 << (index % 8);
+    }
+
+    bitmap
+}
+
+/// Inverse of `build_signer_bitmap`: yields whether each of the `total_operators` operators
+/// signed, in snapshot index order. Bits missing from a short bitmap read as not signed.
+pub fn bitmap_iter(bitmap: &[u8], total_operators: u64) -> impl Iterator<Item = bool> + '_ {
+    (0..total_operators).map(move |index| is_bit_set(bitmap, index as usize))
+}
+
+/// Whether operator `index` is set in a bitmap laid out like `build_signer_bitmap`, the single
+/// bit lookup `bitmap_iter` walks. Bits missing from a short bitmap read as not set.
+pub fn is_bit_set(bitmap: &[u8], index: usize) -> bool {
+    bitmap
+        .get(index / 8)
+        .is_some_and(|byte| (byte >> (index % 8)) & 1 == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_signer_bitmap_bit_order() {
+        let bitmap = build_signer_bitmap(16, &[0, 3, 8, 15]);
+        assert_eq!(bitmap, vec![0b0000_1001, 0b1000_0001]);
+    }
+
+    #[test]
+    fn test_build_signer_bitmap_trailing_bits_are_zero() {
+        // 10 operators need 2 bytes, the 6 trailing bits of the last byte must stay zero
+        let all_signers: Vec<u64> = (0..10).collect();
+        let bitmap = build_signer_bitmap(10, &all_signers);
+        assert_eq!(bitmap, vec![0xFF, 0b0000_0011]);
+
+        // Indices past the operator count are ignored rather than set in the trailing bits
+        let bitmap = build_signer_bitmap(10, &[9, 10, 15]);
+        assert_eq!(bitmap, vec![0, 0b0000_0010]);
+
+        assert!(build_signer_bitmap(0, &[0]).is_empty());
+    }
+
+    #[test]
+    fn test_bitmap_iter_round_trips() {
+        for total_operators in [1u64, 7, 8, 9, 13, 16, 17] {
+            let signer_indices: Vec<u64> = (0..total_operators).filter(|i| i % 3 != 1).collect();
+            let bitmap = build_signer_bitmap(total_operators, &signer_indices);
+
+            let decoded: Vec<u64> = bitmap_iter(&bitmap, total_operators)
+                .enumerate()
+                .filter(|(_, signed)| *signed)
+                .map(|(index, _)| index as u64)
+                .collect();
+
+            assert_eq!(bitmap.len() as u64, total_operators.div_ceil(8));
+            assert_eq!(decoded, signer_indices);
+        }
+    }
+
+    #[test]
+    fn test_bitmap_iter_short_bitmap_reads_as_not_signed() {
+        let signed: Vec<bool> = bitmap_iter(&[0xFF], 10).collect();
+        assert_eq!(signed.len(), 10);
+        assert!(signed[..8].iter().all(|signed| *signed));
+        assert!(!signed[8] && !signed[9]);
+    }
+}
@@ -2,11 +2,19 @@ use core::fmt;
 use std::mem::size_of;
 
 use bytemuck::{Pod, Zeroable};
-use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use jito_bytemuck::{
+    types::{PodU128, PodU64},
+    AccountDeserialize, Discriminator,
+};
 use shank::{ShankAccount, ShankType};
 use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
-use crate::{discriminators::Discriminators, error::NCNProgramError, loaders::check_load};
+use crate::{
+    constants::{MAX_ST_MINTS, MAX_VAULTS, WEIGHT_PRECISION},
+    discriminators::Discriminators,
+    error::NCNProgramError,
+    loaders::check_load,
+};
 
 #[derive(Debug, Clone, Copy, Zeroable, ShankType, Pod)]
 #[repr(C)]
@@ -17,13 +25,19 @@ pub struct StMintEntry {
     // Either a switchboard feed or a weight must be set
     /// The switchboard feed for the mint
     reserve_switchboard_feed: [u8; 32],
+    /// The weight applied to the stake in this mint, scaled by `WEIGHT_PRECISION`
+    weight: PodU128,
 }
 
 impl StMintEntry {
-    pub fn new(st_mint: &Pubkey) -> Self {
+    /// Weight of the mints registered without an explicit weight, counts the stake 1:1
+    pub const DEFAULT_WEIGHT: u128 = WEIGHT_PRECISION;
+
+    pub fn new(st_mint: &Pubkey, weight: u128) -> Self {
         Self {
             st_mint: *st_mint,
             reserve_switchboard_feed: [0; 32],
+            weight: PodU128::from(weight),
         }
     }
 
@@ -31,6 +45,10 @@ impl StMintEntry {
         &self.st_mint
     }
 
+    pub fn weight(&self) -> u128 {
+        self.weight.into()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.st_mint().eq(&Pubkey::default())
     }
@@ -38,7 +56,7 @@ impl StMintEntry {
 
 impl Default for StMintEntry {
     fn default() -> Self {
-        Self::new(&Pubkey::default())
+        Self::new(&Pubkey::default(), 0)
     }
 }
 
@@ -108,9 +126,9 @@ pub struct VaultRegistry {
     /// The bump seed for the PDA
     pub bump: u8,
     /// The list of supported token ( ST ) mints
-    pub st_mint_list: [StMintEntry; 1],
+    pub st_mint_list: [StMintEntry; MAX_ST_MINTS],
     /// The list of vaults
-    pub vault_list: [VaultEntry; 1],
+    pub vault_list: [VaultEntry; MAX_VAULTS],
 }
 
 impl Discriminator for VaultRegistry {
@@ -125,8 +143,8 @@ impl VaultRegistry {
         Self {
             ncn: *ncn,
             bump,
-            st_mint_list: [StMintEntry::default(); 1],
-            vault_list: [VaultEntry::default(); 1],
+            st_mint_list: [StMintEntry::default(); MAX_ST_MINTS],
+            vault_list: [VaultEntry::default(); MAX_VAULTS],
         }
     }
 
@@ -134,8 +152,8 @@ impl VaultRegistry {
         // Initializes field by field to avoid overflowing stack
         self.ncn = *ncn;
         self.bump = bump;
-        self.st_mint_list = [StMintEntry::default(); 1];
-        self.vault_list = [VaultEntry::default(); 1];
+        self.st_mint_list = [StMintEntry::default(); MAX_ST_MINTS];
+        self.vault_list = [VaultEntry::default(); MAX_VAULTS];
     }
 
     pub fn seeds(ncn: &Pubkey) -> Vec<Vec<u8>> {
@@ -173,11 +191,15 @@ impl VaultRegistry {
         self.st_mint_list.iter().any(|m| m.st_mint.eq(mint))
     }
 
-    pub fn check_st_mint_entry(_entry: &StMintEntry) -> Result<(), ProgramError> {
+    pub fn check_st_mint_entry(entry: &StMintEntry) -> Result<(), ProgramError> {
+        if entry.weight() == 0 {
+            return Err(NCNProgramError::WeightNotSet.into());
+        }
+
         Ok(())
     }
 
-    pub fn register_st_mint(&mut self, st_mint: &Pubkey) -> Result<(), ProgramError> {
+    pub fn register_st_mint(&mut self, st_mint: &Pubkey, weight: u128) -> Result<(), ProgramError> {
         // Check if mint is already in the list
         if self.st_mint_list.iter().any(|m| m.st_mint.eq(st_mint)) {
             return Err(NCNProgramError::MintInTable.into());
@@ -190,7 +212,7 @@ impl VaultRegistry {
             .find(|m| m.st_mint == StMintEntry::default().st_mint)
             .ok_or(NCNProgramError::VaultRegistryListFull)?;
 
-        let new_mint_entry = StMintEntry::new(st_mint);
+        let new_mint_entry = StMintEntry::new(st_mint, weight);
 
         Self::check_st_mint_entry(&new_mint_entry)?;
 
@@ -238,10 +260,16 @@ impl VaultRegistry {
         Ok(())
     }
 
-    pub const fn get_vault_entries(&self) -> &[VaultEntry; 1] {
+    pub const fn get_vault_entries(&self) -> &[VaultEntry; MAX_VAULTS] {
         &self.vault_list
     }
 
+    pub fn has_vault(&self, vault: &Pubkey) -> bool {
+        self.vault_list
+            .iter()
+            .any(|m| !m.is_empty() && m.vault.eq(vault))
+    }
+
     pub fn vault_count(&self) -> u64 {
         self.vault_list.iter().filter(|m| !m.is_empty()).count() as u64
     }
@@ -262,7 +290,7 @@ impl VaultRegistry {
             .collect()
     }
 
-    pub const fn get_mint_entries(&self) -> &[StMintEntry; 1] {
+    pub const fn get_mint_entries(&self) -> &[StMintEntry; MAX_ST_MINTS] {
         &self.st_mint_list
     }
 
@@ -279,6 +307,10 @@ impl VaultRegistry {
 
         Ok(*mint_entry)
     }
+
+    pub fn get_st_mint_weight(&self, st_mint: &Pubkey) -> Result<u128, ProgramError> {
+        Ok(self.get_mint_entry(st_mint)?.weight())
+    }
 }
 
 #[rustfmt::skip]
@@ -289,6 +321,7 @@ impl fmt::Display for VaultRegistry {
         writeln!(f, "  ST Mints:                     ")?;
         for mint in self.get_valid_mint_entries() {
             writeln!(f, "    Mint:                       {}", mint.st_mint())?;
+            writeln!(f, "      Weight:                   {}", mint.weight())?;
         }
         writeln!(f, "  Vaults:                     ")?;
         for vault in self.get_valid_vault_entries() {
@@ -314,13 +347,13 @@ mod tests {
 
         let expected_total = size_of::<Pubkey>() // ncn
             + 1 // bump
-            + size_of::<StMintEntry>() // st_mint_list
-            + size_of::<VaultEntry>(); // vault_list
+            + size_of::<[StMintEntry; MAX_ST_MINTS]>() // st_mint_list
+            + size_of::<[VaultEntry; MAX_VAULTS]>(); // vault_list
 
         assert_eq!(size_of::<VaultRegistry>(), expected_total);
 
         let vault_registry = VaultRegistry::new(&Pubkey::default(), 0);
-        assert_eq!(vault_registry.vault_list.len(), 1);
+        assert_eq!(vault_registry.vault_list.len(), MAX_VAULTS);
     }
 
     #[test]
@@ -330,17 +363,19 @@ mod tests {
 
         // Test 1: Initial registration should succeed
         assert_eq!(vault_registry.get_valid_mint_entries().len(), 0);
-        vault_registry.register_st_mint(&mint).unwrap();
+        vault_registry
+            .register_st_mint(&mint, StMintEntry::DEFAULT_WEIGHT)
+            .unwrap();
         assert_eq!(vault_registry.get_valid_mint_entries().len(), 1);
 
         // Test 2: Trying to add the same mint should fail
-        let result = vault_registry.register_st_mint(&mint);
+        let result = vault_registry.register_st_mint(&mint, StMintEntry::DEFAULT_WEIGHT);
         assert!(result.is_err());
         assert_eq!(vault_registry.get_valid_mint_entries().len(), 1);
 
         // Test 7: Attempting to add to a full list should fail
         let overflow_mint = Pubkey::new_unique();
-        let result = vault_registry.register_st_mint(&overflow_mint);
+        let result = vault_registry.register_st_mint(&overflow_mint, StMintEntry::DEFAULT_WEIGHT);
         assert!(result.is_err());
         assert_eq!(vault_registry.get_valid_mint_entries().len(), 1);
 
@@ -348,10 +383,26 @@ mod tests {
         assert!(vault_registry.has_st_mint(&mint));
         assert!(!vault_registry.has_st_mint(&overflow_mint));
 
-        // Test 9: Test mint with
+        // Test 9: Test mint with an explicit weight
         let mut fresh_registry = VaultRegistry::new(&Pubkey::default(), 0);
         let mint_with_weight = Pubkey::new_unique();
-        fresh_registry.register_st_mint(&mint_with_weight).unwrap();
+        fresh_registry
+            .register_st_mint(&mint_with_weight, 2 * WEIGHT_PRECISION)
+            .unwrap();
+        assert_eq!(
+            fresh_registry
+                .get_st_mint_weight(&mint_with_weight)
+                .unwrap(),
+            2 * WEIGHT_PRECISION
+        );
+
+        // Test 10: A zero weight is rejected
+        let mut fresh_registry = VaultRegistry::new(&Pubkey::default(), 0);
+        assert_eq!(
+            fresh_registry.register_st_mint(&Pubkey::new_unique(), 0),
+            Err(NCNProgramError::WeightNotSet.into())
+        );
+        assert_eq!(fresh_registry.st_mint_count(), 0);
     }
 
     #[test]
@@ -360,10 +411,28 @@ mod tests {
         let mint = Pubkey::new_unique();
 
         // First register a mint to update
-        vault_registry.register_st_mint(&mint).unwrap();
+        vault_registry
+            .register_st_mint(&mint, StMintEntry::DEFAULT_WEIGHT)
+            .unwrap();
 
         // Test 1: Verify initial state
         let entry = vault_registry.get_mint_entry(&mint).unwrap();
         assert_eq!(entry.st_mint(), &mint);
     }
+
+    #[test]
+    fn test_has_vault() {
+        let mut vault_registry = VaultRegistry::new(&Pubkey::default(), 0);
+        let vault = Pubkey::new_unique();
+
+        assert!(!vault_registry.has_vault(&vault));
+        // Empty entries hold the default pubkey, which is never a registered vault
+        assert!(!vault_registry.has_vault(&Pubkey::default()));
+
+        vault_registry
+            .register_vault(&vault, &Pubkey::new_unique(), 0, 10)
+            .unwrap();
+        assert!(vault_registry.has_vault(&vault));
+        assert!(!vault_registry.has_vault(&Pubkey::new_unique()));
+    }
 }
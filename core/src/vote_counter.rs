@@ -1,9 +1,72 @@
+use std::{fmt, mem::size_of};
+
 use bytemuck::{Pod, Zeroable};
-use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
-use shank::ShankAccount;
+use jito_bytemuck::{
+    types::{PodU128, PodU64},
+    AccountDeserialize, Discriminator,
+};
+use shank::{ShankAccount, ShankType};
 use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
-use crate::{discriminators::Discriminators, loaders::check_load};
+use crate::{
+    constants::{G1_COMPRESSED_POINT_SIZE, MAX_CONSENSUS_RECORDS, MAX_SIGNER_BITMAP_BYTES},
+    discriminators::Discriminators,
+    error::NCNProgramError,
+    loaders::check_load,
+};
+
+/// The first successful vote of an NCN epoch, used to measure how long consensus took
+#[derive(Debug, Clone, Copy, Zeroable, Pod, ShankType)]
+#[repr(C)]
+pub struct ConsensusRecord {
+    /// The NCN epoch of the vote, `u64::MAX` for an empty record
+    epoch: PodU64,
+    /// The snapshot slot the vote was verified against
+    snapshot_slot: PodU64,
+    /// The slot the vote passed
+    consensus_slot: PodU64,
+}
+
+impl Default for ConsensusRecord {
+    fn default() -> Self {
+        Self {
+            epoch: PodU64::from(u64::MAX),
+            snapshot_slot: PodU64::from(0),
+            consensus_slot: PodU64::from(0),
+        }
+    }
+}
+
+impl ConsensusRecord {
+    pub fn new(epoch: u64, snapshot_slot: u64, consensus_slot: u64) -> Self {
+        Self {
+            epoch: PodU64::from(epoch),
+            snapshot_slot: PodU64::from(snapshot_slot),
+            consensus_slot: PodU64::from(consensus_slot),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch.into()
+    }
+
+    pub fn snapshot_slot(&self) -> u64 {
+        self.snapshot_slot.into()
+    }
+
+    pub fn consensus_slot(&self) -> u64 {
+        self.consensus_slot.into()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.epoch() == u64::MAX
+    }
+
+    /// Slots between the snapshot and the vote passing
+    pub fn slots_to_consensus(&self) -> u64 {
+        self.consensus_slot().saturating_sub(self.snapshot_slot())
+    }
+}
 
 /// Vote counter PDA that tracks the number of successful votes for an NCN
 /// This counter is incremented each time a vote instruction passes successfully
@@ -19,6 +82,28 @@ pub struct VoteCounter {
     pub bump: u8,
     /// Reserved bytes for future use
     pub reserved: [u8; 7],
+    /// Total number of consensus records written, the next record goes to this index modulo
+    /// `MAX_CONSENSUS_RECORDS`
+    consensus_records_written: PodU64,
+    /// The most recent per-epoch consensus records
+    consensus_records: [ConsensusRecord; MAX_CONSENSUS_RECORDS],
+    /// Slot of the last successful vote, `u64::MAX` before the first vote
+    last_vote_slot: PodU64,
+    /// NCN epoch of the last successful vote, `u64::MAX` before the first vote
+    last_vote_epoch: PodU64,
+    /// Successful votes in `last_vote_epoch`
+    votes_this_epoch: PodU64,
+    /// SHA-256 hash of the last accepted aggregate signature, zeroed before the first vote
+    last_aggregate_hash: [u8; 32],
+    /// Running SHA-256 commitment to every voted message, zeroed before the first vote
+    messages_root: [u8; 32],
+    /// NCN epoch of the ed25519 vote tally, `u64::MAX` before the first ed25519 vote
+    ed25519_vote_epoch: PodU64,
+    /// Stake weight of the operators that cast an ed25519 vote in `ed25519_vote_epoch`
+    ed25519_stake_weight: PodU128,
+    /// Bitmap, by NCN operator index, of the operators that cast an ed25519 vote in
+    /// `ed25519_vote_epoch`
+    ed25519_voters: [u8; MAX_SIGNER_BITMAP_BYTES],
 }
 
 impl Discriminator for VoteCounter {
@@ -26,7 +111,23 @@ impl Discriminator for VoteCounter {
 }
 
 impl VoteCounter {
-    pub const LEN: usize = 32 + 8 + 1 + 7; // ncn + count + bump + reserved
+    // ncn + count + bump + reserved + consensus_records_written + consensus_records
+    // + last_vote_slot + last_vote_epoch + votes_this_epoch + last_aggregate_hash + messages_root
+    // + ed25519_vote_epoch + ed25519_stake_weight + ed25519_voters
+    pub const LEN: usize = 32
+        + 8
+        + 1
+        + 7
+        + 8
+        + size_of::<ConsensusRecord>() * MAX_CONSENSUS_RECORDS
+        + 8
+        + 8
+        + 8
+        + 32
+        + 32
+        + 8
+        + 16
+        + MAX_SIGNER_BITMAP_BYTES;
     pub const SIZE: usize = 8 + Self::LEN; // discriminator + data
 
     /// Create a new VoteCounter
@@ -36,6 +137,16 @@ impl VoteCounter {
             count: PodU64::from(0),
             bump,
             reserved: [0; 7],
+            consensus_records_written: PodU64::from(0),
+            consensus_records: [ConsensusRecord::default(); MAX_CONSENSUS_RECORDS],
+            last_vote_slot: PodU64::from(u64::MAX),
+            last_vote_epoch: PodU64::from(u64::MAX),
+            votes_this_epoch: PodU64::from(0),
+            last_aggregate_hash: [0; 32],
+            messages_root: [0; 32],
+            ed25519_vote_epoch: PodU64::from(u64::MAX),
+            ed25519_stake_weight: PodU128::from(0),
+            ed25519_voters: [0; MAX_SIGNER_BITMAP_BYTES],
         }
     }
 
@@ -54,6 +165,208 @@ impl VoteCounter {
         Ok(())
     }
 
+    pub fn last_vote_slot(&self) -> u64 {
+        self.last_vote_slot.into()
+    }
+
+    pub fn last_vote_epoch(&self) -> u64 {
+        self.last_vote_epoch.into()
+    }
+
+    /// Successful votes in `last_vote_epoch`, see `votes_in_epoch` for any other epoch
+    pub fn votes_this_epoch(&self) -> u64 {
+        self.votes_this_epoch.into()
+    }
+
+    /// Successful votes in `epoch`. The per-epoch counter is only reset by the next vote, so an
+    /// epoch without votes reads 0 here even before that happens
+    pub fn votes_in_epoch(&self, epoch: u64) -> u64 {
+        if self.last_vote_epoch() == epoch {
+            self.votes_this_epoch()
+        } else {
+            0
+        }
+    }
+
+    /// Records a successful vote at `slot` in `epoch`, restarting the per-epoch counter when the
+    /// epoch advanced since the last vote
+    pub fn record_vote(&mut self, epoch: u64, slot: u64) -> Result<(), ProgramError> {
+        let votes_this_epoch = if self.last_vote_epoch() == epoch {
+            self.votes_this_epoch()
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        } else {
+            1
+        };
+
+        self.last_vote_slot = PodU64::from(slot);
+        self.last_vote_epoch = PodU64::from(epoch);
+        self.votes_this_epoch = PodU64::from(votes_this_epoch);
+        Ok(())
+    }
+
+    /// The NCN epoch and vote counter value signed by the last successful vote, `None` before the
+    /// first vote
+    pub fn last_vote(&self) -> Option<(u64, u64)> {
+        if self.last_vote_epoch() == u64::MAX {
+            return None;
+        }
+        Some((self.last_vote_epoch(), self.count().checked_sub(1)?))
+    }
+
+    /// Fails with `AlreadyVotedThisEpoch` when a vote was already recorded in `epoch`
+    pub fn check_not_voted_in_epoch(&self, epoch: u64) -> Result<(), NCNProgramError> {
+        if self.votes_in_epoch(epoch) > 0 {
+            return Err(NCNProgramError::AlreadyVotedThisEpoch);
+        }
+        Ok(())
+    }
+
+    pub fn last_aggregate_hash(&self) -> &[u8; 32] {
+        &self.last_aggregate_hash
+    }
+
+    /// Whether `aggregated_signature` is the last accepted aggregate
+    pub fn is_last_aggregate(&self, aggregated_signature: &[u8; G1_COMPRESSED_POINT_SIZE]) -> bool {
+        solana_nostd_sha256::hashv(&[aggregated_signature]) == self.last_aggregate_hash
+    }
+
+    /// Fails with `DuplicateAggregateSubmission` when `aggregated_signature` is the last accepted
+    /// one, e.g. a keeper resubmitting the previous epoch's aggregate
+    pub fn check_aggregate_not_duplicate(
+        &self,
+        aggregated_signature: &[u8; G1_COMPRESSED_POINT_SIZE],
+    ) -> Result<(), NCNProgramError> {
+        if self.is_last_aggregate(aggregated_signature) {
+            return Err(NCNProgramError::DuplicateAggregateSubmission);
+        }
+        Ok(())
+    }
+
+    /// Records `aggregated_signature` as the last accepted aggregate
+    pub fn record_aggregate(&mut self, aggregated_signature: &[u8; G1_COMPRESSED_POINT_SIZE]) {
+        self.last_aggregate_hash = solana_nostd_sha256::hashv(&[aggregated_signature]);
+    }
+
+    /// Total number of consensus records written, including those since overwritten
+    pub fn consensus_records_written(&self) -> u64 {
+        self.consensus_records_written.into()
+    }
+
+    pub fn messages_root(&self) -> &[u8; 32] {
+        &self.messages_root
+    }
+
+    /// Folds a voted `message` into the messages root: `sha256(messages_root || message)`
+    pub fn record_message(&mut self, message: &[u8; 32]) {
+        self.messages_root = solana_nostd_sha256::hashv(&[&self.messages_root, message]);
+    }
+
+    /// Iterates over the stored consensus records, oldest first
+    pub fn consensus_records(&self) -> impl Iterator<Item = &ConsensusRecord> {
+        let written = u64::from(self.consensus_records_written) as usize;
+        let start = written.saturating_sub(MAX_CONSENSUS_RECORDS);
+        (start..written).map(move |i| &self.consensus_records[i % MAX_CONSENSUS_RECORDS])
+    }
+
+    /// Records the first successful vote of `epoch`, later votes in the same epoch are ignored.
+    /// Once `MAX_CONSENSUS_RECORDS` records are stored the oldest one is overwritten.
+    pub fn record_consensus(
+        &mut self,
+        epoch: u64,
+        snapshot_slot: u64,
+        consensus_slot: u64,
+    ) -> Result<(), ProgramError> {
+        if self
+            .consensus_records()
+            .last()
+            .is_some_and(|record| record.epoch() >= epoch)
+        {
+            return Ok(());
+        }
+
+        let written: u64 = self.consensus_records_written.into();
+        self.consensus_records[written as usize % MAX_CONSENSUS_RECORDS] =
+            ConsensusRecord::new(epoch, snapshot_slot, consensus_slot);
+        self.consensus_records_written = PodU64::from(
+            written
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+        );
+        Ok(())
+    }
+
+    pub fn ed25519_vote_epoch(&self) -> u64 {
+        self.ed25519_vote_epoch.into()
+    }
+
+    /// Stake weight of the operators that cast an ed25519 vote in `epoch`
+    pub fn ed25519_stake_weight_in_epoch(&self, epoch: u64) -> u128 {
+        if self.ed25519_vote_epoch() == epoch {
+            self.ed25519_stake_weight.into()
+        } else {
+            0
+        }
+    }
+
+    /// Whether the operator at `ncn_operator_index` cast an ed25519 vote in `epoch`
+    pub fn has_ed25519_vote(&self, epoch: u64, ncn_operator_index: u64) -> bool {
+        self.ed25519_vote_epoch() == epoch
+            && self
+                .ed25519_voters
+                .get(ncn_operator_index as usize / 8)
+                .is_some_and(|byte| byte & (1 << (ncn_operator_index % 8)) != 0)
+    }
+
+    /// Adds the ed25519 vote of the operator at `ncn_operator_index` to the tally of `epoch`,
+    /// restarting the tally when the epoch advanced since the last ed25519 vote. Fails with
+    /// `Ed25519AlreadyVoted` when the operator already voted in `epoch`
+    pub fn record_ed25519_vote(
+        &mut self,
+        epoch: u64,
+        ncn_operator_index: u64,
+        stake_weight: u128,
+    ) -> Result<(), NCNProgramError> {
+        let byte_index = ncn_operator_index as usize / 8;
+        if byte_index >= MAX_SIGNER_BITMAP_BYTES {
+            return Err(NCNProgramError::OperatorIsNotInSnapshot);
+        }
+        if self.has_ed25519_vote(epoch, ncn_operator_index) {
+            return Err(NCNProgramError::Ed25519AlreadyVoted);
+        }
+
+        if self.ed25519_vote_epoch() != epoch {
+            self.ed25519_vote_epoch = PodU64::from(epoch);
+            self.ed25519_stake_weight = PodU128::from(0);
+            self.ed25519_voters = [0; MAX_SIGNER_BITMAP_BYTES];
+        }
+
+        let ed25519_stake_weight: u128 = self.ed25519_stake_weight.into();
+        self.ed25519_stake_weight = PodU128::from(
+            ed25519_stake_weight
+                .checked_add(stake_weight)
+                .ok_or(NCNProgramError::ArithmeticOverflow)?,
+        );
+        self.ed25519_voters[byte_index] |= 1 << (ncn_operator_index % 8);
+        Ok(())
+    }
+
+    /// Average number of slots between the snapshot and consensus over the stored records, `None`
+    /// when nothing has been recorded yet
+    pub fn avg_slots_to_consensus(&self) -> Option<u64> {
+        let (total, count) = self
+            .consensus_records()
+            .fold((0u128, 0u128), |(total, count), record| {
+                (total + record.slots_to_consensus() as u128, count + 1)
+            });
+
+        if count == 0 {
+            return None;
+        }
+
+        Some((total / count) as u64)
+    }
+
     /// Find the program address for the vote counter
     pub fn find_program_address(program_id: &Pubkey, ncn: &Pubkey) -> (Pubkey, u8, Vec<Vec<u8>>) {
         let seeds = vec![b"vote_counter".to_vec(), ncn.as_ref().to_vec()];
@@ -82,6 +395,60 @@ impl VoteCounter {
     }
 }
 
+#[rustfmt::skip]
+impl fmt::Display for VoteCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n\n----------- Vote Counter -------------")?;
+        writeln!(f, "  NCN:                          {}", self.ncn)?;
+        writeln!(f, "  Count:                        {}", self.count())?;
+        writeln!(f, "  Bump:                         {}", self.bump)?;
+        writeln!(f, "  Last Vote Slot:               {}", self.last_vote_slot())?;
+        writeln!(f, "  Last Vote Epoch:              {}", self.last_vote_epoch())?;
+        writeln!(f, "  Votes This Epoch:             {}", self.votes_this_epoch())?;
+        writeln!(f, "  Last Aggregate Hash:          {:?}", self.last_aggregate_hash())?;
+        writeln!(f, "  Messages Root:                {:?}", self.messages_root())?;
+        writeln!(f, "  Consensus Records Written:    {}", self.consensus_records_written())?;
+        writeln!(f, "  Avg Slots To Consensus:       {:?}", self.avg_slots_to_consensus())?;
+        writeln!(f, "  Ed25519 Vote Epoch:           {}", self.ed25519_vote_epoch())?;
+        writeln!(f, "  Ed25519 Stake Weight:         {}", self.ed25519_stake_weight_in_epoch(self.ed25519_vote_epoch()))?;
+        writeln!(f, "  consensus records:")?;
+        for record in self.consensus_records() {
+            writeln!(
+                f,
+                "    Epoch {}: snapshot slot {}, consensus slot {}",
+                record.epoch(),
+                record.snapshot_slot(),
+                record.consensus_slot()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Domain separator so a vote signature can never be mistaken for a signature over other data
+const VOTE_SIGNING_DOMAIN: &[u8] = b"ncn-program-vote";
+
+/// The exact 32 byte message operators sign to vote, shared by off-chain signers and `CastVote`.
+///
+/// Binds the vote to the NCN and the NCN epoch it is cast in so a signature can't be replayed
+/// on another NCN or in another epoch. `CastVote` uses the vote counter value as `proposal_id`
+/// and an empty `payload`.
+pub fn vote_signing_payload(
+    ncn: &Pubkey,
+    epoch: u64,
+    proposal_id: u64,
+    payload: &[u8],
+) -> [u8; 32] {
+    solana_nostd_sha256::hashv(&[
+        VOTE_SIGNING_DOMAIN,
+        ncn.as_ref(),
+        &epoch.to_le_bytes(),
+        &proposal_id.to_le_bytes(),
+        payload,
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,7 +481,209 @@ mod tests {
 
     #[test]
     fn test_vote_counter_size() {
-        assert_eq!(VoteCounter::SIZE, 8 + 32 + 8 + 1 + 7);
-        assert_eq!(VoteCounter::LEN, 32 + 8 + 1 + 7);
+        let records = (8 + 8 + 8) * MAX_CONSENSUS_RECORDS;
+        let vote_history = 8 + 8 + 8 + 32 + 32;
+        let ed25519_tally = 8 + 16 + MAX_SIGNER_BITMAP_BYTES;
+        assert_eq!(
+            VoteCounter::SIZE,
+            8 + 32 + 8 + 1 + 7 + 8 + records + vote_history + ed25519_tally
+        );
+        assert_eq!(
+            VoteCounter::LEN,
+            32 + 8 + 1 + 7 + 8 + records + vote_history + ed25519_tally
+        );
+        assert_eq!(VoteCounter::LEN, size_of::<VoteCounter>());
+    }
+
+    #[test]
+    fn test_record_vote_resets_across_epochs() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        assert_eq!(counter.last_vote_slot(), u64::MAX);
+        assert_eq!(counter.last_vote_epoch(), u64::MAX);
+        assert_eq!(counter.votes_this_epoch(), 0);
+
+        counter.record_vote(4, 400).unwrap();
+        counter.record_vote(4, 410).unwrap();
+        assert_eq!(counter.last_vote_slot(), 410);
+        assert_eq!(counter.last_vote_epoch(), 4);
+        assert_eq!(counter.votes_this_epoch(), 2);
+
+        // Nothing voted yet in the next epoch
+        assert_eq!(counter.votes_in_epoch(5), 0);
+
+        counter.record_vote(5, 500).unwrap();
+        assert_eq!(counter.last_vote_slot(), 500);
+        assert_eq!(counter.last_vote_epoch(), 5);
+        assert_eq!(counter.votes_this_epoch(), 1);
+        assert_eq!(counter.votes_in_epoch(5), 1);
+        assert_eq!(counter.votes_in_epoch(4), 0);
+    }
+
+    #[test]
+    fn test_last_vote() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        assert_eq!(counter.last_vote(), None);
+
+        counter.increment().unwrap();
+        counter.record_vote(4, 400).unwrap();
+        assert_eq!(counter.last_vote(), Some((4, 0)));
+
+        counter.increment().unwrap();
+        counter.record_vote(5, 500).unwrap();
+        assert_eq!(counter.last_vote(), Some((5, 1)));
+    }
+
+    #[test]
+    fn test_check_not_voted_in_epoch() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        assert!(counter.check_not_voted_in_epoch(5).is_ok());
+
+        counter.record_vote(5, 500).unwrap();
+        assert_eq!(
+            counter.check_not_voted_in_epoch(5),
+            Err(NCNProgramError::AlreadyVotedThisEpoch)
+        );
+        assert!(counter.check_not_voted_in_epoch(6).is_ok());
+    }
+
+    #[test]
+    fn test_check_aggregate_not_duplicate() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        let aggregate = [7u8; G1_COMPRESSED_POINT_SIZE];
+        assert!(counter.check_aggregate_not_duplicate(&aggregate).is_ok());
+        assert!(!counter.is_last_aggregate(&aggregate));
+
+        counter.record_aggregate(&aggregate);
+        assert!(counter.is_last_aggregate(&aggregate));
+        assert_eq!(
+            counter.check_aggregate_not_duplicate(&aggregate),
+            Err(NCNProgramError::DuplicateAggregateSubmission)
+        );
+        assert!(counter
+            .check_aggregate_not_duplicate(&[8u8; G1_COMPRESSED_POINT_SIZE])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_record_message_folds_into_root() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        assert_eq!(counter.messages_root(), &[0; 32]);
+
+        let messages = [[1u8; 32], [2u8; 32], [3u8; 32]];
+        for message in messages.iter() {
+            counter.record_message(message);
+        }
+
+        let expected_root = messages.iter().fold([0u8; 32], |root, message| {
+            solana_nostd_sha256::hashv(&[&root, message])
+        });
+        assert_eq!(counter.messages_root(), &expected_root);
+
+        // The same messages in another order commit to another root
+        let mut reordered = VoteCounter::new(&Pubkey::new_unique(), 255);
+        for message in messages.iter().rev() {
+            reordered.record_message(message);
+        }
+        assert_ne!(reordered.messages_root(), counter.messages_root());
+    }
+
+    #[test]
+    fn test_avg_slots_to_consensus() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        assert_eq!(counter.avg_slots_to_consensus(), None);
+
+        counter.record_consensus(1, 100, 110).unwrap();
+        counter.record_consensus(2, 200, 230).unwrap();
+        assert_eq!(counter.avg_slots_to_consensus(), Some(20));
+
+        // Only the first vote of an epoch is recorded
+        counter.record_consensus(2, 200, 500).unwrap();
+        assert_eq!(counter.consensus_records().count(), 2);
+        assert_eq!(counter.avg_slots_to_consensus(), Some(20));
+    }
+
+    #[test]
+    fn test_consensus_records_wrap_around() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+
+        for epoch in 0..(MAX_CONSENSUS_RECORDS as u64 + 2) {
+            counter
+                .record_consensus(epoch, epoch * 100, epoch * 100 + epoch)
+                .unwrap();
+        }
+
+        let epochs: Vec<u64> = counter
+            .consensus_records()
+            .map(|record| record.epoch())
+            .collect();
+        assert_eq!(
+            epochs,
+            (2..(MAX_CONSENSUS_RECORDS as u64 + 2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_record_ed25519_vote() {
+        let mut counter = VoteCounter::new(&Pubkey::new_unique(), 255);
+        assert_eq!(counter.ed25519_vote_epoch(), u64::MAX);
+        assert_eq!(counter.ed25519_stake_weight_in_epoch(5), 0);
+
+        counter.record_ed25519_vote(5, 0, 100).unwrap();
+        counter.record_ed25519_vote(5, 9, 250).unwrap();
+        assert_eq!(counter.ed25519_stake_weight_in_epoch(5), 350);
+        assert!(counter.has_ed25519_vote(5, 0));
+        assert!(counter.has_ed25519_vote(5, 9));
+        assert!(!counter.has_ed25519_vote(5, 1));
+        assert_eq!(
+            counter.record_ed25519_vote(5, 9, 250),
+            Err(NCNProgramError::Ed25519AlreadyVoted)
+        );
+
+        // A new epoch restarts the tally
+        counter.record_ed25519_vote(6, 9, 40).unwrap();
+        assert_eq!(counter.ed25519_vote_epoch(), 6);
+        assert_eq!(counter.ed25519_stake_weight_in_epoch(6), 40);
+        assert_eq!(counter.ed25519_stake_weight_in_epoch(5), 0);
+        assert!(!counter.has_ed25519_vote(6, 0));
+        assert!(counter.has_ed25519_vote(6, 9));
+        // Votes in a stale epoch are not remembered
+        assert!(!counter.has_ed25519_vote(5, 9));
+
+        assert_eq!(
+            counter.record_ed25519_vote(6, MAX_SIGNER_BITMAP_BYTES as u64 * 8, 1),
+            Err(NCNProgramError::OperatorIsNotInSnapshot)
+        );
+    }
+
+    #[test]
+    fn test_vote_counter_display() {
+        let ncn = Pubkey::new_unique();
+        let mut counter = VoteCounter::new(&ncn, 255);
+        for _ in 0..3 {
+            counter.increment().unwrap();
+        }
+        counter.record_vote(4, 410).unwrap();
+        counter.record_consensus(4, 400, 410).unwrap();
+
+        let output = counter.to_string();
+        assert!(output.contains(&ncn.to_string()));
+        assert!(output.contains("Count:                        3\n"));
+        assert!(output.contains("Last Vote Epoch:              4\n"));
+        assert!(output.contains("Epoch 4: snapshot slot 400, consensus slot 410"));
+    }
+
+    #[test]
+    fn test_vote_signing_payload() {
+        let ncn = Pubkey::new_unique();
+        let vote_payload = vote_signing_payload(&ncn, 5, 7, &[]);
+
+        assert_eq!(vote_payload, vote_signing_payload(&ncn, 5, 7, &[]));
+        assert_ne!(
+            vote_payload,
+            vote_signing_payload(&Pubkey::new_unique(), 5, 7, &[])
+        );
+        assert_ne!(vote_payload, vote_signing_payload(&ncn, 6, 7, &[]));
+        assert_ne!(vote_payload, vote_signing_payload(&ncn, 5, 8, &[]));
+        assert_ne!(vote_payload, vote_signing_payload(&ncn, 5, 7, &[1]));
     }
 }
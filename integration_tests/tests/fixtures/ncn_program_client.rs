@@ -8,13 +8,18 @@ use jito_vault_core::{
 };
 use ncn_program_client::{
     instructions::{
-        AdminRegisterStMintBuilder, AdminSetNewAdminBuilder, AdminSetParametersBuilder,
-        CastVoteBuilder, InitializeConfigBuilder, InitializeSnapshotBuilder,
-        InitializeVaultRegistryBuilder, InitializeVoteCounterBuilder, ReallocSnapshotBuilder,
-        RegisterOperatorBuilder, RegisterVaultBuilder, SnapshotVaultOperatorDelegationBuilder,
+        AdminRegisterStMintBuilder, AdminRegisterStMintWithWeightBuilder, AdminRepairConfigBuilder,
+        AdminSetFeeWalletBuilder, AdminSetNewAdminBuilder, AdminSetParametersBuilder,
+        AdminUpdateSnapshotMinimumStakeBuilder, BatchInitializeOperatorSnapshotsBuilder,
+        CastEd25519VoteBuilder, CastVoteBatchBuilder, CastVoteBuilder, CloseSnapshotBuilder,
+        DeregisterOperatorBuilder, InitializeConfigBuilder, InitializeSnapshotBuilder,
+        InitializeVaultRegistryBuilder, InitializeVoteCounterBuilder, ProveNonSignerBuilder,
+        ReallocSnapshotBuilder, RefreshOperatorKeyBuilder, RegisterOperatorBuilder,
+        RegisterVaultBuilder, RolloverSnapshotBuilder, SetOperatorActiveBuilder,
+        SnapshotVaultOperatorDelegationBatchBuilder, SnapshotVaultOperatorDelegationBuilder,
         UpdateOperatorBN128KeysBuilder, UpdateOperatorIpPortBuilder,
     },
-    types::ConfigAdminRole,
+    types::{CastVoteBatchEntry, ConfigAdminRole, OperatorSnapshotEntry},
 };
 use ncn_program_core::{
     account_payer::AccountPayer,
@@ -23,15 +28,20 @@ use ncn_program_core::{
     error::NCNProgramError,
     fees::FeeConfig,
     ncn_operator_account::NCNOperatorAccount,
+    schemes::ed25519::new_ed25519_instruction,
     snapshot::{OperatorSnapshot, Snapshot},
+    utils::bitmap_iter,
     vault_registry::VaultRegistry,
     vote_counter::VoteCounter,
 };
 use solana_program::{
-    instruction::InstructionError, native_token::sol_to_lamports, pubkey::Pubkey,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::sol_to_lamports,
+    pubkey::Pubkey,
     system_instruction::transfer,
 };
-use solana_program_test::{BanksClient, ProgramTestBanksClientExt};
+use solana_program_test::{BanksClient, BanksClientError, ProgramTestBanksClientExt};
 use solana_sdk::{
     commitment_config::CommitmentLevel,
     compute_budget::ComputeBudgetInstruction,
@@ -159,6 +169,35 @@ impl NCNProgramClient {
         Ok(*VoteCounter::try_from_slice_unchecked(vote_counter.data.as_slice()).unwrap())
     }
 
+    /// Fetches the current NCN epoch, from the current slot and the restaking epoch length.
+    pub async fn get_ncn_epoch(&mut self) -> TestResult<u64> {
+        let restaking_config_pda = Config::find_program_address(&jito_restaking_program::id()).0;
+        let restaking_config = self
+            .banks_client
+            .get_account(restaking_config_pda)
+            .await?
+            .unwrap();
+        let epoch_length = Config::try_from_slice_unchecked(restaking_config.data.as_slice())
+            .unwrap()
+            .epoch_length();
+
+        let slot = self.banks_client.get_sysvar::<Clock>().await?.slot;
+
+        Ok(slot / epoch_length)
+    }
+
+    /// Builds the message operators sign to vote on `proposal_id` in the current NCN epoch,
+    /// exactly as `CastVote` computes it.
+    pub async fn vote_signing_payload(
+        &mut self,
+        ncn: Pubkey,
+        proposal_id: u64,
+    ) -> TestResult<[u8; 32]> {
+        let epoch = self.get_ncn_epoch().await?;
+
+        Ok(NcnConfig::consensus_message(&ncn, epoch, proposal_id))
+    }
+
     /// Fetches the VaultRegistry account for a given NCN pubkey.
     pub async fn get_vault_registry(&mut self, ncn_pubkey: Pubkey) -> TestResult<VaultRegistry> {
         let vault_registry_pda =
@@ -253,6 +292,10 @@ impl NCNProgramClient {
             &ncn_fee_wallet.pubkey(),
             400,
             minimum_stake.unwrap_or(100),
+            None,
+            None,
+            None,
+            None,
         )
         .await
     }
@@ -270,18 +313,97 @@ impl NCNProgramClient {
         ncn_fee_wallet: &Pubkey,
         ncn_fee_bps: u16,
         minimum_stake: u128,
+        curve_id: Option<u8>,
+        minimum_operators: Option<u64>,
+        quorum_bps: Option<u16>,
+        max_snapshot_age_slots: Option<u64>,
     ) -> TestResult<()> {
         let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
 
         let (account_payer, _, _) = AccountPayer::find_program_address(&ncn_program::id(), &ncn);
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
 
-        let ix = InitializeConfigBuilder::new()
+        let mut builder = InitializeConfigBuilder::new();
+        builder
             .config(config)
             .ncn(ncn)
             .ncn_fee_wallet(*ncn_fee_wallet)
             .ncn_admin(ncn_admin.pubkey())
             .account_payer(account_payer)
             .tie_breaker_admin(*tie_breaker_admin)
+            .restaking_config(restaking_config)
+            .epochs_before_stall(epochs_before_stall)
+            .epochs_after_consensus_before_close(epochs_after_consensus_before_close)
+            .valid_slots_after_consensus(valid_slots_after_consensus)
+            .minimum_stake(minimum_stake)
+            .ncn_fee_bps(ncn_fee_bps);
+
+        if let Some(curve_id) = curve_id {
+            builder.curve_id(curve_id);
+        }
+
+        if let Some(minimum_operators) = minimum_operators {
+            builder.minimum_operators(minimum_operators);
+        }
+
+        if let Some(quorum_bps) = quorum_bps {
+            builder.quorum_bps(quorum_bps);
+        }
+
+        if let Some(max_snapshot_age_slots) = max_snapshot_age_slots {
+            builder.max_snapshot_age_slots(max_snapshot_age_slots);
+        }
+
+        let ix = builder.instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&ncn_admin.pubkey()),
+            &[&ncn_admin],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Repairs the NCN config with the default test parameters.
+    pub async fn do_repair_config(
+        &mut self,
+        ncn: Pubkey,
+        ncn_admin: &Keypair,
+        ncn_fee_wallet: &Pubkey,
+    ) -> TestResult<()> {
+        self.airdrop(&ncn_admin.pubkey(), 1.0).await?;
+
+        self.repair_config(ncn, ncn_admin, 3, 10, 10000, ncn_fee_wallet, 400, 100)
+            .await
+    }
+
+    /// Sends a transaction to re-validate and re-write the NCN config account.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn repair_config(
+        &mut self,
+        ncn: Pubkey,
+        ncn_admin: &Keypair,
+        epochs_before_stall: u64,
+        epochs_after_consensus_before_close: u64,
+        valid_slots_after_consensus: u64,
+        ncn_fee_wallet: &Pubkey,
+        ncn_fee_bps: u16,
+        minimum_stake: u128,
+    ) -> TestResult<()> {
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let (account_payer, _, _) = AccountPayer::find_program_address(&ncn_program::id(), &ncn);
+
+        let ix = AdminRepairConfigBuilder::new()
+            .config(config)
+            .ncn(ncn)
+            .ncn_fee_wallet(*ncn_fee_wallet)
+            .ncn_admin(ncn_admin.pubkey())
+            .restaking_config(restaking_config)
+            .account_payer(account_payer)
+            .system_program(system_program::id())
             .epochs_before_stall(epochs_before_stall)
             .epochs_after_consensus_before_close(epochs_after_consensus_before_close)
             .valid_slots_after_consensus(valid_slots_after_consensus)
@@ -313,6 +435,34 @@ impl NCNProgramClient {
             .await
     }
 
+    /// Rotates the NCN fee wallet in the NCN config, signed by the NCN admin.
+    pub async fn do_set_fee_wallet(
+        &mut self,
+        new_wallet: Pubkey,
+        ncn_root: &NcnRoot,
+    ) -> TestResult<()> {
+        self.airdrop(&ncn_root.ncn_admin.pubkey(), 1.0).await?;
+
+        let config_pda =
+            NcnConfig::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey).0;
+
+        let ix = AdminSetFeeWalletBuilder::new()
+            .config(config_pda)
+            .ncn(ncn_root.ncn_pubkey)
+            .ncn_admin(ncn_root.ncn_admin.pubkey())
+            .new_wallet(new_wallet)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&ncn_root.ncn_admin.pubkey()),
+            &[&ncn_root.ncn_admin],
+            blockhash,
+        ))
+        .await
+    }
+
     /// Sends a transaction to set a new admin in the NCN config.
     pub async fn set_new_admin(
         &mut self,
@@ -471,6 +621,38 @@ impl NCNProgramClient {
         .await
     }
 
+    /// Registers an st_mint with an explicit weight in the vault registry (admin operation).
+    pub async fn do_admin_register_st_mint_with_weight(
+        &mut self,
+        ncn: Pubkey,
+        st_mint: Pubkey,
+        weight: u128,
+    ) -> TestResult<()> {
+        let vault_registry = VaultRegistry::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let (ncn_config, _, _) = NcnConfig::find_program_address(&ncn_program::id(), &ncn);
+
+        let admin = self.payer.pubkey();
+
+        let ix = AdminRegisterStMintWithWeightBuilder::new()
+            .config(ncn_config)
+            .ncn(ncn)
+            .vault_registry(vault_registry)
+            .admin(admin)
+            .st_mint(st_mint)
+            .weight(weight)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
+
     /// Initializes the snapshot account for a given NCN and epoch.
     pub async fn do_initialize_snapshot(&mut self, ncn: Pubkey) -> TestResult<()> {
         self.initialize_snapshot(ncn).await
@@ -551,58 +733,64 @@ impl NCNProgramClient {
         .await
     }
 
-    /// Snapshots the delegation information from a vault to an operator for a given NCN and epoch.
-    pub async fn do_snapshot_vault_operator_delegation(
-        &mut self,
-        vault: Pubkey,
-        operator: Pubkey,
-        ncn: Pubkey,
-    ) -> TestResult<()> {
-        self.snapshot_vault_operator_delegation(vault, operator, ncn)
-            .await
+    /// Closes the snapshot account, returning its rent to the account payer.
+    pub async fn do_close_snapshot(&mut self, ncn: Pubkey) -> TestResult<()> {
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+
+        self.close_snapshot(&ncn, &snapshot, &config).await
     }
 
-    /// Sends a transaction to snapshot the vault operator delegation.
-    pub async fn snapshot_vault_operator_delegation(
+    /// Sends a transaction to close the snapshot account.
+    pub async fn close_snapshot(
         &mut self,
-        vault: Pubkey,
-        operator: Pubkey,
-        ncn: Pubkey,
+        ncn: &Pubkey,
+        snapshot: &Pubkey,
+        config: &Pubkey,
     ) -> TestResult<()> {
+        let (account_payer, _, _) = AccountPayer::find_program_address(&ncn_program::id(), ncn);
         let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
 
-        let config_pda = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
-
-        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let ix = CloseSnapshotBuilder::new()
+            .config(*config)
+            .ncn(*ncn)
+            .snapshot(*snapshot)
+            .account_payer(account_payer)
+            .restaking_config(restaking_config)
+            .instruction();
 
-        let vault_ncn_ticket =
-            VaultNcnTicket::find_program_address(&jito_vault_program::id(), &vault, &ncn).0;
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
 
-        let ncn_vault_ticket =
-            NcnVaultTicket::find_program_address(&jito_restaking_program::id(), &ncn, &vault).0;
+    /// Rolls the snapshot over into the current epoch
+    pub async fn do_rollover_snapshot(&mut self, ncn: Pubkey) -> TestResult<()> {
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
 
-        let vault_operator_delegation = VaultOperatorDelegation::find_program_address(
-            &jito_vault_program::id(),
-            &vault,
-            &operator,
-        )
-        .0;
+        self.rollover_snapshot(&ncn, &snapshot, &config).await
+    }
 
-        let ncn_operator_state =
-            NcnOperatorState::find_program_address(&jito_restaking_program::id(), &ncn, &operator)
-                .0;
+    /// Sends a transaction to roll the snapshot over into the current epoch
+    pub async fn rollover_snapshot(
+        &mut self,
+        ncn: &Pubkey,
+        snapshot: &Pubkey,
+        config: &Pubkey,
+    ) -> TestResult<()> {
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
 
-        let ix = SnapshotVaultOperatorDelegationBuilder::new()
-            .config(config_pda)
+        let ix = RolloverSnapshotBuilder::new()
+            .config(*config)
             .restaking_config(restaking_config)
-            .ncn(ncn)
-            .operator(operator)
-            .vault(vault)
-            .vault_ncn_ticket(vault_ncn_ticket)
-            .ncn_vault_ticket(ncn_vault_ticket)
-            .vault_operator_delegation(vault_operator_delegation)
-            .ncn_operator_state(ncn_operator_state)
-            .snapshot(snapshot)
+            .ncn(*ncn)
+            .snapshot(*snapshot)
             .instruction();
 
         let blockhash = self.banks_client.get_latest_blockhash().await?;
@@ -615,24 +803,24 @@ impl NCNProgramClient {
         .await
     }
 
-    /// Casts a vote using BLS signature aggregation for a given epoch.
-    pub async fn do_cast_vote(
+    /// Proves `operator` did not sign `message`, flagging it as having missed the vote
+    pub async fn do_prove_non_signer(
         &mut self,
         ncn: Pubkey,
+        operator: Pubkey,
         agg_sig: [u8; 32],
         apk2: [u8; 64],
         signers_bitmap: Vec<u8>,
-    ) -> Result<(), TestError> {
-        let ncn_config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+    ) -> TestResult<()> {
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
         let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
-        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
         let vote_counter = VoteCounter::find_program_address(&ncn_program::id(), &ncn).0;
 
-        self.cast_vote(
-            ncn_config,
+        self.prove_non_signer(
+            config,
             ncn,
+            operator,
             snapshot,
-            restaking_config,
             vote_counter,
             agg_sig,
             apk2,
@@ -641,26 +829,26 @@ impl NCNProgramClient {
         .await
     }
 
-    /// Sends a transaction to cast a vote using BLS signature verification.
+    /// Sends a `ProveNonSigner` transaction
     #[allow(clippy::too_many_arguments)]
-    pub async fn cast_vote(
+    pub async fn prove_non_signer(
         &mut self,
-        ncn_config: Pubkey,
+        config: Pubkey,
         ncn: Pubkey,
+        operator: Pubkey,
         snapshot: Pubkey,
-        restaking_config: Pubkey,
         vote_counter: Pubkey,
         agg_sig: [u8; 32],
         apk2: [u8; 64],
         signers_bitmap: Vec<u8>,
-    ) -> Result<(), TestError> {
+    ) -> TestResult<()> {
         let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
 
-        let ix = CastVoteBuilder::new()
-            .config(ncn_config)
+        let ix = ProveNonSignerBuilder::new()
+            .config(config)
             .ncn(ncn)
+            .operator(operator)
             .snapshot(snapshot)
-            .restaking_config(restaking_config)
             .vote_counter(vote_counter)
             .aggregated_signature(agg_sig)
             .aggregated_g2(apk2)
@@ -677,47 +865,520 @@ impl NCNProgramClient {
         .await
     }
 
-    /// Sets various parameters in the NCN config (admin operation).
-    pub async fn do_set_parameters(
+    /// Snapshots the delegation information from a vault to an operator for a given NCN and epoch.
+    pub async fn do_snapshot_vault_operator_delegation(
         &mut self,
-        starting_valid_epoch: Option<u64>,
-        epochs_before_stall: Option<u64>,
-        epochs_after_consensus_before_close: Option<u64>,
-        valid_slots_after_consensus: Option<u64>,
-        minimum_stake: Option<u128>,
-        ncn_root: &NcnRoot,
+        vault: Pubkey,
+        operator: Pubkey,
+        ncn: Pubkey,
     ) -> TestResult<()> {
-        let config_pda =
-            NcnConfig::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey).0;
+        self.snapshot_vault_operator_delegation(vault, operator, ncn)
+            .await
+    }
 
-        let mut ix = AdminSetParametersBuilder::new();
-        ix.config(config_pda)
-            .ncn(ncn_root.ncn_pubkey)
-            .ncn_admin(ncn_root.ncn_admin.pubkey());
+    /// Sends a transaction to snapshot the vault operator delegation.
+    pub async fn snapshot_vault_operator_delegation(
+        &mut self,
+        vault: Pubkey,
+        operator: Pubkey,
+        ncn: Pubkey,
+    ) -> TestResult<()> {
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
 
-        if let Some(epoch) = starting_valid_epoch {
-            ix.starting_valid_epoch(epoch);
-        }
+        let config_pda = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
 
-        if let Some(epochs) = epochs_before_stall {
-            ix.epochs_before_stall(epochs);
-        }
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
 
-        if let Some(epochs) = epochs_after_consensus_before_close {
-            ix.epochs_after_consensus_before_close(epochs);
-        }
+        let vault_registry = VaultRegistry::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let vault_ncn_ticket =
+            VaultNcnTicket::find_program_address(&jito_vault_program::id(), &vault, &ncn).0;
+
+        let ncn_vault_ticket =
+            NcnVaultTicket::find_program_address(&jito_restaking_program::id(), &ncn, &vault).0;
+
+        let vault_operator_delegation = VaultOperatorDelegation::find_program_address(
+            &jito_vault_program::id(),
+            &vault,
+            &operator,
+        )
+        .0;
+
+        let ncn_operator_state =
+            NcnOperatorState::find_program_address(&jito_restaking_program::id(), &ncn, &operator)
+                .0;
+
+        let ix = SnapshotVaultOperatorDelegationBuilder::new()
+            .config(config_pda)
+            .restaking_config(restaking_config)
+            .ncn(ncn)
+            .operator(operator)
+            .vault(vault)
+            .vault_ncn_ticket(vault_ncn_ticket)
+            .ncn_vault_ticket(ncn_vault_ticket)
+            .vault_operator_delegation(vault_operator_delegation)
+            .ncn_operator_state(ncn_operator_state)
+            .snapshot(snapshot)
+            .vault_registry(vault_registry)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Builds a `SnapshotVaultOperatorDelegationBatch` instruction for the `(index, operator)`
+    /// pairs, in order.
+    pub fn snapshot_vault_operator_delegation_batch_instruction(
+        vault: Pubkey,
+        ncn: Pubkey,
+        operators: &[(u64, Pubkey)],
+    ) -> Instruction {
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let config_pda = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let vault_registry = VaultRegistry::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let vault_ncn_ticket =
+            VaultNcnTicket::find_program_address(&jito_vault_program::id(), &vault, &ncn).0;
+        let ncn_vault_ticket =
+            NcnVaultTicket::find_program_address(&jito_restaking_program::id(), &ncn, &vault).0;
+
+        let mut operator_accounts = Vec::with_capacity(operators.len() * 3);
+        for (_, operator) in operators {
+            let ncn_operator_state = NcnOperatorState::find_program_address(
+                &jito_restaking_program::id(),
+                &ncn,
+                operator,
+            )
+            .0;
+            let vault_operator_delegation = VaultOperatorDelegation::find_program_address(
+                &jito_vault_program::id(),
+                &vault,
+                operator,
+            )
+            .0;
+
+            operator_accounts.push(AccountMeta::new_readonly(*operator, false));
+            operator_accounts.push(AccountMeta::new_readonly(ncn_operator_state, false));
+            operator_accounts.push(AccountMeta::new_readonly(vault_operator_delegation, false));
+        }
+
+        SnapshotVaultOperatorDelegationBatchBuilder::new()
+            .config(config_pda)
+            .restaking_config(restaking_config)
+            .ncn(ncn)
+            .vault(vault)
+            .vault_ncn_ticket(vault_ncn_ticket)
+            .ncn_vault_ticket(ncn_vault_ticket)
+            .snapshot(snapshot)
+            .vault_registry(vault_registry)
+            .operator_indices(operators.iter().map(|(index, _)| *index).collect())
+            .add_remaining_accounts(&operator_accounts)
+            .instruction()
+    }
+
+    /// Snapshots the delegations from `vault` to several operators in one transaction, limited to
+    /// `compute_unit_limit` compute units.
+    pub async fn do_snapshot_vault_operator_delegation_batch(
+        &mut self,
+        vault: Pubkey,
+        ncn: Pubkey,
+        operators: &[(u64, Pubkey)],
+        compute_unit_limit: u32,
+    ) -> TestResult<()> {
+        let compute_budget_ix =
+            ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit);
+        let ix = Self::snapshot_vault_operator_delegation_batch_instruction(vault, ncn, operators);
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[compute_budget_ix, ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Casts a vote using BLS signature aggregation for a given epoch.
+    pub async fn do_cast_vote(
+        &mut self,
+        ncn: Pubkey,
+        agg_sig: [u8; 32],
+        apk2: [u8; 64],
+        signers_bitmap: Vec<u8>,
+    ) -> Result<(), TestError> {
+        let ncn_config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let vote_counter = VoteCounter::find_program_address(&ncn_program::id(), &ncn).0;
+        let ncn_operator_states = self
+            .get_signer_ncn_operator_states(ncn, &signers_bitmap)
+            .await?;
+
+        self.cast_vote(
+            ncn_config,
+            ncn,
+            snapshot,
+            restaking_config,
+            vote_counter,
+            agg_sig,
+            apk2,
+            signers_bitmap,
+            ncn_operator_states,
+        )
+        .await
+    }
+
+    /// Returns the `NcnOperatorState` addresses of every signer in the bitmap, in operator index order.
+    pub async fn get_signer_ncn_operator_states(
+        &mut self,
+        ncn: Pubkey,
+        signers_bitmap: &[u8],
+    ) -> TestResult<Vec<Pubkey>> {
+        let snapshot = self.get_snapshot(ncn).await?;
+
+        let ncn_operator_states = snapshot
+            .operator_snapshots()
+            .iter()
+            .zip(bitmap_iter(signers_bitmap, snapshot.operator_slot_count()))
+            .filter(|(_, signed)| *signed)
+            .map(|(operator_snapshot, _)| {
+                NcnOperatorState::find_program_address(
+                    &jito_restaking_program::id(),
+                    &ncn,
+                    operator_snapshot.operator(),
+                )
+                .0
+            })
+            .collect();
+
+        Ok(ncn_operator_states)
+    }
+
+    /// Sends a transaction to cast a vote using BLS signature verification.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn cast_vote(
+        &mut self,
+        ncn_config: Pubkey,
+        ncn: Pubkey,
+        snapshot: Pubkey,
+        restaking_config: Pubkey,
+        vote_counter: Pubkey,
+        agg_sig: [u8; 32],
+        apk2: [u8; 64],
+        signers_bitmap: Vec<u8>,
+        ncn_operator_states: Vec<Pubkey>,
+    ) -> Result<(), TestError> {
+        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
+
+        let remaining_accounts: Vec<AccountMeta> = ncn_operator_states
+            .iter()
+            .map(|ncn_operator_state| AccountMeta::new_readonly(*ncn_operator_state, false))
+            .collect();
+
+        let ix = CastVoteBuilder::new()
+            .config(ncn_config)
+            .ncn(ncn)
+            .snapshot(snapshot)
+            .restaking_config(restaking_config)
+            .vote_counter(vote_counter)
+            .aggregated_signature(agg_sig)
+            .aggregated_g2(apk2)
+            .operators_signature_bitmap(signers_bitmap)
+            .add_remaining_accounts(&remaining_accounts)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[compute_budget_ix, ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Builds a `CastVote` instruction, supplying the signers' `NcnOperatorState` accounts.
+    pub async fn cast_vote_instruction(
+        &mut self,
+        ncn: Pubkey,
+        agg_sig: [u8; 32],
+        apk2: [u8; 64],
+        signers_bitmap: Vec<u8>,
+        quorum_bps: Option<u16>,
+    ) -> TestResult<Instruction> {
+        let ncn_config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let vote_counter = VoteCounter::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let remaining_accounts: Vec<AccountMeta> = self
+            .get_signer_ncn_operator_states(ncn, &signers_bitmap)
+            .await?
+            .into_iter()
+            .map(|ncn_operator_state| AccountMeta::new_readonly(ncn_operator_state, false))
+            .collect();
+
+        let mut builder = CastVoteBuilder::new();
+        builder
+            .config(ncn_config)
+            .ncn(ncn)
+            .snapshot(snapshot)
+            .restaking_config(restaking_config)
+            .vote_counter(vote_counter)
+            .aggregated_signature(agg_sig)
+            .aggregated_g2(apk2)
+            .operators_signature_bitmap(signers_bitmap)
+            .add_remaining_accounts(&remaining_accounts);
+
+        if let Some(quorum_bps) = quorum_bps {
+            builder.quorum_bps(quorum_bps);
+        }
+
+        Ok(builder.instruction())
+    }
+
+    /// Casts an ed25519 fallback vote for `operator`, signing the current vote message with
+    /// `voter` in the preceding ed25519 program instruction.
+    pub async fn do_cast_ed25519_vote(
+        &mut self,
+        ncn: Pubkey,
+        operator: Pubkey,
+        voter: &Keypair,
+    ) -> TestResult<()> {
+        let count = self.get_vote_counter(ncn).await?.count();
+        let message = self.vote_signing_payload(ncn, count).await?;
+        let signature: [u8; 64] = voter.sign_message(&message).as_ref().try_into().unwrap();
+        let ed25519_ix = new_ed25519_instruction(&voter.pubkey(), &signature, &message);
+
+        self.cast_ed25519_vote(ed25519_ix, ncn, operator).await
+    }
+
+    /// Sends `ed25519_ix` followed by a `CastEd25519Vote` instruction for `operator`.
+    pub async fn cast_ed25519_vote(
+        &mut self,
+        ed25519_ix: Instruction,
+        ncn: Pubkey,
+        operator: Pubkey,
+    ) -> TestResult<()> {
+        let ix = Self::cast_ed25519_vote_instruction(ncn, operator);
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ed25519_ix, ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Builds a `CastEd25519Vote` instruction for `operator`.
+    pub fn cast_ed25519_vote_instruction(ncn: Pubkey, operator: Pubkey) -> Instruction {
+        let ncn_config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let vote_counter = VoteCounter::find_program_address(&ncn_program::id(), &ncn).0;
+
+        CastEd25519VoteBuilder::new()
+            .config(ncn_config)
+            .ncn(ncn)
+            .operator(operator)
+            .snapshot(snapshot)
+            .restaking_config(restaking_config)
+            .vote_counter(vote_counter)
+            .instructions_sysvar(solana_program::sysvar::instructions::id())
+            .instruction()
+    }
+
+    /// Casts a vote that must also be backed by `quorum_bps` of the eligible stake weight.
+    pub async fn do_cast_vote_with_quorum(
+        &mut self,
+        ncn: Pubkey,
+        agg_sig: [u8; 32],
+        apk2: [u8; 64],
+        signers_bitmap: Vec<u8>,
+        quorum_bps: u16,
+    ) -> TestResult<()> {
+        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
+        let ix = self
+            .cast_vote_instruction(ncn, agg_sig, apk2, signers_bitmap, Some(quorum_bps))
+            .await?;
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[compute_budget_ix, ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Builds a `CastVoteBatch` instruction, supplying every vote's signer `NcnOperatorState`
+    /// accounts grouped in batch order.
+    pub async fn cast_vote_batch_instruction(
+        &mut self,
+        ncn: Pubkey,
+        votes: Vec<CastVoteBatchEntry>,
+    ) -> TestResult<Instruction> {
+        let ncn_config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let vote_counter = VoteCounter::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let mut remaining_accounts: Vec<AccountMeta> = vec![];
+        for vote in votes.iter() {
+            let ncn_operator_states = self
+                .get_signer_ncn_operator_states(ncn, &vote.operators_signature_bitmap)
+                .await?;
+            remaining_accounts.extend(
+                ncn_operator_states
+                    .into_iter()
+                    .map(|ncn_operator_state| AccountMeta::new_readonly(ncn_operator_state, false)),
+            );
+        }
+
+        Ok(CastVoteBatchBuilder::new()
+            .config(ncn_config)
+            .ncn(ncn)
+            .snapshot(snapshot)
+            .restaking_config(restaking_config)
+            .vote_counter(vote_counter)
+            .votes(votes)
+            .add_remaining_accounts(&remaining_accounts)
+            .instruction())
+    }
+
+    /// Casts several aggregated votes in a single `CastVoteBatch` instruction.
+    pub async fn do_cast_vote_batch(
+        &mut self,
+        ncn: Pubkey,
+        votes: Vec<CastVoteBatchEntry>,
+    ) -> TestResult<()> {
+        let ix = self.cast_vote_batch_instruction(ncn, votes).await?;
+        self.process_cast_vote_batch_instruction(ix).await
+    }
+
+    /// Sends a `CastVoteBatch` instruction, e.g. one built by `cast_vote_batch_instruction` and
+    /// then tampered with.
+    pub async fn process_cast_vote_batch_instruction(&mut self, ix: Instruction) -> TestResult<()> {
+        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[compute_budget_ix, ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
 
-        if let Some(slots) = valid_slots_after_consensus {
-            ix.valid_slots_after_consensus(slots);
+    /// Simulates an instruction and returns the compute units it consumed.
+    pub async fn simulate_compute_units(&mut self, ix: Instruction) -> TestResult<u64> {
+        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        let simulation = self
+            .banks_client
+            .simulate_transaction(Transaction::new_signed_with_payer(
+                &[compute_budget_ix, ix],
+                Some(&self.payer.pubkey()),
+                &[&self.payer],
+                blockhash,
+            ))
+            .await?;
+
+        if let Some(Err(err)) = simulation.result {
+            return Err(BanksClientError::TransactionError(err).into());
         }
 
-        if let Some(minimum_stake) = minimum_stake {
-            ix.minimum_stake(minimum_stake);
+        Ok(simulation
+            .simulation_details
+            .map(|details| details.units_consumed)
+            .unwrap_or_default())
+    }
+
+    /// Simulates an instruction and returns its log messages.
+    pub async fn simulate_logs(&mut self, ix: Instruction) -> TestResult<Vec<String>> {
+        let payer = self.payer.insecure_clone();
+        self.simulate_logs_with_payer(ix, &payer).await
+    }
+
+    /// Simulates an instruction paid for and signed by `payer` and returns its log messages.
+    pub async fn simulate_logs_with_payer(
+        &mut self,
+        ix: Instruction,
+        payer: &Keypair,
+    ) -> TestResult<Vec<String>> {
+        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(2_000_000);
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        let simulation = self
+            .banks_client
+            .simulate_transaction(Transaction::new_signed_with_payer(
+                &[compute_budget_ix, ix],
+                Some(&payer.pubkey()),
+                &[payer],
+                blockhash,
+            ))
+            .await?;
+
+        if let Some(Err(err)) = simulation.result {
+            return Err(BanksClientError::TransactionError(err).into());
         }
 
+        Ok(simulation
+            .simulation_details
+            .map(|details| details.logs)
+            .unwrap_or_default())
+    }
+
+    /// Sets various parameters in the NCN config (admin operation).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn do_set_parameters(
+        &mut self,
+        starting_valid_epoch: Option<u64>,
+        epochs_before_stall: Option<u64>,
+        epochs_after_consensus_before_close: Option<u64>,
+        valid_slots_after_consensus: Option<u64>,
+        minimum_stake: Option<u128>,
+        enforce_signer_order: Option<bool>,
+        minimum_operators: Option<u64>,
+        reject_duplicate_aggregates: Option<bool>,
+        allow_multiple_votes_per_epoch: Option<bool>,
+        quorum_bps: Option<u16>,
+        max_snapshot_age_slots: Option<u64>,
+        allow_ed25519_votes: Option<bool>,
+        ncn_root: &NcnRoot,
+    ) -> TestResult<()> {
+        let ix = Self::set_parameters_instruction(
+            starting_valid_epoch,
+            epochs_before_stall,
+            epochs_after_consensus_before_close,
+            valid_slots_after_consensus,
+            minimum_stake,
+            enforce_signer_order,
+            minimum_operators,
+            reject_duplicate_aggregates,
+            allow_multiple_votes_per_epoch,
+            quorum_bps,
+            max_snapshot_age_slots,
+            allow_ed25519_votes,
+            ncn_root,
+        );
+
         let blockhash = self.banks_client.get_latest_blockhash().await?;
         self.process_transaction(&Transaction::new_signed_with_payer(
-            &[ix.instruction()],
+            &[ix],
             Some(&ncn_root.ncn_admin.pubkey()),
             &[&ncn_root.ncn_admin],
             blockhash,
@@ -725,6 +1386,205 @@ impl NCNProgramClient {
         .await
     }
 
+    /// Updates the minimum stake of the NCN's snapshot, signed by `ncn_admin`.
+    pub async fn do_admin_update_snapshot_minimum_stake(
+        &mut self,
+        ncn: Pubkey,
+        ncn_admin: &Keypair,
+        minimum_stake: u128,
+    ) -> TestResult<()> {
+        self.airdrop(&ncn_admin.pubkey(), 1.0).await?;
+
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let ix = AdminUpdateSnapshotMinimumStakeBuilder::new()
+            .config(config)
+            .ncn(ncn)
+            .snapshot(snapshot)
+            .ncn_admin(ncn_admin.pubkey())
+            .minimum_stake(minimum_stake)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&ncn_admin.pubkey()),
+            &[ncn_admin],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Inserts several operators into the NCN's snapshot at once, signed by `ncn_admin`.
+    pub async fn do_batch_initialize_operator_snapshots(
+        &mut self,
+        ncn: Pubkey,
+        ncn_admin: &Keypair,
+        operators: Vec<OperatorSnapshotEntry>,
+    ) -> TestResult<()> {
+        self.airdrop(&ncn_admin.pubkey(), 1.0).await?;
+
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let (account_payer, _, _) = AccountPayer::find_program_address(&ncn_program::id(), &ncn);
+
+        let mut operator_accounts = Vec::with_capacity(operators.len() * 3);
+        for entry in operators.iter() {
+            let ncn_operator_state = NcnOperatorState::find_program_address(
+                &jito_restaking_program::id(),
+                &ncn,
+                &entry.operator,
+            )
+            .0;
+            let ncn_operator_account =
+                NCNOperatorAccount::find_program_address(&ncn_program::id(), &ncn, &entry.operator)
+                    .0;
+
+            operator_accounts.push(AccountMeta::new_readonly(entry.operator, false));
+            operator_accounts.push(AccountMeta::new_readonly(ncn_operator_state, false));
+            operator_accounts.push(AccountMeta::new(ncn_operator_account, false));
+        }
+
+        let compute_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(1_400_000);
+        let ix = BatchInitializeOperatorSnapshotsBuilder::new()
+            .config(config)
+            .ncn(ncn)
+            .snapshot(snapshot)
+            .ncn_admin(ncn_admin.pubkey())
+            .restaking_config(restaking_config)
+            .account_payer(account_payer)
+            .system_program(system_program::id())
+            .operators(operators)
+            .add_remaining_accounts(&operator_accounts)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[compute_budget_ix, ix],
+            Some(&ncn_admin.pubkey()),
+            &[ncn_admin],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Marks an operator active or inactive in the NCN's snapshot, signed by `ncn_admin`.
+    pub async fn do_set_operator_active(
+        &mut self,
+        ncn: Pubkey,
+        operator: Pubkey,
+        ncn_admin: &Keypair,
+        active: bool,
+    ) -> TestResult<()> {
+        self.airdrop(&ncn_admin.pubkey(), 1.0).await?;
+
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let ix = SetOperatorActiveBuilder::new()
+            .config(config)
+            .ncn(ncn)
+            .operator(operator)
+            .snapshot(snapshot)
+            .ncn_admin(ncn_admin.pubkey())
+            .active(active)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&ncn_admin.pubkey()),
+            &[ncn_admin],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Builds the instruction setting the given parameters in the NCN config.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_parameters_instruction(
+        starting_valid_epoch: Option<u64>,
+        epochs_before_stall: Option<u64>,
+        epochs_after_consensus_before_close: Option<u64>,
+        valid_slots_after_consensus: Option<u64>,
+        minimum_stake: Option<u128>,
+        enforce_signer_order: Option<bool>,
+        minimum_operators: Option<u64>,
+        reject_duplicate_aggregates: Option<bool>,
+        allow_multiple_votes_per_epoch: Option<bool>,
+        quorum_bps: Option<u16>,
+        max_snapshot_age_slots: Option<u64>,
+        allow_ed25519_votes: Option<bool>,
+        ncn_root: &NcnRoot,
+    ) -> Instruction {
+        let config_pda =
+            NcnConfig::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey).0;
+        let restaking_config = Config::find_program_address(&jito_restaking_program::id()).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey).0;
+        let vote_counter =
+            VoteCounter::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey).0;
+
+        let mut ix = AdminSetParametersBuilder::new();
+        ix.config(config_pda)
+            .ncn(ncn_root.ncn_pubkey)
+            .ncn_admin(ncn_root.ncn_admin.pubkey())
+            .restaking_config(restaking_config)
+            .snapshot(snapshot)
+            .vote_counter(vote_counter);
+
+        if let Some(epoch) = starting_valid_epoch {
+            ix.starting_valid_epoch(epoch);
+        }
+
+        if let Some(epochs) = epochs_before_stall {
+            ix.epochs_before_stall(epochs);
+        }
+
+        if let Some(epochs) = epochs_after_consensus_before_close {
+            ix.epochs_after_consensus_before_close(epochs);
+        }
+
+        if let Some(slots) = valid_slots_after_consensus {
+            ix.valid_slots_after_consensus(slots);
+        }
+
+        if let Some(minimum_stake) = minimum_stake {
+            ix.minimum_stake(minimum_stake);
+        }
+
+        if let Some(enforce_signer_order) = enforce_signer_order {
+            ix.enforce_signer_order(enforce_signer_order);
+        }
+
+        if let Some(minimum_operators) = minimum_operators {
+            ix.minimum_operators(minimum_operators);
+        }
+
+        if let Some(reject_duplicate_aggregates) = reject_duplicate_aggregates {
+            ix.reject_duplicate_aggregates(reject_duplicate_aggregates);
+        }
+
+        if let Some(allow_multiple_votes_per_epoch) = allow_multiple_votes_per_epoch {
+            ix.allow_multiple_votes_per_epoch(allow_multiple_votes_per_epoch);
+        }
+
+        if let Some(quorum_bps) = quorum_bps {
+            ix.quorum_bps(quorum_bps);
+        }
+
+        if let Some(max_snapshot_age_slots) = max_snapshot_age_slots {
+            ix.max_snapshot_age_slots(max_snapshot_age_slots);
+        }
+
+        if let Some(allow_ed25519_votes) = allow_ed25519_votes {
+            ix.allow_ed25519_votes(allow_ed25519_votes);
+        }
+
+        ix.instruction()
+    }
+
     pub async fn do_register_operator(
         &mut self,
         ncn: Pubkey,
@@ -934,6 +1794,103 @@ impl NCNProgramClient {
         ))
         .await
     }
+
+    /// Deregisters an operator, freeing its snapshot slot and closing its ncn operator account
+    pub async fn do_deregister_operator(
+        &mut self,
+        ncn: Pubkey,
+        operator_pubkey: Pubkey,
+        operator_admin: &Keypair,
+    ) -> TestResult<()> {
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let ncn_operator_account =
+            NCNOperatorAccount::find_program_address(&ncn_program::id(), &ncn, &operator_pubkey).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+
+        self.deregister_operator(
+            config,
+            ncn_operator_account,
+            ncn,
+            operator_pubkey,
+            operator_admin,
+            snapshot,
+        )
+        .await
+    }
+
+    /// Sends a transaction to deregister an operator
+    pub async fn deregister_operator(
+        &mut self,
+        config: Pubkey,
+        ncn_operator_account: Pubkey,
+        ncn: Pubkey,
+        operator_pubkey: Pubkey,
+        operator_admin: &Keypair,
+        snapshot: Pubkey,
+    ) -> TestResult<()> {
+        let (account_payer, _, _) = AccountPayer::find_program_address(&ncn_program::id(), &ncn);
+
+        let ix = DeregisterOperatorBuilder::new()
+            .config(config)
+            .ncn_operator_account(ncn_operator_account)
+            .ncn(ncn)
+            .operator(operator_pubkey)
+            .operator_admin(operator_admin.pubkey())
+            .snapshot(snapshot)
+            .account_payer(account_payer)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer, operator_admin],
+            blockhash,
+        ))
+        .await
+    }
+
+    /// Refreshes an operator's keys in the snapshot from its ncn operator account
+    pub async fn do_refresh_operator_key(
+        &mut self,
+        ncn: Pubkey,
+        operator_pubkey: Pubkey,
+    ) -> TestResult<()> {
+        let config = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let ncn_operator_account =
+            NCNOperatorAccount::find_program_address(&ncn_program::id(), &ncn, &operator_pubkey).0;
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+
+        self.refresh_operator_key(config, ncn_operator_account, ncn, operator_pubkey, snapshot)
+            .await
+    }
+
+    /// Sends a transaction to refresh an operator's keys in the snapshot
+    pub async fn refresh_operator_key(
+        &mut self,
+        config: Pubkey,
+        ncn_operator_account: Pubkey,
+        ncn: Pubkey,
+        operator_pubkey: Pubkey,
+        snapshot: Pubkey,
+    ) -> TestResult<()> {
+        let ix = RefreshOperatorKeyBuilder::new()
+            .config(config)
+            .ncn_operator_account(ncn_operator_account)
+            .ncn(ncn)
+            .operator(operator_pubkey)
+            .snapshot(snapshot)
+            .instruction();
+
+        let blockhash = self.banks_client.get_latest_blockhash().await?;
+        self.process_transaction(&Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        ))
+        .await
+    }
 }
 
 /// Asserts that a TestResult contains a specific NCNProgramError.
@@ -4,7 +4,7 @@ use jito_restaking_core::{config::Config, ncn_vault_ticket::NcnVaultTicket};
 use ncn_program_core::{
     g1_point::{G1CompressedPoint, G1Point},
     g2_point::{G2CompressedPoint, G2Point},
-    schemes::Sha256Normalized,
+    schemes::{Sha256Normalized, Sha256NormalizedScheme},
     utils::create_signer_bitmap,
 };
 use solana_program::{clock::Clock, native_token::sol_to_lamports, pubkey::Pubkey};
@@ -99,6 +99,11 @@ impl TestBuilder {
         self.context.banks_client.get_account(*address).await
     }
 
+    /// Overwrites an account directly in the test bank.
+    pub fn set_account(&mut self, address: &Pubkey, account: Account) {
+        self.context.set_account(address, &account.into());
+    }
+
     /// Advances the test clock by a specified number of slots.
     pub async fn warp_slot_incremental(
         &mut self,
@@ -373,6 +378,17 @@ impl TestBuilder {
     /// Updates vault state, registers stMints, and registers the vault itself.
     // 5. Setup Tracked Mints
     pub async fn add_vault_registry_to_test_ncn(&mut self, test_ncn: &TestNcn) -> TestResult<()> {
+        self.add_vault_registry_to_test_ncn_with_st_mint_weight(test_ncn, None)
+            .await
+    }
+
+    /// Same as `add_vault_registry_to_test_ncn`, registering the vaults' st_mints with
+    /// `st_mint_weight` when given instead of the default weight.
+    pub async fn add_vault_registry_to_test_ncn_with_st_mint_weight(
+        &mut self,
+        test_ncn: &TestNcn,
+        st_mint_weight: Option<u128>,
+    ) -> TestResult<()> {
         let mut ncn_program_client = self.ncn_program_client();
         let mut restaking_client = self.restaking_program_client();
         let mut vault_client = self.vault_program_client();
@@ -406,9 +422,15 @@ impl TestBuilder {
             let ncn_vault_ticket =
                 NcnVaultTicket::find_program_address(&jito_restaking_program::id(), &ncn, &vault).0;
 
-            ncn_program_client
-                .do_admin_register_st_mint(ncn, st_mint)
-                .await?;
+            if let Some(st_mint_weight) = st_mint_weight {
+                ncn_program_client
+                    .do_admin_register_st_mint_with_weight(ncn, st_mint, st_mint_weight)
+                    .await?;
+            } else {
+                ncn_program_client
+                    .do_admin_register_st_mint(ncn, st_mint)
+                    .await?;
+            }
 
             ncn_program_client
                 .do_register_vault(ncn, vault, ncn_vault_ticket)
@@ -471,14 +493,14 @@ impl TestBuilder {
         let mut ncn_program_client = self.ncn_program_client();
         let ncn = test_ncn.ncn_root.ncn_pubkey;
 
-        // Get the current vote counter to use as the message
+        // Get the current vote counter to build the message
         let vote_counter = ncn_program_client.get_vote_counter(ncn).await.unwrap();
         let current_count = vote_counter.count();
 
-        // Create message from the current counter value (padded to 32 bytes)
-        let count_bytes = current_count.to_le_bytes();
-        let mut message = [0u8; 32];
-        message[..8].copy_from_slice(&count_bytes);
+        // Create the message operators sign for the current counter value
+        let message = ncn_program_client
+            .vote_signing_payload(ncn, current_count)
+            .await?;
 
         let mut signitures: Vec<G1Point> = vec![];
         let mut apk2_pubkeys: Vec<G2Point> = vec![];
@@ -487,7 +509,7 @@ impl TestBuilder {
                 apk2_pubkeys.push(operator.bn128_g2_pubkey);
                 let signature = operator
                     .bn128_privkey
-                    .sign::<Sha256Normalized, &[u8; 32]>(&message)
+                    .sign_with_scheme(&Sha256NormalizedScheme::for_ncn(&ncn), &message)
                     .unwrap();
                 signitures.push(signature);
             }
@@ -515,6 +537,31 @@ impl TestBuilder {
             .await
     }
 
+    /// Runs a whole voting round: sets up an NCN with `num_operators` operators holding BLS keys,
+    /// snapshots their delegations and casts a vote aggregated from the first `signers` operators.
+    /// Setup failures are returned as errors, while the outcome of the vote is the returned
+    /// result so tests can also assert on rejected votes.
+    pub async fn full_vote_round(
+        &mut self,
+        num_operators: usize,
+        signers: usize,
+    ) -> TestResult<(TestNcn, TestResult<()>)> {
+        assert!(
+            signers > 0 && signers <= num_operators,
+            "signers must be between 1 and num_operators"
+        );
+
+        let test_ncn = self.create_initial_test_ncn(num_operators, None).await?;
+
+        self.warp_slot_incremental(1000).await?;
+        self.update_snapshot_test_ncn_new_epoch(&test_ncn).await?;
+
+        let non_signers = (signers..num_operators).collect();
+        let result = self.cast_vote_for_test_ncn(&test_ncn, non_signers).await;
+
+        Ok((test_ncn, result))
+    }
+
     /// Takes snapshots of VaultOperatorDelegation for all active operator-vault pairs in the TestNcn for the current epoch.
     /// Ensures vaults are updated if necessary before snapshotting.
     // 9. Take all VaultOperatorDelegation snapshots
@@ -572,32 +619,43 @@ impl TestBuilder {
 
     /// Registers all operators in the TestNcn with the NCN program.
     pub async fn register_operators_to_test_ncn(&mut self, test_ncn: &TestNcn) -> TestResult<()> {
-        let mut ncn_program_client = self.ncn_program_client();
         for operator_root in test_ncn.operators.iter() {
-            let g1_pubkey = G1Point::try_from(operator_root.bn128_privkey).unwrap();
-            let g1_compressed = G1CompressedPoint::try_from(g1_pubkey).unwrap();
-            let g2_compressed = G2CompressedPoint::try_from(&operator_root.bn128_privkey).unwrap();
-
-            let signature = operator_root
-                .bn128_privkey
-                .sign::<Sha256Normalized, &[u8; 32]>(&g1_compressed.0)
-                .unwrap();
-
-            ncn_program_client
-                .do_register_operator(
-                    test_ncn.ncn_root.ncn_pubkey,
-                    operator_root.operator_pubkey,
-                    &operator_root.operator_admin,
-                    g1_compressed.0,
-                    g2_compressed.0,
-                    signature.0,
-                )
+            self.register_operator_to_test_ncn(test_ncn, operator_root)
                 .await?;
         }
 
         Ok(())
     }
 
+    /// Registers a single operator of the TestNcn with its BN128 keys.
+    pub async fn register_operator_to_test_ncn(
+        &mut self,
+        test_ncn: &TestNcn,
+        operator_root: &OperatorRoot,
+    ) -> TestResult<()> {
+        let mut ncn_program_client = self.ncn_program_client();
+
+        let g1_pubkey = G1Point::try_from(operator_root.bn128_privkey).unwrap();
+        let g1_compressed = G1CompressedPoint::try_from(g1_pubkey).unwrap();
+        let g2_compressed = G2CompressedPoint::try_from(&operator_root.bn128_privkey).unwrap();
+
+        let signature = operator_root
+            .bn128_privkey
+            .sign::<Sha256Normalized, &[u8; 32]>(&g1_compressed.0)
+            .unwrap();
+
+        ncn_program_client
+            .do_register_operator(
+                test_ncn.ncn_root.ncn_pubkey,
+                operator_root.operator_pubkey,
+                &operator_root.operator_admin,
+                g1_compressed.0,
+                g2_compressed.0,
+                signature.0,
+            )
+            .await
+    }
+
     /// Performs all necessary steps to snapshot the state of the TestNcn for the current epoch.
     /// Initializes epoch state, snapshot, operator snapshots, and VOD snapshots.
     // Intermission 2 - all snapshots are taken
@@ -651,14 +709,14 @@ impl TestBuilder {
             return Ok(());
         }
 
-        // Get the current vote counter to use as the message
+        // Get the current vote counter to build the message
         let vote_counter = ncn_program_client.get_vote_counter(ncn).await.unwrap();
         let current_count = vote_counter.count();
 
-        // Create message from the current counter value (padded to 32 bytes)
-        let count_bytes = current_count.to_le_bytes();
-        let mut vote_message = [0u8; 32];
-        vote_message[..8].copy_from_slice(&count_bytes);
+        // Create the message operators sign for the current counter value
+        let vote_message = ncn_program_client
+            .vote_signing_payload(ncn, current_count)
+            .await?;
 
         // Collect signatures and public keys from all active operators
         let mut signatures: Vec<G1Point> = vec![];
@@ -668,7 +726,7 @@ impl TestBuilder {
             apk2_pubkeys.push(operator.bn128_g2_pubkey);
             let signature = operator
                 .bn128_privkey
-                .sign::<Sha256Normalized, &[u8; 32]>(&vote_message)
+                .sign_with_scheme(&Sha256NormalizedScheme::for_ncn(&ncn), &vote_message)
                 .unwrap();
             signatures.push(signature);
         }
@@ -1,9 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use ncn_program_core::error::NCNProgramError;
+    use ncn_program_core::{
+        error::NCNProgramError,
+        g1_point::{G1CompressedPoint, G1Point},
+        g2_point::{G2CompressedPoint, G2Point},
+        schemes::Sha256NormalizedScheme,
+        utils::create_signer_bitmap,
+    };
+    use solana_sdk::signer::Signer;
 
     use crate::fixtures::{
-        ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder, TestResult,
+        ncn_program_client::{assert_ncn_program_error, NCNProgramClient},
+        test_builder::TestBuilder,
+        TestResult,
     };
 
     #[tokio::test]
@@ -23,6 +32,13 @@ mod tests {
                 Some(10),   // epochs_after_consensus_before_close
                 Some(1000), // valid_slots_after_consensus
                 Some(100),  // minimum_stake
+                Some(true), // enforce_signer_order
+                Some(3),    // minimum_operators
+                Some(true), // reject_duplicate_aggregates
+                Some(true), // allow_multiple_votes_per_epoch
+                Some(5000), // quorum_bps
+                Some(500),  // max_snapshot_age_slots
+                Some(true), // allow_ed25519_votes
                 &ncn_root,
             )
             .await?;
@@ -34,6 +50,13 @@ mod tests {
         assert_eq!(config.epochs_before_stall(), 5);
         assert_eq!(config.epochs_after_consensus_before_close(), 10);
         assert_eq!(config.valid_slots_after_consensus(), 1000);
+        assert!(config.enforce_signer_order());
+        assert_eq!(config.minimum_operators(), 3);
+        assert!(config.reject_duplicate_aggregates());
+        assert!(config.allow_multiple_votes_per_epoch());
+        assert_eq!(config.quorum_bps(), 5000);
+        assert_eq!(config.max_snapshot_age_slots(), 500);
+        assert!(config.allow_ed25519_votes());
 
         // Test invalid epochs_before_stall
         let result = ncn_program_client
@@ -43,6 +66,13 @@ mod tests {
                 None,
                 None,
                 Some(100), // minimum_stake
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
                 &ncn_root,
             )
             .await;
@@ -56,6 +86,13 @@ mod tests {
                 Some(0), // Invalid - too low
                 None,
                 Some(100), // minimum_stake
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
                 &ncn_root,
             )
             .await;
@@ -69,11 +106,183 @@ mod tests {
                 None,
                 Some(99),  // Invalid - too low
                 Some(100), // minimum_stake
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
                 &ncn_root,
             )
             .await;
         assert_ncn_program_error(result, NCNProgramError::InvalidSlotsAfterConsensus, None);
 
+        // Test invalid quorum_bps
+        let result = ncn_program_client
+            .do_set_parameters(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(10_001), // Invalid - above 100%
+                None,
+                None,
+                &ncn_root,
+            )
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::InvalidQuorumBps, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_set_parameters_logs_old_and_new_values() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let ix = NCNProgramClient::set_parameters_instruction(
+            None,
+            None,
+            None,
+            None,
+            Some(250), // minimum_stake
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            &ncn_root,
+        );
+        let logs = ncn_program_client
+            .simulate_logs_with_payer(ix, &ncn_root.ncn_admin)
+            .await?;
+
+        // Matches the `minimum_stake` set by `do_initialize_config`
+        let expected_log = format!(
+            "Admin change: instruction=AdminSetParameters admin={} field=minimum_stake old=100 new=250",
+            ncn_root.ncn_admin.pubkey()
+        );
+        assert!(logs.iter().any(|log| log.contains(&expected_log)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_set_parameters_minimum_stake_blocked_until_voted() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        fixture.warp_slot_incremental(1000).await?;
+        fixture
+            .update_snapshot_test_ncn_new_epoch(&test_ncn)
+            .await?;
+
+        let ncn_root = &test_ncn.ncn_root;
+        let ncn = ncn_root.ncn_pubkey;
+
+        // No vote in this epoch yet, the minimum stake can't change
+        let result = ncn_program_client
+            .do_set_parameters(
+                None,
+                None,
+                None,
+                None,
+                Some(250),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                ncn_root,
+            )
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::ParameterChangeNotAllowed, None);
+
+        // Timing parameters can still change
+        ncn_program_client
+            .do_set_parameters(
+                None,
+                Some(5),
+                None,
+                Some(1000),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                ncn_root,
+            )
+            .await?;
+
+        // Once every operator has voted in this epoch the minimum stake can change
+        let count = ncn_program_client.get_vote_counter(ncn).await?.count();
+        let message = ncn_program_client.vote_signing_payload(ncn, count).await?;
+        let agg_sig = test_ncn
+            .operators
+            .iter()
+            .map(|operator| {
+                operator
+                    .bn128_privkey
+                    .sign_with_scheme(&Sha256NormalizedScheme::for_ncn(&ncn), &message)
+                    .unwrap()
+            })
+            .reduce(|acc: G1Point, x| acc + x)
+            .unwrap();
+        let apk2 = test_ncn
+            .operators
+            .iter()
+            .map(|operator| operator.bn128_g2_pubkey)
+            .reduce(|acc: G2Point, x| acc + x)
+            .unwrap();
+
+        ncn_program_client
+            .do_cast_vote(
+                ncn,
+                G1CompressedPoint::try_from(agg_sig).unwrap().0,
+                G2CompressedPoint::try_from(&apk2).unwrap().0,
+                create_signer_bitmap(&[], test_ncn.operators.len()),
+            )
+            .await?;
+
+        ncn_program_client
+            .do_set_parameters(
+                None,
+                None,
+                None,
+                None,
+                Some(250),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                ncn_root,
+            )
+            .await?;
+
+        let config = ncn_program_client.get_ncn_config(ncn).await?;
+        assert_eq!(config.minimum_stake().stake_weight(), 250);
+
         Ok(())
     }
 }
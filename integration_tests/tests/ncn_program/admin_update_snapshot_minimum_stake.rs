@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::error::NCNProgramError;
+    use solana_sdk::signature::Keypair;
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder, TestResult,
+    };
+
+    #[tokio::test]
+    async fn test_admin_update_snapshot_minimum_stake_flips_eligibility() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        fixture.warp_slot_incremental(1000).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let vault = test_ncn.vaults[0].vault_pubkey;
+
+        for operator_root in test_ncn.operators.iter() {
+            ncn_program_client
+                .do_snapshot_vault_operator_delegation(vault, operator_root.operator_pubkey, ncn)
+                .await?;
+        }
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+        let stake_weight = snapshot
+            .get_operator_snapshot(0)
+            .unwrap()
+            .stake_weight()
+            .stake_weight();
+
+        // Raising the minimum above the operators' stake makes them ineligible
+        ncn_program_client
+            .do_admin_update_snapshot_minimum_stake(
+                ncn,
+                &test_ncn.ncn_root.ncn_admin,
+                stake_weight + 1,
+            )
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.minimum_stake().stake_weight(), stake_weight + 1);
+        assert_eq!(snapshot.operators_can_vote_count(), 0);
+        for operator_root in test_ncn.operators.iter() {
+            let operator_snapshot = snapshot
+                .find_operator_snapshot(&operator_root.operator_pubkey)
+                .unwrap();
+            assert!(!operator_snapshot.has_minimum_stake());
+            assert!(!operator_snapshot.has_minimum_stake_next_epoch());
+        }
+
+        // Lowering it back to their stake makes them eligible again
+        ncn_program_client
+            .do_admin_update_snapshot_minimum_stake(ncn, &test_ncn.ncn_root.ncn_admin, stake_weight)
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.minimum_stake().stake_weight(), stake_weight);
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+        for operator_root in test_ncn.operators.iter() {
+            let operator_snapshot = snapshot
+                .find_operator_snapshot(&operator_root.operator_pubkey)
+                .unwrap();
+            assert!(operator_snapshot.has_minimum_stake());
+            assert!(operator_snapshot.has_minimum_stake_next_epoch());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_admin_update_snapshot_minimum_stake_wrong_admin_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(1, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        let result = ncn_program_client
+            .do_admin_update_snapshot_minimum_stake(ncn, &Keypair::new(), 1)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::IncorrectNcnAdmin, None);
+
+        let result = ncn_program_client
+            .do_admin_update_snapshot_minimum_stake(ncn, &test_ncn.ncn_root.ncn_admin, 0)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::InvalidMinimumStakeWeight, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_client::types::OperatorSnapshotEntry;
+    use ncn_program_core::{
+        error::NCNProgramError,
+        g1_point::{G1CompressedPoint, G1Point},
+        g2_point::G2CompressedPoint,
+        schemes::Sha256Normalized,
+    };
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error,
+        restaking_client::OperatorRoot,
+        test_builder::{TestBuilder, TestNcn},
+        TestResult,
+    };
+
+    /// Entries that fit comfortably in a single transaction
+    const BATCH_SIZE: usize = 2;
+
+    fn entry(ncn_operator_index: u64, operator_root: &OperatorRoot) -> OperatorSnapshotEntry {
+        let g1_compressed = G1CompressedPoint::try_from(operator_root.bn128_privkey).unwrap();
+        let g2_compressed = G2CompressedPoint::try_from(&operator_root.bn128_privkey).unwrap();
+        let signature = operator_root
+            .bn128_privkey
+            .sign::<Sha256Normalized, &[u8; 32]>(&g1_compressed.0)
+            .unwrap();
+
+        OperatorSnapshotEntry {
+            operator: operator_root.operator_pubkey,
+            g1_pubkey: g1_compressed.0,
+            g2_pubkey: g2_compressed.0,
+            signature: signature.0,
+            ncn_operator_index,
+        }
+    }
+
+    /// Sets up an NCN with `operator_count` operators linked to it and an empty snapshot
+    async fn setup(fixture: &mut TestBuilder, operator_count: usize) -> TestResult<TestNcn> {
+        fixture.initialize_restaking_and_vault_programs().await?;
+
+        let mut test_ncn = fixture.create_test_ncn().await?;
+        fixture
+            .add_operators_to_test_ncn(&mut test_ncn, operator_count, None)
+            .await?;
+
+        let mut ncn_program_client = fixture.ncn_program_client();
+        ncn_program_client
+            .setup_ncn_program(&test_ncn.ncn_root)
+            .await?;
+        ncn_program_client
+            .do_full_initialize_snapshot(test_ncn.ncn_root.ncn_pubkey)
+            .await?;
+
+        Ok(test_ncn)
+    }
+
+    #[tokio::test]
+    async fn test_batch_initialize_operator_snapshots() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let test_ncn = setup(&mut fixture, 6).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let entries: Vec<OperatorSnapshotEntry> = test_ncn
+            .operators
+            .iter()
+            .enumerate()
+            .map(|(i, operator_root)| entry(i as u64, operator_root))
+            .collect();
+
+        for batch in entries.chunks(BATCH_SIZE) {
+            ncn_program_client
+                .do_batch_initialize_operator_snapshots(
+                    ncn,
+                    &test_ncn.ncn_root.ncn_admin,
+                    batch.to_vec(),
+                )
+                .await?;
+        }
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), entries.len() as u64);
+        assert!(snapshot.finalized());
+
+        let expected_aggregate = test_ncn
+            .operators
+            .iter()
+            .map(|operator_root| G1Point::try_from(operator_root.bn128_privkey).unwrap())
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            G1CompressedPoint::try_from(expected_aggregate).unwrap().0
+        );
+
+        for entry in entries.iter() {
+            let operator_snapshot = snapshot.find_operator_snapshot(&entry.operator).unwrap();
+            assert_eq!(operator_snapshot.g1_pubkey(), entry.g1_pubkey);
+            assert_eq!(operator_snapshot.g2_pubkey(), entry.g2_pubkey);
+            assert!(operator_snapshot.have_valid_bn128_g2_pubkey());
+            assert!(operator_snapshot.is_active());
+
+            // Batch-inserted operators get their ncn operator account like registered ones
+            let ncn_operator_account = ncn_program_client
+                .get_ncn_operator_account(ncn, entry.operator)
+                .await?;
+            assert_eq!(ncn_operator_account.g1_pubkey(), &entry.g1_pubkey);
+            assert_eq!(ncn_operator_account.g2_pubkey(), &entry.g2_pubkey);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_batch_initialize_operator_snapshots_without_proof_of_possession_fails(
+    ) -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let test_ncn = setup(&mut fixture, 2).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        // The second operator's keys come with the first operator's proof of possession
+        let first = entry(0, &test_ncn.operators[0]);
+        let mut second = entry(1, &test_ncn.operators[1]);
+        second.signature = first.signature;
+
+        let result = ncn_program_client
+            .do_batch_initialize_operator_snapshots(
+                ncn,
+                &test_ncn.ncn_root.ncn_admin,
+                vec![first, second],
+            )
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::InvalidG2Pubkey, Some(1));
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_batch_initialize_operator_snapshots_wrong_index_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let test_ncn = setup(&mut fixture, 2).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        // Both operators claim each other's NCN operator index
+        let entries = vec![
+            entry(1, &test_ncn.operators[0]),
+            entry(0, &test_ncn.operators[1]),
+        ];
+
+        let result = ncn_program_client
+            .do_batch_initialize_operator_snapshots(ncn, &test_ncn.ncn_root.ncn_admin, entries)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::NcnOperatorIndexMismatch, Some(1));
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{error::NCNProgramError, schemes::ed25519::new_ed25519_instruction};
+    use solana_sdk::{
+        compute_budget::ComputeBudgetInstruction,
+        signature::{Keypair, Signer},
+    };
+
+    use crate::fixtures::{
+        ncn_program_client::{assert_ncn_program_error, NCNProgramClient},
+        test_builder::{TestBuilder, TestNcn},
+        TestResult,
+    };
+
+    /// Creates a test NCN with `operator_count` operators, snapshots it and enables ed25519 votes
+    async fn setup_ed25519_votes(
+        fixture: &mut TestBuilder,
+        ncn_program_client: &mut NCNProgramClient,
+        operator_count: usize,
+    ) -> TestResult<TestNcn> {
+        let test_ncn = fixture
+            .create_initial_test_ncn(operator_count, None)
+            .await?;
+        fixture.warp_slot_incremental(1000).await?;
+        fixture
+            .update_snapshot_test_ncn_new_epoch(&test_ncn)
+            .await?;
+
+        ncn_program_client
+            .do_set_parameters(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(true),
+                &test_ncn.ncn_root,
+            )
+            .await?;
+
+        Ok(test_ncn)
+    }
+
+    #[tokio::test]
+    async fn test_cast_ed25519_vote() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let test_ncn = setup_ed25519_votes(&mut fixture, &mut ncn_program_client, 3).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let epoch = ncn_program_client.get_ncn_epoch().await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        let operator_snapshots: Vec<_> = test_ncn
+            .operators
+            .iter()
+            .map(|operator_root| {
+                *snapshot
+                    .find_operator_snapshot(&operator_root.operator_pubkey)
+                    .unwrap()
+            })
+            .collect();
+
+        for operator_root in test_ncn.operators[..2].iter() {
+            ncn_program_client
+                .do_cast_ed25519_vote(
+                    ncn,
+                    operator_root.operator_pubkey,
+                    &operator_root.operator_admin,
+                )
+                .await?;
+        }
+
+        let vote_counter = ncn_program_client.get_vote_counter(ncn).await?;
+        assert_eq!(vote_counter.ed25519_vote_epoch(), epoch);
+        assert_eq!(
+            vote_counter.ed25519_stake_weight_in_epoch(epoch),
+            operator_snapshots[..2]
+                .iter()
+                .map(|operator_snapshot| operator_snapshot.stake_weight().stake_weight())
+                .sum::<u128>()
+        );
+        for (i, operator_snapshot) in operator_snapshots.iter().enumerate() {
+            assert_eq!(
+                vote_counter.has_ed25519_vote(epoch, operator_snapshot.ncn_operator_index()),
+                i < 2
+            );
+        }
+        // Ed25519 votes are tallied apart from the BLS votes
+        assert_eq!(vote_counter.count(), 0);
+        assert_eq!(vote_counter.votes_in_epoch(epoch), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cast_ed25519_vote_disabled_by_default() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        fixture.warp_slot_incremental(1000).await?;
+        fixture
+            .update_snapshot_test_ncn_new_epoch(&test_ncn)
+            .await?;
+
+        let operator_root = &test_ncn.operators[0];
+        let result = ncn_program_client
+            .do_cast_ed25519_vote(
+                test_ncn.ncn_root.ncn_pubkey,
+                operator_root.operator_pubkey,
+                &operator_root.operator_admin,
+            )
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::Ed25519VotesDisabled, Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cast_ed25519_vote_twice_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let test_ncn = setup_ed25519_votes(&mut fixture, &mut ncn_program_client, 2).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let operator_root = &test_ncn.operators[0];
+
+        ncn_program_client
+            .do_cast_ed25519_vote(
+                ncn,
+                operator_root.operator_pubkey,
+                &operator_root.operator_admin,
+            )
+            .await?;
+
+        fixture.warp_slot_incremental(1).await?;
+
+        let result = ncn_program_client
+            .do_cast_ed25519_vote(
+                ncn,
+                operator_root.operator_pubkey,
+                &operator_root.operator_admin,
+            )
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::Ed25519AlreadyVoted, Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cast_ed25519_vote_wrong_voter_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let test_ncn = setup_ed25519_votes(&mut fixture, &mut ncn_program_client, 2).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        // A valid signature of the vote message, but not by the operator's voter
+        let result = ncn_program_client
+            .do_cast_ed25519_vote(ncn, test_ncn.operators[0].operator_pubkey, &Keypair::new())
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::InvalidEd25519Instruction, Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cast_ed25519_vote_wrong_message_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let test_ncn = setup_ed25519_votes(&mut fixture, &mut ncn_program_client, 2).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let operator_root = &test_ncn.operators[0];
+
+        // Signs the message of the next vote counter value instead of the current one
+        let message = ncn_program_client.vote_signing_payload(ncn, 1).await?;
+        let voter = &operator_root.operator_admin;
+        let signature: [u8; 64] = voter.sign_message(&message).as_ref().try_into().unwrap();
+        let ed25519_ix = new_ed25519_instruction(&voter.pubkey(), &signature, &message);
+
+        let result = ncn_program_client
+            .cast_ed25519_vote(ed25519_ix, ncn, operator_root.operator_pubkey)
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::InvalidEd25519Instruction, Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cast_ed25519_vote_without_ed25519_instruction_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let test_ncn = setup_ed25519_votes(&mut fixture, &mut ncn_program_client, 2).await?;
+
+        // The instruction before the vote is not an ed25519 program instruction
+        let result = ncn_program_client
+            .cast_ed25519_vote(
+                ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+                test_ncn.ncn_root.ncn_pubkey,
+                test_ncn.operators[0].operator_pubkey,
+            )
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::InvalidEd25519Instruction, Some(1));
+
+        Ok(())
+    }
+}
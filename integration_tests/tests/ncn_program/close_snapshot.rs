@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use jito_restaking_core::config::Config;
+    use ncn_program_core::{
+        account_payer::AccountPayer, error::NCNProgramError, snapshot::Snapshot,
+    };
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder, TestResult,
+    };
+
+    // Matches `epochs_after_consensus_before_close` set by `do_initialize_config`
+    const EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE: u64 = 10;
+
+    async fn ncn_epoch_length(fixture: &TestBuilder) -> TestResult<u64> {
+        let restaking_config_address =
+            Config::find_program_address(&jito_restaking_program::id()).0;
+        let restaking_config = fixture
+            .restaking_program_client()
+            .get_config(&restaking_config_address)
+            .await?;
+
+        Ok(restaking_config.epoch_length())
+    }
+
+    #[tokio::test]
+    async fn test_close_snapshot_and_reinitialize() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let epoch_length = ncn_epoch_length(&fixture).await?;
+
+        fixture
+            .warp_slot_incremental(epoch_length * EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE)
+            .await?;
+
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let account_payer = AccountPayer::find_program_address(&ncn_program::id(), &ncn).0;
+
+        let snapshot_lamports = fixture.get_account(&snapshot).await?.unwrap().lamports;
+        let account_payer_lamports = fixture.get_account(&account_payer).await?.unwrap().lamports;
+
+        ncn_program_client.do_close_snapshot(ncn).await?;
+
+        assert!(fixture.get_account(&snapshot).await?.is_none());
+        assert_eq!(
+            fixture.get_account(&account_payer).await?.unwrap().lamports,
+            account_payer_lamports + snapshot_lamports
+        );
+
+        // A fresh snapshot can be created for the next epoch
+        fixture.warp_slot_incremental(epoch_length).await?;
+        ncn_program_client.do_full_initialize_snapshot(ncn).await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), 0);
+        assert_eq!(snapshot.last_snapshot_slot(), 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close_snapshot_before_close_window_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let epoch_length = ncn_epoch_length(&fixture).await?;
+
+        // One epoch short of the close window
+        fixture
+            .warp_slot_incremental(epoch_length * (EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE - 1))
+            .await?;
+
+        let result = ncn_program_client.do_close_snapshot(ncn).await;
+
+        assert_ncn_program_error(
+            result,
+            NCNProgramError::CannotCloseAccountNotEnoughEpochs,
+            None,
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close_snapshot_after_deregistration() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let leaving_operator = &test_ncn.operators[1];
+
+        // The NCN still counts the deregistered operator among its operators
+        ncn_program_client
+            .do_deregister_operator(
+                ncn,
+                leaving_operator.operator_pubkey,
+                &leaving_operator.operator_admin,
+            )
+            .await?;
+
+        let epoch_length = ncn_epoch_length(&fixture).await?;
+        fixture
+            .warp_slot_incremental(epoch_length * EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE)
+            .await?;
+
+        ncn_program_client.do_close_snapshot(ncn).await?;
+
+        let snapshot = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        assert!(fixture.get_account(&snapshot).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close_snapshot_with_unregistered_operators_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let mut test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        // Joins the NCN without registering with the NCN program
+        fixture
+            .add_operators_to_test_ncn(&mut test_ncn, 1, None)
+            .await?;
+
+        let epoch_length = ncn_epoch_length(&fixture).await?;
+        fixture
+            .warp_slot_incremental(epoch_length * EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE)
+            .await?;
+
+        let result = ncn_program_client.do_close_snapshot(ncn).await;
+
+        assert_ncn_program_error(result, NCNProgramError::CannotCloseAccount, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{
+        error::NCNProgramError,
+        g1_point::{G1CompressedPoint, G1Point},
+        g2_point::{G2CompressedPoint, G2Point},
+        ncn_operator_account::NCNOperatorAccount,
+        schemes::Sha256NormalizedScheme,
+        utils::create_signer_bitmap,
+    };
+    use solana_program::instruction::InstructionError;
+    use solana_sdk::signature::Keypair;
+
+    use crate::fixtures::{
+        assert_ix_error, ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder,
+        TestResult,
+    };
+
+    #[tokio::test]
+    async fn test_deregister_operator_shrinks_aggregate_pubkey() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let leaving_operator = &test_ncn.operators[1];
+
+        fixture.warp_slot_incremental(1000).await?;
+        fixture
+            .update_snapshot_test_ncn_new_epoch(&test_ncn)
+            .await?;
+
+        ncn_program_client
+            .do_deregister_operator(
+                ncn,
+                leaving_operator.operator_pubkey,
+                &leaving_operator.operator_admin,
+            )
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), 2);
+        assert!(snapshot
+            .find_operator_snapshot(&leaving_operator.operator_pubkey)
+            .is_none());
+
+        // The aggregate is now the sum of the remaining operators' keys
+        let remaining: G1Point = test_ncn
+            .operators
+            .iter()
+            .filter(|operator| operator.operator_pubkey != leaving_operator.operator_pubkey)
+            .map(|operator| operator.bn128_g1_pubkey)
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            G1CompressedPoint::try_from(remaining).unwrap().0
+        );
+
+        // The ncn operator account is closed so the operator can register again
+        let ncn_operator_account = NCNOperatorAccount::find_program_address(
+            &ncn_program::id(),
+            &ncn,
+            &leaving_operator.operator_pubkey,
+        )
+        .0;
+        assert!(fixture.get_account(&ncn_operator_account).await?.is_none());
+
+        // The remaining operators keep their bits 0 and 2 and can still reach consensus
+        let current_count = ncn_program_client.get_vote_counter(ncn).await?.count();
+        let message = ncn_program_client
+            .vote_signing_payload(ncn, current_count)
+            .await?;
+
+        let mut signatures: Vec<G1Point> = vec![];
+        let mut apk2_pubkeys: Vec<G2Point> = vec![];
+        for operator in [&test_ncn.operators[0], &test_ncn.operators[2]] {
+            apk2_pubkeys.push(operator.bn128_g2_pubkey);
+            signatures.push(
+                operator
+                    .bn128_privkey
+                    .sign_with_scheme(&Sha256NormalizedScheme::for_ncn(&ncn), &message)
+                    .unwrap(),
+            );
+        }
+        let apk2 = apk2_pubkeys.into_iter().reduce(|acc, x| acc + x).unwrap();
+        let agg_sig = signatures.into_iter().reduce(|acc, x| acc + x).unwrap();
+        let signers_bitmap = create_signer_bitmap(&[1], test_ncn.operators.len());
+
+        ncn_program_client
+            .do_cast_vote(
+                ncn,
+                G1CompressedPoint::try_from(agg_sig).unwrap().0,
+                G2CompressedPoint::try_from(&apk2).unwrap().0,
+                signers_bitmap,
+            )
+            .await?;
+
+        let vote_counter = ncn_program_client.get_vote_counter(ncn).await?;
+        assert_eq!(vote_counter.count(), current_count + 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deregister_operator_not_in_snapshot_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let operator = &test_ncn.operators[0];
+
+        // Simulate an ncn operator account whose operator never made it into the snapshot
+        let ncn_operator_account = NCNOperatorAccount::find_program_address(
+            &ncn_program::id(),
+            &ncn,
+            &operator.operator_pubkey,
+        )
+        .0;
+        let ncn_operator_account_data = fixture.get_account(&ncn_operator_account).await?.unwrap();
+
+        ncn_program_client
+            .do_deregister_operator(ncn, operator.operator_pubkey, &operator.operator_admin)
+            .await?;
+        fixture.set_account(&ncn_operator_account, ncn_operator_account_data);
+
+        let result = ncn_program_client
+            .do_deregister_operator(ncn, operator.operator_pubkey, &operator.operator_admin)
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::OperatorIsNotInSnapshot, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_deregister_operator_wrong_admin_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        let result = ncn_program_client
+            .do_deregister_operator(ncn, test_ncn.operators[0].operator_pubkey, &Keypair::new())
+            .await;
+
+        assert_ix_error(result, InstructionError::InvalidAccountData);
+
+        Ok(())
+    }
+}
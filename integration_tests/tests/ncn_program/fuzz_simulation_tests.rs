@@ -6,7 +6,7 @@ mod fuzz_tests {
         constants::MAX_OPERATORS,
         g1_point::{G1CompressedPoint, G1Point},
         g2_point::{G2CompressedPoint, G2Point},
-        schemes::Sha256Normalized,
+        schemes::Sha256NormalizedScheme,
         utils::create_signer_bitmap,
     };
     use solana_sdk::{native_token::sol_to_lamports, signature::Keypair, signer::Signer};
@@ -203,17 +203,17 @@ mod fuzz_tests {
 
         // 5. Cast votes from operators
         {
-            // Get the current vote counter to use as the message
+            // Get the current vote counter to build the message
             let vote_counter = ncn_program_client
                 .get_vote_counter(ncn_pubkey)
                 .await
                 .unwrap();
             let current_count = vote_counter.count();
 
-            // Create message from the current counter value (padded to 32 bytes)
-            let count_bytes = current_count.to_le_bytes();
-            let mut vote_message = [0u8; 32];
-            vote_message[..8].copy_from_slice(&count_bytes);
+            // Create the message operators sign for the current counter value
+            let vote_message = ncn_program_client
+                .vote_signing_payload(ncn_pubkey, current_count)
+                .await?;
 
             // All operators sign the same message (no non-signers in this simulation)
             let mut signatures: Vec<G1Point> = vec![];
@@ -223,7 +223,7 @@ mod fuzz_tests {
                 apk2_pubkeys.push(operator_root.bn128_g2_pubkey);
                 let signature = operator_root
                     .bn128_privkey
-                    .sign::<Sha256Normalized, &[u8; 32]>(&vote_message)
+                    .sign_with_scheme(&Sha256NormalizedScheme::for_ncn(&ncn_pubkey), &vote_message)
                     .unwrap();
                 signatures.push(signature);
             }
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use ncn_program_core::error::NCNProgramError;
+    use jito_restaking_core::config::Config;
+    use ncn_program_core::{
+        account_payer::AccountPayer, error::NCNProgramError, g1_point::CurveId,
+    };
     use solana_program::instruction::InstructionError;
     use solana_sdk::signature::{Keypair, Signer};
 
@@ -20,6 +23,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_initialize_ncn_config_caches_epoch_length() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let mut restaking_client = fixture.restaking_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let restaking_config = restaking_client
+            .get_config(&Config::find_program_address(&jito_restaking_program::id()).0)
+            .await?;
+        let config = ncn_program_client
+            .get_ncn_config(ncn_root.ncn_pubkey)
+            .await?;
+        let epoch_length = restaking_config.epoch_length();
+        assert_eq!(config.epoch_length(), epoch_length);
+
+        let epoch = ncn_program_client.get_ncn_epoch().await?;
+        let slot = fixture.clock().await.slot;
+        assert_eq!(config.current_epoch(slot), Ok(epoch));
+
+        // The epoch only advances on the first slot of the next epoch
+        let next_epoch_start = (epoch + 1) * epoch_length;
+        assert_eq!(config.current_epoch(next_epoch_start - 1), Ok(epoch));
+        assert_eq!(config.current_epoch(next_epoch_start), Ok(epoch + 1));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_initialize_ncn_config_double_init_fails() -> TestResult<()> {
         let mut fixture = TestBuilder::new().await;
@@ -74,6 +108,10 @@ mod tests {
                 &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
                 400,                          // Default fee BPS
                 100,                          // minimum_stake
+                None,                         // curve_id
+                None,                         // minimum_operators
+                None,                         // quorum_bps
+                None,                         // max_snapshot_age_slots
             )
             .await;
         assert_ncn_program_error(result, NCNProgramError::InvalidEpochsBeforeStall, None);
@@ -90,6 +128,10 @@ mod tests {
                 &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
                 400,                          // Default fee BPS
                 100,                          // minimum_stake
+                None,                         // curve_id
+                None,                         // minimum_operators
+                None,                         // quorum_bps
+                None,                         // max_snapshot_age_slots
             )
             .await;
         assert_ncn_program_error(result, NCNProgramError::InvalidEpochsBeforeClose, None);
@@ -106,10 +148,152 @@ mod tests {
                 &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
                 400,                          // Default fee BPS
                 100,                          // minimum_stake
+                None,                         // curve_id
+                None,                         // minimum_operators
+                None,                         // quorum_bps
+                None,                         // max_snapshot_age_slots
             )
             .await;
         assert_ncn_program_error(result, NCNProgramError::InvalidSlotsAfterConsensus, None);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_initialize_ncn_config_defaults_to_bn254() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let config = ncn_program_client
+            .get_ncn_config(ncn_root.ncn_pubkey)
+            .await?;
+        assert_eq!(config.curve(), Ok(CurveId::Bn254));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_initialize_ncn_config_minimum_operators() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        let (account_payer, _, _) =
+            AccountPayer::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey);
+        ncn_program_client.airdrop(&account_payer, 100.0).await?;
+
+        ncn_program_client
+            .initialize_config(
+                ncn_root.ncn_pubkey,
+                &ncn_root.ncn_admin,
+                &ncn_root.ncn_admin.pubkey(),
+                3,
+                10,
+                10000,
+                &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
+                400,                          // Default fee BPS
+                100,                          // minimum_stake
+                None,                         // curve_id
+                Some(3),                      // minimum_operators
+                None,                         // quorum_bps
+                None,                         // max_snapshot_age_slots
+            )
+            .await?;
+
+        let config = ncn_program_client
+            .get_ncn_config(ncn_root.ncn_pubkey)
+            .await?;
+        assert_eq!(config.minimum_operators(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_initialize_ncn_config_quorum_bps() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        let (account_payer, _, _) =
+            AccountPayer::find_program_address(&ncn_program::id(), &ncn_root.ncn_pubkey);
+        ncn_program_client.airdrop(&account_payer, 100.0).await?;
+
+        let result = ncn_program_client
+            .initialize_config(
+                ncn_root.ncn_pubkey,
+                &ncn_root.ncn_admin,
+                &ncn_root.ncn_admin.pubkey(),
+                3,
+                10,
+                10000,
+                &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
+                400,                          // Default fee BPS
+                100,                          // minimum_stake
+                None,                         // curve_id
+                None,                         // minimum_operators
+                Some(10_001),                 // Invalid - above 100%
+                None,                         // max_snapshot_age_slots
+            )
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::InvalidQuorumBps, None);
+
+        ncn_program_client
+            .initialize_config(
+                ncn_root.ncn_pubkey,
+                &ncn_root.ncn_admin,
+                &ncn_root.ncn_admin.pubkey(),
+                3,
+                10,
+                10000,
+                &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
+                400,                          // Default fee BPS
+                100,                          // minimum_stake
+                None,                         // curve_id
+                None,                         // minimum_operators
+                Some(6667),                   // quorum_bps
+                Some(500),                    // max_snapshot_age_slots
+            )
+            .await?;
+
+        let config = ncn_program_client
+            .get_ncn_config(ncn_root.ncn_pubkey)
+            .await?;
+        assert_eq!(config.quorum_bps(), 6667);
+        assert_eq!(config.max_snapshot_age_slots(), 500);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_initialize_ncn_config_unsupported_curve_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        let result = ncn_program_client
+            .initialize_config(
+                ncn_root.ncn_pubkey,
+                &ncn_root.ncn_admin,
+                &ncn_root.ncn_admin.pubkey(),
+                3,
+                10,
+                10000,
+                &ncn_root.ncn_admin.pubkey(), // Use NCN admin as fee wallet
+                400,                          // Default fee BPS
+                100,                          // minimum_stake
+                Some(1),                      // Invalid - only BN254 is supported
+                None,                         // minimum_operators
+                None,                         // quorum_bps
+                None,                         // max_snapshot_age_slots
+            )
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::UnsupportedCurve, None);
+
+        Ok(())
+    }
 }
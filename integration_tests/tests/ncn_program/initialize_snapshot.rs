@@ -1,5 +1,9 @@
 #[cfg(test)]
 mod tests {
+    use ncn_program_core::{
+        account_payer::AccountPayer, constants::MAX_REALLOC_BYTES, snapshot::Snapshot,
+    };
+    use solana_sdk::rent::Rent;
 
     use crate::fixtures::{test_builder::TestBuilder, TestResult};
 
@@ -12,4 +16,55 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_realloc_snapshot_is_idempotent() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        fixture.initialize_restaking_and_vault_programs().await?;
+        let test_ncn = fixture.create_test_ncn().await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        ncn_program_client
+            .setup_ncn_program(&test_ncn.ncn_root)
+            .await?;
+
+        let snapshot_address = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let account_payer = AccountPayer::find_program_address(&ncn_program::id(), &ncn).0;
+
+        // More reallocations than needed to reach the full size in one transaction
+        let num_reallocs = (Snapshot::SIZE as f64 / MAX_REALLOC_BYTES as f64).ceil() as u64 - 1;
+        ncn_program_client.do_initialize_snapshot(ncn).await?;
+        ncn_program_client
+            .do_realloc_snapshot(ncn, num_reallocs + 3)
+            .await?;
+
+        let snapshot_account = fixture.get_account(&snapshot_address).await?.unwrap();
+        assert_eq!(snapshot_account.data.len(), Snapshot::SIZE);
+        assert_eq!(
+            snapshot_account.lamports,
+            Rent::default().minimum_balance(Snapshot::SIZE)
+        );
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), 0);
+        // Matches the `minimum_stake` set by `do_initialize_config`
+        assert_eq!(snapshot.minimum_stake().stake_weight(), 100);
+
+        let account_payer_lamports = fixture.get_account(&account_payer).await?.unwrap().lamports;
+
+        // Once at full size and initialized, further reallocations change nothing
+        ncn_program_client.do_realloc_snapshot(ncn, 2).await?;
+
+        assert_eq!(
+            fixture.get_account(&snapshot_address).await?.unwrap(),
+            snapshot_account
+        );
+        assert_eq!(
+            fixture.get_account(&account_payer).await?.unwrap().lamports,
+            account_payer_lamports
+        );
+
+        Ok(())
+    }
 }
@@ -1,5 +1,10 @@
 mod admin_set_parameters;
+mod admin_update_snapshot_minimum_stake;
+mod batch_initialize_operator_snapshots;
+mod cast_ed25519_vote;
 mod cast_vote;
+mod close_snapshot;
+mod deregister_operator;
 mod fuzz_simulation_tests;
 mod initialize_config;
 mod initialize_operator_snapshot;
@@ -7,10 +12,17 @@ mod initialize_snapshot;
 mod initialize_vault_registry;
 mod initialize_vote_counter;
 mod meta_tests;
+mod prove_non_signer;
+mod refresh_operator_key;
 mod register_operator;
+mod register_st_mint_with_weight;
 mod register_vault;
+mod repair_config;
 mod restaking_variations;
+mod rollover_snapshot;
+mod set_fee_wallet;
 mod set_new_admin;
+mod set_operator_active;
 mod simulation_test;
 mod snapshot_vault_operator_delegation;
 mod update_operator_bn128_keys;
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{
+        config::Config as NcnConfig,
+        error::NCNProgramError,
+        g1_point::{G1CompressedPoint, G1Point},
+        g2_point::{G2CompressedPoint, G2Point},
+        schemes::Sha256NormalizedScheme,
+        utils::create_signer_bitmap,
+    };
+
+    use crate::fixtures::{
+        ncn_program_client::{assert_ncn_program_error, NCNProgramClient},
+        test_builder::{TestBuilder, TestNcn},
+        TestResult,
+    };
+
+    /// Signs `message` with every operator not in `non_signers`, returning the compressed
+    /// aggregated signature and G2 pubkey along with the signer bitmap
+    fn sign_message(
+        test_ncn: &TestNcn,
+        message: &[u8; 32],
+        non_signers: &[usize],
+    ) -> ([u8; 32], [u8; 64], Vec<u8>) {
+        let mut signatures: Vec<G1Point> = vec![];
+        let mut apk2_pubkeys: Vec<G2Point> = vec![];
+        for (i, operator) in test_ncn.operators.iter().enumerate() {
+            if non_signers.contains(&i) {
+                continue;
+            }
+            apk2_pubkeys.push(operator.bn128_g2_pubkey);
+            signatures.push(
+                operator
+                    .bn128_privkey
+                    .sign_with_scheme(
+                        &Sha256NormalizedScheme::for_ncn(&test_ncn.ncn_root.ncn_pubkey),
+                        message,
+                    )
+                    .unwrap(),
+            );
+        }
+
+        let apk2 = apk2_pubkeys.into_iter().reduce(|acc, x| acc + x).unwrap();
+        let agg_sig = signatures.into_iter().reduce(|acc, x| acc + x).unwrap();
+
+        (
+            G1CompressedPoint::try_from(agg_sig).unwrap().0,
+            G2CompressedPoint::try_from(&apk2).unwrap().0,
+            create_signer_bitmap(non_signers, test_ncn.operators.len()),
+        )
+    }
+
+    /// Sets up `test_ncn` with a vote that operator 2 did not sign and returns the message of that
+    /// vote
+    async fn cast_last_vote(
+        fixture: &mut TestBuilder,
+        ncn_program_client: &mut NCNProgramClient,
+        test_ncn: &TestNcn,
+    ) -> TestResult<[u8; 32]> {
+        fixture.warp_slot_incremental(1000).await?;
+        fixture.update_snapshot_test_ncn_new_epoch(test_ncn).await?;
+        fixture.cast_vote_for_test_ncn(test_ncn, vec![2]).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let (epoch, count) = ncn_program_client
+            .get_vote_counter(ncn)
+            .await?
+            .last_vote()
+            .unwrap();
+        Ok(NcnConfig::consensus_message(&ncn, epoch, count))
+    }
+
+    #[tokio::test]
+    async fn test_prove_non_signer_absent_from_bitmap() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let non_signer = test_ncn.operators[2].operator_pubkey;
+
+        let message = cast_last_vote(&mut fixture, &mut ncn_program_client, &test_ncn).await?;
+        let (agg_sig, apk2, signers_bitmap) = sign_message(&test_ncn, &message, &[2]);
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert!(!snapshot
+            .find_operator_snapshot(&non_signer)
+            .unwrap()
+            .missed_last_vote());
+
+        ncn_program_client
+            .do_prove_non_signer(ncn, non_signer, agg_sig, apk2, signers_bitmap)
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert!(snapshot
+            .find_operator_snapshot(&non_signer)
+            .unwrap()
+            .missed_last_vote());
+
+        // The signers are left untouched
+        for operator_root in &test_ncn.operators[..2] {
+            assert!(!snapshot
+                .find_operator_snapshot(&operator_root.operator_pubkey)
+                .unwrap()
+                .missed_last_vote());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prove_non_signer_present_in_bitmap_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let signer = test_ncn.operators[0].operator_pubkey;
+
+        let message = cast_last_vote(&mut fixture, &mut ncn_program_client, &test_ncn).await?;
+        let (agg_sig, apk2, signers_bitmap) = sign_message(&test_ncn, &message, &[2]);
+
+        let result = ncn_program_client
+            .do_prove_non_signer(ncn, signer, agg_sig, apk2, signers_bitmap)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::OperatorSignedMessage, Some(1));
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert!(!snapshot
+            .find_operator_snapshot(&signer)
+            .unwrap()
+            .missed_last_vote());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prove_non_signer_before_first_vote_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let non_signer = test_ncn.operators[2].operator_pubkey;
+
+        // Nothing reached consensus yet, so no message can be proven against
+        let (agg_sig, apk2, signers_bitmap) = sign_message(&test_ncn, &[7u8; 32], &[2]);
+        let result = ncn_program_client
+            .do_prove_non_signer(ncn, non_signer, agg_sig, apk2, signers_bitmap)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::ConsensusNotReached, Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prove_non_signer_invalid_aggregate_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let non_signer = test_ncn.operators[2].operator_pubkey;
+
+        let message = cast_last_vote(&mut fixture, &mut ncn_program_client, &test_ncn).await?;
+
+        // Signed over a message that never reached consensus, the next counter value
+        let (epoch, count) = ncn_program_client
+            .get_vote_counter(ncn)
+            .await?
+            .last_vote()
+            .unwrap();
+        let next_message = NcnConfig::consensus_message(&ncn, epoch, count + 1);
+        let (agg_sig, apk2, signers_bitmap) = sign_message(&test_ncn, &next_message, &[2]);
+        let result = ncn_program_client
+            .do_prove_non_signer(ncn, non_signer, agg_sig, apk2, signers_bitmap)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::VoteMessageMismatch, Some(1));
+
+        // Two non-signers out of three never reached consensus
+        let (agg_sig, apk2, signers_bitmap) = sign_message(&test_ncn, &message, &[1, 2]);
+        let result = ncn_program_client
+            .do_prove_non_signer(ncn, non_signer, agg_sig, apk2, signers_bitmap)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::QuorumNotMet, Some(1));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use jito_bytemuck::AccountDeserialize;
+    use ncn_program_core::{
+        g1_point::{G1CompressedPoint, G1Point},
+        g2_point::G2CompressedPoint,
+        ncn_operator_account::NCNOperatorAccount,
+        privkey::PrivKey,
+    };
+
+    use crate::fixtures::{test_builder::TestBuilder, TestResult};
+
+    #[tokio::test]
+    async fn test_refresh_operator_key_after_rotation() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let rotated_operator = &test_ncn.operators[1];
+
+        let new_private_key = PrivKey::from_random();
+        let new_g1_point = G1Point::try_from(new_private_key).unwrap();
+        let new_g1_compressed = G1CompressedPoint::try_from(new_private_key).unwrap();
+        let new_g2_compressed = G2CompressedPoint::try_from(&new_private_key).unwrap();
+
+        // Rotate the key on the ncn operator account only, leaving the snapshot stale
+        let ncn_operator_account = NCNOperatorAccount::find_program_address(
+            &ncn_program::id(),
+            &ncn,
+            &rotated_operator.operator_pubkey,
+        )
+        .0;
+        let mut account = fixture.get_account(&ncn_operator_account).await?.unwrap();
+        NCNOperatorAccount::try_from_slice_unchecked_mut(&mut account.data)?.update_keys(
+            &new_g1_compressed.0,
+            &new_g2_compressed.0,
+            0,
+        )?;
+        fixture.set_account(&ncn_operator_account, account);
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        let stale_g1_pubkey = snapshot
+            .find_operator_snapshot(&rotated_operator.operator_pubkey)
+            .unwrap()
+            .g1_pubkey();
+        assert_eq!(
+            stale_g1_pubkey,
+            G1CompressedPoint::try_from(rotated_operator.bn128_g1_pubkey)
+                .unwrap()
+                .0
+        );
+
+        ncn_program_client
+            .do_refresh_operator_key(ncn, rotated_operator.operator_pubkey)
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        let operator_snapshot = snapshot
+            .find_operator_snapshot(&rotated_operator.operator_pubkey)
+            .unwrap();
+        assert_eq!(operator_snapshot.g1_pubkey(), new_g1_compressed.0);
+        assert_eq!(operator_snapshot.g2_pubkey(), new_g2_compressed.0);
+
+        // The aggregate now holds the new key in place of the old one
+        let expected_aggregate: G1Point = test_ncn
+            .operators
+            .iter()
+            .map(|operator| {
+                if operator.operator_pubkey == rotated_operator.operator_pubkey {
+                    new_g1_point
+                } else {
+                    operator.bn128_g1_pubkey
+                }
+            })
+            .reduce(|acc, x| acc + x)
+            .unwrap();
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            G1CompressedPoint::try_from(expected_aggregate).unwrap().0
+        );
+
+        Ok(())
+    }
+}
@@ -77,6 +77,126 @@ mod tests {
         assert_eq!(ncn_operator_account.g1_pubkey(), &g1_compressed.0);
         assert_eq!(ncn_operator_account.g2_pubkey(), &g2_compressed.0);
 
+        // The G2 pubkey is stored in the snapshot for on-chain pairing checks
+        let snapshot = ncn_program_client.get_snapshot(ncn_root.ncn_pubkey).await?;
+        let operator_snapshot = snapshot
+            .find_operator_snapshot(&operator_root.operator_pubkey)
+            .unwrap();
+        assert_eq!(operator_snapshot.g2_pubkey(), g2_compressed.0);
+        assert!(operator_snapshot.have_valid_bn128_g2_pubkey());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_operator_index_matches_snapshot_slot() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(4, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+
+        for operator in test_ncn.operators.iter() {
+            let ncn_operator_account = ncn_program_client
+                .get_ncn_operator_account(ncn, operator.operator_pubkey)
+                .await?;
+            let ncn_operator_index = ncn_operator_account.ncn_operator_index();
+
+            // The index on the account resolves to the operator's slot in the snapshot, so
+            // clients can set its signer bitmap bit without scanning the snapshot
+            let operator_snapshot = snapshot.get_operator_snapshot(ncn_operator_index).unwrap();
+            assert_eq!(operator_snapshot.operator(), &operator.operator_pubkey);
+            assert_eq!(operator_snapshot.ncn_operator_index(), ncn_operator_index);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_last_operator_finalizes_snapshot() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        fixture.initialize_restaking_and_vault_programs().await?;
+        let mut test_ncn = fixture.create_test_ncn().await?;
+        ncn_program_client
+            .setup_ncn_program(&test_ncn.ncn_root)
+            .await?;
+        fixture
+            .add_operators_to_test_ncn(&mut test_ncn, 3, None)
+            .await?;
+        fixture
+            .add_vaults_to_test_ncn(&mut test_ncn, 1, None)
+            .await?;
+        fixture.add_delegation_in_test_ncn(&test_ncn, 100).await?;
+        fixture.add_vault_registry_to_test_ncn(&test_ncn).await?;
+        ncn_program_client
+            .do_full_initialize_snapshot(test_ncn.ncn_root.ncn_pubkey)
+            .await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let operator_count = test_ncn.operators.len();
+
+        for (index, operator_root) in test_ncn.operators.iter().enumerate() {
+            fixture.warp_slot_incremental(1).await?;
+            fixture
+                .register_operator_to_test_ncn(&test_ncn, operator_root)
+                .await?;
+            let slot = fixture.clock().await.slot;
+
+            let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+            if index + 1 < operator_count {
+                assert!(!snapshot.finalized());
+                assert_eq!(snapshot.slot_finalized(), u64::MAX);
+            } else {
+                assert!(snapshot.finalized());
+                assert_eq!(snapshot.slot_finalized(), slot);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_operator_snapshot_matches_local_g1_pubkey() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+
+        // What `verify-operator-registration` checks: the snapshotted G1 pubkey is the
+        // operator's local one, at the index its ncn operator account was assigned
+        for (operator, other_operator) in test_ncn
+            .operators
+            .iter()
+            .zip(test_ncn.operators.iter().rev())
+        {
+            let local_g1_pubkey = G1CompressedPoint::try_from(operator.bn128_g1_pubkey)
+                .unwrap()
+                .0;
+            let other_g1_pubkey = G1CompressedPoint::try_from(other_operator.bn128_g1_pubkey)
+                .unwrap()
+                .0;
+            let ncn_operator_account = ncn_program_client
+                .get_ncn_operator_account(ncn, operator.operator_pubkey)
+                .await?;
+
+            let operator_snapshot = snapshot
+                .find_operator_snapshot(&operator.operator_pubkey)
+                .unwrap();
+            assert_eq!(operator_snapshot.g1_pubkey(), local_g1_pubkey);
+            assert_ne!(operator_snapshot.g1_pubkey(), other_g1_pubkey);
+            assert_eq!(
+                operator_snapshot.ncn_operator_index(),
+                ncn_operator_account.ncn_operator_index()
+            );
+        }
+
         Ok(())
     }
 
@@ -130,6 +250,155 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_register_operator_swapped_signatures_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+
+        let mut restaking_program_client = fixture.restaking_program_client();
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        // Setup NCN
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        // Setup two operators and their handshakes
+        let mut operator_roots = Vec::new();
+        for _ in 0..2 {
+            let operator_root = restaking_program_client
+                .do_initialize_operator(Some(200))
+                .await?;
+            restaking_program_client
+                .do_initialize_ncn_operator_state(&ncn_root, &operator_root.operator_pubkey)
+                .await?;
+            operator_roots.push(operator_root);
+        }
+        fixture.warp_slot_incremental(1).await.unwrap();
+        for operator_root in operator_roots.iter() {
+            restaking_program_client
+                .do_ncn_warmup_operator(&ncn_root, &operator_root.operator_pubkey)
+                .await?;
+            restaking_program_client
+                .do_operator_warmup_ncn(operator_root, &ncn_root.ncn_pubkey)
+                .await?;
+        }
+        ncn_program_client
+            .do_full_initialize_snapshot(ncn_root.ncn_pubkey)
+            .await?;
+
+        // Each operator signs its own G1 pubkey
+        let keys: Vec<_> = operator_roots
+            .iter()
+            .map(|operator_root| {
+                let g1_compressed =
+                    G1CompressedPoint::try_from(operator_root.bn128_privkey).unwrap();
+                let g2_compressed =
+                    G2CompressedPoint::try_from(&operator_root.bn128_privkey).unwrap();
+                let signature = operator_root
+                    .bn128_privkey
+                    .sign::<Sha256Normalized, &[u8; 32]>(&g1_compressed.0)
+                    .unwrap();
+                (g1_compressed, g2_compressed, signature)
+            })
+            .collect();
+
+        // Registering each operator with the other one's signature must fail the proof of possession
+        for (index, operator_root) in operator_roots.iter().enumerate() {
+            let (g1_compressed, g2_compressed, _) = keys[index];
+            let (_, _, swapped_signature) = keys[1 - index];
+
+            let result = ncn_program_client
+                .do_register_operator(
+                    ncn_root.ncn_pubkey,
+                    operator_root.operator_pubkey,
+                    &operator_root.operator_admin,
+                    g1_compressed.0,
+                    g2_compressed.0,
+                    swapped_signature.0,
+                )
+                .await;
+
+            assert_ncn_program_error(result, NCNProgramError::InvalidG2Pubkey, None);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_operator_duplicate_g1_pubkey_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+
+        let mut restaking_program_client = fixture.restaking_program_client();
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        // Setup NCN
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        // Setup two operators and their handshakes
+        let mut operator_roots = Vec::new();
+        for _ in 0..2 {
+            let operator_root = restaking_program_client
+                .do_initialize_operator(Some(200))
+                .await?;
+            restaking_program_client
+                .do_initialize_ncn_operator_state(&ncn_root, &operator_root.operator_pubkey)
+                .await?;
+            operator_roots.push(operator_root);
+        }
+        fixture.warp_slot_incremental(1).await.unwrap();
+        for operator_root in operator_roots.iter() {
+            restaking_program_client
+                .do_ncn_warmup_operator(&ncn_root, &operator_root.operator_pubkey)
+                .await?;
+            restaking_program_client
+                .do_operator_warmup_ncn(operator_root, &ncn_root.ncn_pubkey)
+                .await?;
+        }
+        ncn_program_client
+            .do_full_initialize_snapshot(ncn_root.ncn_pubkey)
+            .await?;
+
+        // Both operators present the first operator's keys, with a valid proof of possession
+        let privkey = operator_roots[0].bn128_privkey;
+        let g1_compressed = G1CompressedPoint::try_from(privkey).unwrap();
+        let g2_compressed = G2CompressedPoint::try_from(&privkey).unwrap();
+        let signature = privkey
+            .sign::<Sha256Normalized, &[u8; 32]>(&g1_compressed.0)
+            .unwrap();
+
+        ncn_program_client
+            .do_register_operator(
+                ncn_root.ncn_pubkey,
+                operator_roots[0].operator_pubkey,
+                &operator_roots[0].operator_admin,
+                g1_compressed.0,
+                g2_compressed.0,
+                signature.0,
+            )
+            .await?;
+
+        let result = ncn_program_client
+            .do_register_operator(
+                ncn_root.ncn_pubkey,
+                operator_roots[1].operator_pubkey,
+                &operator_roots[1].operator_admin,
+                g1_compressed.0,
+                g2_compressed.0,
+                signature.0,
+            )
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::DuplicateOperatorG1Pubkey, None);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_register_operator_without_registry_fails() -> TestResult<()> {
         let mut fixture = TestBuilder::new().await;
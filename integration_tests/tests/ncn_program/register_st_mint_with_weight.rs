@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{constants::WEIGHT_PRECISION, error::NCNProgramError};
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder, TestResult,
+    };
+
+    /// Sets up an NCN with one operator and a vault delegating `delegation` to it, registers the
+    /// vault's st_mint with `st_mint_weight` and snapshots the delegation. Returns the weight
+    /// stored in the vault registry and the operator's stake weight.
+    async fn snapshot_with_st_mint_weight(
+        st_mint_weight: u128,
+        delegation: u64,
+    ) -> TestResult<(u128, u128)> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let mut vault_client = fixture.vault_client();
+
+        fixture.initialize_restaking_and_vault_programs().await?;
+        let mut test_ncn = fixture.create_test_ncn().await?;
+        ncn_program_client
+            .setup_ncn_program(&test_ncn.ncn_root)
+            .await?;
+        fixture
+            .add_operators_to_test_ncn(&mut test_ncn, 1, None)
+            .await?;
+        fixture
+            .add_vaults_to_test_ncn(&mut test_ncn, 1, None)
+            .await?;
+        fixture
+            .add_delegation_in_test_ncn(&test_ncn, delegation)
+            .await?;
+        fixture
+            .add_vault_registry_to_test_ncn_with_st_mint_weight(&test_ncn, Some(st_mint_weight))
+            .await?;
+        ncn_program_client
+            .do_full_initialize_snapshot(test_ncn.ncn_root.ncn_pubkey)
+            .await?;
+        fixture.register_operators_to_test_ncn(&test_ncn).await?;
+        fixture
+            .add_vault_operator_delegation_snapshots_to_test_ncn(&test_ncn)
+            .await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let st_mint = vault_client
+            .get_vault(&test_ncn.vaults[0].vault_pubkey)
+            .await?
+            .supported_mint;
+        let registry_weight = ncn_program_client
+            .get_vault_registry(ncn)
+            .await?
+            .get_st_mint_weight(&st_mint)
+            .unwrap();
+        let stake_weight = ncn_program_client
+            .get_operator_snapshot(test_ncn.operators[0].operator_pubkey, ncn)
+            .await?
+            .stake_weight()
+            .stake_weight();
+
+        Ok((registry_weight, stake_weight))
+    }
+
+    #[tokio::test]
+    async fn test_register_st_mint_with_weight_scales_stake_weight() -> TestResult<()> {
+        // Two mints backing the same delegation, one weighted 2x and the other 0.5x
+        let (heavy_registry_weight, heavy_stake_weight) =
+            snapshot_with_st_mint_weight(2 * WEIGHT_PRECISION, 10_000).await?;
+        let (light_registry_weight, light_stake_weight) =
+            snapshot_with_st_mint_weight(WEIGHT_PRECISION / 2, 10_000).await?;
+
+        assert_eq!(heavy_registry_weight, 2 * WEIGHT_PRECISION);
+        assert_eq!(light_registry_weight, WEIGHT_PRECISION / 2);
+
+        assert_eq!(heavy_stake_weight, 20_000);
+        assert_eq!(light_stake_weight, 5_000);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_st_mint_with_zero_weight_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let mut vault_client = fixture.vault_client();
+
+        fixture.initialize_restaking_and_vault_programs().await?;
+        let mut test_ncn = fixture.create_test_ncn().await?;
+        ncn_program_client
+            .setup_ncn_program(&test_ncn.ncn_root)
+            .await?;
+        fixture
+            .add_vaults_to_test_ncn(&mut test_ncn, 1, None)
+            .await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let st_mint = vault_client
+            .get_vault(&test_ncn.vaults[0].vault_pubkey)
+            .await?
+            .supported_mint;
+
+        let result = ncn_program_client
+            .do_admin_register_st_mint_with_weight(ncn, st_mint, 0)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::WeightNotSet, None);
+
+        let vault_registry = ncn_program_client.get_vault_registry(ncn).await?;
+        assert_eq!(vault_registry.st_mint_count(), 0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::{
+        config::{Config as NcnConfig, ConfigAdminRole},
+        error::NCNProgramError,
+    };
+    use solana_program::{instruction::InstructionError, pubkey::Pubkey};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    use crate::fixtures::{
+        assert_ix_error, ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder,
+        TestResult,
+    };
+
+    // Account header (discriminator + padding) followed by the `ncn` and `tie_breaker_admin` keys
+    const CONFIG_ADMIN_FIELDS_END: usize = 8 + 32 + 32;
+
+    #[tokio::test]
+    async fn test_repair_config_after_partial_init() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+        let ncn = ncn_root.ncn_pubkey;
+
+        ncn_program_client
+            .do_initialize_config(ncn, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let tie_breaker_admin = Pubkey::new_unique();
+        ncn_program_client
+            .do_set_new_admin(
+                ConfigAdminRole::TieBreakerAdmin,
+                tie_breaker_admin,
+                &ncn_root,
+            )
+            .await?;
+
+        let epoch_length = ncn_program_client.get_ncn_config(ncn).await?.epoch_length();
+
+        // Simulate an interrupted init: the account exists but only the admin keys were written
+        let config_pda = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let mut config_account = fixture.get_account(&config_pda).await?.unwrap();
+        config_account.data[0] = 0;
+        config_account.data[CONFIG_ADMIN_FIELDS_END..].fill(0);
+        fixture.set_account(&config_pda, config_account);
+
+        let ncn_fee_wallet = Keypair::new().pubkey();
+        ncn_program_client
+            .do_repair_config(ncn, &ncn_root.ncn_admin, &ncn_fee_wallet)
+            .await?;
+
+        let config = ncn_program_client.get_ncn_config(ncn).await?;
+        assert_eq!(config.ncn, ncn);
+        assert_eq!(config.tie_breaker_admin, tie_breaker_admin);
+        assert_eq!(config.epochs_before_stall(), 3);
+        assert_eq!(config.epochs_after_consensus_before_close(), 10);
+        assert_eq!(config.valid_slots_after_consensus(), 10000);
+        assert_eq!(config.minimum_stake().stake_weight(), 100);
+        // The cached epoch length is restored from the restaking config
+        assert_eq!(config.epoch_length(), epoch_length);
+        assert_eq!(
+            config.bump,
+            NcnConfig::find_program_address(&ncn_program::id(), &ncn).1
+        );
+
+        // Repairing again with the same parameters leaves the config untouched
+        let repaired_data = fixture.get_account(&config_pda).await?.unwrap().data;
+
+        fixture.warp_slot_incremental(1).await?;
+        ncn_program_client
+            .do_repair_config(ncn, &ncn_root.ncn_admin, &ncn_fee_wallet)
+            .await?;
+
+        let data_after_second_repair = fixture.get_account(&config_pda).await?.unwrap().data;
+        assert_eq!(repaired_data, data_after_second_repair);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repair_initialized_config_keeps_minimum_stake_and_fees() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+        let ncn = ncn_root.ncn_pubkey;
+
+        ncn_program_client
+            .do_initialize_config(ncn, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let config_pda = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let initialized_data = fixture.get_account(&config_pda).await?.unwrap().data;
+
+        // Repairing a fully initialized config in a later epoch can't change the minimum stake
+        // or the fees
+        let epoch_length = ncn_program_client.get_ncn_config(ncn).await?.epoch_length();
+        fixture.warp_slot_incremental(epoch_length).await?;
+        ncn_program_client
+            .repair_config(
+                ncn,
+                &ncn_root.ncn_admin,
+                3,
+                10,
+                10000,
+                &Keypair::new().pubkey(),
+                200,
+                500,
+            )
+            .await?;
+
+        let repaired_data = fixture.get_account(&config_pda).await?.unwrap().data;
+        assert_eq!(initialized_data, repaired_data);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repair_config_grows_config_without_reserved_tail() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+        let ncn = ncn_root.ncn_pubkey;
+
+        ncn_program_client
+            .do_initialize_config(ncn, &ncn_root.ncn_admin, None)
+            .await?;
+
+        // Simulate a config written before the fields from `minimum_operators` on existed
+        let config_pda = NcnConfig::find_program_address(&ncn_program::id(), &ncn).0;
+        let mut config_account = fixture.get_account(&config_pda).await?.unwrap();
+        let legacy_size = NcnConfig::SIZE - NcnConfig::TAIL_SIZE;
+        config_account.data.truncate(legacy_size);
+        let legacy_data = config_account.data.clone();
+        fixture.set_account(&config_pda, config_account);
+
+        ncn_program_client
+            .do_repair_config(ncn, &ncn_root.ncn_admin, &Keypair::new().pubkey())
+            .await?;
+
+        let config_account = fixture.get_account(&config_pda).await?.unwrap();
+        assert_eq!(config_account.data.len(), NcnConfig::SIZE);
+        assert_eq!(config_account.data[..legacy_size], legacy_data[..]);
+
+        // The fields the legacy config lacked take their defaults
+        let config = ncn_program_client.get_ncn_config(ncn).await?;
+        assert_eq!(config.minimum_operators(), 0);
+        assert!(!config.allow_multiple_votes_per_epoch());
+        assert!(!config.allow_ed25519_votes());
+        assert!(config.epoch_length() > 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repair_config_wrong_admin_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let wrong_admin = Keypair::new();
+        let result = ncn_program_client
+            .do_repair_config(ncn_root.ncn_pubkey, &wrong_admin, &Keypair::new().pubkey())
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::IncorrectNcnAdmin, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repair_config_invalid_parameters_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        let result = ncn_program_client
+            .repair_config(
+                ncn_root.ncn_pubkey,
+                &ncn_root.ncn_admin,
+                0, // Invalid - too low
+                10,
+                10000,
+                &Keypair::new().pubkey(),
+                400,
+                100,
+            )
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::InvalidEpochsBeforeStall, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_repair_config_uninitialized_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        // There is no config account to repair
+        let result = ncn_program_client
+            .do_repair_config(
+                ncn_root.ncn_pubkey,
+                &ncn_root.ncn_admin,
+                &Keypair::new().pubkey(),
+            )
+            .await;
+
+        assert_ix_error(result, InstructionError::InvalidAccountOwner);
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 mod tests {
 
     use jito_restaking_core::MAX_FEE_BPS;
+    use jito_vault_core::vault_operator_delegation::VaultOperatorDelegation;
     use ncn_program_core::error::NCNProgramError;
     use solana_sdk::msg;
 
@@ -153,4 +154,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_stale_vault_operator_delegation() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let mut vault_program_client = fixture.vault_program_client();
+
+        const OPERATOR_COUNT: usize = 1;
+
+        let test_ncn = fixture
+            .create_initial_test_ncn(OPERATOR_COUNT, Some(0))
+            .await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let operator = test_ncn.operators[0].operator_pubkey;
+        let vault = test_ncn.vaults[0].vault_pubkey;
+
+        // Keep the delegation as it is in this epoch
+        let vault_operator_delegation = VaultOperatorDelegation::find_program_address(
+            &jito_vault_program::id(),
+            &vault,
+            &operator,
+        )
+        .0;
+        let stale_delegation = fixture
+            .get_account(&vault_operator_delegation)
+            .await?
+            .unwrap();
+
+        {
+            // Fast forward to a new epoch and update the vault
+            fixture.warp_epoch_incremental(1).await?;
+            vault_program_client
+                .do_full_vault_update(&vault, &[operator])
+                .await?;
+        }
+
+        {
+            // The vault is up to date but its delegation is rolled back to the previous epoch
+            fixture.set_account(&vault_operator_delegation, stale_delegation);
+
+            let result = ncn_program_client
+                .do_snapshot_vault_operator_delegation(vault, operator, ncn)
+                .await;
+
+            assert_ncn_program_error(
+                result,
+                NCNProgramError::VaultOperatorDelegationNotUpdated,
+                None,
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use jito_bytemuck::AccountDeserialize;
+    use jito_restaking_core::config::Config;
+    use ncn_program_core::{
+        error::NCNProgramError, snapshot::Snapshot, stake_weight::StakeWeights,
+    };
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder, TestResult,
+    };
+
+    // Matches the `minimum_stake` set by `do_initialize_config`
+    const MINIMUM_STAKE: u128 = 100;
+
+    #[tokio::test]
+    async fn test_rollover_snapshot_crosses_minimum_stake() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let rising_operator = test_ncn.operators[0].operator_pubkey;
+        let falling_operator = test_ncn.operators[1].operator_pubkey;
+
+        // Give one operator a stake weight rising above the minimum next epoch and the other one
+        // a stake weight falling below it
+        let snapshot_address = Snapshot::find_program_address(&ncn_program::id(), &ncn).0;
+        let mut snapshot_account = fixture.get_account(&snapshot_address).await?.unwrap();
+        {
+            let snapshot = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_account.data)?;
+            for (operator, stake_weight, next_epoch_stake_weight) in [
+                (rising_operator, MINIMUM_STAKE - 1, MINIMUM_STAKE + 1),
+                (falling_operator, MINIMUM_STAKE + 1, MINIMUM_STAKE - 1),
+            ] {
+                snapshot
+                    .find_mut_operator_snapshot(&operator)
+                    .unwrap()
+                    .snapshot_vault_operator_delegation(
+                        1,
+                        &StakeWeights::new(stake_weight),
+                        &StakeWeights::new(next_epoch_stake_weight),
+                        &StakeWeights::new(MINIMUM_STAKE),
+                    )
+                    .unwrap();
+            }
+        }
+        fixture.set_account(&snapshot_address, snapshot_account);
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert!(!snapshot
+            .find_operator_snapshot(&rising_operator)
+            .unwrap()
+            .has_minimum_stake());
+        assert!(snapshot
+            .find_operator_snapshot(&falling_operator)
+            .unwrap()
+            .has_minimum_stake());
+
+        ncn_program_client.do_rollover_snapshot(ncn).await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+
+        let rising_operator_snapshot = snapshot.find_operator_snapshot(&rising_operator).unwrap();
+        assert_eq!(
+            rising_operator_snapshot.stake_weight().stake_weight(),
+            MINIMUM_STAKE + 1
+        );
+        assert!(rising_operator_snapshot.has_minimum_stake());
+
+        let falling_operator_snapshot = snapshot.find_operator_snapshot(&falling_operator).unwrap();
+        assert_eq!(
+            falling_operator_snapshot.stake_weight().stake_weight(),
+            MINIMUM_STAKE - 1
+        );
+        assert!(!falling_operator_snapshot.has_minimum_stake());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rollover_snapshot_twice_in_same_epoch_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        ncn_program_client.do_rollover_snapshot(ncn).await?;
+
+        fixture.warp_slot_incremental(1).await?;
+        let result = ncn_program_client.do_rollover_snapshot(ncn).await;
+        assert_ncn_program_error(result, NCNProgramError::SnapshotAlreadyRolledOver, None);
+
+        // The next epoch can be rolled over again
+        let restaking_config_address =
+            Config::find_program_address(&jito_restaking_program::id()).0;
+        let epoch_length = fixture
+            .restaking_program_client()
+            .get_config(&restaking_config_address)
+            .await?
+            .epoch_length();
+        fixture.warp_slot_incremental(epoch_length).await?;
+
+        ncn_program_client.do_rollover_snapshot(ncn).await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        let slot = fixture.clock().await.slot;
+        assert_eq!(snapshot.last_rollover_epoch(), slot / epoch_length);
+
+        Ok(())
+    }
+}
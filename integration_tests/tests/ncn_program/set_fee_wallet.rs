@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::error::NCNProgramError;
+    use solana_program::{pubkey::Pubkey, system_program};
+    use solana_sdk::signature::Keypair;
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error, restaking_client::NcnRoot,
+        test_builder::TestBuilder, TestResult,
+    };
+
+    #[tokio::test]
+    async fn test_set_fee_wallet_rotates_wallet() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        fixture.warp_slot_incremental(1).await?;
+
+        let epoch = ncn_program_client.get_ncn_epoch().await?;
+        let config = ncn_program_client
+            .get_ncn_config(ncn_root.ncn_pubkey)
+            .await?;
+        let ncn_fee = config
+            .fee_config
+            .calculate_ncn_fee(1_000_000, epoch)
+            .unwrap();
+        let protocol_fee_wallet = *config.fee_config.protocol_fee_wallet();
+
+        let new_wallet = Pubkey::new_unique();
+        ncn_program_client
+            .do_set_fee_wallet(new_wallet, &ncn_root)
+            .await?;
+
+        // The NCN fees now go to the new wallet, the amounts and the protocol wallet are unchanged
+        let config = ncn_program_client
+            .get_ncn_config(ncn_root.ncn_pubkey)
+            .await?;
+        assert_eq!(*config.fee_config.ncn_fee_wallet(), new_wallet);
+        assert_eq!(
+            config
+                .fee_config
+                .calculate_ncn_fee(1_000_000, epoch)
+                .unwrap(),
+            ncn_fee
+        );
+        assert_eq!(
+            *config.fee_config.protocol_fee_wallet(),
+            protocol_fee_wallet
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_fee_wallet_rejects_invalid_wallet() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+        let ncn_root = fixture.setup_ncn().await?;
+
+        ncn_program_client
+            .do_initialize_config(ncn_root.ncn_pubkey, &ncn_root.ncn_admin, None)
+            .await?;
+
+        fixture.warp_slot_incremental(1).await?;
+
+        for invalid_wallet in [Pubkey::default(), system_program::id()] {
+            let result = ncn_program_client
+                .do_set_fee_wallet(invalid_wallet, &ncn_root)
+                .await;
+            assert_ncn_program_error(result, NCNProgramError::DefaultNcnWallet, None);
+            fixture.warp_slot_incremental(1).await?;
+        }
+
+        let wrong_ncn_root = NcnRoot {
+            ncn_pubkey: ncn_root.ncn_pubkey,
+            ncn_admin: Keypair::new(),
+        };
+        let result = ncn_program_client
+            .do_set_fee_wallet(Pubkey::new_unique(), &wrong_ncn_root)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::IncorrectNcnAdmin, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use ncn_program_core::error::NCNProgramError;
+    use solana_sdk::signature::Keypair;
+
+    use crate::fixtures::{
+        ncn_program_client::assert_ncn_program_error, test_builder::TestBuilder, TestResult,
+    };
+
+    #[tokio::test]
+    async fn test_set_operator_active_updates_eligibility() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(3, None).await?;
+        fixture.warp_slot_incremental(1000).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let ncn_admin = &test_ncn.ncn_root.ncn_admin;
+        let vault = test_ncn.vaults[0].vault_pubkey;
+        let paused_operator = test_ncn.operators[1].operator_pubkey;
+
+        for operator_root in test_ncn.operators.iter() {
+            ncn_program_client
+                .do_snapshot_vault_operator_delegation(vault, operator_root.operator_pubkey, ncn)
+                .await?;
+        }
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_can_vote_count(), 3);
+        let total_aggregated_g1_pubkey = snapshot.total_aggregated_g1_pubkey();
+
+        ncn_program_client
+            .do_set_operator_active(ncn, paused_operator, ncn_admin, false)
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_can_vote_count(), 2);
+        assert_eq!(snapshot.operators_registered(), 3);
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            total_aggregated_g1_pubkey
+        );
+        assert!(!snapshot
+            .find_operator_snapshot(&paused_operator)
+            .unwrap()
+            .is_active());
+        assert!(snapshot
+            .get_active_operator_snapshots()
+            .iter()
+            .all(|operator_snapshot| *operator_snapshot.operator() != paused_operator));
+        let eligible_voters = snapshot.get_eligible_voters(0, 0).unwrap();
+        assert_eq!(eligible_voters.len(), 2);
+        assert!(eligible_voters
+            .iter()
+            .all(|operator_snapshot| *operator_snapshot.operator() != paused_operator));
+
+        ncn_program_client
+            .do_set_operator_active(ncn, paused_operator, ncn_admin, true)
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_can_vote_count(), 3);
+        assert_eq!(
+            snapshot.total_aggregated_g1_pubkey(),
+            total_aggregated_g1_pubkey
+        );
+        assert_eq!(snapshot.get_active_operator_snapshots().len(), 3);
+        assert_eq!(snapshot.get_eligible_voters(0, 0).unwrap().len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_set_operator_active_wrong_admin_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(1, None).await?;
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+
+        let result = ncn_program_client
+            .do_set_operator_active(
+                ncn,
+                test_ncn.operators[0].operator_pubkey,
+                &Keypair::new(),
+                false,
+            )
+            .await;
+
+        assert_ncn_program_error(result, NCNProgramError::IncorrectNcnAdmin, None);
+
+        Ok(())
+    }
+}
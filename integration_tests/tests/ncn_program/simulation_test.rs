@@ -5,7 +5,7 @@ mod tests {
         error::NCNProgramError,
         g1_point::{G1CompressedPoint, G1Point},
         g2_point::{G2CompressedPoint, G2Point},
-        schemes::Sha256Normalized,
+        schemes::{Sha256Normalized, Sha256NormalizedScheme},
         utils::create_signer_bitmap,
     };
 
@@ -247,10 +247,10 @@ mod tests {
                     .unwrap();
                 let current_count = vote_counter.count();
 
-                // Create message from the current counter value (padded to 32 bytes)
-                let count_bytes = current_count.to_le_bytes();
-                let mut sunny_vote_message = [0u8; 32];
-                sunny_vote_message[..8].copy_from_slice(&count_bytes);
+                // Create the message operators sign for the current counter value
+                let sunny_vote_message = ncn_program_client
+                    .vote_signing_payload(ncn_pubkey, current_count)
+                    .await?;
 
                 let mut sunny_signatures: Vec<G1Point> = vec![];
                 let mut sunny_apk2_pubkeys: Vec<G2Point> = vec![];
@@ -264,7 +264,10 @@ mod tests {
                         sunny_apk2_pubkeys.push(operator.bn128_g2_pubkey);
                         let signature = operator
                             .bn128_privkey
-                            .sign::<Sha256Normalized, &[u8; 32]>(&sunny_vote_message)
+                            .sign_with_scheme(
+                                &Sha256NormalizedScheme::for_ncn(&ncn_pubkey),
+                                &sunny_vote_message,
+                            )
                             .unwrap();
                         sunny_signatures.push(signature);
                     } else {
@@ -308,10 +311,10 @@ mod tests {
                     .unwrap();
                 let current_count = vote_counter.count();
 
-                // Create message from the current counter value (padded to 32 bytes)
-                let count_bytes = current_count.to_le_bytes();
-                let mut cloudy_vote_message = [0u8; 32];
-                cloudy_vote_message[..8].copy_from_slice(&count_bytes);
+                // Create the message operators sign for the current counter value
+                let cloudy_vote_message = ncn_program_client
+                    .vote_signing_payload(ncn_pubkey, current_count)
+                    .await?;
 
                 let mut signatures: Vec<G1Point> = vec![];
                 let mut apk2_pubkeys: Vec<G2Point> = vec![];
@@ -325,7 +328,10 @@ mod tests {
                         apk2_pubkeys.push(operator.bn128_g2_pubkey);
                         let signature = operator
                             .bn128_privkey
-                            .sign::<Sha256Normalized, &[u8; 32]>(&cloudy_vote_message)
+                            .sign_with_scheme(
+                                &Sha256NormalizedScheme::for_ncn(&ncn_pubkey),
+                                &cloudy_vote_message,
+                            )
                             .unwrap();
                         signatures.push(signature);
                     } else {
@@ -2,13 +2,35 @@
 mod tests {
 
     use ncn_program_core::{
+        constants::{MAX_SNAPSHOT_BATCH_OPERATORS, SNAPSHOT_BATCH_MIN_REMAINING_COMPUTE_UNITS},
+        error::NCNProgramError,
         g1_point::{G1CompressedPoint, G1Point},
         g2_point::G2CompressedPoint,
         schemes::Sha256Normalized,
+        snapshot::Snapshot,
     };
+    use solana_program::pubkey::Pubkey;
     use solana_sdk::msg;
 
-    use crate::fixtures::{test_builder::TestBuilder, TestResult};
+    use crate::fixtures::{
+        ncn_program_client::{assert_ncn_program_error, NCNProgramClient},
+        test_builder::TestBuilder,
+        TestResult,
+    };
+
+    /// The registered operators not snapshotted since `slot`, in snapshot index order
+    fn pending_operators(snapshot: &Snapshot, slot: u64) -> Vec<(u64, Pubkey)> {
+        snapshot
+            .registered_operator_snapshots()
+            .filter(|(_, operator_snapshot)| operator_snapshot.last_snapshot_slot() < slot)
+            .map(|(_, operator_snapshot)| {
+                (
+                    operator_snapshot.ncn_operator_index(),
+                    *operator_snapshot.operator(),
+                )
+            })
+            .collect()
+    }
 
     #[tokio::test]
     async fn test_snapshot_vault_operator_delegation() -> TestResult<()> {
@@ -34,6 +56,90 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_snapshot_vault_operator_delegation_twice_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(1, None).await?;
+
+        fixture.warp_slot_incremental(1000).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let vault = test_ncn.vaults[0].vault_pubkey;
+        let operator = test_ncn.operators[0].operator_pubkey;
+
+        ncn_program_client
+            .do_snapshot_vault_operator_delegation(vault, operator, ncn)
+            .await?;
+        let operator_snapshot = ncn_program_client
+            .get_operator_snapshot(operator, ncn)
+            .await?;
+        assert!(operator_snapshot.contains_vault(&vault));
+
+        fixture.warp_slot_incremental(1).await?;
+
+        // The same delegation is not counted twice in the epoch
+        let result = ncn_program_client
+            .do_snapshot_vault_operator_delegation(vault, operator, ncn)
+            .await;
+        assert_ncn_program_error(
+            result,
+            NCNProgramError::DuplicateVaultOperatorDelegation,
+            None,
+        );
+
+        let operator_snapshot_after = ncn_program_client
+            .get_operator_snapshot(operator, ncn)
+            .await?;
+        assert_eq!(
+            operator_snapshot_after.stake_weight().stake_weight(),
+            operator_snapshot.stake_weight().stake_weight()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_vault_operator_delegation_unregistered_vault_fails() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let mut test_ncn = fixture.create_initial_test_ncn(1, None).await?;
+
+        // The vault registry is already full, the new vault is only connected to the NCN
+        fixture
+            .add_vaults_to_test_ncn(&mut test_ncn, 1, None)
+            .await?;
+
+        fixture.warp_slot_incremental(1000).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let unregistered_vault = test_ncn.vaults[1].vault_pubkey;
+        let operator = test_ncn.operators[0].operator_pubkey;
+
+        let vault_registry = ncn_program_client.get_vault_registry(ncn).await?;
+        assert!(!vault_registry.has_vault(&unregistered_vault));
+
+        let result = ncn_program_client
+            .do_snapshot_vault_operator_delegation(unregistered_vault, operator, ncn)
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::VaultNotInRegistry, None);
+
+        let result = ncn_program_client
+            .do_snapshot_vault_operator_delegation_batch(
+                unregistered_vault,
+                ncn,
+                &[(0, operator)],
+                1_400_000,
+            )
+            .await;
+        assert_ncn_program_error(result, NCNProgramError::VaultNotInRegistry, None);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_snapshot_aggregates_the_right_g1_pubkey() -> TestResult<()> {
         let mut fixture = TestBuilder::new().await;
@@ -219,9 +325,6 @@ mod tests {
         {
             // Warp to new epoch
             fixture.warp_epoch_incremental(1).await?;
-            fixture
-                .update_snapshot_test_ncn_new_epoch(&test_ncn)
-                .await?;
 
             let current_epoch = fixture.clock().await.epoch;
             println!("=== Testing Epoch: {} ===", current_epoch);
@@ -236,6 +339,11 @@ mod tests {
             // Record the current slot before snapshot
             let current_slot = fixture.clock().await.slot;
 
+            // The vault's delegation is snapshotted only once in this epoch, after adding more
+            fixture
+                .vault_client()
+                .do_full_vault_update(&vault_address, &[operator])
+                .await?;
             fixture.add_delegation_in_test_ncn(&test_ncn, 1000).await?;
             fixture
                 .add_vault_operator_delegation_snapshots_to_test_ncn(&test_ncn)
@@ -398,7 +506,15 @@ mod tests {
         }
 
         {
-            // adding more delegation should takes the operator to over the minimum stake weight
+            // adding more delegation should takes the operator to over the minimum stake weight,
+            // in a new epoch since each vault is snapshotted once per epoch
+            fixture.warp_epoch_incremental(1).await?;
+            vault_client
+                .do_full_vault_update(
+                    &test_ncn.vaults[0].vault_pubkey,
+                    &[test_ncn.operators[0].operator_pubkey],
+                )
+                .await?;
             fixture
                 .add_delegation_in_test_ncn(&test_ncn, MINIMUM_STAKE_WEIGHT as u64)
                 .await?;
@@ -426,6 +542,13 @@ mod tests {
 
         {
             // cooling down some of the delegation should only take effect in the next epoch
+            fixture.warp_epoch_incremental(1).await?;
+            vault_client
+                .do_full_vault_update(
+                    &test_ncn.vaults[0].vault_pubkey,
+                    &[test_ncn.operators[0].operator_pubkey],
+                )
+                .await?;
             vault_client
                 .do_cooldown_delegation(
                     &test_ncn.vaults[0],
@@ -506,4 +629,118 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_snapshot_vault_operator_delegation_batch_resumes() -> TestResult<()> {
+        let mut fixture = TestBuilder::new().await;
+
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        // More operators than fit in one transaction
+        const OPERATORS: usize = MAX_SNAPSHOT_BATCH_OPERATORS + 4;
+
+        let test_ncn = fixture.create_initial_test_ncn(OPERATORS, None).await?;
+
+        fixture.warp_slot_incremental(1000).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let vault_address = test_ncn.vaults[0].vault_pubkey;
+        let current_slot = fixture.clock().await.slot;
+
+        // Crank in batches, resuming from the first operator not snapshotted yet
+        let mut transactions = 0;
+        loop {
+            let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+            let pending = pending_operators(&snapshot, current_slot);
+            if pending.is_empty() {
+                break;
+            }
+            assert!(
+                transactions < OPERATORS,
+                "The crank stopped making progress"
+            );
+
+            let batch = &pending[..pending.len().min(MAX_SNAPSHOT_BATCH_OPERATORS)];
+            ncn_program_client
+                .do_snapshot_vault_operator_delegation_batch(vault_address, ncn, batch, 1_400_000)
+                .await?;
+            transactions += 1;
+        }
+
+        assert_eq!(transactions, 2);
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(snapshot.operators_registered(), OPERATORS as u64);
+
+        for operator_root in test_ncn.operators.iter() {
+            let operator_snapshot = snapshot
+                .find_operator_snapshot(&operator_root.operator_pubkey)
+                .unwrap();
+            assert!(operator_snapshot.last_snapshot_slot() >= current_slot);
+            assert!(operator_snapshot.stake_weight().stake_weight() > 0);
+            assert!(operator_snapshot.has_minimum_stake() && operator_snapshot.is_active());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_vault_operator_delegation_batch_stops_at_compute_limit() -> TestResult<()>
+    {
+        let mut fixture = TestBuilder::new().await;
+
+        let mut ncn_program_client = fixture.ncn_program_client();
+
+        let test_ncn = fixture.create_initial_test_ncn(2, None).await?;
+
+        fixture.warp_slot_incremental(1000).await?;
+
+        let ncn = test_ncn.ncn_root.ncn_pubkey;
+        let vault_address = test_ncn.vaults[0].vault_pubkey;
+        let current_slot = fixture.clock().await.slot;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        let pending = pending_operators(&snapshot, current_slot);
+        assert_eq!(pending.len(), 2);
+
+        // Enough compute for one operator, but below the reserve before the second one
+        let single_operator_units = ncn_program_client
+            .simulate_compute_units(
+                NCNProgramClient::snapshot_vault_operator_delegation_batch_instruction(
+                    vault_address,
+                    ncn,
+                    &pending[..1],
+                ),
+            )
+            .await?;
+        let compute_unit_limit =
+            single_operator_units + SNAPSHOT_BATCH_MIN_REMAINING_COMPUTE_UNITS / 2;
+
+        ncn_program_client
+            .do_snapshot_vault_operator_delegation_batch(
+                vault_address,
+                ncn,
+                &pending,
+                compute_unit_limit as u32,
+            )
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert_eq!(pending_operators(&snapshot, current_slot), pending[1..]);
+
+        // The next batch resumes with the operator left over
+        ncn_program_client
+            .do_snapshot_vault_operator_delegation_batch(
+                vault_address,
+                ncn,
+                &pending[1..],
+                1_400_000,
+            )
+            .await?;
+
+        let snapshot = ncn_program_client.get_snapshot(ncn).await?;
+        assert!(pending_operators(&snapshot, current_slot).is_empty());
+
+        Ok(())
+    }
 }
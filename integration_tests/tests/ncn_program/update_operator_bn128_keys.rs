@@ -89,6 +89,7 @@ mod tests {
         // Verify G1 key in snapshot was updated
         assert_eq!(updated_operator_snapshot.g1_pubkey(), new_g1_compressed.0);
         assert_ne!(updated_operator_snapshot.g1_pubkey(), initial_g1_compressed);
+        assert_eq!(updated_operator_snapshot.g2_pubkey(), new_g2_compressed.0);
 
         // Verify other snapshot fields remain unchanged
         assert_eq!(
@@ -342,10 +343,10 @@ mod tests {
             .await;
 
         assert!(result.is_err());
-        // Should fail due to BLS verification error (signature invalid)
+        // Should fail the proof of possession (signature invalid)
         crate::fixtures::ncn_program_client::assert_ncn_program_error(
             result,
-            NCNProgramError::BLSVerificationError,
+            NCNProgramError::InvalidG2Pubkey,
             Some(0),
         );
 
@@ -1,6 +1,6 @@
-use jito_bytemuck::{AccountDeserialize, Discriminator};
+use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
 use jito_jsm_core::loader::{load_signer, load_system_account, load_system_program};
-use jito_restaking_core::ncn::Ncn;
+use jito_restaking_core::{config::Config as RestakingConfig, ncn::Ncn};
 use ncn_program_core::{
     account_payer::AccountPayer,
     config::Config,
@@ -11,6 +11,7 @@ use ncn_program_core::{
     },
     error::NCNProgramError,
     fees::FeeConfig,
+    g1_point::CurveId,
     stake_weight::StakeWeights,
 };
 use solana_program::{
@@ -24,6 +25,10 @@ use solana_program::{
 /// - `epochs_before_stall`: Number of epochs before voting is considered stalled
 /// - `epochs_after_consensus_before_close`: Number of epochs after consensus before accounts can be closed
 /// - `valid_slots_after_consensus`: Number of slots after consensus where voting is still valid
+/// - `curve_id`: Curve the operators' BLS keys live on, defaults to BN254 when omitted
+/// - `minimum_operators`: Operators with the minimum stake required before votes are accepted, defaults to 0 which disables the guard
+/// - `quorum_bps`: Minimum share, in bps, of the eligible stake weight the signers of a cast vote must hold, at most 10000, defaults to 0 which disables the check
+/// - `max_snapshot_age_slots`: Maximum slots between the last snapshot update and a cast vote, defaults to 0 which disables the check
 ///
 /// ### Accounts:
 /// 1. `[writable]` config: The config account PDA to initialize `[seeds = [b"config", ncn.key().as_ref()], bump]`
@@ -32,6 +37,7 @@ use solana_program::{
 /// 4. `[]` tie_breaker_admin: Pubkey of the admin authorized to break voting ties
 /// 5. `[writable, signer]` account_payer: Account paying for the initialization and rent
 /// 6. `[]` system_program: Solana System Program
+/// 7. `[]` restaking_config: Restaking configuration account, whose epoch length is cached in the config
 #[allow(clippy::too_many_arguments)]
 pub fn process_admin_initialize_config(
     program_id: &Pubkey,
@@ -41,8 +47,12 @@ pub fn process_admin_initialize_config(
     valid_slots_after_consensus: u64,
     minimum_stake: u128,
     ncn_fee_bps: u16,
+    curve_id: Option<u8>,
+    minimum_operators: Option<u64>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
 ) -> ProgramResult {
-    let [config, ncn, ncn_fee_wallet, ncn_admin, tie_breaker_admin, account_payer, system_program] =
+    let [config, ncn, ncn_fee_wallet, ncn_admin, tie_breaker_admin, account_payer, system_program, restaking_config] =
         accounts
     else {
         msg!("Error: Not enough account keys provided");
@@ -53,56 +63,31 @@ pub fn process_admin_initialize_config(
     load_system_program(system_program)?;
     load_signer(ncn_admin, false)?;
     Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    RestakingConfig::load(&jito_restaking_program::id(), restaking_config, false)?;
     AccountPayer::load(program_id, account_payer, ncn.key, true)?;
 
     let epoch = Clock::get()?.epoch;
     msg!("Current epoch: {}", epoch);
 
-    msg!(
-        "Validating epochs_before_stall: {} (min: {}, max: {})",
+    validate_config_parameters(
         epochs_before_stall,
-        MIN_EPOCHS_BEFORE_STALL,
-        MAX_EPOCHS_BEFORE_STALL
-    );
-    if !(MIN_EPOCHS_BEFORE_STALL..=MAX_EPOCHS_BEFORE_STALL).contains(&epochs_before_stall) {
-        msg!(
-            "Error: Invalid epochs_before_stall value: {}",
-            epochs_before_stall
-        );
-        return Err(NCNProgramError::InvalidEpochsBeforeStall.into());
-    }
-
-    msg!(
-        "Validating epochs_after_consensus_before_close: {} (min: {}, max: {})",
         epochs_after_consensus_before_close,
-        MIN_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE,
-        MAX_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE
-    );
-    if !(MIN_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE..=MAX_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE)
-        .contains(&epochs_after_consensus_before_close)
-    {
-        msg!(
-            "Error: Invalid epochs_after_consensus_before_close value: {}",
-            epochs_after_consensus_before_close
-        );
-        return Err(NCNProgramError::InvalidEpochsBeforeClose.into());
-    }
-
-    msg!(
-        "Validating valid_slots_after_consensus: {} (min: {}, max: {})",
         valid_slots_after_consensus,
-        MIN_VALID_SLOTS_AFTER_CONSENSUS,
-        MAX_VALID_SLOTS_AFTER_CONSENSUS
-    );
-    if !(MIN_VALID_SLOTS_AFTER_CONSENSUS..=MAX_VALID_SLOTS_AFTER_CONSENSUS)
-        .contains(&valid_slots_after_consensus)
-    {
-        msg!(
-            "Error: Invalid valid_slots_after_consensus value: {}",
-            valid_slots_after_consensus
-        );
-        return Err(NCNProgramError::InvalidSlotsAfterConsensus.into());
-    }
+    )?;
+
+    let curve = match curve_id {
+        Some(curve_id) => CurveId::try_from(curve_id).map_err(|e| {
+            msg!("Error: Unsupported curve id: {}", curve_id);
+            e
+        })?,
+        None => CurveId::default(),
+    };
+
+    let epoch_length = {
+        let restaking_config_data = restaking_config.data.borrow();
+        let restaking_config = RestakingConfig::try_from_slice_unchecked(&restaking_config_data)?;
+        restaking_config.epoch_length()
+    };
 
     let ncn_data = ncn.data.borrow();
     let ncn_account = Ncn::try_from_slice_unchecked(&ncn_data)?;
@@ -171,6 +156,71 @@ pub fn process_admin_initialize_config(
         &minimum_stake,
         config_bump,
     );
+    config.curve_id = curve as u8;
+    config.minimum_operators = PodU64::from(minimum_operators.unwrap_or(0));
+    config.epoch_length = PodU64::from(epoch_length);
+    config.max_snapshot_age_slots = PodU64::from(max_snapshot_age_slots.unwrap_or(0));
+    config
+        .set_quorum_bps(quorum_bps.unwrap_or(0))
+        .map_err(|e| {
+            msg!("Error: Invalid quorum_bps value");
+            e
+        })?;
     config.fee_config.check_fees_okay(epoch)?;
     Ok(())
 }
+
+/// Checks the consensus timing parameters against their allowed ranges
+pub fn validate_config_parameters(
+    epochs_before_stall: u64,
+    epochs_after_consensus_before_close: u64,
+    valid_slots_after_consensus: u64,
+) -> ProgramResult {
+    msg!(
+        "Validating epochs_before_stall: {} (min: {}, max: {})",
+        epochs_before_stall,
+        MIN_EPOCHS_BEFORE_STALL,
+        MAX_EPOCHS_BEFORE_STALL
+    );
+    if !(MIN_EPOCHS_BEFORE_STALL..=MAX_EPOCHS_BEFORE_STALL).contains(&epochs_before_stall) {
+        msg!(
+            "Error: Invalid epochs_before_stall value: {}",
+            epochs_before_stall
+        );
+        return Err(NCNProgramError::InvalidEpochsBeforeStall.into());
+    }
+
+    msg!(
+        "Validating epochs_after_consensus_before_close: {} (min: {}, max: {})",
+        epochs_after_consensus_before_close,
+        MIN_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE,
+        MAX_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE
+    );
+    if !(MIN_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE..=MAX_EPOCHS_AFTER_CONSENSUS_BEFORE_CLOSE)
+        .contains(&epochs_after_consensus_before_close)
+    {
+        msg!(
+            "Error: Invalid epochs_after_consensus_before_close value: {}",
+            epochs_after_consensus_before_close
+        );
+        return Err(NCNProgramError::InvalidEpochsBeforeClose.into());
+    }
+
+    msg!(
+        "Validating valid_slots_after_consensus: {} (min: {}, max: {})",
+        valid_slots_after_consensus,
+        MIN_VALID_SLOTS_AFTER_CONSENSUS,
+        MAX_VALID_SLOTS_AFTER_CONSENSUS
+    );
+    if !(MIN_VALID_SLOTS_AFTER_CONSENSUS..=MAX_VALID_SLOTS_AFTER_CONSENSUS)
+        .contains(&valid_slots_after_consensus)
+    {
+        msg!(
+            "Error: Invalid valid_slots_after_consensus value: {}",
+            valid_slots_after_consensus
+        );
+        return Err(NCNProgramError::InvalidSlotsAfterConsensus.into());
+    }
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use jito_bytemuck::AccountDeserialize;
 use jito_jsm_core::loader::{load_signer, load_token_mint};
 use jito_restaking_core::ncn::Ncn;
-use ncn_program_core::{config::Config, vault_registry::VaultRegistry};
+use ncn_program_core::{config::Config, utils::log_admin_change, vault_registry::VaultRegistry};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
     pubkey::Pubkey,
@@ -9,6 +9,12 @@ use solana_program::{
 
 /// Registers a new staked token mint in the vault registry.
 ///
+/// The stake in the mint is scaled by `weight` when snapshotting delegations; a weight of
+/// `WEIGHT_PRECISION` counts it 1:1. `AdminRegisterStMint` registers with that default weight.
+///
+/// ### Parameters:
+/// - `weight`: The weight of the mint, must be nonzero
+///
 /// ### Accounts:
 /// 1. `[]` config: NCN configuration account
 /// 2. `[writable]` vault_registry: The vault registry to update
@@ -18,6 +24,7 @@ use solana_program::{
 pub fn process_admin_register_st_mint(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    weight: u128,
 ) -> ProgramResult {
     let [config, ncn, st_mint, vault_registry, admin] = accounts else {
         msg!("Error: Not enough account keys provided");
@@ -50,7 +57,17 @@ pub fn process_admin_register_st_mint(
     let vault_registry_account =
         VaultRegistry::try_from_slice_unchecked_mut(&mut vault_registry_data)?;
 
-    vault_registry_account.register_st_mint(st_mint.key)?;
+    let st_mint_count = vault_registry_account.st_mint_count();
+    vault_registry_account.register_st_mint(st_mint.key, weight)?;
+
+    msg!("Registered st mint {} with weight {}", st_mint.key, weight);
+    log_admin_change(
+        "AdminRegisterStMint",
+        admin.key,
+        "st_mint_count",
+        st_mint_count,
+        vault_registry_account.st_mint_count(),
+    );
 
     Ok(())
 }
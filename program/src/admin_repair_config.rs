@@ -0,0 +1,179 @@
+use jito_bytemuck::{types::PodU64, AccountDeserialize, Discriminator};
+use jito_jsm_core::loader::{load_signer, load_system_program};
+use jito_restaking_core::{config::Config as RestakingConfig, ncn::Ncn};
+use ncn_program_core::{
+    account_payer::AccountPayer, config::Config, error::NCNProgramError, fees::FeeConfig,
+    loaders::check_load, stake_weight::StakeWeights,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+use crate::admin_initialize_config::validate_config_parameters;
+
+/// Re-validates and re-writes the config account, recovering a config left partially written by
+/// an interrupted `InitializeConfig`. Requires NCN admin signature.
+///
+/// The instruction is idempotent: repairing with the same parameters always yields the same
+/// config. Admin roles, the signer order flag, the curve id, the minimum operators guard and the
+/// duplicate aggregate flag are preserved. When the config was already initialized, its starting
+/// valid epoch, minimum stake and fee config are kept too, so `minimum_stake` and `ncn_fee_bps`
+/// only apply to a config that never finished initializing and the `AdminSetParameters` guards
+/// can't be bypassed. The cached epoch length is re-read from the restaking config.
+///
+/// A config written with an earlier, shorter layout is grown to `Config::SIZE` with rent from the
+/// account payer, and the fields it lacked take the defaults of `Config::new`.
+///
+/// ### Parameters:
+/// - `epochs_before_stall`: Number of epochs before voting is considered stalled
+/// - `epochs_after_consensus_before_close`: Number of epochs after consensus before accounts can be closed
+/// - `valid_slots_after_consensus`: Number of slots after consensus where voting is still valid
+/// - `minimum_stake`: Minimum stake weight required to vote
+/// - `ncn_fee_bps`: NCN fee basis points
+///
+/// ### Accounts:
+/// 1. `[writable]` config: The existing config account PDA `[seeds = [b"config", ncn.key().as_ref()], bump]`
+/// 2. `[]` ncn: The NCN account this config belongs to
+/// 3. `[]` ncn_fee_wallet: Wallet receiving the NCN fees
+/// 4. `[signer]` ncn_admin: Admin authority for the NCN
+/// 5. `[]` restaking_config: Restaking configuration account, whose epoch length is cached in the config
+/// 6. `[writable]` account_payer: Account paying for the reallocation of a shorter config
+/// 7. `[]` system_program: Solana System Program
+pub fn process_admin_repair_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    epochs_before_stall: u64,
+    epochs_after_consensus_before_close: u64,
+    valid_slots_after_consensus: u64,
+    minimum_stake: u128,
+    ncn_fee_bps: u16,
+) -> ProgramResult {
+    let [config, ncn, ncn_fee_wallet, ncn_admin, restaking_config, account_payer, system_program] =
+        accounts
+    else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    load_signer(ncn_admin, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    RestakingConfig::load(&jito_restaking_program::id(), restaking_config, false)?;
+    AccountPayer::load(program_id, account_payer, ncn.key, true)?;
+    load_system_program(system_program)?;
+
+    // The discriminator may not have been written yet, so it is checked separately below
+    let (config_pda, config_bump, _) = Config::find_program_address(program_id, ncn.key);
+    check_load(program_id, config, &config_pda, None, true)?;
+
+    let previous_size = config.data_len();
+    if previous_size == 0 || previous_size > Config::SIZE {
+        msg!(
+            "Error: Invalid config size. Expected at most: {}, got: {}",
+            Config::SIZE,
+            previous_size
+        );
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let discriminator = config.data.borrow()[0];
+    if discriminator != 0 && discriminator != Config::DISCRIMINATOR {
+        msg!("Error: Invalid config discriminator: {}", discriminator);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let was_initialized = discriminator == Config::DISCRIMINATOR;
+    let was_full_size = previous_size == Config::SIZE;
+
+    let epoch = Clock::get()?.epoch;
+    msg!("Current epoch: {}", epoch);
+
+    validate_config_parameters(
+        epochs_before_stall,
+        epochs_after_consensus_before_close,
+        valid_slots_after_consensus,
+    )?;
+
+    let epoch_length = {
+        let restaking_config_data = restaking_config.data.borrow();
+        let restaking_config = RestakingConfig::try_from_slice_unchecked(&restaking_config_data)?;
+        restaking_config.epoch_length()
+    };
+
+    let ncn_data = ncn.data.borrow();
+    let ncn_account = Ncn::try_from_slice_unchecked(&ncn_data)?;
+    if ncn_account.admin != *ncn_admin.key {
+        msg!(
+            "Error: Incorrect NCN admin. Expected: {}, got: {}",
+            ncn_account.admin,
+            ncn_admin.key
+        );
+        return Err(NCNProgramError::IncorrectNcnAdmin.into());
+    }
+
+    if !was_full_size {
+        msg!(
+            "Growing config from {} to {} bytes",
+            previous_size,
+            Config::SIZE
+        );
+        AccountPayer::pay_and_realloc(program_id, ncn.key, account_payer, config, Config::SIZE)?;
+        config.try_borrow_mut_data()?[previous_size..].fill(0);
+    }
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    config_data[0] = Config::DISCRIMINATOR;
+    let config = Config::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    let tie_breaker_admin = config.tie_breaker_admin;
+    let enforce_signer_order = config.enforce_signer_order;
+    let curve = config.curve()?;
+    let minimum_operators = config.minimum_operators;
+    let reject_duplicate_aggregates = config.reject_duplicate_aggregates;
+    let allow_multiple_votes_per_epoch = config.allow_multiple_votes_per_epoch;
+    let quorum_bps = config.quorum_bps;
+    let max_snapshot_age_slots = config.max_snapshot_age_slots;
+    let allow_ed25519_votes = config.allow_ed25519_votes;
+    let (starting_valid_epoch, fee_config, minimum_stake) = if was_initialized {
+        (
+            config.starting_valid_epoch(),
+            config.fee_config,
+            *config.minimum_stake(),
+        )
+    } else {
+        (
+            epoch,
+            FeeConfig::new(ncn_fee_wallet.key, ncn_fee_bps, epoch)?,
+            StakeWeights::new(minimum_stake),
+        )
+    };
+
+    msg!(
+        "Repairing config (previously initialized: {}), keeping tie_breaker_admin: {}",
+        was_initialized,
+        tie_breaker_admin
+    );
+
+    *config = Config::new(
+        ncn.key,
+        &tie_breaker_admin,
+        starting_valid_epoch,
+        valid_slots_after_consensus,
+        epochs_before_stall,
+        epochs_after_consensus_before_close,
+        &fee_config,
+        &minimum_stake,
+        config_bump,
+    );
+    config.enforce_signer_order = enforce_signer_order;
+    config.curve_id = curve as u8;
+    config.minimum_operators = minimum_operators;
+    config.reject_duplicate_aggregates = reject_duplicate_aggregates;
+    config.allow_multiple_votes_per_epoch = allow_multiple_votes_per_epoch;
+    config.epoch_length = PodU64::from(epoch_length);
+    config.quorum_bps = quorum_bps;
+    config.max_snapshot_age_slots = max_snapshot_age_slots;
+    config.allow_ed25519_votes = allow_ed25519_votes;
+    config.fee_config.check_fees_okay(epoch)?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use ncn_program_core::{
+    config::Config as NcnConfig, error::NCNProgramError, utils::log_admin_change,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+/// Rotates the wallet receiving the NCN fees, e.g. after its keys were compromised.
+/// Takes effect immediately and leaves the fee percentages untouched.
+///
+/// The system program, which is also the default pubkey, is rejected as the new wallet.
+///
+/// ### Parameters:
+/// - `new_wallet`: The new NCN fee wallet
+///
+/// ### Accounts:
+/// 1. `[writable]` config: NCN configuration account
+/// 2. `[]` ncn: The NCN account
+/// 3. `[signer]` ncn_admin: Admin authority for the NCN
+pub fn process_admin_set_fee_wallet(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_wallet: Pubkey,
+) -> ProgramResult {
+    let [config, ncn_account, ncn_admin] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    load_signer(ncn_admin, true)?;
+    NcnConfig::load(program_id, config, ncn_account.key, true)?;
+    Ncn::load(&jito_restaking_program::id(), ncn_account, false)?;
+
+    {
+        let ncn_data = ncn_account.data.borrow();
+        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        if ncn.admin != *ncn_admin.key {
+            msg!("Error: Incorrect NCN admin");
+            return Err(NCNProgramError::IncorrectNcnAdmin.into());
+        }
+    }
+
+    if new_wallet == Pubkey::default() || new_wallet == system_program::id() {
+        msg!("Error: NCN fee wallet cannot be the default pubkey or the system program");
+        return Err(NCNProgramError::DefaultNcnWallet.into());
+    }
+
+    let mut config_data = config.try_borrow_mut_data()?;
+    let config = NcnConfig::try_from_slice_unchecked_mut(&mut config_data)?;
+
+    log_admin_change(
+        "AdminSetFeeWallet",
+        ncn_admin.key,
+        "ncn_fee_wallet",
+        config.fee_config.ncn_fee_wallet(),
+        new_wallet,
+    );
+    config.fee_config.set_ncn_fee_wallet(&new_wallet);
+
+    Ok(())
+}
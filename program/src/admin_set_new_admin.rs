@@ -4,6 +4,7 @@ use jito_restaking_core::ncn::Ncn;
 use ncn_program_core::{
     config::{Config as NcnConfig, ConfigAdminRole},
     error::NCNProgramError,
+    utils::log_admin_change,
 };
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
@@ -52,10 +53,12 @@ pub fn process_admin_set_new_admin(
 
     match role {
         ConfigAdminRole::TieBreakerAdmin => {
-            msg!(
-                "Setting new tie breaker admin from {:?} to {:?}",
+            log_admin_change(
+                "AdminSetNewAdmin",
+                ncn_admin.key,
+                "tie_breaker_admin",
                 config.tie_breaker_admin,
-                new_admin.key
+                new_admin.key,
             );
             config.tie_breaker_admin = *new_admin.key;
         }
@@ -1,6 +1,9 @@
-use jito_bytemuck::{types::PodU64, AccountDeserialize};
+use jito_bytemuck::{
+    types::{PodBool, PodU64},
+    AccountDeserialize, Discriminator,
+};
 use jito_jsm_core::loader::load_signer;
-use jito_restaking_core::ncn::Ncn;
+use jito_restaking_core::{config::Config as RestakingConfig, ncn::Ncn};
 use ncn_program_core::{
     config::Config,
     constants::{
@@ -9,25 +12,44 @@ use ncn_program_core::{
         MIN_EPOCHS_BEFORE_STALL, MIN_VALID_SLOTS_AFTER_CONSENSUS,
     },
     error::NCNProgramError,
+    loaders::load_ncn_epoch,
+    snapshot::Snapshot,
     stake_weight::StakeWeights,
+    utils::log_admin_change,
+    vote_counter::VoteCounter,
 };
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-    pubkey::Pubkey,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
 };
 
 /// Updates program configuration parameters after initialization.
 ///
+/// `minimum_stake` can't change while the snapshot has registered operators and no vote has
+/// been cast in the current NCN epoch, so operators aren't disqualified from a pending vote.
+/// The other parameters can change at any time.
+///
 /// ### Parameters:
 /// - `starting_valid_epoch`: Optional starting epoch
 /// - `epochs_before_stall`: Optional number of epochs before stall
 /// - `epochs_after_consensus_before_close`: Optional number of epochs after consensus before close
 /// - `valid_slots_after_consensus`: Optional number of valid slots after consensus
+/// - `enforce_signer_order`: Optional flag requiring cast vote signer accounts in the order of the bitmap's set bits
+/// - `minimum_operators`: Optional number of registered operators with the minimum stake required before votes are accepted, 0 disables the guard
+/// - `reject_duplicate_aggregates`: Optional flag rejecting cast votes whose aggregate signature is identical to the last accepted one
+/// - `allow_multiple_votes_per_epoch`: Optional flag accepting more than one cast vote in the same epoch
+/// - `quorum_bps`: Optional minimum share, in bps, of the eligible stake weight the signers of a cast vote must hold, at most `MAX_QUORUM_BPS`, 0 disables the check
+/// - `max_snapshot_age_slots`: Optional maximum slots between the last snapshot update and a cast vote, 0 disables the check
+/// - `allow_ed25519_votes`: Optional flag accepting ed25519 fallback votes through `CastEd25519Vote`
 ///
 /// ### Accounts:
 /// 1. `[writable]` config: NCN configuration account
 /// 2. `[]` ncn: The NCN account
 /// 3. `[signer]` ncn_admin: Admin authority for the NCN
+/// 4. `[]` restaking_config: Restaking configuration account
+/// 5. `[]` snapshot: Snapshot account, may be uninitialized
+/// 6. `[]` vote_counter: Vote counter account, may be uninitialized
+#[allow(clippy::too_many_arguments)]
 pub fn process_admin_set_parameters(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -36,8 +58,16 @@ pub fn process_admin_set_parameters(
     epochs_after_consensus_before_close: Option<u64>,
     minimum_stake: Option<u128>,
     valid_slots_after_consensus: Option<u64>,
+    enforce_signer_order: Option<bool>,
+    minimum_operators: Option<u64>,
+    reject_duplicate_aggregates: Option<bool>,
+    allow_multiple_votes_per_epoch: Option<bool>,
+    quorum_bps: Option<u16>,
+    max_snapshot_age_slots: Option<u64>,
+    allow_ed25519_votes: Option<bool>,
 ) -> ProgramResult {
-    let [config, ncn_account, ncn_admin] = accounts else {
+    let [config, ncn_account, ncn_admin, restaking_config, snapshot, vote_counter] = accounts
+    else {
         msg!("Error: Not enough account keys provided");
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -45,6 +75,7 @@ pub fn process_admin_set_parameters(
     load_signer(ncn_admin, true)?;
     Config::load(program_id, config, ncn_account.key, true)?;
     Ncn::load(&jito_restaking_program::id(), ncn_account, false)?;
+    RestakingConfig::load(&jito_restaking_program::id(), restaking_config, false)?;
 
     {
         let ncn_data = ncn_account.data.borrow();
@@ -64,23 +95,40 @@ pub fn process_admin_set_parameters(
     }
 
     if let Some(epoch) = starting_valid_epoch {
-        msg!(
-            "Updating valid_starting_epoch from {} to {}",
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "starting_valid_epoch",
             u64::from(config.starting_valid_epoch),
-            epoch
+            epoch,
         );
         config.starting_valid_epoch = PodU64::from(epoch);
     }
 
+    if minimum_stake.is_some()
+        && is_vote_pending(
+            program_id,
+            ncn_account.key,
+            restaking_config,
+            snapshot,
+            vote_counter,
+        )?
+    {
+        msg!("Error: Cannot change minimum_stake before the current epoch is voted on");
+        return Err(NCNProgramError::ParameterChangeNotAllowed.into());
+    }
+
     if let Some(epochs) = epochs_before_stall {
         if !(MIN_EPOCHS_BEFORE_STALL..=MAX_EPOCHS_BEFORE_STALL).contains(&epochs) {
             msg!("Error: Invalid epochs_before_stall value");
             return Err(NCNProgramError::InvalidEpochsBeforeStall.into());
         }
-        msg!(
-            "Updating epochs_before_stall from {} to {}",
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "epochs_before_stall",
             u64::from(config.epochs_before_stall),
-            epochs
+            epochs,
         );
         config.epochs_before_stall = PodU64::from(epochs);
     }
@@ -92,10 +140,12 @@ pub fn process_admin_set_parameters(
             msg!("Error: Invalid epochs_after_consensus_before_close value");
             return Err(NCNProgramError::InvalidEpochsBeforeClose.into());
         }
-        msg!(
-            "Updating epochs_after_consensus_before_close from {} to {}",
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "epochs_after_consensus_before_close",
             u64::from(config.epochs_after_consensus_before_close),
-            epochs
+            epochs,
         );
         config.epochs_after_consensus_before_close = PodU64::from(epochs);
     }
@@ -105,10 +155,12 @@ pub fn process_admin_set_parameters(
             msg!("Error: Invalid valid_slots_after_consensus value");
             return Err(NCNProgramError::InvalidSlotsAfterConsensus.into());
         }
-        msg!(
-            "Updating valid_slots_after_consensus from {} to {}",
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "valid_slots_after_consensus",
             u64::from(config.valid_slots_after_consensus),
-            slots
+            slots,
         );
         config.valid_slots_after_consensus = PodU64::from(slots);
     }
@@ -119,13 +171,141 @@ pub fn process_admin_set_parameters(
             return Err(NCNProgramError::InvalidMinimumStakeWeight.into());
         }
         let minimum_stake = StakeWeights::new(weight);
-        msg!(
-            "Updating minimum_stake from {} to {}",
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "minimum_stake",
             config.minimum_stake.stake_weight(),
-            minimum_stake.stake_weight()
+            minimum_stake.stake_weight(),
         );
         config.minimum_stake = minimum_stake;
     }
 
+    if let Some(enforce) = enforce_signer_order {
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "enforce_signer_order",
+            config.enforce_signer_order(),
+            enforce,
+        );
+        config.enforce_signer_order = PodBool::from(enforce);
+    }
+
+    if let Some(minimum_operators) = minimum_operators {
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "minimum_operators",
+            config.minimum_operators(),
+            minimum_operators,
+        );
+        config.minimum_operators = PodU64::from(minimum_operators);
+    }
+
+    if let Some(reject) = reject_duplicate_aggregates {
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "reject_duplicate_aggregates",
+            config.reject_duplicate_aggregates(),
+            reject,
+        );
+        config.reject_duplicate_aggregates = PodBool::from(reject);
+    }
+
+    if let Some(allow) = allow_multiple_votes_per_epoch {
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "allow_multiple_votes_per_epoch",
+            config.allow_multiple_votes_per_epoch(),
+            allow,
+        );
+        config.allow_multiple_votes_per_epoch = PodBool::from(allow);
+    }
+
+    if let Some(quorum_bps) = quorum_bps {
+        let old_quorum_bps = config.quorum_bps();
+        config.set_quorum_bps(quorum_bps).map_err(|e| {
+            msg!("Error: Invalid quorum_bps value");
+            e
+        })?;
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "quorum_bps",
+            old_quorum_bps,
+            quorum_bps,
+        );
+    }
+
+    if let Some(max_snapshot_age_slots) = max_snapshot_age_slots {
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "max_snapshot_age_slots",
+            config.max_snapshot_age_slots(),
+            max_snapshot_age_slots,
+        );
+        config.max_snapshot_age_slots = PodU64::from(max_snapshot_age_slots);
+    }
+
+    if let Some(allow) = allow_ed25519_votes {
+        log_admin_change(
+            "AdminSetParameters",
+            ncn_admin.key,
+            "allow_ed25519_votes",
+            config.allow_ed25519_votes(),
+            allow,
+        );
+        config.allow_ed25519_votes = PodBool::from(allow);
+    }
+
     Ok(())
 }
+
+/// Whether the snapshot has registered operators and no vote has been cast in the current NCN
+/// epoch. A snapshot that isn't fully initialized has nothing to vote on, an uninitialized vote
+/// counter has never been voted on.
+fn is_vote_pending(
+    program_id: &Pubkey,
+    ncn: &Pubkey,
+    restaking_config: &AccountInfo,
+    snapshot: &AccountInfo,
+    vote_counter: &AccountInfo,
+) -> Result<bool, ProgramError> {
+    if *snapshot.key != Snapshot::find_program_address(program_id, ncn).0 {
+        msg!("Error: Invalid snapshot PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if *vote_counter.key != VoteCounter::find_program_address(program_id, ncn).0 {
+        msg!("Error: Invalid vote counter PDA");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if snapshot.data_is_empty() || snapshot.try_borrow_data()?[0] != Snapshot::DISCRIMINATOR {
+        return Ok(false);
+    }
+
+    Snapshot::load(program_id, snapshot, ncn, false)?;
+    let operators_registered = {
+        let snapshot_data = snapshot.data.borrow();
+        let snapshot_account = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
+        snapshot_account.operators_registered()
+    };
+    if operators_registered == 0 {
+        return Ok(false);
+    }
+
+    if vote_counter.data_is_empty() {
+        return Ok(true);
+    }
+
+    VoteCounter::load(program_id, vote_counter, ncn, false)?;
+    let (current_epoch, _) = load_ncn_epoch(restaking_config, Clock::get()?.slot, None)?;
+    let vote_counter_data = vote_counter.data.borrow();
+    let vote_counter_account = VoteCounter::try_from_slice_unchecked(&vote_counter_data)?;
+
+    Ok(vote_counter_account.votes_in_epoch(current_epoch) == 0)
+}
@@ -0,0 +1,75 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::ncn::Ncn;
+use ncn_program_core::{
+    config::Config, error::NCNProgramError, snapshot::Snapshot, stake_weight::StakeWeights,
+    utils::log_admin_change,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Updates the minimum stake of an existing snapshot. Requires NCN admin signature.
+///
+/// `AdminSetParameters` only changes the minimum stake of snapshots created afterwards, this
+/// applies a new minimum to the open snapshot. Every registered operator's `has_minimum_stake`
+/// and `has_minimum_stake_next_epoch` flags are recomputed in place from its snapshotted stake
+/// weights.
+///
+/// ### Parameters:
+/// - `minimum_stake`: The new minimum stake weight, must not be zero
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[]` ncn: The NCN account
+/// 3. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 4. `[signer]` ncn_admin: Admin authority for the NCN
+pub fn process_admin_update_snapshot_minimum_stake(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    minimum_stake: u128,
+) -> ProgramResult {
+    let [config, ncn_account, snapshot, ncn_admin] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    load_signer(ncn_admin, false)?;
+    Config::load(program_id, config, ncn_account.key, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn_account, false)?;
+    Snapshot::load(program_id, snapshot, ncn_account.key, true)?;
+
+    {
+        let ncn_data = ncn_account.data.borrow();
+        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        if ncn.admin != *ncn_admin.key {
+            msg!("Error: Incorrect NCN admin");
+            return Err(NCNProgramError::IncorrectNcnAdmin.into());
+        }
+    }
+
+    if minimum_stake == 0 {
+        msg!("Error: Minimum stake weight cannot be zero");
+        return Err(NCNProgramError::InvalidMinimumStakeWeight.into());
+    }
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    log_admin_change(
+        "AdminUpdateSnapshotMinimumStake",
+        ncn_admin.key,
+        "minimum_stake",
+        snapshot_account.minimum_stake().stake_weight(),
+        minimum_stake,
+    );
+    snapshot_account.set_minimum_stake(StakeWeights::new(minimum_stake));
+
+    msg!(
+        "{} operators have the minimum stake",
+        snapshot_account.operators_can_vote_count()
+    );
+
+    Ok(())
+}
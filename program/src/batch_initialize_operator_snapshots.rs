@@ -0,0 +1,160 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::{load_signer, load_system_account, load_system_program};
+use jito_restaking_core::{config::Config as RestakingConfig, ncn::Ncn, operator::Operator};
+use ncn_program_core::{
+    account_payer::AccountPayer, config::Config, constants::MAX_OPERATORS, error::NCNProgramError,
+    instruction::OperatorSnapshotEntry, loaders::load_ncn_epoch, snapshot::Snapshot,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::register_operator::{record_snapshot_finalization, register_operator_keys};
+
+/// Inserts several operators into the snapshot in one transaction, to bootstrap a large NCN.
+/// Requires NCN admin signature.
+///
+/// Every operator goes through the same checks as `RegisterOperator`, see
+/// `register_operator_keys`: its NCN link must be active, its G1 and G2 pubkeys must come from
+/// the same private key and its signature must prove possession of it. Its ncn operator account
+/// is created with no IP address and port, see `UpdateOperatorIpPort`. An entry whose
+/// `ncn_operator_index` is not the NCN operator index of its `ncn_operator_state` is rejected with
+/// `NcnOperatorIndexMismatch`. Fails when an index is already taken in the snapshot. Inserting the
+/// last operator of the NCN finalizes the snapshot, as with `RegisterOperator`.
+///
+/// ### Parameters:
+/// - `operators`: The operator, G1 and G2 pubkeys, proof of possession and NCN operator index of
+///   every operator to insert
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[]` ncn: The NCN account
+/// 3. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 4. `[signer]` ncn_admin: Admin authority for the NCN
+/// 5. `[]` restaking_config: Restaking configuration account
+/// 6. `[writable]` account_payer: Account paying for the ncn operator accounts
+/// 7. `[]` system_program: Solana System Program
+/// 8. `[]` operator_accounts: Remaining accounts, the `operator`, `ncn_operator_state` and
+///    writable `ncn_operator_account` of every operator, in `operators` order
+pub fn process_batch_initialize_operator_snapshots(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    operators: Vec<OperatorSnapshotEntry>,
+) -> ProgramResult {
+    if operators.is_empty() || operators.len() > MAX_OPERATORS {
+        msg!("Error: Invalid number of operators {}", operators.len());
+        return Err(NCNProgramError::InvalidInputLength.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let config = next_account_info(account_info_iter)?;
+    let ncn_account = next_account_info(account_info_iter)?;
+    let snapshot = next_account_info(account_info_iter)?;
+    let ncn_admin = next_account_info(account_info_iter)?;
+    let restaking_config = next_account_info(account_info_iter)?;
+    let account_payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let operator_accounts = account_info_iter.as_slice();
+
+    if operator_accounts.len() != operators.len() * 3 {
+        msg!(
+            "Error: Expected {} operator accounts, got {}",
+            operators.len() * 3,
+            operator_accounts.len()
+        );
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    load_signer(ncn_admin, false)?;
+    Config::load(program_id, config, ncn_account.key, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn_account, false)?;
+    Snapshot::load(program_id, snapshot, ncn_account.key, true)?;
+    RestakingConfig::load(&jito_restaking_program::id(), restaking_config, false)?;
+    AccountPayer::load(program_id, account_payer, ncn_account.key, true)?;
+    load_system_program(system_program)?;
+
+    {
+        let ncn_data = ncn_account.data.borrow();
+        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        if ncn.admin != *ncn_admin.key {
+            msg!("Error: Incorrect NCN admin");
+            return Err(NCNProgramError::IncorrectNcnAdmin.into());
+        }
+    }
+
+    let current_slot = Clock::get()?.slot;
+    let (_, ncn_epoch_length) = load_ncn_epoch(restaking_config, current_slot, None)?;
+
+    let mut operator_snapshots = Vec::with_capacity(operators.len());
+    for (entry, accounts) in operators.iter().zip(operator_accounts.chunks_exact(3)) {
+        let [operator, ncn_operator_state, ncn_operator_account] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if operator.key.ne(&entry.operator) {
+            msg!(
+                "Error: Operator account {} does not match entry operator {}",
+                operator.key,
+                entry.operator
+            );
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Operator::load(&jito_restaking_program::id(), operator, false)?;
+        load_system_account(ncn_operator_account, true)?;
+
+        let operator_snapshot = register_operator_keys(
+            program_id,
+            ncn_account,
+            operator,
+            ncn_operator_state,
+            ncn_operator_account,
+            account_payer,
+            system_program,
+            current_slot,
+            ncn_epoch_length,
+            entry.g1_pubkey,
+            entry.g2_pubkey,
+            entry.signature,
+            [0; 4],
+            0,
+        )?;
+
+        if operator_snapshot.ncn_operator_index() != entry.ncn_operator_index {
+            msg!(
+                "Error: Operator {} has NCN operator index {}, not {}",
+                operator.key,
+                operator_snapshot.ncn_operator_index(),
+                entry.ncn_operator_index
+            );
+            return Err(NCNProgramError::NcnOperatorIndexMismatch.into());
+        }
+
+        operator_snapshots.push(operator_snapshot);
+    }
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    snapshot_account
+        .add_operator_snapshots(&operator_snapshots, current_slot)
+        .map_err(|e| {
+            msg!("Error: Could not insert the operator snapshots");
+            e
+        })?;
+
+    msg!(
+        "{} operators inserted, {} registered",
+        operator_snapshots.len(),
+        snapshot_account.operators_registered()
+    );
+
+    record_snapshot_finalization(ncn_account, snapshot_account, current_slot)?;
+
+    Ok(())
+}
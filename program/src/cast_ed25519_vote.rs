@@ -0,0 +1,164 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::get_epoch;
+use jito_restaking_core::{config::Config, ncn::Ncn, operator::Operator};
+use ncn_program_core::{
+    config::Config as NcnConfig, error::NCNProgramError,
+    schemes::ed25519::check_ed25519_instruction_data, snapshot::Snapshot,
+    vote_counter::VoteCounter,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    ed25519_program,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        Sysvar,
+    },
+};
+
+use crate::cast_vote::{check_snapshot_age, check_snapshot_finalized};
+
+/// Casts an ed25519 fallback vote with the voter key of a jito `Operator`.
+///
+/// The signature is verified by the ed25519 program: the previous instruction of the transaction
+/// must be an ed25519 program instruction verifying a single signature by the operator's voter
+/// over `NcnConfig::consensus_message` of the NCN, the current NCN epoch and the current vote
+/// counter count, otherwise the vote fails with `InvalidEd25519Instruction`. The vote adds the operator's
+/// stake weight to the vote counter's ed25519 tally of the epoch, kept apart from the BLS votes,
+/// and an operator can only be counted once per epoch.
+///
+/// Fails with `Ed25519VotesDisabled` unless `allow_ed25519_votes` is set in the config. As with
+/// `CastVote`, the snapshot must be finalized and not older than `max_snapshot_age_slots`, and the
+/// operator must be active with the minimum stake.
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account (named `ncn_config` in code)
+/// 2. `[]` ncn: The NCN account
+/// 3. `[]` operator: The voting operator
+/// 4. `[]` snapshot: Snapshot containing the operator's snapshot
+/// 5. `[]` restaking_config: Restaking configuration account
+/// 6. `[writable]` vote_counter: Vote counter PDA holding the ed25519 tally
+/// 7. `[]` instructions_sysvar: Instructions sysvar, to read the ed25519 program instruction
+pub fn process_cast_ed25519_vote(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [ncn_config, ncn, operator, snapshot, restaking_config, vote_counter, instructions_sysvar] =
+        accounts
+    else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    NcnConfig::load(program_id, ncn_config, ncn.key, false)?;
+    Config::load(&jito_restaking_program::id(), restaking_config, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    Operator::load(&jito_restaking_program::id(), operator, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, false)?;
+    VoteCounter::load(program_id, vote_counter, ncn.key, true)?;
+
+    if !solana_program::sysvar::instructions::check_id(instructions_sysvar.key) {
+        msg!("Error: Invalid instructions sysvar");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let allow_ed25519_votes = {
+        let ncn_config_data = ncn_config.data.borrow();
+        let ncn_config = NcnConfig::try_from_slice_unchecked(&ncn_config_data)?;
+        ncn_config.allow_ed25519_votes()
+    };
+    if !allow_ed25519_votes {
+        msg!("Error: Ed25519 votes are disabled in the config");
+        return Err(NCNProgramError::Ed25519VotesDisabled.into());
+    }
+
+    check_snapshot_finalized(ncn, snapshot)?;
+
+    let ncn_epoch_length = {
+        let ncn_config_data = ncn_config.data.borrow();
+        let ncn_config = NcnConfig::try_from_slice_unchecked(&ncn_config_data)?;
+        ncn_config.epoch_length()
+    };
+
+    let voter = {
+        let operator_data = operator.data.borrow();
+        let operator_account = Operator::try_from_slice_unchecked(&operator_data)?;
+        operator_account.voter
+    };
+
+    let current_slot = Clock::get()?.slot;
+    check_snapshot_age(ncn_config, snapshot, current_slot)?;
+
+    let current_epoch = get_epoch(current_slot, ncn_epoch_length)?;
+
+    let (ncn_operator_index, stake_weight) = {
+        let snapshot_data = snapshot.data.borrow();
+        let snapshot_account = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
+        let operator_snapshot = snapshot_account
+            .find_operator_snapshot(operator.key)
+            .ok_or_else(|| {
+                msg!("Error: Operator {} is not in the snapshot", operator.key);
+                NCNProgramError::OperatorIsNotInSnapshot
+            })?;
+
+        if !operator_snapshot.is_active() {
+            msg!("Error: Operator {} is not active", operator.key);
+            return Err(NCNProgramError::OperatorSnapshotIsNotActive.into());
+        }
+        if !operator_snapshot.has_minimum_stake() {
+            msg!("Error: Operator {} has no minimum stake", operator.key);
+            return Err(NCNProgramError::OperatorHasNoMinimumStake.into());
+        }
+
+        (
+            operator_snapshot.ncn_operator_index(),
+            operator_snapshot.stake_weight().stake_weight(),
+        )
+    };
+
+    let mut vote_counter_data = vote_counter.try_borrow_mut_data()?;
+    let vote_counter_account = VoteCounter::try_from_slice_unchecked_mut(&mut vote_counter_data)?;
+
+    let message =
+        NcnConfig::consensus_message(ncn.key, current_epoch, vote_counter_account.count());
+
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        msg!("Error: No ed25519 program instruction before the vote");
+        return Err(NCNProgramError::InvalidEd25519Instruction.into());
+    }
+    let ed25519_instruction =
+        load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    if ed25519_instruction.program_id != ed25519_program::id() {
+        msg!("Error: The previous instruction is not an ed25519 program instruction");
+        return Err(NCNProgramError::InvalidEd25519Instruction.into());
+    }
+    check_ed25519_instruction_data(&ed25519_instruction.data, &voter, &message).map_err(|e| {
+        msg!(
+            "Error: The ed25519 instruction does not sign the vote message with voter {}",
+            voter
+        );
+        e
+    })?;
+
+    vote_counter_account
+        .record_ed25519_vote(current_epoch, ncn_operator_index, stake_weight)
+        .map_err(|e| {
+            msg!(
+                "Error: Operator {} already cast an ed25519 vote in epoch {}",
+                operator.key,
+                current_epoch
+            );
+            e
+        })?;
+
+    msg!(
+        "Ed25519 vote cast by operator {}! Ed25519 stake weight in epoch {}: {}",
+        operator.key,
+        current_epoch,
+        vote_counter_account.ed25519_stake_weight_in_epoch(current_epoch)
+    );
+
+    Ok(())
+}
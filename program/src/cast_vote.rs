@@ -1,19 +1,20 @@
 use jito_bytemuck::AccountDeserialize;
-use jito_jsm_core::get_epoch;
-use jito_restaking_core::{config::Config, ncn::Ncn};
+use jito_jsm_core::{get_epoch, slot_toggle::SlotToggleState};
+use jito_restaking_core::{config::Config, ncn::Ncn, ncn_operator_state::NcnOperatorState};
 use ncn_program_core::{
     config::Config as NcnConfig,
-    constants::{G1_COMPRESSED_POINT_SIZE, G2_COMPRESSED_POINT_SIZE},
+    constants::{
+        G1_COMPRESSED_POINT_SIZE, G2_COMPRESSED_POINT_SIZE, MAX_QUORUM_BPS, MAX_SIGNER_BITMAP_BYTES,
+    },
     error::NCNProgramError,
-    g1_point::{G1CompressedPoint, G1Point},
+    g1_point::{CurveId, G1CompressedPoint},
     g2_point::{G2CompressedPoint, G2Point},
-    schemes::Sha256Normalized,
+    schemes::{Sha256Normalized, Sha256NormalizedScheme},
     snapshot::Snapshot,
+    utils::bitmap_iter,
     vote_counter::VoteCounter,
 };
 
-use num::CheckedAdd;
-
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     clock::Clock,
@@ -30,8 +31,25 @@ use solana_program::{
 /// - `aggregated_g2`: Aggregated G2 public key in compressed format (64 bytes)
 /// - `aggregated_signature`: Aggregated G1 signature in compressed format (32 bytes)
 /// - `operators_signature_bitmap`: Bitmap indicating which operators signed the vote
+/// - `quorum_bps`: Optional minimum share, in bps, of the stake weight of operators with the
+///   minimum stake that the signers must hold. The `quorum_bps` of the config applies when it is
+///   stricter
 ///
-/// Note: The message used for signature verification is the current vote counter count
+/// Note: The message used for signature verification is `NcnConfig::consensus_message` of the
+/// NCN, the current NCN epoch and the current vote counter count, derived on-chain and never supplied
+/// by the caller, and hashed to the curve under `Sha256NormalizedScheme::for_ncn` of the NCN. When `reject_duplicate_aggregates` is set in the config, an aggregate signature
+/// identical to the last accepted one is rejected before verification. An aggregate signature
+/// that does not verify over the derived message, e.g. one replayed from an earlier epoch or
+/// count, is rejected with `VoteMessageMismatch`. A verified vote is rejected with
+/// `AlreadyVotedThisEpoch` when a vote was already cast in the current epoch, unless
+/// `allow_multiple_votes_per_epoch` is set in the config. Votes are rejected with
+/// `SnapshotNotFinalized` until every operator of the NCN is registered in the snapshot, and
+/// with `SnapshotTooStale` when the snapshot was last updated more than the config's
+/// `max_snapshot_age_slots` slots ago. An `aggregated_g2` that isn't the sum of the snapshot's
+/// G2 pubkeys of the operators set in the bitmap is rejected with `G2AggregateMismatch`, and a
+/// signer with no G2 pubkey in the snapshot with `InvalidG2Pubkey`. A signer paused with
+/// `SetOperatorActive` is rejected with `OperatorSnapshotIsNotActive`, and paused operators
+/// count towards neither the 1/3 non-signer limit nor the stake quorum
 ///
 /// ### Accounts:
 /// 1. `[]` config: NCN configuration account (named `ncn_config` in code)
@@ -39,19 +57,29 @@ use solana_program::{
 /// 3. `[]` snapshot: Snapshot containing stakes and operator snapshots
 /// 4. `[]` restaking_config: Restaking configuration account
 /// 5. `[writable]` vote_counter: Vote counter PDA to increment on successful vote
+/// 6. `[]` ncn_operator_states: Remaining accounts, one `NcnOperatorState` per signer, used to
+///    check each signer's NCN link is still active. The bitmap always yields signers in
+///    ascending operator index order, so `enforce_signer_order` constrains these accounts
+///    instead: when set in the config, the n-th account must belong to the n-th set bit of the
+///    bitmap and any other order is rejected with `SignerOrderViolation`. Otherwise any order
+///    is accepted
 pub fn process_cast_vote(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     aggregated_g2: [u8; G2_COMPRESSED_POINT_SIZE],
     aggregated_signature: [u8; G1_COMPRESSED_POINT_SIZE],
     operators_signature_bitmap: Vec<u8>,
+    quorum_bps: Option<u16>,
 ) -> ProgramResult {
+    check_signer_bitmap_size(&operators_signature_bitmap)?;
+
     let account_info_iter = &mut accounts.iter();
     let ncn_config = next_account_info(account_info_iter)?;
     let ncn = next_account_info(account_info_iter)?;
     let snapshot = next_account_info(account_info_iter)?;
     let restaking_config = next_account_info(account_info_iter)?;
     let vote_counter = next_account_info(account_info_iter)?;
+    let ncn_operator_states = account_info_iter.as_slice();
 
     NcnConfig::load(program_id, ncn_config, ncn.key, false)?;
     Config::load(&jito_restaking_program::id(), restaking_config, false)?;
@@ -59,63 +87,283 @@ pub fn process_cast_vote(
     Snapshot::load(program_id, snapshot, ncn.key, false)?;
     VoteCounter::load(program_id, vote_counter, ncn.key, true)?;
 
-    // Get the current counter value to use as the message for signature verification
-    let vote_counter_data = vote_counter.data.borrow();
-    let vote_counter_account = VoteCounter::try_from_slice_unchecked(&vote_counter_data)?;
-    let current_count = vote_counter_account.count();
-    let message = current_count.to_le_bytes();
-    // Pad to 32 bytes for signature verification
-    let mut message_32 = [0u8; 32];
-    message_32[..8].copy_from_slice(&message);
-    drop(vote_counter_data);
-
-    let ncn_epoch_length = {
-        let config_data = restaking_config.data.borrow();
-        let config = Config::try_from_slice_unchecked(&config_data)?;
-        config.epoch_length()
+    check_snapshot_finalized(ncn, snapshot)?;
+
+    let (
+        enforce_signer_order,
+        curve,
+        minimum_operators,
+        reject_duplicate_aggregates,
+        allow_multiple_votes_per_epoch,
+        quorum_bps,
+        ncn_epoch_length,
+    ) = {
+        let ncn_config_data = ncn_config.data.borrow();
+        let ncn_config = NcnConfig::try_from_slice_unchecked(&ncn_config_data)?;
+        (
+            ncn_config.enforce_signer_order(),
+            ncn_config.curve()?,
+            ncn_config.minimum_operators(),
+            ncn_config.reject_duplicate_aggregates(),
+            ncn_config.allow_multiple_votes_per_epoch(),
+            ncn_config.effective_quorum_bps(quorum_bps),
+            ncn_config.epoch_length(),
+        )
+    };
+
+    // Get the current counter value to build the message for signature verification
+    let current_count = {
+        let vote_counter_data = vote_counter.data.borrow();
+        let vote_counter_account = VoteCounter::try_from_slice_unchecked(&vote_counter_data)?;
+        if reject_duplicate_aggregates {
+            vote_counter_account.check_aggregate_not_duplicate(&aggregated_signature)?;
+        }
+        vote_counter_account.count()
     };
 
     let current_slot = Clock::get()?.slot;
+    check_snapshot_age(ncn_config, snapshot, current_slot)?;
+
+    let current_epoch = get_epoch(current_slot, ncn_epoch_length)?;
+    let message_32 = NcnConfig::consensus_message(ncn.key, current_epoch, current_count);
 
     let snapshot_data = snapshot.data.borrow();
     let snapshot = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
 
-    let operators_registered = snapshot.operators_registered();
+    msg!("Total operators: {}", snapshot.operators_registered());
+    msg!("Current slot: {}", current_slot);
+
+    verify_vote(
+        ncn,
+        snapshot,
+        ncn_operator_states,
+        enforce_signer_order,
+        curve,
+        minimum_operators,
+        current_slot,
+        ncn_epoch_length,
+        aggregated_g2,
+        aggregated_signature,
+        &operators_signature_bitmap,
+        quorum_bps,
+        &message_32,
+    )?;
+
+    // Increment the vote counter PDA after successful signature verification
+    // NOTE: This counter could track anything
+    let mut vote_counter_data = vote_counter.try_borrow_mut_data()?;
+    let vote_counter_account = VoteCounter::try_from_slice_unchecked_mut(&mut vote_counter_data)?;
+
+    if !allow_multiple_votes_per_epoch {
+        vote_counter_account
+            .check_not_voted_in_epoch(current_epoch)
+            .map_err(|e| {
+                msg!("Error: A vote was already cast in epoch {}", current_epoch);
+                e
+            })?;
+    }
 
-    msg!("Total operators: {}", operators_registered);
+    let previous_count = vote_counter_account.count();
+    vote_counter_account.increment()?;
+    let new_count = vote_counter_account.count();
+
+    vote_counter_account.record_vote(current_epoch, current_slot)?;
+    vote_counter_account.record_aggregate(&aggregated_signature);
+    vote_counter_account.record_message(&message_32);
+    vote_counter_account.record_consensus(
+        current_epoch,
+        snapshot.last_snapshot_slot(),
+        current_slot,
+    )?;
+
+    msg!(
+        "Vote successfully cast! Counter incremented from {} to {}",
+        previous_count,
+        new_count
+    );
+
+    Ok(())
+}
 
-    let slot = Clock::get()?.slot;
-    msg!("Current slot: {}", slot);
+/// Rejects bitmaps longer than `MAX_SIGNER_BITMAP_BYTES` before any account is processed
+pub fn check_signer_bitmap_size(operators_signature_bitmap: &[u8]) -> ProgramResult {
+    if operators_signature_bitmap.len() > MAX_SIGNER_BITMAP_BYTES {
+        msg!(
+            "Bitmap too large: {} bytes (max: {})",
+            operators_signature_bitmap.len(),
+            MAX_SIGNER_BITMAP_BYTES
+        );
+        return Err(NCNProgramError::BitmapTooLarge.into());
+    }
+    Ok(())
+}
 
-    // Check bitmap size
-    let required_bitmap_bytes = (operators_registered
-        .checked_add(7)
-        .ok_or(ProgramError::ArithmeticOverflow)?)
-        / 8;
-    if operators_signature_bitmap.len() as u64 != required_bitmap_bytes {
+/// Checks that the signer bitmap has one bit per operator slot of `snapshot` and that no bit is
+/// set for a slot freed by a deregistered operator
+pub fn check_signer_bitmap_slots(
+    snapshot: &Snapshot,
+    operators_signature_bitmap: &[u8],
+) -> ProgramResult {
+    let operator_slot_count = snapshot.operator_slot_count();
+    if operators_signature_bitmap.len() as u64 != operator_slot_count.div_ceil(8) {
         msg!("Invalid bitmap size");
         return Err(NCNProgramError::InvalidInputLength.into());
     }
 
+    for (slot, signed) in bitmap_iter(operators_signature_bitmap, operator_slot_count).enumerate() {
+        if signed && snapshot.get_operator_snapshot(slot as u64).is_none() {
+            msg!(
+                "Bit {} is set but no operator is registered in that slot",
+                slot
+            );
+            return Err(NCNProgramError::OperatorIsNotInSnapshot.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects votes until every operator of the NCN is registered in the snapshot, since the total
+/// aggregated G1 pubkey would otherwise be missing the keys of the unregistered operators.
+/// Deregistered operators count toward the NCN's operators, see `Snapshot::has_every_operator`.
+pub fn check_snapshot_finalized(ncn: &AccountInfo, snapshot: &AccountInfo) -> ProgramResult {
+    let ncn_operator_count = {
+        let ncn_data = ncn.try_borrow_data()?;
+        let ncn_account = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        ncn_account.operator_count()
+    };
+
+    let (has_every_operator, operators_registered, operators_deregistered) = {
+        let snapshot_data = snapshot.try_borrow_data()?;
+        let snapshot_account = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
+        (
+            snapshot_account.has_every_operator(ncn_operator_count),
+            snapshot_account.operators_registered(),
+            snapshot_account.operators_deregistered(),
+        )
+    };
+
+    if !has_every_operator {
+        msg!(
+            "Error: Snapshot not finalized. Registered: {}, Deregistered: {}, NCN operators: {}",
+            operators_registered,
+            operators_deregistered,
+            ncn_operator_count
+        );
+        return Err(NCNProgramError::SnapshotNotFinalized.into());
+    }
+
+    Ok(())
+}
+
+/// Rejects votes on a snapshot last updated more than `max_snapshot_age_slots` slots before
+/// `current_slot`, when the config sets a maximum age
+pub fn check_snapshot_age(
+    ncn_config: &AccountInfo,
+    snapshot: &AccountInfo,
+    current_slot: u64,
+) -> ProgramResult {
+    let last_snapshot_slot = {
+        let snapshot_data = snapshot.try_borrow_data()?;
+        let snapshot_account = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
+        snapshot_account.last_snapshot_slot()
+    };
+
+    let ncn_config_data = ncn_config.try_borrow_data()?;
+    let ncn_config = NcnConfig::try_from_slice_unchecked(&ncn_config_data)?;
+    ncn_config
+        .check_snapshot_age(last_snapshot_slot, current_slot)
+        .map_err(|e| {
+            msg!(
+                "Error: Snapshot too stale. Last snapshot slot: {}, current slot: {}, max age: {}",
+                last_snapshot_slot,
+                current_slot,
+                ncn_config.max_snapshot_age_slots()
+            );
+            e
+        })?;
+
+    Ok(())
+}
+
+/// Verifies one aggregated vote against the snapshot without touching the vote counter.
+///
+/// Checks that at least `minimum_operators` operators are registered and have the minimum stake,
+/// the bitmap size, that at least one operator signed, that every
+/// signer is active, still has the minimum stake and an active NCN link, that the active non-signers stay within a third of the active operators, that the signers hold at least
+/// `quorum_bps` of the active eligible stake weight when given and finally the aggregated signature
+/// over `message`, failing with `VoteMessageMismatch` when it does not verify
+#[allow(clippy::too_many_arguments)]
+pub fn verify_vote(
+    ncn: &AccountInfo,
+    snapshot: &Snapshot,
+    ncn_operator_states: &[AccountInfo],
+    enforce_signer_order: bool,
+    curve: CurveId,
+    minimum_operators: u64,
+    current_slot: u64,
+    ncn_epoch_length: u64,
+    aggregated_g2: [u8; G2_COMPRESSED_POINT_SIZE],
+    aggregated_signature: [u8; G1_COMPRESSED_POINT_SIZE],
+    operators_signature_bitmap: &[u8],
+    quorum_bps: Option<u16>,
+    message: &[u8; 32],
+) -> ProgramResult {
+    let operators_registered = snapshot.operators_registered();
+
+    if operators_registered < minimum_operators {
+        msg!(
+            "Only {} operators registered, at least {} are required for consensus",
+            operators_registered,
+            minimum_operators
+        );
+        return Err(NCNProgramError::NotEnoughOperatorsRegistered.into());
+    }
+
+    let operators_can_vote_count = snapshot.operators_can_vote_count();
+    if operators_can_vote_count < minimum_operators {
+        msg!(
+            "Only {} operators have the minimum stake, at least {} are required for consensus",
+            operators_can_vote_count,
+            minimum_operators
+        );
+        return Err(NCNProgramError::InsufficientOperators.into());
+    }
+    if snapshot.is_single_operator() {
+        msg!("Warning: only one operator is registered, its key is the whole aggregate");
+    }
+
+    check_signer_bitmap_slots(snapshot, operators_signature_bitmap)?;
+
     // Convert aggregated_g2 pubkey to G2Point
     let aggregated_g2_compressed_point = G2CompressedPoint::from(aggregated_g2);
-    let aggregated_g2_point = G2Point::try_from(aggregated_g2_compressed_point)
+    let aggregated_g2_point = curve
+        .g2_decompress(aggregated_g2_compressed_point)
         .map_err(|_| NCNProgramError::G2PointDecompressionError)?;
 
-    // Aggregate the G1 public keys of operators who signed
-    let mut aggregated_nonsigners_pubkey: Option<G1Point> = None;
+    // Every registered operator left out of the bitmap is subtracted from the total aggregated
+    // G1 pubkey, but only the active ones count against the quorum
     let mut non_signers_count: u64 = 0;
+    let mut active_non_signers_count: u64 = 0;
+    let mut signers_count: usize = 0;
 
-    for (i, operator_snapshot) in snapshot.operator_snapshots().iter().enumerate() {
-        if i >= operators_registered as usize {
-            break;
-        }
-
-        let byte_index = i / 8;
-        let bit_index = i % 8;
-        let signed = (operators_signature_bitmap[byte_index] >> bit_index) & 1 == 1;
+    for (i, signed) in
+        bitmap_iter(operators_signature_bitmap, snapshot.operator_slot_count()).enumerate()
+    {
+        // Slots freed by a deregistered operator are neither signers nor non-signers
+        let Some(operator_snapshot) = snapshot.get_operator_snapshot(i as u64) else {
+            continue;
+        };
 
         if signed {
+            if !operator_snapshot.is_active() {
+                msg!(
+                    "The operator {} is inactive and can't vote",
+                    operator_snapshot.operator()
+                );
+                return Err(NCNProgramError::OperatorSnapshotIsNotActive.into());
+            }
+
             let snapshot_epoch =
                 get_epoch(operator_snapshot.last_snapshot_slot(), ncn_epoch_length)?;
             let current_epoch = get_epoch(current_slot, ncn_epoch_length)?;
@@ -128,98 +376,213 @@ pub fn process_cast_vote(
                 );
                 return Err(NCNProgramError::OperatorHasNoMinimumStake.into());
             }
-        } else {
-            // Convert bytes to G1Point
-            let g1_compressed = G1CompressedPoint::from(operator_snapshot.g1_pubkey());
-            let g1_point = G1Point::try_from(&g1_compressed)
-                .map_err(|_| NCNProgramError::G1PointDecompressionError)?;
 
-            if aggregated_nonsigners_pubkey.is_none() {
-                aggregated_nonsigners_pubkey = Some(g1_point);
+            // The operator may have cooled down its NCN link after the snapshot was taken
+            let expected_ncn_operator_state = NcnOperatorState::find_program_address(
+                &jito_restaking_program::id(),
+                ncn.key,
+                operator_snapshot.operator(),
+            )
+            .0;
+            let ncn_operator_state = if enforce_signer_order {
+                let ncn_operator_state = ncn_operator_states
+                    .get(signers_count)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                if ncn_operator_state.key.ne(&expected_ncn_operator_state) {
+                    msg!(
+                        "Signer account {} does not match operator index {}",
+                        signers_count,
+                        i
+                    );
+                    return Err(NCNProgramError::SignerOrderViolation.into());
+                }
+                ncn_operator_state
             } else {
-                // Add this G1 pubkey to the aggregate using G1Point addition
-                let current = aggregated_nonsigners_pubkey.unwrap();
-                aggregated_nonsigners_pubkey = Some(
-                    current
-                        .checked_add(&g1_point)
-                        .ok_or(NCNProgramError::AltBN128AddError)?,
+                ncn_operator_states
+                    .iter()
+                    .find(|account| account.key.eq(&expected_ncn_operator_state))
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?
+            };
+            NcnOperatorState::load(
+                &jito_restaking_program::id(),
+                ncn_operator_state,
+                ncn,
+                operator_snapshot.operator(),
+                false,
+            )?;
+            let is_link_active = {
+                let ncn_operator_state_data = ncn_operator_state.data.borrow();
+                let ncn_operator_state_account =
+                    NcnOperatorState::try_from_slice_unchecked(&ncn_operator_state_data)?;
+
+                let ncn_operator_state = ncn_operator_state_account
+                    .ncn_opt_in_state
+                    .state(current_slot, ncn_epoch_length)?;
+
+                let operator_ncn_state = ncn_operator_state_account
+                    .operator_opt_in_state
+                    .state(current_slot, ncn_epoch_length)?;
+
+                matches!(
+                    ncn_operator_state,
+                    SlotToggleState::Active | SlotToggleState::WarmUp
+                ) && matches!(
+                    operator_ncn_state,
+                    SlotToggleState::Active | SlotToggleState::WarmUp
+                )
+            };
+            if !is_link_active {
+                msg!(
+                    "The operator {} NCN link is no longer active",
+                    operator_snapshot.operator()
                 );
+                return Err(NCNProgramError::OperatorLinkInactive.into());
             }
 
+            signers_count = signers_count
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        } else {
             non_signers_count = non_signers_count
                 .checked_add(1)
-                .ok_or(ProgramError::ArithmeticOverflow)?
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            if operator_snapshot.is_active() {
+                active_non_signers_count = active_non_signers_count
+                    .checked_add(1)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+            }
         }
     }
 
-    // If non_signers_count is more than 1/3 of registered operators, throw an error because quorum didn't meet
-    if non_signers_count > operators_registered / 3 {
+    if signers_count == 0 {
+        msg!("No operator is set in the signers bitmap");
+        return Err(NCNProgramError::NoSignersInBitmap.into());
+    }
+
+    // If more than 1/3 of the active operators did not sign, throw an error because quorum didn't meet
+    let active_operators_count = snapshot.active_operators_count();
+    if active_non_signers_count > active_operators_count / 3 {
         msg!(
-            "Quorum not met: non-signers count ({}) exceeds 1/3 of registered operators ({})",
-            non_signers_count,
-            operators_registered
+            "Quorum not met: non-signers count ({}) exceeds 1/3 of active operators ({})",
+            active_non_signers_count,
+            active_operators_count
         );
         return Err(NCNProgramError::QuorumNotMet.into());
     }
 
-    let total_aggregate_g1_pubkey_compressed =
-        G1CompressedPoint::from(snapshot.total_aggregated_g1_pubkey());
-    let total_aggregated_g1_pubkey = G1Point::try_from(&total_aggregate_g1_pubkey_compressed)
-        .map_err(|_| NCNProgramError::G1PointDecompressionError)?;
+    if let Some(quorum_bps) = quorum_bps {
+        // Inactive operators and operators without the minimum stake count towards neither side
+        let signer_stake_weight = snapshot.signer_stake_weight_total(operators_signature_bitmap)?;
+        let eligible_stake_weight = snapshot.total_eligible_stake_weight()?;
 
-    let signature_compressed = G1CompressedPoint(aggregated_signature);
-    let signature = G1Point::try_from(&signature_compressed)
-        .map_err(|_| NCNProgramError::G1PointDecompressionError)?;
+        let signer_stake_weight_scaled = signer_stake_weight
+            .checked_mul(MAX_QUORUM_BPS as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let required_stake_weight_scaled = eligible_stake_weight
+            .checked_mul(quorum_bps as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    // If there are no non-signers, we should verify the aggregate signature with the total G1
-    // pubkey because adding to the initial non-signers pubkey would result in error since it is
-    // initialized to all zeros and this is not a valid point of the curve BN128
-    if non_signers_count == 0 {
-        msg!("All operators signed, verifying aggregate signature with total G1 pubkey");
-        aggregated_g2_point
-            .verify_aggregated_signature::<Sha256Normalized, &[u8], G1Point>(
-                signature,
-                &message_32,
-                total_aggregated_g1_pubkey,
-            )
-            .map_err(|_| NCNProgramError::SignatureVerificationFailed)?;
+        if eligible_stake_weight == 0 || signer_stake_weight_scaled < required_stake_weight_scaled {
+            msg!(
+                "Stake quorum not met: signers stake weight {} of eligible {} is below {} bps",
+                signer_stake_weight,
+                eligible_stake_weight,
+                quorum_bps
+            );
+            return Err(NCNProgramError::SignerStakeQuorumNotMet.into());
+        }
+    }
+
+    verify_signers_signature(
+        snapshot,
+        curve,
+        aggregated_g2_point,
+        aggregated_signature,
+        operators_signature_bitmap,
+        signers_count,
+        non_signers_count,
+        message,
+        &Sha256NormalizedScheme::for_ncn(ncn.key),
+    )
+    .map_err(|e| {
+        if e == ProgramError::from(NCNProgramError::SignatureVerificationFailed) {
+            msg!("The aggregate signature does not sign the derived vote message");
+            return NCNProgramError::VoteMessageMismatch.into();
+        }
+        e
+    })
+}
+
+/// Verifies `aggregated_signature` over `message` against `aggregated_g2_point` and the G1 pubkeys
+/// of the operators set in `operators_signature_bitmap`, given how many signed and did not.
+/// Fails with `G2AggregateMismatch` when `aggregated_g2_point` is not the sum of the signers' G2
+/// pubkeys stored in the snapshot
+#[allow(clippy::too_many_arguments)]
+pub fn verify_signers_signature(
+    snapshot: &Snapshot,
+    curve: CurveId,
+    aggregated_g2_point: G2Point,
+    aggregated_signature: [u8; G1_COMPRESSED_POINT_SIZE],
+    operators_signature_bitmap: &[u8],
+    signers_count: usize,
+    non_signers_count: u64,
+    message: &[u8; 32],
+    scheme: &Sha256NormalizedScheme,
+) -> ProgramResult {
+    // Reconstruct the signers' aggregated G1 pubkey from whichever side of the bitmap is smaller.
+    // When everyone signed, the stored total already is the signers' aggregate
+    let apk1 = if non_signers_count == 0 {
+        msg!(
+            "All {} operators signed, using the total aggregated G1 pubkey",
+            signers_count
+        );
+        snapshot.total_aggregated_g1_point()?
+    } else if signers_count < non_signers_count as usize {
+        msg!("Aggregating {} signers G1 pubkeys", signers_count);
+        snapshot.aggregate_signers_by_addition(operators_signature_bitmap)?
     } else {
-        msg!("Total non signers: {}", non_signers_count);
-        let aggregated_nonsigners_pubkey =
-            aggregated_nonsigners_pubkey.ok_or(NCNProgramError::NoNonSignersAggregatedPubkey)?;
-
-        let apk1 = total_aggregated_g1_pubkey
-            .checked_add(&aggregated_nonsigners_pubkey.negate())
-            .ok_or(NCNProgramError::AltBN128AddError)?;
-
-        msg!("Aggregated non-signers G1 pubkey {:?}", apk1.0);
-        msg!("Aggregated G2 pubkey {:?}", aggregated_g2_point.0);
-
-        // One Pairing attempt
-        msg!("Verifying aggregate signature one pairing");
-        aggregated_g2_point
-            .verify_aggregated_signature::<Sha256Normalized, &[u8], G1Point>(
-                signature,
-                &message_32,
-                apk1,
-            )
-            .map_err(|_| NCNProgramError::SignatureVerificationFailed)?;
+        msg!("Subtracting {} non signers G1 pubkeys", non_signers_count);
+        let non_signer_bitmap: Vec<u8> = operators_signature_bitmap
+            .iter()
+            .map(|byte| !byte)
+            .collect();
+        snapshot.aggregate_signers_by_subtraction(&non_signer_bitmap)?
+    };
+
+    // Some pairing implementations accept any signature against the point at infinity
+    if apk1.is_identity() {
+        msg!("The aggregated signers G1 pubkey is the point at infinity");
+        return Err(NCNProgramError::NoSignersInBitmap.into());
     }
 
-    // Increment the vote counter PDA after successful signature verification
-    // NOTE: This counter could track anything
-    let mut vote_counter_data = vote_counter.try_borrow_mut_data()?;
-    let vote_counter_account = VoteCounter::try_from_slice_unchecked_mut(&mut vote_counter_data)?;
+    // The supplied G2 aggregate must be the sum of the signers' G2 keys, not just any key the
+    // signature happens to verify under
+    let signers_g2_point = snapshot.aggregate_signers_g2(operators_signature_bitmap)?;
+    if signers_g2_point.0 != aggregated_g2_point.0 {
+        msg!("The aggregated G2 pubkey does not match the signers in the bitmap");
+        return Err(NCNProgramError::G2AggregateMismatch.into());
+    }
 
-    let previous_count = vote_counter_account.count();
-    vote_counter_account.increment()?;
-    let new_count = vote_counter_account.count();
+    let signature_compressed = G1CompressedPoint(aggregated_signature);
+    let signature = curve
+        .g1_decompress(&signature_compressed)
+        .map_err(|_| NCNProgramError::G1PointDecompressionError)?;
 
-    msg!(
-        "Vote successfully cast! Counter incremented from {} to {}",
-        previous_count,
-        new_count
-    );
+    msg!("Aggregated signers G1 pubkey {:?}", apk1.0);
+    msg!("Aggregated G2 pubkey {:?}", aggregated_g2_point.0);
+
+    let message = scheme.domain_separated_message(message)?;
+
+    // One Pairing attempt
+    msg!("Verifying aggregate signature one pairing");
+    curve
+        .verify_aggregated_signature::<Sha256Normalized, &[u8]>(
+            aggregated_g2_point,
+            signature,
+            &message,
+            apk1,
+        )
+        .map_err(|_| NCNProgramError::SignatureVerificationFailed)?;
 
     Ok(())
 }
@@ -0,0 +1,200 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::get_epoch;
+use jito_restaking_core::{config::Config, ncn::Ncn};
+use ncn_program_core::{
+    config::Config as NcnConfig, error::NCNProgramError, instruction::CastVoteBatchEntry,
+    snapshot::Snapshot, utils::bitmap_iter, vote_counter::VoteCounter,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::cast_vote::{
+    check_signer_bitmap_size, check_snapshot_age, check_snapshot_finalized, verify_vote,
+};
+
+/// Casts several aggregated votes against the same snapshot in one instruction.
+///
+/// Each entry is verified exactly like `CastVote`, with the message being
+/// `NcnConfig::consensus_message` of the vote counter value at the time the entry is processed,
+/// so entry `n` signs the payload for `count + n`. The counter is
+/// incremented after every successful entry. If any entry fails the whole instruction reverts
+/// and the failing entry index is logged. Unless `allow_multiple_votes_per_epoch` is set in the
+/// config, only one vote is accepted per epoch, so a batch with more than one entry fails with
+/// `AlreadyVotedThisEpoch`. Every entry is held to the `quorum_bps` stake quorum of the config.
+/// Like `CastVote`, the batch is rejected with `SnapshotNotFinalized` until every operator of the
+/// NCN is registered in the snapshot, and with `SnapshotTooStale` when the snapshot is older than
+/// the config's `max_snapshot_age_slots`.
+///
+/// ### Parameters:
+/// - `votes`: The aggregated votes to verify, in the order they should be counted
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account (named `ncn_config` in code)
+/// 2. `[]` ncn: The NCN account
+/// 3. `[]` snapshot: Snapshot containing stakes and operator snapshots
+/// 4. `[]` restaking_config: Restaking configuration account
+/// 5. `[writable]` vote_counter: Vote counter PDA to increment on each successful vote
+/// 6. `[]` ncn_operator_states: Remaining accounts, the signers' `NcnOperatorState` accounts
+///    for every entry, grouped per entry in batch order, as `CastVote` expects them. Accounts
+///    left over after the last entry are rejected with `InvalidInputLength`
+pub fn process_cast_vote_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    votes: Vec<CastVoteBatchEntry>,
+) -> ProgramResult {
+    for vote in votes.iter() {
+        check_signer_bitmap_size(&vote.operators_signature_bitmap)?;
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let ncn_config = next_account_info(account_info_iter)?;
+    let ncn = next_account_info(account_info_iter)?;
+    let snapshot = next_account_info(account_info_iter)?;
+    let restaking_config = next_account_info(account_info_iter)?;
+    let vote_counter = next_account_info(account_info_iter)?;
+    let ncn_operator_states = account_info_iter.as_slice();
+
+    NcnConfig::load(program_id, ncn_config, ncn.key, false)?;
+    Config::load(&jito_restaking_program::id(), restaking_config, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, false)?;
+    VoteCounter::load(program_id, vote_counter, ncn.key, true)?;
+
+    check_snapshot_finalized(ncn, snapshot)?;
+
+    if votes.is_empty() {
+        msg!("Vote batch is empty");
+        return Err(NCNProgramError::InvalidInputLength.into());
+    }
+
+    let (
+        enforce_signer_order,
+        curve,
+        minimum_operators,
+        reject_duplicate_aggregates,
+        allow_multiple_votes_per_epoch,
+        quorum_bps,
+        ncn_epoch_length,
+    ) = {
+        let ncn_config_data = ncn_config.data.borrow();
+        let ncn_config = NcnConfig::try_from_slice_unchecked(&ncn_config_data)?;
+        (
+            ncn_config.enforce_signer_order(),
+            ncn_config.curve()?,
+            ncn_config.minimum_operators(),
+            ncn_config.reject_duplicate_aggregates(),
+            ncn_config.allow_multiple_votes_per_epoch(),
+            ncn_config.effective_quorum_bps(None),
+            ncn_config.epoch_length(),
+        )
+    };
+
+    let current_slot = Clock::get()?.slot;
+    check_snapshot_age(ncn_config, snapshot, current_slot)?;
+
+    let current_epoch = get_epoch(current_slot, ncn_epoch_length)?;
+
+    let snapshot_data = snapshot.data.borrow();
+    let snapshot = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
+
+    msg!("Total operators: {}", snapshot.operators_registered());
+    msg!("Votes in batch: {}", votes.len());
+
+    let mut ncn_operator_states_offset: usize = 0;
+
+    for (index, vote) in votes.into_iter().enumerate() {
+        let signers = bitmap_iter(
+            &vote.operators_signature_bitmap,
+            snapshot.operator_slot_count(),
+        )
+        .filter(|signed| *signed)
+        .count();
+        let entry_ncn_operator_states_end = ncn_operator_states_offset
+            .checked_add(signers)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let entry_ncn_operator_states = ncn_operator_states
+            .get(ncn_operator_states_offset..entry_ncn_operator_states_end)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        ncn_operator_states_offset = entry_ncn_operator_states_end;
+
+        let current_count = {
+            let vote_counter_data = vote_counter.data.borrow();
+            let vote_counter_account = VoteCounter::try_from_slice_unchecked(&vote_counter_data)?;
+            if reject_duplicate_aggregates {
+                vote_counter_account
+                    .check_aggregate_not_duplicate(&vote.aggregated_signature)
+                    .map_err(|e| {
+                        msg!("Vote batch entry {} repeats the last aggregate", index);
+                        e
+                    })?;
+            }
+            vote_counter_account.count()
+        };
+
+        let message = NcnConfig::consensus_message(ncn.key, current_epoch, current_count);
+
+        verify_vote(
+            ncn,
+            snapshot,
+            entry_ncn_operator_states,
+            enforce_signer_order,
+            curve,
+            minimum_operators,
+            current_slot,
+            ncn_epoch_length,
+            vote.aggregated_g2,
+            vote.aggregated_signature,
+            &vote.operators_signature_bitmap,
+            quorum_bps,
+            &message,
+        )
+        .map_err(|e| {
+            msg!("Vote batch entry {} failed", index);
+            e
+        })?;
+
+        let mut vote_counter_data = vote_counter.try_borrow_mut_data()?;
+        let vote_counter_account =
+            VoteCounter::try_from_slice_unchecked_mut(&mut vote_counter_data)?;
+        if !allow_multiple_votes_per_epoch {
+            vote_counter_account
+                .check_not_voted_in_epoch(current_epoch)
+                .map_err(|e| {
+                    msg!("Vote batch entry {} repeats this epoch's vote", index);
+                    e
+                })?;
+        }
+        vote_counter_account.increment()?;
+        vote_counter_account.record_vote(current_epoch, current_slot)?;
+        vote_counter_account.record_aggregate(&vote.aggregated_signature);
+        vote_counter_account.record_message(&message);
+        vote_counter_account.record_consensus(
+            current_epoch,
+            snapshot.last_snapshot_slot(),
+            current_slot,
+        )?;
+
+        msg!(
+            "Vote batch entry {} cast! Counter incremented to {}",
+            index,
+            vote_counter_account.count()
+        );
+    }
+
+    if ncn_operator_states_offset != ncn_operator_states.len() {
+        msg!(
+            "{} ncn operator state accounts passed after the last vote batch entry",
+            ncn_operator_states.len() - ncn_operator_states_offset
+        );
+        return Err(NCNProgramError::InvalidInputLength.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::{config::Config, ncn::Ncn};
+use ncn_program_core::{
+    account_payer::AccountPayer, config::Config as NcnConfig, error::NCNProgramError,
+    loaders::load_ncn_epoch_and_snapshot_epoch, snapshot::Snapshot,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+/// Closes the snapshot account and returns its rent to the account payer.
+///
+/// The snapshot can only be closed once `epochs_after_consensus_before_close` epochs have passed
+/// since it was last updated, and only after every operator of the NCN has been registered in it.
+/// Deregistered operators count toward the NCN's operators, see `Snapshot::has_every_operator`.
+/// A new snapshot can be initialized for a later epoch once it is closed.
+///
+/// ### Accounts:
+/// 1. `[]` config: The NCN program configuration
+/// 2. `[]` ncn: The NCN account
+/// 3. `[writable]` snapshot: The snapshot account to close
+/// 4. `[writable]` account_payer: Account receiving the snapshot's lamports
+/// 5. `[]` restaking_config: Restaking configuration account
+pub fn process_close_snapshot(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [config, ncn, snapshot, account_payer, restaking_config] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    NcnConfig::load(program_id, config, ncn.key, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    Snapshot::load_to_close(program_id, snapshot, ncn.key)?;
+    AccountPayer::load(program_id, account_payer, ncn.key, true)?;
+    Config::load(&jito_restaking_program::id(), restaking_config, false)?;
+
+    let epochs_after_consensus_before_close = {
+        let config_data = config.try_borrow_data()?;
+        let config_account = NcnConfig::try_from_slice_unchecked(&config_data)?;
+        config_account.epochs_after_consensus_before_close()
+    };
+
+    let ncn_operator_count = {
+        let ncn_data = ncn.try_borrow_data()?;
+        let ncn_account = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        ncn_account.operator_count()
+    };
+
+    let (has_every_operator, operators_registered, operators_deregistered, last_update_slot) = {
+        let snapshot_data = snapshot.try_borrow_data()?;
+        let snapshot_account = Snapshot::try_from_slice_unchecked(&snapshot_data)?;
+        (
+            snapshot_account.has_every_operator(ncn_operator_count),
+            snapshot_account.operators_registered(),
+            snapshot_account.operators_deregistered(),
+            snapshot_account
+                .slot_created()
+                .max(snapshot_account.last_snapshot_slot()),
+        )
+    };
+
+    if !has_every_operator {
+        msg!(
+            "Error: Snapshot still has unregistered operators. Registered: {}, Deregistered: {}, NCN operators: {}",
+            operators_registered,
+            operators_deregistered,
+            ncn_operator_count
+        );
+        return Err(NCNProgramError::CannotCloseAccount.into());
+    }
+
+    let current_slot = Clock::get()?.slot;
+    let (current_epoch, snapshot_epoch) =
+        load_ncn_epoch_and_snapshot_epoch(restaking_config, current_slot, last_update_slot)?;
+
+    let closable_epoch = snapshot_epoch
+        .checked_add(epochs_after_consensus_before_close)
+        .ok_or(NCNProgramError::ArithmeticOverflow)?;
+
+    if current_epoch < closable_epoch {
+        msg!(
+            "Error: Snapshot can only be closed from epoch {}, current epoch: {}",
+            closable_epoch,
+            current_epoch
+        );
+        return Err(NCNProgramError::CannotCloseAccountNotEnoughEpochs.into());
+    }
+
+    snapshot.try_borrow_mut_data()?.fill(0);
+
+    AccountPayer::close_account(program_id, account_payer, snapshot)
+}
@@ -0,0 +1,81 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::operator::Operator;
+use ncn_program_core::{
+    account_payer::AccountPayer, config::Config, ncn_operator_account::NCNOperatorAccount,
+    snapshot::Snapshot,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+/// Deregisters an operator from the NCN program.
+///
+/// The operator's G1 pubkey is subtracted from the snapshot's total aggregated pubkey, its slot in
+/// the snapshot is freed and its ncn operator account is closed, returning the rent to the account
+/// payer. The operator can register again afterwards.
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[writable]` ncn_operator_account: The ncn operator account PDA account to close
+/// 3. `[]` ncn: The NCN account
+/// 4. `[]` operator: The operator to deregister
+/// 5. `[signer]` operator_admin: The operator admin that must sign
+/// 6. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 7. `[writable]` account_payer: Account receiving the ncn operator account's lamports
+pub fn process_deregister_operator(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [config, ncn_operator_account, ncn, operator, operator_admin, snapshot, account_payer] =
+        accounts
+    else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    Config::load(program_id, config, ncn.key, false)?;
+    NCNOperatorAccount::load(
+        program_id,
+        ncn_operator_account,
+        ncn.key,
+        operator.key,
+        true,
+    )?;
+    Operator::load(&jito_restaking_program::id(), operator, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, true)?;
+    AccountPayer::load(program_id, account_payer, ncn.key, true)?;
+
+    // Verify that the operator_admin is authorized to deregister this operator
+    {
+        let operator_data = operator.data.borrow();
+        let operator_account = Operator::try_from_slice_unchecked(&operator_data)?;
+
+        if operator_account.admin.ne(operator_admin.key) {
+            msg!("Error: Operator admin is not authorized to deregister this operator");
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !operator_admin.is_signer {
+            msg!("Error: Operator admin must sign the transaction");
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    let slot = Clock::get()?.slot;
+
+    {
+        let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+        let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+        snapshot_account.deregister_operator(operator.key, slot)?;
+    }
+
+    ncn_operator_account.try_borrow_mut_data()?.fill(0);
+    AccountPayer::close_account(program_id, account_payer, ncn_operator_account)?;
+
+    msg!("Operator {} deregistered successfully", operator.key);
+
+    Ok(())
+}
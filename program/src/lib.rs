@@ -1,25 +1,39 @@
 mod admin_initialize_config;
 mod admin_register_st_mint;
+mod admin_repair_config;
+mod admin_set_fee_wallet;
 mod admin_set_new_admin;
 mod admin_set_parameters;
+mod admin_update_snapshot_minimum_stake;
+mod batch_initialize_operator_snapshots;
+mod cast_ed25519_vote;
 mod cast_vote;
+mod cast_vote_batch;
+mod close_snapshot;
+mod deregister_operator;
 
 mod initialize_snapshot;
 
 mod initialize_vault_registry;
 mod initialize_vote_counter;
+mod prove_non_signer;
 mod realloc_snapshot;
+mod refresh_operator_key;
 
 mod register_operator;
 mod register_vault;
+mod rollover_snapshot;
+mod set_operator_active;
 mod snapshot_vault_operator_delegation;
+mod snapshot_vault_operator_delegation_batch;
 mod update_operator_bn128_keys;
 mod update_operator_ip_port;
 
+use admin_set_fee_wallet::process_admin_set_fee_wallet;
 use admin_set_new_admin::process_admin_set_new_admin;
 use borsh::BorshDeserialize;
 
-use ncn_program_core::instruction::NCNProgramInstruction;
+use ncn_program_core::{instruction::NCNProgramInstruction, vault_registry::StMintEntry};
 use solana_program::{
     account_info::AccountInfo, declare_id, entrypoint::ProgramResult, msg,
     program_error::ProgramError, pubkey::Pubkey,
@@ -30,13 +44,22 @@ use solana_security_txt::security_txt;
 use crate::{
     admin_initialize_config::process_admin_initialize_config,
     admin_register_st_mint::process_admin_register_st_mint,
-    admin_set_parameters::process_admin_set_parameters, cast_vote::process_cast_vote,
+    admin_repair_config::process_admin_repair_config,
+    admin_set_parameters::process_admin_set_parameters,
+    admin_update_snapshot_minimum_stake::process_admin_update_snapshot_minimum_stake,
+    batch_initialize_operator_snapshots::process_batch_initialize_operator_snapshots,
+    cast_ed25519_vote::process_cast_ed25519_vote, cast_vote::process_cast_vote,
+    cast_vote_batch::process_cast_vote_batch, close_snapshot::process_close_snapshot,
+    deregister_operator::process_deregister_operator,
     initialize_snapshot::process_initialize_snapshot,
     initialize_vault_registry::process_initialize_vault_registry,
     initialize_vote_counter::process_initialize_vote_counter,
-    realloc_snapshot::process_realloc_snapshot, register_operator::process_register_operator,
-    register_vault::process_register_vault,
+    prove_non_signer::process_prove_non_signer, realloc_snapshot::process_realloc_snapshot,
+    refresh_operator_key::process_refresh_operator_key,
+    register_operator::process_register_operator, register_vault::process_register_vault,
+    rollover_snapshot::process_rollover_snapshot, set_operator_active::process_set_operator_active,
     snapshot_vault_operator_delegation::process_snapshot_vault_operator_delegation,
+    snapshot_vault_operator_delegation_batch::process_snapshot_vault_operator_delegation_batch,
     update_operator_bn128_keys::process_update_operator_bn128_keys,
     update_operator_ip_port::process_update_operator_ip_port,
 };
@@ -79,6 +102,10 @@ pub fn process_instruction(
             valid_slots_after_consensus,
             minimum_stake,
             ncn_fee_bps,
+            curve_id,
+            minimum_operators,
+            quorum_bps,
+            max_snapshot_age_slots,
         } => {
             msg!("Instruction: InitializeConfig");
             process_admin_initialize_config(
@@ -89,6 +116,10 @@ pub fn process_instruction(
                 valid_slots_after_consensus,
                 minimum_stake,
                 ncn_fee_bps,
+                curve_id,
+                minimum_operators,
+                quorum_bps,
+                max_snapshot_age_slots,
             )
         }
         NCNProgramInstruction::InitializeVaultRegistry => {
@@ -153,6 +184,7 @@ pub fn process_instruction(
             aggregated_g2,
             aggregated_signature,
             operators_signature_bitmap,
+            quorum_bps,
         } => {
             msg!("Instruction: CastVote");
             process_cast_vote(
@@ -161,8 +193,13 @@ pub fn process_instruction(
                 aggregated_g2,
                 aggregated_signature,
                 operators_signature_bitmap,
+                quorum_bps,
             )
         }
+        NCNProgramInstruction::CastVoteBatch { votes } => {
+            msg!("Instruction: CastVoteBatch");
+            process_cast_vote_batch(program_id, accounts, votes)
+        }
 
         // ---------------------------------------------------- //
         //                        ADMIN                         //
@@ -173,6 +210,13 @@ pub fn process_instruction(
             epochs_after_consensus_before_close,
             valid_slots_after_consensus,
             minimum_stake,
+            enforce_signer_order,
+            minimum_operators,
+            reject_duplicate_aggregates,
+            allow_multiple_votes_per_epoch,
+            quorum_bps,
+            max_snapshot_age_slots,
+            allow_ed25519_votes,
         } => {
             msg!("Instruction: AdminSetParameters");
             process_admin_set_parameters(
@@ -183,6 +227,13 @@ pub fn process_instruction(
                 epochs_after_consensus_before_close,
                 minimum_stake,
                 valid_slots_after_consensus,
+                enforce_signer_order,
+                minimum_operators,
+                reject_duplicate_aggregates,
+                allow_multiple_votes_per_epoch,
+                quorum_bps,
+                max_snapshot_age_slots,
+                allow_ed25519_votes,
             )
         }
         NCNProgramInstruction::AdminSetNewAdmin { role } => {
@@ -191,7 +242,87 @@ pub fn process_instruction(
         }
         NCNProgramInstruction::AdminRegisterStMint {} => {
             msg!("Instruction: AdminRegisterStMint");
-            process_admin_register_st_mint(program_id, accounts)
+            process_admin_register_st_mint(program_id, accounts, StMintEntry::DEFAULT_WEIGHT)
+        }
+        NCNProgramInstruction::AdminRepairConfig {
+            epochs_before_stall,
+            epochs_after_consensus_before_close,
+            valid_slots_after_consensus,
+            minimum_stake,
+            ncn_fee_bps,
+        } => {
+            msg!("Instruction: AdminRepairConfig");
+            process_admin_repair_config(
+                program_id,
+                accounts,
+                epochs_before_stall,
+                epochs_after_consensus_before_close,
+                valid_slots_after_consensus,
+                minimum_stake,
+                ncn_fee_bps,
+            )
+        }
+
+        // ---------------------------------------------------- //
+        //                        CLOSE                         //
+        // ---------------------------------------------------- //
+        NCNProgramInstruction::CloseSnapshot {} => {
+            msg!("Instruction: CloseSnapshot");
+            process_close_snapshot(program_id, accounts)
+        }
+        NCNProgramInstruction::DeregisterOperator {} => {
+            msg!("Instruction: DeregisterOperator");
+            process_deregister_operator(program_id, accounts)
+        }
+        NCNProgramInstruction::RefreshOperatorKey {} => {
+            msg!("Instruction: RefreshOperatorKey");
+            process_refresh_operator_key(program_id, accounts)
+        }
+        NCNProgramInstruction::RolloverSnapshot {} => {
+            msg!("Instruction: RolloverSnapshot");
+            process_rollover_snapshot(program_id, accounts)
+        }
+        NCNProgramInstruction::ProveNonSigner {
+            aggregated_signature,
+            aggregated_g2,
+            operators_signature_bitmap,
+        } => {
+            msg!("Instruction: ProveNonSigner");
+            process_prove_non_signer(
+                program_id,
+                accounts,
+                aggregated_signature,
+                aggregated_g2,
+                operators_signature_bitmap,
+            )
+        }
+        NCNProgramInstruction::SnapshotVaultOperatorDelegationBatch { operator_indices } => {
+            msg!("Instruction: SnapshotVaultOperatorDelegationBatch");
+            process_snapshot_vault_operator_delegation_batch(program_id, accounts, operator_indices)
+        }
+        NCNProgramInstruction::AdminUpdateSnapshotMinimumStake { minimum_stake } => {
+            msg!("Instruction: AdminUpdateSnapshotMinimumStake");
+            process_admin_update_snapshot_minimum_stake(program_id, accounts, minimum_stake)
+        }
+        NCNProgramInstruction::BatchInitializeOperatorSnapshots { operators } => {
+            msg!("Instruction: BatchInitializeOperatorSnapshots");
+            process_batch_initialize_operator_snapshots(program_id, accounts, operators)
+        }
+        NCNProgramInstruction::SetOperatorActive { active } => {
+            msg!("Instruction: SetOperatorActive");
+            process_set_operator_active(program_id, accounts, active)
+        }
+        NCNProgramInstruction::AdminSetFeeWallet { new_wallet } => {
+            msg!("Instruction: AdminSetFeeWallet");
+            process_admin_set_fee_wallet(program_id, accounts, new_wallet)
+        }
+        NCNProgramInstruction::AdminRegisterStMintWithWeight { weight } => {
+            msg!("Instruction: AdminRegisterStMintWithWeight");
+            process_admin_register_st_mint(program_id, accounts, weight)
+        }
+        NCNProgramInstruction::CastEd25519Vote {} => {
+            msg!("Instruction: CastEd25519Vote");
+            process_cast_ed25519_vote(program_id, accounts)
         }
     }
 }
@@ -0,0 +1,143 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::{ncn::Ncn, operator::Operator};
+use ncn_program_core::{
+    config::Config as NcnConfig,
+    constants::{G1_COMPRESSED_POINT_SIZE, G2_COMPRESSED_POINT_SIZE},
+    error::NCNProgramError,
+    g2_point::G2CompressedPoint,
+    schemes::Sha256NormalizedScheme,
+    snapshot::Snapshot,
+    utils::bitmap_iter,
+    vote_counter::VoteCounter,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::cast_vote::{
+    check_signer_bitmap_size, check_signer_bitmap_slots, verify_signers_signature,
+};
+
+/// Proves that an operator did not sign the message of the last successful vote.
+///
+/// The message is `NcnConfig::consensus_message` of the NCN and the epoch and counter value of
+/// the vote counter's last vote, derived on-chain so only a message that reached consensus can
+/// flag an operator. Fails with `ConsensusNotReached` before the first vote. The aggregated
+/// signature over the message must verify against the signers in `operators_signature_bitmap`,
+/// otherwise the proof fails with `VoteMessageMismatch`, and the active non-signers must stay
+/// within a third of the active operators, as in `CastVote`. The operator's bit must be unset, in
+/// which case its operator snapshot is flagged with `missed_last_vote`. Permissionless, as the
+/// building block for slashing.
+///
+/// ### Parameters:
+/// - `aggregated_signature`: Aggregated G1 signature in compressed format (32 bytes)
+/// - `aggregated_g2`: Aggregated G2 public key of the signers in compressed format (64 bytes)
+/// - `operators_signature_bitmap`: Bitmap indicating which operators signed the message
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[]` ncn: The NCN account
+/// 3. `[]` operator: The operator proven to have not signed
+/// 4. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 5. `[]` vote_counter: Vote counter PDA holding the last vote
+pub fn process_prove_non_signer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    aggregated_signature: [u8; G1_COMPRESSED_POINT_SIZE],
+    aggregated_g2: [u8; G2_COMPRESSED_POINT_SIZE],
+    operators_signature_bitmap: Vec<u8>,
+) -> ProgramResult {
+    check_signer_bitmap_size(&operators_signature_bitmap)?;
+
+    let [config, ncn, operator, snapshot, vote_counter] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    NcnConfig::load(program_id, config, ncn.key, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    Operator::load(&jito_restaking_program::id(), operator, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, true)?;
+    VoteCounter::load(program_id, vote_counter, ncn.key, false)?;
+
+    let curve = {
+        let config_data = config.data.borrow();
+        let config = NcnConfig::try_from_slice_unchecked(&config_data)?;
+        config.curve()?
+    };
+
+    let message = {
+        let vote_counter_data = vote_counter.data.borrow();
+        let vote_counter_account = VoteCounter::try_from_slice_unchecked(&vote_counter_data)?;
+        let Some((last_vote_epoch, last_vote_count)) = vote_counter_account.last_vote() else {
+            msg!("Error: No vote has reached consensus yet");
+            return Err(NCNProgramError::ConsensusNotReached.into());
+        };
+        NcnConfig::consensus_message(ncn.key, last_vote_epoch, last_vote_count)
+    };
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    check_signer_bitmap_slots(snapshot_account, &operators_signature_bitmap)?;
+
+    let operators_registered = snapshot_account.operators_registered();
+    let active_operators_count = snapshot_account.active_operators_count();
+    let active_non_signers_count = snapshot_account
+        .non_signers(&operators_signature_bitmap)
+        .len() as u64;
+    let signers_count = bitmap_iter(
+        &operators_signature_bitmap,
+        snapshot_account.operator_slot_count(),
+    )
+    .filter(|signed| *signed)
+    .count();
+    let non_signers_count = operators_registered
+        .checked_sub(signers_count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if active_non_signers_count > active_operators_count / 3 {
+        msg!(
+            "Quorum not met: non-signers count ({}) exceeds 1/3 of active operators ({})",
+            active_non_signers_count,
+            active_operators_count
+        );
+        return Err(NCNProgramError::QuorumNotMet.into());
+    }
+
+    let aggregated_g2_point = curve
+        .g2_decompress(G2CompressedPoint::from(aggregated_g2))
+        .map_err(|_| NCNProgramError::G2PointDecompressionError)?;
+
+    verify_signers_signature(
+        snapshot_account,
+        curve,
+        aggregated_g2_point,
+        aggregated_signature,
+        &operators_signature_bitmap,
+        signers_count,
+        non_signers_count,
+        &message,
+        &Sha256NormalizedScheme::for_ncn(ncn.key),
+    )
+    .map_err(|e| {
+        if e == ProgramError::from(NCNProgramError::SignatureVerificationFailed) {
+            msg!("The aggregate signature does not sign the last consensus message");
+            return NCNProgramError::VoteMessageMismatch.into();
+        }
+        e
+    })?;
+
+    snapshot_account
+        .record_missed_vote(operator.key, &operators_signature_bitmap)
+        .map_err(|e| {
+            if e == NCNProgramError::OperatorSignedMessage {
+                msg!("Error: Operator {} did not miss the vote", operator.key);
+            }
+            e
+        })?;
+
+    msg!("Operator {} proven to have missed the vote", operator.key);
+
+    Ok(())
+}
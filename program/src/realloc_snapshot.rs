@@ -11,6 +11,10 @@ use solana_program::{
 
 /// Reallocates the snapshot account to its full size and initializes the data structure.
 ///
+/// Idempotent, so it can be called more times than needed: once the account is at full size and
+/// initialized it returns without charging the account payer, and the data structure is only
+/// initialized the first time the full size is reached.
+///
 /// ### Accounts:
 /// 1. `[]` ncn: The NCN account
 /// 2. `[]` config: The NCN program configuration
@@ -36,33 +40,36 @@ pub fn process_realloc_snapshot(program_id: &Pubkey, accounts: &[AccountInfo]) -
     }
 
     let snapshot_size = snapshot.data_len();
-    if snapshot_size < Snapshot::SIZE {
+    if snapshot_size >= Snapshot::SIZE {
+        if snapshot.try_borrow_data()?[0] == Snapshot::DISCRIMINATOR {
+            msg!("Snapshot already at full size and initialized, nothing to do");
+            return Ok(());
+        }
+    } else {
         let new_size = get_new_size(snapshot_size, Snapshot::SIZE)?;
         AccountPayer::pay_and_realloc(program_id, ncn.key, account_payer, snapshot, new_size)?;
-    } else {
-        msg!("Snapshot size is sufficient, no reallocation needed");
+
+        if snapshot.data_len() < Snapshot::SIZE {
+            msg!("Snapshot reallocated to {} bytes", snapshot.data_len());
+            return Ok(());
+        }
     }
 
-    let should_initialize = snapshot.data_len() >= Snapshot::SIZE
-        && snapshot.try_borrow_data()?[0] != Snapshot::DISCRIMINATOR;
+    let current_slot = Clock::get()?.slot;
 
-    if should_initialize {
-        let current_slot = Clock::get()?.slot;
+    let minimum_stake = {
+        let config_data = config.try_borrow_data()?;
+        let config_account = Config::try_from_slice_unchecked(&config_data)?;
+        *config_account.minimum_stake()
+    };
 
-        let minimum_stake = {
-            let config_data = config.try_borrow_data()?;
-            let config_account = Config::try_from_slice_unchecked(&config_data)?;
-            *config_account.minimum_stake()
-        };
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    snapshot_data[0] = Snapshot::DISCRIMINATOR;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
 
-        let mut snapshot_data = snapshot.try_borrow_mut_data()?;
-        snapshot_data[0] = Snapshot::DISCRIMINATOR;
-        let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+    snapshot_account.initialize(ncn.key, snapshot_bump, current_slot, minimum_stake);
 
-        snapshot_account.initialize(ncn.key, snapshot_bump, current_slot, minimum_stake);
-    } else {
-        msg!("Snapshot already initialized, skipping initialization");
-    }
+    msg!("Snapshot reached full size and was initialized");
 
     Ok(())
 }
@@ -0,0 +1,60 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::operator::Operator;
+use ncn_program_core::{
+    config::Config, ncn_operator_account::NCNOperatorAccount, snapshot::Snapshot,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Copies an operator's BN128 keys from its ncn operator account into the snapshot.
+///
+/// The old G1 pubkey is subtracted from the snapshot's total aggregated pubkey and the new one is
+/// added. Permissionless, since the ncn operator account is the source of truth for the keys.
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[]` ncn_operator_account: The ncn operator account holding the operator's current keys
+/// 3. `[]` ncn: The NCN account
+/// 4. `[]` operator: The operator whose keys are refreshed
+/// 5. `[writable]` snapshot: Snapshot account containing operator snapshots
+pub fn process_refresh_operator_key(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let [config, ncn_operator_account, ncn, operator, snapshot] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    Config::load(program_id, config, ncn.key, false)?;
+    NCNOperatorAccount::load(
+        program_id,
+        ncn_operator_account,
+        ncn.key,
+        operator.key,
+        false,
+    )?;
+    Operator::load(&jito_restaking_program::id(), operator, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, true)?;
+
+    let (g1_pubkey, g2_pubkey) = {
+        let ncn_operator_account_data = ncn_operator_account.data.borrow();
+        let ncn_operator_account =
+            NCNOperatorAccount::try_from_slice_unchecked(&ncn_operator_account_data)?;
+        (
+            ncn_operator_account.g1_pubkey,
+            ncn_operator_account.g2_pubkey,
+        )
+    };
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    snapshot_account.refresh_operator_keys(operator.key, &g1_pubkey, &g2_pubkey)?;
+
+    msg!("Operator {} keys refreshed in snapshot", operator.key);
+
+    Ok(())
+}
@@ -9,7 +9,8 @@ use ncn_program_core::{
     config::Config,
     constants::{G1_COMPRESSED_POINT_SIZE, G2_COMPRESSED_POINT_SIZE, MAX_OPERATORS},
     error::NCNProgramError,
-    g1_point::G1Point,
+    events::SnapshotFinalizedEvent,
+    g1_point::{G1CompressedPoint, G1Point},
     g2_point::{G2CompressedPoint, G2Point},
     loaders::load_ncn_epoch,
     ncn_operator_account::NCNOperatorAccount,
@@ -26,6 +27,9 @@ use solana_program::{
 
 /// Registers an operator by creating a new PDA account with BLS key verification.
 ///
+/// Registering the last operator of the NCN finalizes the snapshot, see
+/// `record_snapshot_finalization`.
+///
 /// ### Parameters:
 /// - `g1_pubkey`: G1 public key in compressed format (32 bytes)
 /// - `g2_pubkey`: G2 public key in compressed format (64 bytes)
@@ -65,13 +69,6 @@ pub fn process_register_operator(
     AccountPayer::load(program_id, account_payer, ncn.key, true)?;
     load_system_program(system_program)?;
     Snapshot::load(program_id, snapshot, ncn.key, true)?;
-    NcnOperatorState::load(
-        &jito_restaking_program::id(),
-        ncn_operator_state,
-        ncn,
-        operator,
-        false,
-    )?;
 
     // Verify that the operator_admin is authorized to register this operator
     {
@@ -92,6 +89,73 @@ pub fn process_register_operator(
     let current_slot = Clock::get()?.slot;
     let (_, ncn_epoch_length) = load_ncn_epoch(restaking_config, current_slot, None)?;
 
+    let operator_snapshot = register_operator_keys(
+        program_id,
+        ncn,
+        operator,
+        ncn_operator_state,
+        ncn_operator_account,
+        account_payer,
+        system_program,
+        current_slot,
+        ncn_epoch_length,
+        g1_pubkey,
+        g2_pubkey,
+        signature,
+        ip_address,
+        port,
+    )?;
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    // Add the operator snapshot to the snapshot
+    snapshot_account.add_operator_snapshot(operator_snapshot, current_slot)?;
+
+    snapshot_account.register_operator_g1_pubkey(&g1_pubkey)?;
+
+    msg!(
+        "Operator registered successfully with index {}",
+        operator_snapshot.ncn_operator_index()
+    );
+
+    record_snapshot_finalization(ncn, snapshot_account, current_slot)?;
+
+    Ok(())
+}
+
+/// Runs the per-operator checks of registration and creates the operator's ncn operator account,
+/// shared by `RegisterOperator` and `BatchInitializeOperatorSnapshots`.
+///
+/// The operator <> NCN connection in `ncn_operator_state` must be active, `g1_pubkey` and
+/// `g2_pubkey` must come from the same private key and `signature` must prove possession of it.
+/// Returns the operator snapshot to add to the snapshot, indexed by the NCN operator index of
+/// `ncn_operator_state`.
+#[allow(clippy::too_many_arguments)]
+pub fn register_operator_keys(
+    program_id: &Pubkey,
+    ncn: &AccountInfo,
+    operator: &AccountInfo,
+    ncn_operator_state: &AccountInfo,
+    ncn_operator_account: &AccountInfo,
+    account_payer: &AccountInfo,
+    system_program: &AccountInfo,
+    current_slot: u64,
+    ncn_epoch_length: u64,
+    g1_pubkey: [u8; G1_COMPRESSED_POINT_SIZE],
+    g2_pubkey: [u8; G2_COMPRESSED_POINT_SIZE],
+    signature: [u8; 64],
+    ip_address: [u8; 4],
+    port: u16,
+) -> Result<OperatorSnapshot, ProgramError> {
+    NcnOperatorState::load(
+        &jito_restaking_program::id(),
+        ncn_operator_state,
+        ncn,
+        operator,
+        false,
+    )?;
+
     // Check if the operator <> NCN connection is active
     let is_active = {
         let ncn_operator_state_data = ncn_operator_state.data.borrow();
@@ -124,15 +188,28 @@ pub fn process_register_operator(
 
     // Verify BLS signature: signature should be G1 pubkey signed by G2 private key
     {
-        let signature = G1Point::from(signature);
+        let g1_compressed = G1CompressedPoint::from(g1_pubkey);
         let g2_compressed = G2CompressedPoint::from(g2_pubkey);
+        let signature = G1Point::from(signature);
+
+        let g1_point = G1Point::try_from(&g1_compressed)
+            .map_err(|_| NCNProgramError::G1PointDecompressionError)?;
         let g2_point = G2Point::try_from(g2_compressed)
             .map_err(|_| NCNProgramError::G2PointDecompressionError)?;
 
-        g2_point
-            .verify_operator_registeration(signature, g1_pubkey)
+        // First verify that G1 and G2 are from the same private key
+        let keypair_valid = g1_point
+            .verify_g2(&g2_point)
             .map_err(|_| NCNProgramError::BLSVerificationError)?;
 
+        if !keypair_valid {
+            msg!("Error: G1 and G2 public keys are not from the same private key");
+            return Err(ProgramError::from(NCNProgramError::BLSVerificationError));
+        }
+
+        // Proof of possession: the G1 pubkey signed by the G2 private key
+        g2_point.verify_proof_of_possession(signature, g1_pubkey)?;
+
         msg!("BLS signature verification successful");
     }
 
@@ -158,9 +235,6 @@ pub fn process_register_operator(
         &ncn_operator_account_seeds,
     )?;
 
-    let clock = Clock::get()?;
-    let slot = clock.slot;
-
     let operator_index = {
         let operator_data = operator.data.borrow();
         let operator_account = Operator::try_from_slice_unchecked(&operator_data)?;
@@ -198,13 +272,12 @@ pub fn process_register_operator(
         &g1_pubkey,
         &g2_pubkey,
         ncn_operator_index,
-        slot,
+        current_slot,
         ip_address,
         port,
         ncn_operator_account_bump,
     );
 
-    // Create operator snapshot and add it to the snapshot
     let operator_snapshot = OperatorSnapshot::new(
         operator.key,
         current_slot,
@@ -212,20 +285,38 @@ pub fn process_register_operator(
         ncn_operator_index,
         operator_index,
         g1_pubkey,
+        g2_pubkey,
     )?;
 
-    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
-    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
-
-    // Add the operator snapshot to the snapshot
-    snapshot_account.add_operator_snapshot(operator_snapshot, slot)?;
+    Ok(operator_snapshot)
+}
 
-    snapshot_account.register_operator_g1_pubkey(&g1_pubkey)?;
+/// Records `slot` as the finalized slot of the snapshot once every operator of the NCN is
+/// registered, and emits `SnapshotFinalizedEvent` the first time it happens
+pub fn record_snapshot_finalization(
+    ncn: &AccountInfo,
+    snapshot: &mut Snapshot,
+    slot: u64,
+) -> ProgramResult {
+    let ncn_operator_count = {
+        let ncn_data = ncn.try_borrow_data()?;
+        let ncn_account = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        ncn_account.operator_count()
+    };
 
-    msg!(
-        "Operator registered successfully with index {}",
-        ncn_operator_index
-    );
+    if snapshot.record_finalization(ncn_operator_count, slot) {
+        msg!(
+            "Snapshot finalized at slot {} with {} operators",
+            slot,
+            snapshot.operators_registered()
+        );
+        SnapshotFinalizedEvent {
+            ncn: *ncn.key,
+            slot_finalized: slot,
+            operators_registered: snapshot.operators_registered(),
+        }
+        .emit();
+    }
 
     Ok(())
 }
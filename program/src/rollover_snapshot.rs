@@ -0,0 +1,42 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::{config::Config, ncn::Ncn};
+use ncn_program_core::{config::Config as NcnConfig, loaders::load_ncn_epoch, snapshot::Snapshot};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+/// Rolls the snapshot over into a new NCN epoch without a full re-snapshot.
+///
+/// For every active operator snapshot the next epoch stake weight becomes the current stake
+/// weight and the minimum stake check is recomputed. Permissionless, but can only run once per
+/// NCN epoch.
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[]` restaking_config: Restaking configuration account
+/// 3. `[]` ncn: The NCN account
+/// 4. `[writable]` snapshot: Snapshot account containing operator snapshots
+pub fn process_rollover_snapshot(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [config, restaking_config, ncn, snapshot] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    NcnConfig::load(program_id, config, ncn.key, false)?;
+    Config::load(&jito_restaking_program::id(), restaking_config, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, true)?;
+
+    let current_slot = Clock::get()?.slot;
+    let (ncn_epoch, _) = load_ncn_epoch(restaking_config, current_slot, None)?;
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    snapshot_account.rollover(ncn_epoch, current_slot)?;
+
+    msg!("Snapshot rolled over into epoch {}", ncn_epoch);
+
+    Ok(())
+}
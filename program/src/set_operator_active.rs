@@ -0,0 +1,69 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_jsm_core::loader::load_signer;
+use jito_restaking_core::{ncn::Ncn, operator::Operator};
+use ncn_program_core::{config::Config, error::NCNProgramError, snapshot::Snapshot};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Marks a registered operator active or inactive without deregistering it.
+/// Requires NCN admin signature.
+///
+/// An inactive operator is left out of `operators_can_vote_count`, the eligibility queries and
+/// both quorums of a vote, and can't sign one. It keeps its snapshot slot and its G1 pubkey
+/// stays in the total aggregated G1 pubkey, so it can be marked active again later.
+///
+/// ### Parameters:
+/// - `active`: Whether the operator should be active
+///
+/// ### Accounts:
+/// 1. `[]` config: NCN configuration account
+/// 2. `[]` ncn: The NCN account
+/// 3. `[]` operator: The operator to mark active or inactive
+/// 4. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 5. `[signer]` ncn_admin: Admin authority for the NCN
+pub fn process_set_operator_active(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    active: bool,
+) -> ProgramResult {
+    let [config, ncn_account, operator, snapshot, ncn_admin] = accounts else {
+        msg!("Error: Not enough account keys provided");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    load_signer(ncn_admin, false)?;
+    Config::load(program_id, config, ncn_account.key, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn_account, false)?;
+    Operator::load(&jito_restaking_program::id(), operator, false)?;
+    Snapshot::load(program_id, snapshot, ncn_account.key, true)?;
+
+    {
+        let ncn_data = ncn_account.data.borrow();
+        let ncn = Ncn::try_from_slice_unchecked(&ncn_data)?;
+        if ncn.admin != *ncn_admin.key {
+            msg!("Error: Incorrect NCN admin");
+            return Err(NCNProgramError::IncorrectNcnAdmin.into());
+        }
+    }
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    snapshot_account
+        .set_operator_active(operator.key, active)
+        .map_err(|e| {
+            msg!("Error: Operator {} is not in the snapshot", operator.key);
+            e
+        })?;
+
+    msg!(
+        "Operator {} active: {}, {} operators can vote",
+        operator.key,
+        active,
+        snapshot_account.operators_can_vote_count()
+    );
+
+    Ok(())
+}
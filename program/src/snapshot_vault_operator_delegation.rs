@@ -13,6 +13,8 @@ use ncn_program_core::{
     loaders::load_ncn_epoch,
     snapshot::{OperatorSnapshot, Snapshot},
     stake_weight::StakeWeights,
+    utils::get_epoch,
+    vault_registry::VaultRegistry,
 };
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
@@ -21,6 +23,12 @@ use solana_program::{
 
 /// Records the delegation between a vault and an operator at a specific epoch.
 ///
+/// Fails when the vault or its delegation to the operator was not updated in the current epoch,
+/// so the snapshot never records stale stake weights, and when the vault is not registered in the
+/// vault registry. The stake is scaled by the weight of the vault's st_mint in the vault registry.
+/// Each vault's delegation is counted once per epoch, snapshotting it again in the same epoch
+/// fails with `DuplicateVaultOperatorDelegation`.
+///
 /// ### Parameters:
 /// - `epoch`: The target epoch
 ///
@@ -35,11 +43,12 @@ use solana_program::{
 /// 8. `[]` ncn_operator_state: The connection between NCN and operator
 /// 9. `[]` vault_operator_delegation: The delegation between vault and operator
 /// 10. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 11. `[]` vault_registry: The vault registry holding the st_mint weights
 pub fn process_snapshot_vault_operator_delegation(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
-    let [ncn_config, restaking_config, ncn, operator, vault, vault_ncn_ticket, ncn_vault_ticket, ncn_operator_state, vault_operator_delegation, snapshot] =
+    let [ncn_config, restaking_config, ncn, operator, vault, vault_ncn_ticket, ncn_vault_ticket, ncn_operator_state, vault_operator_delegation, snapshot, vault_registry] =
         accounts
     else {
         msg!("Error: Not enough account keys provided");
@@ -49,10 +58,83 @@ pub fn process_snapshot_vault_operator_delegation(
     NcnConfig::load(program_id, ncn_config, ncn.key, false)?;
     Config::load(&jito_restaking_program::id(), restaking_config, false)?;
     Ncn::load(&jito_restaking_program::id(), ncn, false)?;
-    Operator::load(&jito_restaking_program::id(), operator, false)?;
     Vault::load(&jito_vault_program::id(), vault, false)?;
     Snapshot::load(program_id, snapshot, ncn.key, true)?;
 
+    let st_mint_weight = load_st_mint_weight(program_id, ncn, vault, vault_registry)?;
+
+    let current_slot = Clock::get()?.slot;
+
+    let (_, ncn_epoch_length) = load_ncn_epoch(restaking_config, current_slot, None)?;
+
+    let is_vault_ncn_connection_active = load_vault_ncn_connection(
+        ncn,
+        vault,
+        vault_ncn_ticket,
+        ncn_vault_ticket,
+        current_slot,
+        ncn_epoch_length,
+    )?;
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    snapshot_operator_delegation(
+        snapshot_account,
+        vault,
+        operator,
+        ncn_operator_state,
+        vault_operator_delegation,
+        is_vault_ncn_connection_active,
+        st_mint_weight,
+        current_slot,
+        ncn_epoch_length,
+    )
+}
+
+/// Loads the vault registry, checks the vault is registered in it and returns the weight of the
+/// vault's st_mint
+pub(crate) fn load_st_mint_weight(
+    program_id: &Pubkey,
+    ncn: &AccountInfo,
+    vault: &AccountInfo,
+    vault_registry: &AccountInfo,
+) -> Result<u128, ProgramError> {
+    VaultRegistry::load(program_id, vault_registry, ncn.key, false)?;
+
+    let st_mint = {
+        let vault_data = vault.data.borrow();
+        let vault_account = Vault::try_from_slice_unchecked(&vault_data)?;
+        vault_account.supported_mint
+    };
+
+    let vault_registry_data = vault_registry.data.borrow();
+    let vault_registry_account = VaultRegistry::try_from_slice_unchecked(&vault_registry_data)?;
+
+    if !vault_registry_account.has_vault(vault.key) {
+        msg!("Error: Vault {} is not in the vault registry", vault.key);
+        return Err(NCNProgramError::VaultNotInRegistry.into());
+    }
+
+    vault_registry_account
+        .get_st_mint_weight(&st_mint)
+        .map_err(|e| {
+            msg!("Error: St mint {} is not in the vault registry", st_mint);
+            e
+        })
+}
+
+/// Loads the vault's tickets with the NCN and checks the vault is up to date. Returns whether
+/// both sides of the vault-NCN connection are active, which holds for every operator snapshotted
+/// against this vault.
+pub(crate) fn load_vault_ncn_connection(
+    ncn: &AccountInfo,
+    vault: &AccountInfo,
+    vault_ncn_ticket: &AccountInfo,
+    ncn_vault_ticket: &AccountInfo,
+    current_slot: u64,
+    ncn_epoch_length: u64,
+) -> Result<bool, ProgramError> {
     NcnVaultTicket::load(
         &jito_restaking_program::id(),
         ncn_vault_ticket,
@@ -71,6 +153,64 @@ pub fn process_snapshot_vault_operator_delegation(
         )?;
     }
 
+    // check vault is up to date
+    let vault_needs_update = {
+        let vault_data = vault.data.borrow();
+        let vault_account = Vault::try_from_slice_unchecked(&vault_data)?;
+
+        vault_account.is_update_needed(current_slot, ncn_epoch_length)?
+    };
+    if vault_needs_update {
+        msg!("Error: Vault is not up to date");
+        return Err(NCNProgramError::VaultNeedsUpdate.into());
+    }
+
+    let ncn_vault_okay = {
+        let ncn_vault_ticket_data = ncn_vault_ticket.data.borrow();
+        let ncn_vault_ticket_account =
+            NcnVaultTicket::try_from_slice_unchecked(&ncn_vault_ticket_data)?;
+
+        // If the NCN removes a vault, it should immediately be barred from the snapshot
+        ncn_vault_ticket_account
+            .state
+            .is_active(current_slot, ncn_epoch_length)?
+    };
+
+    let vault_ncn_okay = {
+        if vault_ncn_ticket.data_is_empty() {
+            false
+        } else {
+            let vault_ncn_ticket_data = vault_ncn_ticket.data.borrow();
+            let vault_ncn_ticket_account =
+                VaultNcnTicket::try_from_slice_unchecked(&vault_ncn_ticket_data)?;
+
+            // If a vault removes itself from the ncn, it should still be able to participate
+            // until it is finished cooling down - this is so the operators with delegation
+            // from this vault can still participate
+            vault_ncn_ticket_account
+                .state
+                .is_active_or_cooldown(current_slot, ncn_epoch_length)?
+        }
+    };
+
+    Ok(vault_ncn_okay && ncn_vault_okay)
+}
+
+/// Loads an operator's accounts and records its delegation from `vault` in its operator snapshot
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn snapshot_operator_delegation(
+    snapshot_account: &mut Snapshot,
+    vault: &AccountInfo,
+    operator: &AccountInfo,
+    ncn_operator_state: &AccountInfo,
+    vault_operator_delegation: &AccountInfo,
+    is_vault_ncn_connection_active: bool,
+    st_mint_weight: u128,
+    current_slot: u64,
+    ncn_epoch_length: u64,
+) -> ProgramResult {
+    Operator::load(&jito_restaking_program::id(), operator, false)?;
+
     if !vault_operator_delegation.data_is_empty() {
         VaultOperatorDelegation::load(
             &jito_vault_program::id(),
@@ -88,24 +228,26 @@ pub fn process_snapshot_vault_operator_delegation(
         false,
     )?;
 
-    let current_slot = Clock::get()?.slot;
-
-    let (_, ncn_epoch_length) = load_ncn_epoch(restaking_config, current_slot, None)?;
-
-    // check vault is up to date
-    let vault_needs_update = {
-        let vault_data = vault.data.borrow();
-        let vault_account = Vault::try_from_slice_unchecked(&vault_data)?;
+    // The vault being up to date implies its delegations are, unless one was tampered with or
+    // left behind by a partial update, in which case its stake weights would be stale
+    let delegation_needs_update = {
+        let vault_operator_delegation_data = vault_operator_delegation.data.borrow();
+        let vault_operator_delegation_account =
+            VaultOperatorDelegation::try_from_slice_unchecked(&vault_operator_delegation_data)?;
 
-        vault_account.is_update_needed(current_slot, ncn_epoch_length)?
+        get_epoch(
+            vault_operator_delegation_account.last_update_slot(),
+            ncn_epoch_length,
+        )? < get_epoch(current_slot, ncn_epoch_length)?
     };
-    if vault_needs_update {
-        msg!("Error: Vault is not up to date");
-        return Err(NCNProgramError::VaultNeedsUpdate.into());
+    if delegation_needs_update {
+        msg!(
+            "Error: Vault operator delegation for operator {} is not up to date",
+            operator.key
+        );
+        return Err(NCNProgramError::VaultOperatorDelegationNotUpdated.into());
     }
 
-    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
-    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
     let operator_snapshot = *snapshot_account
         .find_operator_snapshot(operator.key)
         .ok_or_else(|| {
@@ -118,40 +260,9 @@ pub fn process_snapshot_vault_operator_delegation(
 
     let mut cloned_operator_snapshot = operator_snapshot;
 
-    // Check if operator has valid BN128 G1 pubkey and determine active status
-    let is_vault_ncn_connection_active = {
-        let ncn_vault_okay = {
-            let ncn_vault_ticket_data = ncn_vault_ticket.data.borrow();
-            let ncn_vault_ticket_account =
-                NcnVaultTicket::try_from_slice_unchecked(&ncn_vault_ticket_data)?;
-
-            // If the NCN removes a vault, it should immediately be barred from the snapshot
-            ncn_vault_ticket_account
-                .state
-                .is_active(current_slot, ncn_epoch_length)?
-        };
-
-        let vault_ncn_okay = {
-            if vault_ncn_ticket.data_is_empty() {
-                false
-            } else {
-                let vault_ncn_ticket_data = vault_ncn_ticket.data.borrow();
-                let vault_ncn_ticket_account =
-                    VaultNcnTicket::try_from_slice_unchecked(&vault_ncn_ticket_data)?;
-
-                // If a vault removes itself from the ncn, it should still be able to participate
-                // until it is finished cooling down - this is so the operators with delegation
-                // from this vault can still participate
-                vault_ncn_ticket_account
-                    .state
-                    .is_active_or_cooldown(current_slot, ncn_epoch_length)?
-            }
-        };
-
-        let delegation_dne = vault_operator_delegation.data_is_empty();
-
-        vault_ncn_okay && ncn_vault_okay && !delegation_dne
-    };
+    // The vault must also have a delegation to this operator
+    let is_vault_ncn_connection_active =
+        is_vault_ncn_connection_active && !vault_operator_delegation.data_is_empty();
 
     let is_operator_ncn_connection_active = {
         let ncn_operator_state_data = ncn_operator_state.data.borrow();
@@ -179,7 +290,10 @@ pub fn process_snapshot_vault_operator_delegation(
         let vault_operator_delegation_account =
             VaultOperatorDelegation::try_from_slice_unchecked(&vault_operator_delegation_data)?;
 
-        OperatorSnapshot::calculate_stake_weights(vault_operator_delegation_account)?
+        OperatorSnapshot::calculate_stake_weights(
+            vault_operator_delegation_account,
+            st_mint_weight,
+        )?
     } else {
         (0u128, 0u128)
     };
@@ -188,20 +302,33 @@ pub fn process_snapshot_vault_operator_delegation(
     let this_epoch_stake_weight = StakeWeights::snapshot(total_stake_weight)?;
     let next_epoch_stake_weight = StakeWeights::snapshot(next_epoch_stake_weight)?;
     let _ncn_operator_index = {
-        cloned_operator_snapshot.snapshot_vault_operator_delegation(
-            current_slot,
-            &this_epoch_stake_weight,
-            &next_epoch_stake_weight,
-            snapshot_account.minimum_stake(),
-        )?;
+        cloned_operator_snapshot
+            .snapshot_vault_delegation(
+                vault.key,
+                current_slot,
+                ncn_epoch_length,
+                &this_epoch_stake_weight,
+                &next_epoch_stake_weight,
+                snapshot_account.minimum_stake(),
+            )
+            .map_err(|e| {
+                if e == NCNProgramError::DuplicateVaultOperatorDelegation {
+                    msg!(
+                        "Error: Delegation of vault {} to operator {} was already snapshotted this epoch",
+                        vault.key,
+                        operator.key
+                    );
+                }
+                e
+            })?;
 
         cloned_operator_snapshot.ncn_operator_index()
     };
 
-    snapshot_account.update_operator_snapshot(
+    snapshot_account.try_update_operator_snapshot(
         cloned_operator_snapshot.ncn_operator_index() as usize,
         &cloned_operator_snapshot,
-    );
+    )?;
 
     Ok(())
 }
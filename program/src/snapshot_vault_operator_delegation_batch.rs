@@ -0,0 +1,162 @@
+use jito_bytemuck::AccountDeserialize;
+use jito_restaking_core::{config::Config, ncn::Ncn};
+use jito_vault_core::vault::Vault;
+use ncn_program_core::{
+    config::Config as NcnConfig,
+    constants::{MAX_SNAPSHOT_BATCH_OPERATORS, SNAPSHOT_BATCH_MIN_REMAINING_COMPUTE_UNITS},
+    error::NCNProgramError,
+    loaders::load_ncn_epoch,
+    snapshot::Snapshot,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    compute_units::sol_remaining_compute_units,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::snapshot_vault_operator_delegation::{
+    load_st_mint_weight, load_vault_ncn_connection, snapshot_operator_delegation,
+};
+
+/// Records the delegations between a vault and several operators in one instruction.
+///
+/// Each operator is snapshotted exactly like `SnapshotVaultOperatorDelegation`, in the order of
+/// `operator_indices`. Before every operator but the first the remaining compute units are
+/// checked, and once fewer than `SNAPSHOT_BATCH_MIN_REMAINING_COMPUTE_UNITS` remain the
+/// instruction stops and succeeds with the operators processed so far. Progress is kept in each
+/// operator snapshot's `last_snapshot_slot`, so a crank can resume from the first operator not yet
+/// snapshotted this epoch.
+///
+/// ### Parameters:
+/// - `operator_indices`: The operators' indices in the snapshot, at most
+///   `MAX_SNAPSHOT_BATCH_OPERATORS`
+///
+/// ### Accounts:
+/// 1. `[]` ncn_config: NCN configuration account
+/// 2. `[]` restaking_config: Restaking configuration account
+/// 3. `[]` ncn: The NCN account
+/// 4. `[]` vault: The vault account
+/// 5. `[]` vault_ncn_ticket: The vault NCN ticket
+/// 6. `[]` ncn_vault_ticket: The NCN vault ticket
+/// 7. `[writable]` snapshot: Snapshot account containing operator snapshots
+/// 8. `[]` vault_registry: The vault registry holding the st_mint weights
+/// 9. `[]` operator_accounts: Remaining accounts, the `operator`, `ncn_operator_state` and
+///    `vault_operator_delegation` of every operator, in `operator_indices` order
+pub fn process_snapshot_vault_operator_delegation_batch(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    operator_indices: Vec<u64>,
+) -> ProgramResult {
+    if operator_indices.is_empty() || operator_indices.len() > MAX_SNAPSHOT_BATCH_OPERATORS {
+        msg!(
+            "Error: Expected between 1 and {} operators, got {}",
+            MAX_SNAPSHOT_BATCH_OPERATORS,
+            operator_indices.len()
+        );
+        return Err(NCNProgramError::InvalidInputLength.into());
+    }
+
+    let account_info_iter = &mut accounts.iter();
+    let ncn_config = next_account_info(account_info_iter)?;
+    let restaking_config = next_account_info(account_info_iter)?;
+    let ncn = next_account_info(account_info_iter)?;
+    let vault = next_account_info(account_info_iter)?;
+    let vault_ncn_ticket = next_account_info(account_info_iter)?;
+    let ncn_vault_ticket = next_account_info(account_info_iter)?;
+    let snapshot = next_account_info(account_info_iter)?;
+    let vault_registry = next_account_info(account_info_iter)?;
+    let operator_accounts = account_info_iter.as_slice();
+
+    if operator_accounts.len() != operator_indices.len() * 3 {
+        msg!(
+            "Error: Expected {} operator accounts, got {}",
+            operator_indices.len() * 3,
+            operator_accounts.len()
+        );
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    NcnConfig::load(program_id, ncn_config, ncn.key, false)?;
+    Config::load(&jito_restaking_program::id(), restaking_config, false)?;
+    Ncn::load(&jito_restaking_program::id(), ncn, false)?;
+    Vault::load(&jito_vault_program::id(), vault, false)?;
+    Snapshot::load(program_id, snapshot, ncn.key, true)?;
+
+    let st_mint_weight = load_st_mint_weight(program_id, ncn, vault, vault_registry)?;
+
+    let current_slot = Clock::get()?.slot;
+
+    let (_, ncn_epoch_length) = load_ncn_epoch(restaking_config, current_slot, None)?;
+
+    let is_vault_ncn_connection_active = load_vault_ncn_connection(
+        ncn,
+        vault,
+        vault_ncn_ticket,
+        ncn_vault_ticket,
+        current_slot,
+        ncn_epoch_length,
+    )?;
+
+    let mut snapshot_data = snapshot.try_borrow_mut_data()?;
+    let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
+
+    let mut operators_snapshotted = 0;
+    for (operator_index, accounts) in operator_indices
+        .iter()
+        .zip(operator_accounts.chunks_exact(3))
+    {
+        let [operator, ncn_operator_state, vault_operator_delegation] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if operators_snapshotted > 0
+            && sol_remaining_compute_units() < SNAPSHOT_BATCH_MIN_REMAINING_COMPUTE_UNITS
+        {
+            msg!(
+                "Stopping before operator index {} with {} compute units remaining",
+                operator_index,
+                sol_remaining_compute_units()
+            );
+            break;
+        }
+
+        let is_operator_at_index = snapshot_account
+            .get_operator_snapshot(*operator_index)
+            .is_some_and(|operator_snapshot| operator_snapshot.operator() == operator.key);
+        if !is_operator_at_index {
+            msg!(
+                "Error: Operator {} is not at index {} in the snapshot",
+                operator.key,
+                operator_index
+            );
+            return Err(NCNProgramError::OperatorIsNotInSnapshot.into());
+        }
+
+        snapshot_operator_delegation(
+            snapshot_account,
+            vault,
+            operator,
+            ncn_operator_state,
+            vault_operator_delegation,
+            is_vault_ncn_connection_active,
+            st_mint_weight,
+            current_slot,
+            ncn_epoch_length,
+        )?;
+
+        operators_snapshotted += 1;
+    }
+
+    msg!(
+        "Snapshotted {} of {} operators",
+        operators_snapshotted,
+        operator_indices.len()
+    );
+
+    Ok(())
+}
@@ -7,7 +7,6 @@ use ncn_program_core::{
     g1_point::{G1CompressedPoint, G1Point},
     g2_point::{G2CompressedPoint, G2Point},
     ncn_operator_account::NCNOperatorAccount,
-    schemes::sha256_normalized::Sha256Normalized,
     snapshot::Snapshot,
 };
 use solana_program::{
@@ -113,11 +112,8 @@ pub fn process_update_operator_bn128_keys(
             return Err(ProgramError::from(NCNProgramError::BLSVerificationError));
         }
 
-        // Verify the BLS signature: the signature should be the new G1 pubkey signed by the new G2 private key
-        // The message being signed is the new G1 pubkey itself
-        g2_point
-            .verify_signature::<Sha256Normalized, _, _>(signature, &g1_pubkey)
-            .map_err(|_| NCNProgramError::BLSVerificationError)?;
+        // Proof of possession: the new G1 pubkey signed by the new G2 private key
+        g2_point.verify_proof_of_possession(signature, g1_pubkey)?;
 
         msg!("BLS signature verification successful");
     }
@@ -137,6 +133,9 @@ pub fn process_update_operator_bn128_keys(
     let snapshot_account = Snapshot::try_from_slice_unchecked_mut(&mut snapshot_data)?;
 
     snapshot_account.update_g1_operator_pubkey(&g1_pubkey, operator.key)?;
+    if let Some(operator_snapshot) = snapshot_account.find_mut_operator_snapshot(operator.key) {
+        operator_snapshot.update_g2_pubkey(&g2_pubkey);
+    }
 
     msg!(
         "Operator BLS keys updated successfully for operator {}",